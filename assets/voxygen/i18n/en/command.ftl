@@ -80,6 +80,7 @@ command-object-desc = Spawn an object
 command-outcome-desc = Create an outcome
 command-permit_build-desc = Grants player a bounded box they can build in
 command-players-desc = Lists players currently online
+command-plugin-desc = List, reload or unload server plugins
 command-poise-desc = Set your current poise
 command-portal-desc = Spawns a portal
 command-region-desc = Send messages to everyone in your region of the world
@@ -102,6 +103,8 @@ command-skill_point-desc = Give yourself skill points for a particular skill tre
 command-skill_preset-desc = Gives your character desired skills.
 command-spawn-desc = Spawn a test entity
 command-spot-desc = Find and teleport to the closest spot of a certain kind.
+command-structure_capture-desc = Saves the blocks between two corners as a structure
+command-structure_paste-desc = Places a saved structure at your position, rotated by quarter turns and optionally mirrored
 command-sudo-desc = Run command as if you were another entity
 command-tell-desc = Send a message to another player
 command-tether-desc = Tether another entity to yourself
@@ -319,6 +322,14 @@ command-outcome-invalid_outcome = { $outcome } is not a valid outcome
 command-death_effect-unknown = Unknown death effect { $effect }.
 command-spot-spot_not_found = Didn't find any spots of that kind in this world.
 command-spot-world_feature = The `worldgen` feature has to be enabled to run this command.
+command-structure-captured = Captured structure '{ $name }'
+command-structure-pasted = Placed { $count } blocks of structure '{ $name }'
+command-structure-invalid = Structure name '{ $name }' is invalid. Names may only contain lowercase ASCII, digits and underscores
+command-structure-not-found = Structure '{ $name }' does not exist
+command-structure-unloaded = Cannot capture unloaded terrain at { $pos }
+command-structure-too-large = Structures can be at most { $max } blocks along each axis
+command-structure-too-many-blocks = The region has too many different blocks to fit in a structure
+command-structure-io = Failed to access structure files: { $error }
 command-cannot-send-message-hidden = Cannot send messages as a hidden spectator.
 command-destroyed-tethers = All tethers destroyed! You are now free
 command-destroyed-no-tethers = You're not connected to any tethers
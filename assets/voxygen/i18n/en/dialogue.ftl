@@ -4,6 +4,9 @@ dialogue-question-site =
     .a0 = Where am I?
     .a1 = Where are we?
     .a2 = What is this place?
+dialogue-question-news =
+    .a0 = Any news?
+    .a1 = Heard anything interesting lately?
 dialogue-question-self =
     .a0 = Who are you?
     .a1 = Tell me about yourself
@@ -14,6 +17,10 @@ dialogue-question-sentiment =
     .a0 = How do you feel about...
     .a1 = What do you think about...
 dialogue-cancel_hire = I want to stop hiring you.
+dialogue-companion-orders = I have orders for you.
+dialogue-companion-follow = Follow me.
+dialogue-companion-guard = Guard this place.
+dialogue-companion-wait = Wait here for me.
 dialogue-me = Me
 dialogue-buy_hire_days =
    .day = A day
@@ -41,6 +48,22 @@ dialogue-question-quest-escort-where = Where are we going?
 dialogue-question-quest-slay-where = Where is the { TAIL($body) }?
 dialogue-question-quest-slay-claim = The monster has been slain!
 
+dialogue-question-quest-fetch-claim = I've brought the { $item }.
+dialogue-question-quest-deliver-claim = I have a delivery of { $item } for you.
+dialogue-question-quest-explore-where = Where did you want me to go?
+dialogue-question-quest-explore-claim = I've been to the place you asked about.
+
+dialogue-topic-wares = What are you selling?
+dialogue-wares-show = Show me your wares.
+dialogue-wares-news = Heard any news on the road?
+dialogue-wares-help = Maybe I can help.
+
+dialogue-topic-guard_watch = How's the watch going?
+dialogue-guard_watch-trouble = Any trouble around here?
+dialogue-guard_watch-night = Isn't it late to be on duty?
+dialogue-guard_watch-thanks = I've been helping out around town.
+dialogue-guard_watch-help = I can take care of it.
+
 dialogue-play_game = Let's play a game
 dialogue-game-what_game =
     .a0 = What game do you want to play?
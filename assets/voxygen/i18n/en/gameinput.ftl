@@ -42,6 +42,7 @@ gameinput-social = Social
 gameinput-sit = Sit
 gameinput-crawl = Crawl
 gameinput-diary = Diary
+gameinput-questlog = Quest Log
 gameinput-settings = Settings
 gameinput-giveup = Give Up
 gameinput-respawn = Respawn
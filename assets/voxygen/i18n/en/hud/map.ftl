@@ -29,6 +29,9 @@ hud-map-character = Character
 hud-map-character-label = { $name }'s last known location
 hud-map-creature-label = Last known location of { $body }
 hud-map-escort-label = Escort { $name } to { $place }.
+hud-map-deliver-label = Deliver goods to { $name } in { $place }.
+hud-map-explore-label = Explore { $place }.
+hud-map-site-held_by = Held by { $site }
 hud-map-difficulty_dungeon =
     Dungeon
 
@@ -51,6 +54,7 @@ hud-map-haniwa = Haniwa Catacomb
 hud-map-cultist = Cultist Dungeon
 hud-map-sahagin = Sahagin Island
 hud-map-myrmidon = Myrmidon Dungeon
+hud-map-procedural_dungeon = Dungeon
 hud-map-terracotta = Terracotta Ruins
 hud-map-vampire_castle = Vampire Castle
 hud-map-placed_by = Placed by { $name }
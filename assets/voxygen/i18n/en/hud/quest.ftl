@@ -9,3 +9,26 @@ hud-quest-decline = Decline
 hud-dialogue = Dialogue
 
 hud-dialogue-ack = Press [{ $key }] to acknowledge
+
+hud-quest-log = Quest Log
+hud-quest-log-empty = You have no quests. Ask around, people might need your help.
+hud-quest-log-entry = Quest for { $name }
+hud-quest-log-reward = Reward:
+hud-quest-contact = Contact: { $name }
+hud-quest-destination = Destination: { $place }
+hud-quest-time-left = Time left: { $minutes } min
+hud-quest-status-active = In progress
+hud-quest-status-completed = Completed
+hud-quest-status-failed = Failed
+hud-quest-track = Track
+hud-quest-untrack = Untrack
+hud-quest-show-on-map = Show on map
+hud-quest-abandon = Abandon
+hud-quest-tracked = Tracked quest
+hud-quest-objective-escort = Escort { $name } to { $place }.
+hud-quest-objective-slay = Slay the { $body }.
+hud-quest-objective-fetch = Bring { $amount } { $item } to { $name }.
+hud-quest-objective-deliver = Deliver { $amount } { $item } to { $name } in { $place }.
+hud-quest-objective-explore = Explore { $place }.
+hud-quest-objective-hire = { $name } is working for you as a companion.
+hud-quest-objective-report = Return to { $name } to claim your reward.
@@ -438,6 +438,28 @@ npc-info-unknown =
     .a0 = I don't know.
     .a1 = I'm not sure.
 
+npc-dialogue-wares-ask =
+    .a0 = Only the finest goods, for the right price. Interested?
+    .a1 = A bit of everything! What are you after?
+npc-dialogue-wares-trade =
+    .a0 = Take a look.
+    .a1 = Have a look and see what catches your eye.
+npc-dialogue-wares-news =
+    .a0 = The roads aren't as safe as they used to be. I could use a hand.
+    .a1 = Business is slow, some people around here need help.
+npc-dialogue-guard_watch-status =
+    .a0 = Quiet, for now.
+    .a1 = Nothing gets past me.
+npc-dialogue-guard_watch-trouble =
+    .a0 = There's always something. Want to make yourself useful?
+    .a1 = Monsters have been spotted nearby. We could use some help.
+npc-dialogue-guard_watch-night =
+    .a0 = Someone has to keep watch while the town sleeps.
+    .a1 = Night is when the trouble starts.
+npc-dialogue-guard_watch-thanks =
+    .a0 = I've heard. The town owes you one.
+    .a1 = Good work. We need more people like you.
+
 npc-response-hire_time = How long do you want to hire me for?
 npc-response-accept_hire =
     .a0 = Let's go!
@@ -452,6 +474,10 @@ npc-dialogue-hire_expired =
     .a1 = My service has ended, I'm going to go my own way now. Goodbye!
 npc-dialogue-hire_cancelled_unhappy = I can't continue working for you in good conscience. Goodbye.
 npc-dialogue-hire_arrive_tavern = I'll hang out at { $tavern } for a bit, see you later!
+npc-question-companion-orders = What do you need me to do?
+npc-response-companion-order =
+    .a0 = Understood.
+    .a1 = Consider it done.
 npc-response-no_problem = No problem! See you later.
 
 npc-response-quest-nothing =
@@ -495,6 +521,39 @@ npc-response-quest-slay-thanks =
     .a1 = You have my gratitude... and my money!
     .a2 = You've done us a huge favour, many thanks.
 
+npc-response-quest-fetch-ask =
+    .a0 = We're running short of { $item } around here. Bring me { $amount } and I'll pay you { $coins } coins.
+    .a1 = Could you find { $amount } { $item } for me? There's { $coins } coins in it for you.
+npc-response-quest-fetch-start =
+    .a0 = Come back to me when you have it.
+    .a1 = Thank you! I'll be waiting here.
+npc-response-quest-deliver-ask =
+    .a0 = Could you take { $amount } { $item } to { $name } in { $dst }? They'll pay you { $coins } coins if you get there within { $mins } minutes.
+    .a1 = { $name } in { $dst } needs { $item }. Deliver { $amount } to them in the next { $mins } minutes and they'll give you { $coins } coins.
+npc-response-quest-deliver-start =
+    .a0 = Here are the goods. I've marked { $name }'s whereabouts on your map.
+    .a1 = Take these, and don't dawdle! You'll find the way on your map.
+npc-response-quest-items-thanks =
+    .a0 = Just what we needed, thank you!
+    .a1 = That's all of it. Much appreciated!
+npc-response-quest-items-missing = You don't have { $amount } { $item } with you.
+npc-response-quest-explore-ask =
+    .a0 = Nobody has been to { $place } in ages. Go and take a look, and come back within { $mins } minutes to tell me about it. I'll pay you { $coins } coins.
+    .a1 = I'd love to know what { $place } is like these days. Would you go there for me? { $coins } coins if you're back in { $mins } minutes.
+npc-response-quest-explore-start =
+    .a0 = Wonderful! I've marked the place on your map.
+    .a1 = Off you go then, the place is on your map.
+npc-response-quest-explore-where = I've marked the place on your map again.
+npc-response-quest-explore-thanks =
+    .a0 = Fascinating! Thank you for telling me.
+    .a1 = So that's what it's like. Thank you!
+
+npc-goods-coin = coins
+npc-goods-food = food
+npc-goods-wood = wood
+npc-goods-stone = stone
+npc-goods-ore = ore
+
 npc-response-like_you =
     .a0 = I like you!
     .a1 = You seem like a good friend.
@@ -590,6 +649,65 @@ npc-speech-pirate_raid =
     .a1 = Come plunder { $site } matey.
     .a2 = We're going to plunder { $site }.
 
+## Diplomacy
+
+npc-speech-at_war =
+    .a0 = We're at war with { $site }, keep your eyes open.
+    .a1 = Those dogs from { $site } will get what's coming to them.
+    .a2 = I hear { $site } is sending raiders our way.
+npc-speech-war_declared =
+    .a0 = It's war! { $site } has gone too far this time.
+    .a1 = We're at war with { $site } now. Sharpen your blades.
+npc-speech-peace_made =
+    .a0 = Things have calmed down with { $site }, thank goodness.
+    .a1 = The war with { $site } is over, for now.
+npc-speech-alliance_formed =
+    .a0 = We've become allies with { $site }.
+    .a1 = { $site } stands with us now.
+npc-speech-raid_start =
+    .a0 = To { $site }! Show them no mercy!
+    .a1 = We march on { $site }.
+    .a2 = { $site } will be ours by nightfall.
+npc-speech-site_conquered =
+    .a0 = { $site } is ours!
+    .a1 = Have you heard? We took { $site }!
+npc-speech-site_lost =
+    .a0 = { $site } has fallen to the enemy...
+    .a1 = We lost { $site }. Dark days are ahead.
+npc-speech-site_occupied =
+    .a0 = { $site } answers to new masters now.
+    .a1 = I suppose we'll have to get used to our new rulers.
+
+## News passed on between NPCs and told to players
+
+npc-news-none =
+    .a0 = Nothing I've heard of.
+    .a1 = It's been quiet lately.
+# Used TAIL() to strip the article
+npc-news-monster_attack =
+    .a0 = I heard a { TAIL($body) } killed someone near { $site }.
+    .a1 = Watch out near { $site }, a { TAIL($body) } has been attacking people.
+npc-news-monster_slain =
+    .a0 = Someone slew the { TAIL($body) } near { $site }!
+    .a1 = They say an adventurer killed a { TAIL($body) } by { $site }.
+npc-news-murder =
+    .a0 = There's been a murder in { $site }!
+    .a1 = I heard somebody was killed in cold blood at { $site }.
+npc-news-theft =
+    .a0 = Thieves have been at work in { $site }.
+    .a1 = Keep an eye on your belongings, there's been stealing in { $site }.
+npc-news-war =
+    .a0 = { $site_a } and { $site_b } are at war.
+    .a1 = I heard { $site_a } declared war on { $site_b }.
+npc-news-peace =
+    .a0 = { $site_a } and { $site_b } have made peace.
+npc-news-alliance =
+    .a0 = { $site_a } and { $site_b } are allies now.
+    .a1 = I heard { $site_a } joined forces with { $site_b }.
+npc-news-site_captured =
+    .a0 = { $site } was taken by { $by }!
+    .a1 = Did you hear? Raiders from { $by } captured { $site }.
+
 ## Signs
 
 npc-signs-keep_out =  Keep Out!
@@ -16,6 +16,7 @@ use crate::addr::ConnectionArgs;
 use byteorder::{ByteOrder, LittleEndian};
 use common::{
     character::{CharacterId, CharacterItem},
+    cmd::PluginCommandData,
     comp::{
         self, AdminRole, CharacterState, ChatMode, ControlAction, ControlEvent, Controller,
         ControllerInputs, GroupManip, Hardcore, InputKind, InventoryAction, InventoryEvent,
@@ -97,6 +98,9 @@ pub const MAX_SELECTABLE_VIEW_DISTANCE: u32 = 65;
 
 const PING_ROLLING_AVERAGE_SECS: usize = 10;
 
+/// How many finished quests are kept in the quest log
+const MAX_FINISHED_QUESTS: usize = 20;
+
 /// Client frontend events.
 ///
 /// These events are returned to the frontend that ticks the client.
@@ -199,6 +203,8 @@ impl WorldData {
 pub struct SiteMarker {
     pub marker: Marker,
     pub economy: Option<EconomyInfo>,
+    /// Who holds the site, if it isn't the seat of its own faction
+    pub holder: Option<Content>,
 }
 
 struct WeatherLerp {
@@ -304,6 +310,8 @@ pub struct Client {
     force_update_counter: u64,
 
     role: Option<AdminRole>,
+    /// Commands registered by server plugins
+    plugin_commands: Vec<PluginCommandData>,
     max_group_size: u32,
     // Client has received an invite (inviter uid, time out instant)
     invite: Option<(Uid, Instant, Duration, InviteKind)>,
@@ -315,6 +323,8 @@ pub struct Client {
     // The pending trade the client is involved in, and it's id
     pending_trade: Option<(TradeId, PendingTrade, Option<SitePrices>)>,
     waypoint: Option<String>,
+    /// Quests the character takes part in, followed by the ones it finished
+    quests: Vec<rtsim::QuestInfo>,
 
     network: Option<Network>,
     participant: Option<Participant>,
@@ -1048,6 +1058,7 @@ impl Client {
                     Some((m.site?, SiteMarker {
                         marker: m.clone(),
                         economy: None,
+                        holder: None,
                     }))
                 })
                 .collect(),
@@ -1066,6 +1077,7 @@ impl Client {
             force_update_counter: 0,
 
             role,
+            plugin_commands: Vec::new(),
             max_group_size,
             invite: None,
             group_leader: None,
@@ -1073,6 +1085,7 @@ impl Client {
             pending_invites: HashSet::new(),
             pending_trade: None,
             waypoint: None,
+            quests: Vec::new(),
 
             network: Some(network),
             participant: Some(participant),
@@ -1223,7 +1236,9 @@ impl Client {
                     | ClientGeneral::UpdateMapMarker(_)
                     | ClientGeneral::SpectatePosition(_)
                     | ClientGeneral::SpectateEntity(_)
-                    | ClientGeneral::SetBattleMode(_) => {
+                    | ClientGeneral::SetBattleMode(_)
+                    | ClientGeneral::AbandonQuest(_)
+                    | ClientGeneral::PluginMessage { .. } => {
                         #[cfg(feature = "tracy")]
                         {
                             ingame = 1.0;
@@ -2238,6 +2253,14 @@ impl Client {
         }
     }
 
+    /// Quests the character takes part in, and the last few it finished
+    pub fn quests(&self) -> &[rtsim::QuestInfo] { &self.quests }
+
+    /// Give up on a quest, its contact will consider it failed
+    pub fn abandon_quest(&mut self, quest: rtsim::QuestId) {
+        self.send_msg(ClientGeneral::AbandonQuest(quest));
+    }
+
     pub fn do_talk(&mut self, tgt: Option<EcsEntity>) {
         if let Some(controller) = self
             .state
@@ -2467,11 +2490,47 @@ impl Client {
         }
         */
 
+        #[cfg(feature = "plugins")]
+        self.send_plugin_messages()?;
+
         // 7) Finish the tick, pass control back to the frontend.
         self.tick += 1;
         Ok(frontend_events)
     }
 
+    /// Hand a message from a server plugin to its client half
+    #[cfg(feature = "plugins")]
+    fn deliver_plugin_message(&mut self, plugin: &str, channel: &str, data: &[u8]) {
+        let ecs = self.state.ecs();
+        common_state::plugin::memory_manager::with_ecs_world(ecs, |ecs_world| {
+            ecs.write_resource::<PluginMgr>()
+                .message_event(ecs_world, plugin, None, channel, data)
+        });
+    }
+
+    /// Send the messages client plugins addressed to their server half
+    #[cfg(feature = "plugins")]
+    fn send_plugin_messages(&mut self) -> Result<(), Error> {
+        let messages = self
+            .state
+            .ecs()
+            .write_resource::<PluginMgr>()
+            .take_messages();
+        if self.presence.is_none() {
+            return Ok(());
+        }
+        for (plugin, message) in messages {
+            if let common_state::plugin::PluginMessage::ToServer { channel, data } = message {
+                self.in_game_stream.send(ClientGeneral::PluginMessage {
+                    plugin,
+                    channel,
+                    data,
+                })?;
+            }
+        }
+        Ok(())
+    }
+
     /// Clean up the client after a tick.
     pub fn cleanup(&mut self) {
         // Cleanup the local state
@@ -2840,6 +2899,9 @@ impl Client {
                 debug!(?role, "Updating client role");
                 self.role = role;
             },
+            ServerGeneral::PluginCommands(commands) => {
+                self.plugin_commands = commands;
+            },
             _ => unreachable!("Not a general msg"),
         }
         Ok(())
@@ -3007,6 +3069,19 @@ impl Client {
             ServerGeneral::Dialogue(sender, dialogue) => {
                 frontend_events.push(Event::Dialogue(sender, dialogue));
             },
+            ServerGeneral::QuestLog(quests) => {
+                // Unresolved quests missing from the update were resolved elsewhere, resolved
+                // ones are kept around so the player can look back at them
+                self.quests.retain(|old| {
+                    old.resolution.is_some() && quests.iter().all(|new| new.id != old.id)
+                });
+                let finished = self.quests.len();
+                if finished > MAX_FINISHED_QUESTS {
+                    self.quests.drain(..finished - MAX_FINISHED_QUESTS);
+                }
+                self.quests.extend(quests);
+                self.quests.sort_by_key(|quest| quest.resolution.is_some());
+            },
             ServerGeneral::SetViewDistance(vd) => {
                 self.view_distance = Some(vd);
                 frontend_events.push(Event::SetViewDistance(vd));
@@ -3041,6 +3116,16 @@ impl Client {
                     rich.economy = Some(economy);
                 }
             },
+            ServerGeneral::SiteHolders(holders) => {
+                for site in self.sites.values_mut() {
+                    site.holder = None;
+                }
+                for (id, holder) in holders {
+                    if let Some(site) = self.sites.get_mut(&id) {
+                        site.holder = Some(holder);
+                    }
+                }
+            },
             ServerGeneral::MapMarker(event) => {
                 frontend_events.push(Event::MapMarker(event));
             },
@@ -3057,6 +3142,14 @@ impl Client {
                 self.update_available_recipes();
             },
             ServerGeneral::Gizmos(gizmos) => frontend_events.push(Event::Gizmos(gizmos)),
+            #[cfg(feature = "plugins")]
+            ServerGeneral::PluginMessage {
+                plugin,
+                channel,
+                data,
+            } => self.deliver_plugin_message(&plugin, &channel, &data),
+            #[cfg(not(feature = "plugins"))]
+            ServerGeneral::PluginMessage { .. } => {},
             _ => unreachable!("Not a in_game message"),
         }
         Ok(())
@@ -3316,6 +3409,16 @@ impl Client {
 
     pub fn role(&self) -> &Option<AdminRole> { &self.role }
 
+    /// Commands registered by server plugins
+    pub fn plugin_commands(&self) -> &[PluginCommandData] { &self.plugin_commands }
+
+    /// The plugin command with the given keyword
+    pub fn plugin_command(&self, keyword: &str) -> Option<&PluginCommandData> {
+        self.plugin_commands
+            .iter()
+            .find(|command| command.keyword == keyword)
+    }
+
     /// Clean client ECS state
     fn clean_state(&mut self) {
         // Clear pending trade
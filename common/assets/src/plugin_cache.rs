@@ -198,16 +198,22 @@ impl CombinedCache {
 
     /// Add a tar archive (a plugin) to the system.
     /// All files in that tar file become potential assets.
+    /// Registering an archive again replaces the previous version of it.
     pub fn register_tar(&self, path: PathBuf) -> std::io::Result<()> {
         let tar_source = Tar::open(&path)?;
         let cache = AssetCache::with_source(tar_source);
-        self.0
+        let mut plugin_list = self
+            .0
             .downcast_raw_source::<CombinedSource>()
             .unwrap()
             .plugin_list
             .write()
-            .unwrap()
-            .push(PluginEntry { path, cache });
+            .unwrap();
+        if let Some(entry) = plugin_list.iter_mut().find(|entry| entry.path == path) {
+            entry.cache = cache;
+        } else {
+            plugin_list.push(PluginEntry { path, cache });
+        }
         Ok(())
     }
 
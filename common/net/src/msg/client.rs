@@ -5,6 +5,7 @@ use common::{
     comp::{self, AdminRole, Skill},
     event::PluginHash,
     resources::BattleMode,
+    rtsim::QuestId,
     terrain::block::Block,
 };
 use serde::{Deserialize, Serialize};
@@ -109,6 +110,8 @@ pub enum ClientGeneral {
     RequestSiteInfo(SiteId),
     UpdateMapMarker(comp::MapMarkerChange),
     SetBattleMode(BattleMode),
+    /// Give up on a quest the character is taking part in
+    AbandonQuest(QuestId),
 
     SpectatePosition(Vec3<f32>),
     SpectateEntity(Option<common::uid::Uid>),
@@ -131,6 +134,12 @@ pub enum ClientGeneral {
         lossy_terrain_compression: bool,
     },
     RequestPlugins(Vec<PluginHash>),
+    /// Data for the server half of a plugin
+    PluginMessage {
+        plugin: String,
+        channel: String,
+        data: Vec<u8>,
+    },
 }
 
 impl ClientMsg {
@@ -173,7 +182,8 @@ impl ClientMsg {
                         | ClientGeneral::RequestPlayerPhysics { .. }
                         | ClientGeneral::RequestLossyTerrainCompression { .. }
                         | ClientGeneral::UpdateMapMarker(_)
-                        | ClientGeneral::SetBattleMode(_) => {
+                        | ClientGeneral::SetBattleMode(_)
+                        | ClientGeneral::AbandonQuest(_) => {
                             c_type == ClientType::Game && presence.is_some()
                         },
                         ClientGeneral::SpectatePosition(_) | ClientGeneral::SpectateEntity(_) => {
@@ -188,6 +198,9 @@ impl ClientMsg {
                         // LodZoneRequest is required by the char select screen
                         | ClientGeneral::LodZoneRequest { .. } => true,
                         | ClientGeneral::RequestPlugins(_) => true,
+                        ClientGeneral::PluginMessage { .. } => {
+                            c_type == ClientType::Game && presence.is_some()
+                        },
                     }
             },
             ClientMsg::Ping(_) => true,
@@ -21,6 +21,13 @@ pub use self::{
 };
 use serde::{Deserialize, Serialize};
 
+/// Largest payload of a message between the server and client half of a
+/// plugin
+pub const MAX_PLUGIN_MESSAGE_SIZE: usize = 16 * 1024;
+/// Number of plugin messages a single plugin may send per tick, the server
+/// applies the same limit to each client
+pub const MAX_PLUGIN_MESSAGES_PER_TICK: usize = 32;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PingMsg {
     Ping,
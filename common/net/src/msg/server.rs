@@ -6,6 +6,7 @@ use crate::sync;
 use common::{
     calendar::Calendar,
     character::{self, CharacterItem},
+    cmd::PluginCommandData,
     comp::{
         self, AdminRole, Content, body::Gender, gizmos::Gizmos, invite::InviteKind,
         item::MaterialStatManifest,
@@ -18,7 +19,7 @@ use common::{
     rtsim,
     shared_server_config::ServerConstants,
     terrain::{Block, TerrainChunk, TerrainChunkMeta, TerrainChunkSize},
-    trade::{PendingTrade, SitePrices, TradeId, TradeResult},
+    trade::{PendingTrade, SiteId, SitePrices, TradeId, TradeResult},
     uid::Uid,
     uuid::Uuid,
     weather::SharedWeatherGrid,
@@ -183,6 +184,12 @@ pub enum ServerGeneral {
     ExitInGameSuccess,
     InventoryUpdate(comp::Inventory, Vec<comp::InventoryUpdateEvent>),
     Dialogue(Uid, rtsim::Dialogue<true>),
+    /// The quests the character is involved in, replaces the previous list.
+    /// Resolved quests are only included once, after they were resolved.
+    QuestLog(Vec<rtsim::QuestInfo>),
+    /// Who holds the sites that changed hands or belong to a larger faction,
+    /// replaces the previous list.
+    SiteHolders(Vec<(SiteId, Content)>),
     /// NOTE: The client can infer that entity view distance will be at most the
     /// terrain view distance that we send here (and if lower it won't be
     /// modified). So we just need to send the terrain VD back to the client
@@ -227,6 +234,14 @@ pub enum ServerGeneral {
     SpectatePosition(Vec3<f32>),
     /// Plugin data requested from the server
     PluginData(Vec<u8>),
+    /// Commands registered by server plugins, replaces the previous list
+    PluginCommands(Vec<PluginCommandData>),
+    /// Data for the client half of a plugin
+    PluginMessage {
+        plugin: String,
+        channel: String,
+        data: Vec<u8>,
+    },
     /// Update the list of available recipes. Usually called after a new recipe
     /// is acquired
     UpdateRecipes,
@@ -367,6 +382,8 @@ impl ServerMsg {
                         | ServerGeneral::InventoryUpdate(_, _)
                         | ServerGeneral::GroupInventoryUpdate(_, _)
                         | ServerGeneral::Dialogue(_, _)
+                        | ServerGeneral::QuestLog(_)
+                        | ServerGeneral::SiteHolders(_)
                         | ServerGeneral::TerrainChunkUpdate { .. }
                         | ServerGeneral::TerrainBlockUpdates(_)
                         | ServerGeneral::SetViewDistance(_)
@@ -380,7 +397,8 @@ impl ServerMsg {
                         | ServerGeneral::LocalWindUpdate(_)
                         | ServerGeneral::SpectatePosition(_)
                         | ServerGeneral::UpdateRecipes
-                        | ServerGeneral::Gizmos(_) => {
+                        | ServerGeneral::Gizmos(_)
+                        | ServerGeneral::PluginMessage { .. } => {
                             c_type == ClientType::Game && presence.is_some()
                         },
                         // Always possible
@@ -397,7 +415,7 @@ impl ServerMsg {
                         | ServerGeneral::Notification(_)
                         | ServerGeneral::SetPlayerRole(_)
                         | ServerGeneral::LodZoneUpdate { .. } => true,
-                        ServerGeneral::PluginData(_) => true,
+                        ServerGeneral::PluginData(_) | ServerGeneral::PluginCommands(_) => true,
                     }
             },
             ServerMsg::Ping(_) => true,
@@ -16,6 +16,7 @@ use hashbrown::{HashMap, HashSet};
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use std::{
+    borrow::Cow,
     fmt::{self, Display},
     str::FromStr,
 };
@@ -267,52 +268,7 @@ lazy_static! {
         preset_list
     };
 
-    /// Map from string to a Spot's kind (except RonFile)
-    pub static ref SPOT_PARSER: HashMap<String, Spot> = {
-        let spot_to_string = |kind| match kind {
-            Spot::DwarvenGrave => "dwarven_grave",
-            Spot::SaurokAltar => "saurok_altar",
-            Spot::MyrmidonTemple => "myrmidon_temple",
-            Spot::GnarlingTotem => "gnarling_totem",
-            Spot::WitchHouse => "witch_house",
-            Spot::GnomeSpring => "gnome_spring",
-            Spot::WolfBurrow => "wolf_burrow",
-            Spot::Igloo => "igloo",
-            Spot::LionRock => "lion_rock",
-            Spot::TreeStumpForest => "tree_stump_forest",
-            Spot::DesertBones => "desert_bones",
-            Spot::Arch => "arch",
-            Spot::AirshipCrash => "airship_crash",
-            Spot::FruitTree => "fruit_tree",
-            Spot::Shipwreck => "shipwreck",
-            Spot::Shipwreck2 => "shipwreck2",
-            Spot::FallenTree => "fallen_tree",
-            Spot::GraveSmall => "grave_small",
-            Spot::JungleTemple => "jungle_temple",
-            Spot::SaurokTotem => "saurok_totem",
-            Spot::JungleOutpost => "jungle_outpost",
-            // unused here, but left for completeness
-            Spot::RonFile(props) => &props.base_structures,
-        };
-
-        let mut map = HashMap::new();
-        for spot_kind in Spot::iter() {
-            map.insert(spot_to_string(spot_kind).to_owned(), spot_kind);
-        }
-
-        map
-    };
-
-    pub static ref SPOTS: Vec<String> = {
-        let mut config_spots = crate::spot::RON_SPOT_PROPERTIES
-            .0
-            .iter()
-            .map(|s| s.base_structures.clone())
-            .collect::<Vec<_>>();
-
-        config_spots.extend(SPOT_PARSER.keys().cloned());
-        config_spots
-    };
+    pub static ref SPOTS: Vec<String> = Spot::iter().map(|spot| spot.name().to_owned()).collect();
 }
 
 pub enum EntityTarget {
@@ -412,6 +368,7 @@ pub enum ServerChatCommand {
     Outcome,
     PermitBuild,
     Players,
+    Plugin,
     Poise,
     Portal,
     Region,
@@ -440,6 +397,8 @@ pub enum ServerChatCommand {
     SkillPreset,
     Spawn,
     Spot,
+    StructureCapture,
+    StructurePaste,
     Sudo,
     Tell,
     Tether,
@@ -463,48 +422,51 @@ impl ServerChatCommand {
         let cmd = ChatCommandData::new;
         match self {
             ServerChatCommand::Adminify => cmd(
-                vec![PlayerName(Required), Enum("role", ROLES.clone(), Optional)],
+                vec![
+                    PlayerName(Required),
+                    Enum("role".into(), ROLES.clone(), Optional),
+                ],
                 Content::localized("command-adminify-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Airship => cmd(
                 vec![
                     Enum(
-                        "kind",
+                        "kind".into(),
                         comp::ship::ALL_AIRSHIPS
                             .iter()
                             .map(|b| format!("{b:?}"))
                             .collect(),
                         Optional,
                     ),
-                    Float("destination_degrees_ccw_of_east", 90.0, Optional),
+                    Float("destination_degrees_ccw_of_east".into(), 90.0, Optional),
                 ],
                 Content::localized("command-airship-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Alias => cmd(
-                vec![Any("name", Required)],
+                vec![Any("name".into(), Required)],
                 Content::localized("command-alias-desc"),
                 Some(Moderator),
             ),
             ServerChatCommand::Aura => cmd(
                 vec![
-                    Float("aura_radius", 10.0, Required),
-                    Float("aura_duration", 10.0, Optional),
-                    Boolean("new_entity", "true".to_string(), Optional),
-                    Enum("aura_target", GroupTarget::all_options(), Optional),
-                    Enum("aura_kind", AuraKindVariant::all_options(), Required),
-                    Any("aura spec", Optional),
+                    Float("aura_radius".into(), 10.0, Required),
+                    Float("aura_duration".into(), 10.0, Optional),
+                    Boolean("new_entity".into(), "true".to_string(), Optional),
+                    Enum("aura_target".into(), GroupTarget::all_options(), Optional),
+                    Enum("aura_kind".into(), AuraKindVariant::all_options(), Required),
+                    Any("aura spec".into(), Optional),
                 ],
                 Content::localized("command-aura-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Buff => cmd(
                 vec![
-                    Enum("buff", BUFFS.clone(), Required),
-                    Float("strength", 0.01, Optional),
-                    Float("duration", 10.0, Optional),
-                    Any("buff data spec", Optional),
+                    Enum("buff".into(), BUFFS.clone(), Required),
+                    Float("strength".into(), 0.01, Optional),
+                    Float("duration".into(), 10.0, Optional),
+                    Any("buff data spec".into(), Optional),
                 ],
                 Content::localized("command-buff-desc"),
                 Some(Admin),
@@ -512,8 +474,8 @@ impl ServerChatCommand {
             ServerChatCommand::Ban => cmd(
                 vec![
                     PlayerName(Required),
-                    Boolean("overwrite", "true".to_string(), Optional),
-                    Any("ban duration", Optional),
+                    Boolean("overwrite".into(), "true".to_string(), Optional),
+                    Any("ban duration".into(), Optional),
                     Message(Optional),
                 ],
                 Content::localized("command-ban-desc"),
@@ -522,22 +484,25 @@ impl ServerChatCommand {
             ServerChatCommand::BanIp => cmd(
                 vec![
                     PlayerName(Required),
-                    Boolean("overwrite", "true".to_string(), Optional),
-                    Any("ban duration", Optional),
+                    Boolean("overwrite".into(), "true".to_string(), Optional),
+                    Any("ban duration".into(), Optional),
                     Message(Optional),
                 ],
                 Content::localized("command-ban-ip-desc"),
                 Some(Moderator),
             ),
             ServerChatCommand::BanLog => cmd(
-                vec![PlayerName(Required), Integer("max entries", 10, Optional)],
+                vec![
+                    PlayerName(Required),
+                    Integer("max entries".into(), 10, Optional),
+                ],
                 Content::localized("command-ban-ip-desc"),
                 Some(Moderator),
             ),
             #[rustfmt::skip]
             ServerChatCommand::BattleMode => cmd(
                 vec![Enum(
-                    "battle mode",
+                    "battle mode".into(),
                     vec!["pvp".to_owned(), "pve".to_owned()],
                     Optional,
                 )],
@@ -547,7 +512,7 @@ impl ServerChatCommand {
             ),
             ServerChatCommand::IntoNpc => cmd(
                 vec![AssetPath(
-                    "entity_config",
+                    "entity_config".into(),
                     "common.entity.",
                     ENTITY_CONFIGS.clone(),
                     Required,
@@ -556,13 +521,13 @@ impl ServerChatCommand {
                 Some(Admin),
             ),
             ServerChatCommand::Body => cmd(
-                vec![Enum("body", ENTITIES.clone(), Required)],
+                vec![Enum("body".into(), ENTITIES.clone(), Required)],
                 Content::localized("command-body-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::BattleModeForce => cmd(
                 vec![Enum(
-                    "battle mode",
+                    "battle mode".into(),
                     vec!["pvp".to_owned(), "pve".to_owned()],
                     Required,
                 )],
@@ -572,14 +537,14 @@ impl ServerChatCommand {
             ServerChatCommand::Build => cmd(vec![], Content::localized("command-build-desc"), None),
             ServerChatCommand::AreaAdd => cmd(
                 vec![
-                    Any("name", Required),
-                    Enum("kind", AREA_KINDS.clone(), Required),
-                    Integer("xlo", 0, Required),
-                    Integer("xhi", 10, Required),
-                    Integer("ylo", 0, Required),
-                    Integer("yhi", 10, Required),
-                    Integer("zlo", 0, Required),
-                    Integer("zhi", 10, Required),
+                    Any("name".into(), Required),
+                    Enum("kind".into(), AREA_KINDS.clone(), Required),
+                    Integer("xlo".into(), 0, Required),
+                    Integer("xhi".into(), 10, Required),
+                    Integer("ylo".into(), 0, Required),
+                    Integer("yhi".into(), 10, Required),
+                    Integer("zlo".into(), 0, Required),
+                    Integer("zhi".into(), 10, Required),
                 ],
                 Content::localized("command-area_add-desc"),
                 Some(Admin),
@@ -591,8 +556,8 @@ impl ServerChatCommand {
             ),
             ServerChatCommand::AreaRemove => cmd(
                 vec![
-                    Any("name", Required),
-                    Enum("kind", AREA_KINDS.clone(), Required),
+                    Any("name".into(), Required),
+                    Enum("kind".into(), AREA_KINDS.clone(), Required),
                 ],
                 Content::localized("command-area_remove-desc"),
                 Some(Admin),
@@ -603,18 +568,22 @@ impl ServerChatCommand {
                 Some(Admin),
             ),
             ServerChatCommand::ClearPersistedTerrain => cmd(
-                vec![Integer("chunk_radius", 6, Required)],
+                vec![Integer("chunk_radius".into(), 6, Required)],
                 Content::localized("command-clear_persisted_terrain-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::DeathEffect => cmd(
                 vec![
-                    Enum("death_effect", vec!["transform".to_string()], Required),
+                    Enum(
+                        "death_effect".into(),
+                        vec!["transform".to_string()],
+                        Required,
+                    ),
                     // NOTE: I added this for QoL as transform is currently the only death effect
                     // and takes an asset path, when more on-death effects are added to the command
                     // remove this.
                     AssetPath(
-                        "entity_config",
+                        "entity_config".into(),
                         "common.entity.",
                         ENTITY_CONFIGS.clone(),
                         Required,
@@ -624,17 +593,23 @@ impl ServerChatCommand {
                 Some(Admin),
             ),
             ServerChatCommand::DebugColumn => cmd(
-                vec![Integer("x", 15000, Required), Integer("y", 15000, Required)],
+                vec![
+                    Integer("x".into(), 15000, Required),
+                    Integer("y".into(), 15000, Required),
+                ],
                 Content::localized("command-debug_column-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::DebugWays => cmd(
-                vec![Integer("x", 15000, Required), Integer("y", 15000, Required)],
+                vec![
+                    Integer("x".into(), 15000, Required),
+                    Integer("y".into(), 15000, Required),
+                ],
                 Content::localized("command-debug_ways-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::DisconnectAllPlayers => cmd(
-                vec![Any("confirm", Required)],
+                vec![Any("confirm".into(), Required)],
                 Content::localized("command-disconnect_all_players-desc"),
                 Some(Admin),
             ),
@@ -649,7 +624,7 @@ impl ServerChatCommand {
                 Some(Admin),
             ),
             ServerChatCommand::Explosion => cmd(
-                vec![Float("radius", 5.0, Required)],
+                vec![Float("radius".into(), 5.0, Required)],
                 Content::localized("command-explosion-desc"),
                 Some(Admin),
             ),
@@ -660,8 +635,8 @@ impl ServerChatCommand {
             ),
             ServerChatCommand::GiveItem => cmd(
                 vec![
-                    AssetPath("item", "common.items.", ITEM_SPECS.clone(), Required),
-                    Integer("num", 1, Optional),
+                    AssetPath("item".into(), "common.items.", ITEM_SPECS.clone(), Required),
+                    Integer("num".into(), 1, Optional),
                 ],
                 Content::localized("command-give_item-desc"),
                 Some(Admin),
@@ -669,7 +644,7 @@ impl ServerChatCommand {
             ServerChatCommand::Gizmos => cmd(
                 vec![
                     Enum(
-                        "kind",
+                        "kind".into(),
                         ["All".to_string(), "None".to_string()]
                             .into_iter()
                             .chain(
@@ -685,22 +660,26 @@ impl ServerChatCommand {
                 Some(Admin),
             ),
             ServerChatCommand::GizmosRange => cmd(
-                vec![Float("range", 32.0, Required)],
+                vec![Float("range".into(), 32.0, Required)],
                 Content::localized("command-gizmos_range-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Goto => cmd(
                 vec![
-                    Float("x", 0.0, Required),
-                    Float("y", 0.0, Required),
-                    Float("z", 0.0, Required),
-                    Boolean("Dismount from ship", "true".to_string(), Optional),
+                    Float("x".into(), 0.0, Required),
+                    Float("y".into(), 0.0, Required),
+                    Float("z".into(), 0.0, Required),
+                    Boolean("Dismount from ship".into(), "true".to_string(), Optional),
                 ],
                 Content::localized("command-goto-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::GotoRand => cmd(
-                vec![Boolean("Dismount from ship", "true".to_string(), Optional)],
+                vec![Boolean(
+                    "Dismount from ship".into(),
+                    "true".to_string(),
+                    Optional,
+                )],
                 Content::localized("command-goto-rand"),
                 Some(Admin),
             ),
@@ -728,7 +707,7 @@ impl ServerChatCommand {
                 None,
             ),
             ServerChatCommand::Health => cmd(
-                vec![Integer("hp", 100, Required)],
+                vec![Integer("hp".into(), 100, Required)],
                 Content::localized("command-health-desc"),
                 Some(Admin),
             ),
@@ -738,16 +717,16 @@ impl ServerChatCommand {
                 Some(Moderator),
             ),
             ServerChatCommand::JoinFaction => cmd(
-                vec![Any("faction", Optional)],
+                vec![Any("faction".into(), Optional)],
                 Content::localized("command-join_faction-desc"),
                 None,
             ),
             ServerChatCommand::Jump => cmd(
                 vec![
-                    Float("x", 0.0, Required),
-                    Float("y", 0.0, Required),
-                    Float("z", 0.0, Required),
-                    Boolean("Dismount from ship", "true".to_string(), Optional),
+                    Float("x".into(), 0.0, Required),
+                    Float("y".into(), 0.0, Required),
+                    Float("z".into(), 0.0, Required),
+                    Boolean("Dismount from ship".into(), "true".to_string(), Optional),
                 ],
                 Content::localized("command-jump-desc"),
                 Some(Admin),
@@ -759,44 +738,47 @@ impl ServerChatCommand {
             ),
             ServerChatCommand::Kill => cmd(vec![], Content::localized("command-kill-desc"), None),
             ServerChatCommand::KillNpcs => cmd(
-                vec![Float("radius", 100.0, Optional), Flag("--also-pets")],
+                vec![
+                    Float("radius".into(), 100.0, Optional),
+                    Flag("--also-pets".into()),
+                ],
                 Content::localized("command-kill_npcs-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Kit => cmd(
-                vec![Enum("kit_name", KITS.to_vec(), Required)],
+                vec![Enum("kit_name".into(), KITS.to_vec(), Required)],
                 Content::localized("command-kit-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Lantern => cmd(
                 vec![
-                    Float("strength", 5.0, Required),
-                    Float("r", 1.0, Optional),
-                    Float("g", 1.0, Optional),
-                    Float("b", 1.0, Optional),
+                    Float("strength".into(), 5.0, Required),
+                    Float("r".into(), 1.0, Optional),
+                    Float("g".into(), 1.0, Optional),
+                    Float("b".into(), 1.0, Optional),
                 ],
                 Content::localized("command-lantern-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Light => cmd(
                 vec![
-                    Float("r", 1.0, Optional),
-                    Float("g", 1.0, Optional),
-                    Float("b", 1.0, Optional),
-                    Float("x", 0.0, Optional),
-                    Float("y", 0.0, Optional),
-                    Float("z", 0.0, Optional),
-                    Float("strength", 5.0, Optional),
+                    Float("r".into(), 1.0, Optional),
+                    Float("g".into(), 1.0, Optional),
+                    Float("b".into(), 1.0, Optional),
+                    Float("x".into(), 0.0, Optional),
+                    Float("y".into(), 0.0, Optional),
+                    Float("z".into(), 0.0, Optional),
+                    Float("strength".into(), 5.0, Optional),
                 ],
                 Content::localized("command-light-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::MakeBlock => cmd(
                 vec![
-                    Enum("block", BLOCK_KINDS.clone(), Required),
-                    Integer("r", 255, Optional),
-                    Integer("g", 255, Optional),
-                    Integer("b", 255, Optional),
+                    Enum("block".into(), BLOCK_KINDS.clone(), Required),
+                    Integer("r".into(), 255, Optional),
+                    Integer("g".into(), 255, Optional),
+                    Integer("b".into(), 255, Optional),
                 ],
                 Content::localized("command-make_block-desc"),
                 Some(Admin),
@@ -804,58 +786,73 @@ impl ServerChatCommand {
             ServerChatCommand::MakeNpc => cmd(
                 vec![
                     AssetPath(
-                        "entity_config",
+                        "entity_config".into(),
                         "common.entity.",
                         ENTITY_CONFIGS.clone(),
                         Required,
                     ),
-                    Integer("num", 1, Optional),
+                    Integer("num".into(), 1, Optional),
                 ],
                 Content::localized("command-make_npc-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::MakeSprite => cmd(
-                vec![Enum("sprite", SPRITE_KINDS.clone(), Required)],
+                vec![Enum("sprite".into(), SPRITE_KINDS.clone(), Required)],
                 Content::localized("command-make_sprite-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Motd => cmd(vec![], Content::localized("command-motd-desc"), None),
             ServerChatCommand::Object => cmd(
-                vec![Enum("object", OBJECTS.clone(), Required)],
+                vec![Enum("object".into(), OBJECTS.clone(), Required)],
                 Content::localized("command-object-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Outcome => cmd(
-                vec![Enum("outcome", OUTCOME_KINDS.clone(), Required)],
+                vec![Enum("outcome".into(), OUTCOME_KINDS.clone(), Required)],
                 Content::localized("command-outcome-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::PermitBuild => cmd(
-                vec![Any("area_name", Required)],
+                vec![Any("area_name".into(), Required)],
                 Content::localized("command-permit_build-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Players => {
                 cmd(vec![], Content::localized("command-players-desc"), None)
             },
+            ServerChatCommand::Plugin => cmd(
+                vec![
+                    Enum(
+                        "action".into(),
+                        ["list", "reload", "unload"]
+                            .iter()
+                            .map(|s| s.to_string())
+                            .collect(),
+                        Required,
+                    ),
+                    Any("plugin".into(), Optional),
+                ],
+                Content::localized("command-plugin-desc"),
+                Some(Admin),
+            ),
             ServerChatCommand::Poise => cmd(
-                vec![Integer("poise", 100, Required)],
+                vec![Integer("poise".into(), 100, Required)],
                 Content::localized("command-poise-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Portal => cmd(
                 vec![
-                    Float("x", 0., Required),
-                    Float("y", 0., Required),
-                    Float("z", 0., Required),
-                    Boolean("requires_no_aggro", "true".to_string(), Optional),
-                    Float("buildup_time", 5., Optional),
+                    Float("x".into(), 0., Required),
+                    Float("y".into(), 0., Required),
+                    Float("z".into(), 0., Required),
+                    Boolean("requires_no_aggro".into(), "true".to_string(), Optional),
+                    Float("buildup_time".into(), 5., Optional),
                 ],
                 Content::localized("command-portal-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::ReloadChunks => cmd(
-                vec![Integer("chunk_radius", 6, Optional)],
+                vec![Integer("chunk_radius".into(), 6, Optional)],
                 Content::localized("command-reload_chunks-desc"),
                 Some(Admin),
             ),
@@ -865,12 +862,12 @@ impl ServerChatCommand {
                 Some(Admin),
             ),
             ServerChatCommand::RemoveLights => cmd(
-                vec![Float("radius", 20.0, Optional)],
+                vec![Float("radius".into(), 20.0, Optional)],
                 Content::localized("command-remove_lights-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::RevokeBuild => cmd(
-                vec![Any("area_name", Required)],
+                vec![Any("area_name".into(), Required)],
                 Content::localized("command-revoke_build-desc"),
                 Some(Admin),
             ),
@@ -885,7 +882,7 @@ impl ServerChatCommand {
                 None,
             ),
             ServerChatCommand::Safezone => cmd(
-                vec![Float("range", 100.0, Optional)],
+                vec![Float("range".into(), 100.0, Optional)],
                 Content::localized("command-safezone-desc"),
                 Some(Moderator),
             ),
@@ -897,25 +894,29 @@ impl ServerChatCommand {
             ServerChatCommand::ServerPhysics => cmd(
                 vec![
                     PlayerName(Required),
-                    Boolean("enabled", "true".to_string(), Optional),
+                    Boolean("enabled".into(), "true".to_string(), Optional),
                     Message(Optional),
                 ],
                 Content::localized("command-server_physics-desc"),
                 Some(Moderator),
             ),
             ServerChatCommand::SetMotd => cmd(
-                vec![Any("locale", Optional), Message(Optional)],
+                vec![Any("locale".into(), Optional), Message(Optional)],
                 Content::localized("command-set_motd-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::SetBodyType => cmd(
                 vec![
                     Enum(
-                        "body type",
+                        "body type".into(),
                         vec!["Female".to_string(), "Male".to_string()],
                         Required,
                     ),
-                    Boolean("permanent", "false".to_string(), Requirement::Optional),
+                    Boolean(
+                        "permanent".into(),
+                        "false".to_string(),
+                        Requirement::Optional,
+                    ),
                 ],
                 Content::localized("command-set_body_type-desc"),
                 Some(Admin),
@@ -923,7 +924,7 @@ impl ServerChatCommand {
             ServerChatCommand::Ship => cmd(
                 vec![
                     Enum(
-                        "kind",
+                        "kind".into(),
                         comp::ship::ALL_SHIPS
                             .iter()
                             .map(|b| format!("{b:?}"))
@@ -931,11 +932,11 @@ impl ServerChatCommand {
                         Optional,
                     ),
                     Boolean(
-                        "Whether the ship should be tethered to the target (or its mount)",
+                        "Whether the ship should be tethered to the target (or its mount)".into(),
                         "false".to_string(),
                         Optional,
                     ),
-                    Float("destination_degrees_ccw_of_east", 90.0, Optional),
+                    Float("destination_degrees_ccw_of_east".into(), 90.0, Optional),
                 ],
                 Content::localized("command-ship-desc"),
                 Some(Admin),
@@ -945,41 +946,63 @@ impl ServerChatCommand {
             ServerChatCommand::Site => cmd(
                 vec![
                     SiteName(Required),
-                    Boolean("Dismount from ship", "true".to_string(), Optional),
+                    Boolean("Dismount from ship".into(), "true".to_string(), Optional),
                 ],
                 Content::localized("command-site-desc"),
                 Some(Moderator),
             ),
             ServerChatCommand::SkillPoint => cmd(
                 vec![
-                    Enum("skill tree", SKILL_TREES.clone(), Required),
-                    Integer("amount", 1, Optional),
+                    Enum("skill tree".into(), SKILL_TREES.clone(), Required),
+                    Integer("amount".into(), 1, Optional),
                 ],
                 Content::localized("command-skill_point-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::SkillPreset => cmd(
-                vec![Enum("preset_name", PRESET_LIST.to_vec(), Required)],
+                vec![Enum("preset_name".into(), PRESET_LIST.to_vec(), Required)],
                 Content::localized("command-skill_preset-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Spawn => cmd(
                 vec![
-                    Enum("alignment", ALIGNMENTS.clone(), Required),
-                    Enum("entity", ENTITIES.clone(), Required),
-                    Integer("amount", 1, Optional),
-                    Boolean("ai", "true".to_string(), Optional),
-                    Float("scale", 1.0, Optional),
-                    Boolean("tethered", "false".to_string(), Optional),
+                    Enum("alignment".into(), ALIGNMENTS.clone(), Required),
+                    Enum("entity".into(), ENTITIES.clone(), Required),
+                    Integer("amount".into(), 1, Optional),
+                    Boolean("ai".into(), "true".to_string(), Optional),
+                    Float("scale".into(), 1.0, Optional),
+                    Boolean("tethered".into(), "false".to_string(), Optional),
                 ],
                 Content::localized("command-spawn-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Spot => cmd(
-                vec![Enum("Spot kind to find", SPOTS.clone(), Required)],
+                vec![Enum("Spot kind to find".into(), SPOTS.clone(), Required)],
                 Content::localized("command-spot-desc"),
                 Some(Admin),
             ),
+            ServerChatCommand::StructureCapture => cmd(
+                vec![
+                    Any("name".into(), Required),
+                    Integer("xlo".into(), 0, Required),
+                    Integer("xhi".into(), 10, Required),
+                    Integer("ylo".into(), 0, Required),
+                    Integer("yhi".into(), 10, Required),
+                    Integer("zlo".into(), 0, Required),
+                    Integer("zhi".into(), 10, Required),
+                ],
+                Content::localized("command-structure_capture-desc"),
+                Some(Admin),
+            ),
+            ServerChatCommand::StructurePaste => cmd(
+                vec![
+                    Any("name".into(), Required),
+                    Integer("rotation".into(), 0, Optional),
+                    Boolean("mirror".into(), "false".to_string(), Optional),
+                ],
+                Content::localized("command-structure_paste-desc"),
+                Some(Admin),
+            ),
             ServerChatCommand::Sudo => cmd(
                 vec![EntityTarget(Required), SubCommand],
                 Content::localized("command-sudo-desc"),
@@ -991,44 +1014,47 @@ impl ServerChatCommand {
                 None,
             ),
             ServerChatCommand::Time => cmd(
-                vec![Enum("time", TIMES.clone(), Optional)],
+                vec![Enum("time".into(), TIMES.clone(), Optional)],
                 Content::localized("command-time-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::TimeScale => cmd(
-                vec![Float("time scale", 1.0, Optional)],
+                vec![Float("time scale".into(), 1.0, Optional)],
                 Content::localized("command-time_scale-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Tp => cmd(
                 vec![
                     EntityTarget(Optional),
-                    Boolean("Dismount from ship", "true".to_string(), Optional),
+                    Boolean("Dismount from ship".into(), "true".to_string(), Optional),
                 ],
                 Content::localized("command-tp-desc"),
                 Some(Moderator),
             ),
             ServerChatCommand::RtsimTp => cmd(
                 vec![
-                    Integer("npc index", 0, Required),
-                    Boolean("Dismount from ship", "true".to_string(), Optional),
+                    Integer("npc index".into(), 0, Required),
+                    Boolean("Dismount from ship".into(), "true".to_string(), Optional),
                 ],
                 Content::localized("command-rtsim_tp-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::RtsimInfo => cmd(
-                vec![Integer("npc index", 0, Required)],
+                vec![Integer("npc index".into(), 0, Required)],
                 Content::localized("command-rtsim_info-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::RtsimNpc => cmd(
-                vec![Any("query", Required), Integer("max number", 20, Optional)],
+                vec![
+                    Any("query".into(), Required),
+                    Integer("max number".into(), 20, Optional),
+                ],
                 Content::localized("command-rtsim_npc-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::RtsimPurge => cmd(
                 vec![Boolean(
-                    "whether purging of rtsim data should occur on next startup",
+                    "whether purging of rtsim data should occur on next startup".into(),
                     true.to_string(),
                     Required,
                 )],
@@ -1064,7 +1090,7 @@ impl ServerChatCommand {
                 Some(Admin),
             ),
             ServerChatCommand::Whitelist => cmd(
-                vec![Any("add/remove", Required), PlayerName(Required)],
+                vec![Any("add/remove".into(), Required), PlayerName(Required)],
                 Content::localized("command-whitelist-desc"),
                 Some(Moderator),
             ),
@@ -1074,30 +1100,30 @@ impl ServerChatCommand {
                 None,
             ),
             ServerChatCommand::MakeVolume => cmd(
-                vec![Integer("size", 15, Optional)],
+                vec![Integer("size".into(), 15, Optional)],
                 Content::localized("command-make_volume-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::Location => cmd(
-                vec![Any("name", Required)],
+                vec![Any("name".into(), Required)],
                 Content::localized("command-location-desc"),
                 None,
             ),
             ServerChatCommand::CreateLocation => cmd(
-                vec![Any("name", Required)],
+                vec![Any("name".into(), Required)],
                 Content::localized("command-create_location-desc"),
                 Some(Moderator),
             ),
             ServerChatCommand::DeleteLocation => cmd(
-                vec![Any("name", Required)],
+                vec![Any("name".into(), Required)],
                 Content::localized("command-delete_location-desc"),
                 Some(Moderator),
             ),
             ServerChatCommand::WeatherZone => cmd(
                 vec![
-                    Enum("weather kind", WEATHERS.clone(), Required),
-                    Float("radius", 500.0, Optional),
-                    Float("time", 300.0, Optional),
+                    Enum("weather kind".into(), WEATHERS.clone(), Required),
+                    Float("radius".into(), 500.0, Optional),
+                    Float("time".into(), 300.0, Optional),
                 ],
                 Content::localized("command-weather_zone-desc"),
                 Some(Admin),
@@ -1109,15 +1135,15 @@ impl ServerChatCommand {
             ),
             ServerChatCommand::Scale => cmd(
                 vec![
-                    Float("factor", 1.0, Required),
-                    Boolean("reset_mass", true.to_string(), Optional),
+                    Float("factor".into(), 1.0, Required),
+                    Boolean("reset_mass".into(), true.to_string(), Optional),
                 ],
                 Content::localized("command-scale-desc"),
                 Some(Admin),
             ),
             ServerChatCommand::RepairEquipment => cmd(
                 vec![ArgumentSpec::Boolean(
-                    "repair inventory",
+                    "repair inventory".into(),
                     true.to_string(),
                     Optional,
                 )],
@@ -1127,7 +1153,7 @@ impl ServerChatCommand {
             ServerChatCommand::Tether => cmd(
                 vec![
                     EntityTarget(Required),
-                    Boolean("automatic length", "true".to_string(), Optional),
+                    Boolean("automatic length".into(), "true".to_string(), Optional),
                 ],
                 Content::localized("command-tether-desc"),
                 Some(Admin),
@@ -1207,6 +1233,7 @@ impl ServerChatCommand {
             ServerChatCommand::Outcome => "outcome",
             ServerChatCommand::PermitBuild => "permit_build",
             ServerChatCommand::Players => "players",
+            ServerChatCommand::Plugin => "plugin",
             ServerChatCommand::Poise => "poise",
             ServerChatCommand::Portal => "portal",
             ServerChatCommand::ResetRecipes => "reset_recipes",
@@ -1226,6 +1253,8 @@ impl ServerChatCommand {
             ServerChatCommand::SkillPreset => "skill_preset",
             ServerChatCommand::Spawn => "spawn",
             ServerChatCommand::Spot => "spot",
+            ServerChatCommand::StructureCapture => "structure_capture",
+            ServerChatCommand::StructurePaste => "structure_paste",
             ServerChatCommand::Sudo => "sudo",
             ServerChatCommand::Tell => "tell",
             ServerChatCommand::Time => "time",
@@ -1348,7 +1377,7 @@ impl FromStr for ServerChatCommand {
     }
 }
 
-#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum Requirement {
     Required,
     Optional,
@@ -1366,14 +1395,14 @@ pub enum ArgumentSpec {
     /// * label
     /// * suggested tab-completion
     /// * whether it's optional
-    Float(&'static str, f32, Requirement),
+    Float(Cow<'static, str>, f32, Requirement),
     /// The argument is an integer. The associated values are
     /// * label
     /// * suggested tab-completion
     /// * whether it's optional
-    Integer(&'static str, i32, Requirement),
+    Integer(Cow<'static, str>, i32, Requirement),
     /// The argument is any string that doesn't contain spaces
-    Any(&'static str, Requirement),
+    Any(Cow<'static, str>, Requirement),
     /// The argument is a command name (such as in /help)
     Command(Requirement),
     /// This is the final argument, consuming all characters until the end of
@@ -1385,20 +1414,20 @@ pub enum ArgumentSpec {
     /// * label
     /// * Predefined string completions
     /// * whether it's optional
-    Enum(&'static str, Vec<String>, Requirement),
+    Enum(Cow<'static, str>, Vec<String>, Requirement),
     /// The argument is an asset path. The associated values are
     /// * label
     /// * Path prefix shared by all assets
     /// * List of all asset paths as strings for completion
     /// * whether it's optional
-    AssetPath(&'static str, &'static str, Vec<String>, Requirement),
+    AssetPath(Cow<'static, str>, &'static str, Vec<String>, Requirement),
     /// The argument is likely a boolean. The associated values are
     /// * label
     /// * suggested tab-completion
     /// * whether it's optional
-    Boolean(&'static str, String, Requirement),
+    Boolean(Cow<'static, str>, String, Requirement),
     /// The argument is a flag that enables or disables a feature.
-    Flag(&'static str),
+    Flag(Cow<'static, str>),
 }
 
 impl ArgumentSpec {
@@ -1507,6 +1536,154 @@ impl ArgumentSpec {
     }
 }
 
+/// Description of a command registered by a plugin. Unlike
+/// [`ServerChatCommand`] it is only known at runtime, so the server sends it to
+/// clients for completion and `/help`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PluginCommandData {
+    pub keyword: String,
+    pub args: Vec<PluginArgumentSpec>,
+    pub description: String,
+    pub needs_role: Option<Role>,
+}
+
+/// Owned counterpart of [`ArgumentSpec`] for plugin commands, see there for
+/// the meaning of the associated values
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PluginArgumentSpec {
+    PlayerName(Requirement),
+    EntityTarget(Requirement),
+    SiteName(Requirement),
+    Float(String, f32, Requirement),
+    Integer(String, i32, Requirement),
+    Any(String, Requirement),
+    Command(Requirement),
+    Message(Requirement),
+    Enum(String, Vec<String>, Requirement),
+    Boolean(String, String, Requirement),
+    Flag(String),
+}
+
+impl PluginArgumentSpec {
+    pub fn to_argument_spec(&self) -> ArgumentSpec {
+        match self {
+            Self::PlayerName(req) => ArgumentSpec::PlayerName(*req),
+            Self::EntityTarget(req) => ArgumentSpec::EntityTarget(*req),
+            Self::SiteName(req) => ArgumentSpec::SiteName(*req),
+            Self::Float(label, x, req) => ArgumentSpec::Float(label.clone().into(), *x, *req),
+            Self::Integer(label, x, req) => ArgumentSpec::Integer(label.clone().into(), *x, *req),
+            Self::Any(label, req) => ArgumentSpec::Any(label.clone().into(), *req),
+            Self::Command(req) => ArgumentSpec::Command(*req),
+            Self::Message(req) => ArgumentSpec::Message(*req),
+            Self::Enum(label, options, req) => {
+                ArgumentSpec::Enum(label.clone().into(), options.clone(), *req)
+            },
+            Self::Boolean(label, x, req) => {
+                ArgumentSpec::Boolean(label.clone().into(), x.clone(), *req)
+            },
+            Self::Flag(label) => ArgumentSpec::Flag(label.clone().into()),
+        }
+    }
+
+    pub fn requirement(&self) -> Requirement {
+        match self {
+            Self::PlayerName(r)
+            | Self::EntityTarget(r)
+            | Self::SiteName(r)
+            | Self::Float(_, _, r)
+            | Self::Integer(_, _, r)
+            | Self::Any(_, r)
+            | Self::Command(r)
+            | Self::Message(r)
+            | Self::Enum(_, _, r)
+            | Self::Boolean(_, _, r) => *r,
+            Self::Flag(_) => Requirement::Optional,
+        }
+    }
+
+    /// Whether `arg` is a valid value for this argument
+    fn accepts(&self, arg: &str) -> bool {
+        match self {
+            Self::Float(..) => arg.parse::<f32>().is_ok(),
+            Self::Integer(..) => arg.parse::<i32>().is_ok(),
+            Self::Enum(_, options, _) => options.iter().any(|option| option == arg),
+            Self::Boolean(..) => arg.parse::<bool>().is_ok(),
+            Self::Flag(label) => arg == label,
+            Self::PlayerName(_)
+            | Self::EntityTarget(_)
+            | Self::SiteName(_)
+            | Self::Any(..)
+            | Self::Command(_)
+            | Self::Message(_) => true,
+        }
+    }
+}
+
+impl PluginCommandData {
+    pub fn data(&self) -> ChatCommandData {
+        ChatCommandData::new(
+            self.args
+                .iter()
+                .map(PluginArgumentSpec::to_argument_spec)
+                .collect(),
+            Content::Plain(self.description.clone()),
+            self.needs_role,
+        )
+    }
+
+    pub fn usage_string(&self) -> String {
+        std::iter::once(format!("/{}", self.keyword))
+            .chain(
+                self.args
+                    .iter()
+                    .map(|arg| arg.to_argument_spec().usage_string()),
+            )
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// A message that explains what the command does
+    pub fn help_content(&self) -> Content {
+        Content::localized_with_args("command-help-template", [
+            ("usage", Content::Plain(self.usage_string())),
+            ("description", Content::Plain(self.description.clone())),
+        ])
+    }
+
+    /// Check `args` against the argument specs before they are handed to the
+    /// plugin. Arguments are matched by position, optional ones may only be
+    /// left out at the end.
+    pub fn check_args(&self, args: &[String]) -> Result<(), String> {
+        for (i, spec) in self.args.iter().enumerate() {
+            if matches!(spec, PluginArgumentSpec::Message(_)) {
+                // Consumes all remaining arguments
+                return Ok(());
+            }
+            match args.get(i) {
+                Some(arg) if !spec.accepts(arg) => {
+                    return Err(format!(
+                        "Invalid value '{arg}' for {}",
+                        spec.to_argument_spec().usage_string()
+                    ));
+                },
+                Some(_) => {},
+                None if spec.requirement() == Requirement::Required => {
+                    return Err(format!(
+                        "Missing argument {}",
+                        spec.to_argument_spec().usage_string()
+                    ));
+                },
+                None => {},
+            }
+        }
+        if args.len() > self.args.len() {
+            Err("Too many arguments".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
 pub trait CommandEnumArg: FromStr {
     fn all_options() -> Vec<String>;
 }
@@ -1595,6 +1772,45 @@ mod tests {
         assert_eq!(list, list2);
     }
 
+    #[test]
+    fn plugin_command_args() {
+        let command = PluginCommandData {
+            keyword: "bounty".to_string(),
+            args: vec![
+                PluginArgumentSpec::PlayerName(Requirement::Required),
+                PluginArgumentSpec::Integer("amount".into().to_string(), 10, Requirement::Required),
+                PluginArgumentSpec::Enum(
+                    "currency".into().to_string(),
+                    vec!["coins".to_string(), "gems".to_string()],
+                    Requirement::Optional,
+                ),
+            ],
+            description: "Put a bounty on a player".to_string(),
+            needs_role: None,
+        };
+        let args = |args: &[&str]| args.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(command.check_args(&args(&["bob", "5"])), Ok(()));
+        assert_eq!(command.check_args(&args(&["bob", "5", "gems"])), Ok(()));
+        assert!(command.check_args(&args(&["bob"])).is_err());
+        assert!(command.check_args(&args(&["bob", "many"])).is_err());
+        assert!(command.check_args(&args(&["bob", "5", "shells"])).is_err());
+        assert!(
+            command
+                .check_args(&args(&["bob", "5", "gems", "now"]))
+                .is_err()
+        );
+        assert_eq!(
+            command
+                .data()
+                .args
+                .iter()
+                .map(ArgumentSpec::usage_string)
+                .collect::<Vec<_>>(),
+            vec!["<player>", "<amount>", "[currency]"]
+        );
+    }
+
     #[test]
     fn test_loading_skill_presets() {
         SkillPresetManifest::load_expect_combined_static(PRESET_MANIFEST_PATH);
@@ -239,6 +239,7 @@ pub struct UpdateCharacterDataEvent {
         Vec<(comp::Pet, comp::Body, comp::Stats)>,
         comp::ActiveAbilities,
         Option<comp::MapMarker>,
+        Option<rtsim::Companion>,
     ),
     pub metadata: UpdateCharacterMetadata,
 }
@@ -284,11 +285,24 @@ pub struct RequestPluginsEvent {
     pub plugins: Vec<PluginHash>,
 }
 
+/// Message from the client half of a plugin to its server half
+pub struct PluginMessageEvent {
+    pub entity: EcsEntity,
+    pub plugin: String,
+    pub channel: String,
+    pub data: Vec<u8>,
+}
+
 pub struct SetBattleModeEvent {
     pub entity: EcsEntity,
     pub battle_mode: BattleMode,
 }
 
+pub struct AbandonQuestEvent {
+    pub entity: EcsEntity,
+    pub quest: rtsim::QuestId,
+}
+
 // These events are generated in common systems in addition to server systems
 // (but note on the client the event buses aren't registered and these events
 // aren't actually emitted).
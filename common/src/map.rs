@@ -85,6 +85,7 @@ pub enum MarkerKind {
     Sahagin,
     VampireCastle,
     Myrmidon,
+    ProceduralDungeon,
     Character,
     Unknown,
 }
@@ -16,8 +16,10 @@
 use crate::{
     assets::AssetExt,
     character::CharacterId,
-    comp::{agent::FlightMode, inventory::item::ItemDef},
+    comp::{self, agent::FlightMode, inventory::item::ItemDef},
     map::Marker,
+    resources::Time,
+    trade::Good,
     util::Dir,
 };
 use common_i18n::Content;
@@ -285,11 +287,34 @@ pub enum NpcAction {
     /// Attack the given target
     Attack(Actor),
     Dialogue(Actor, Dialogue),
+    /// Invite the given target to trade
+    Trade(Actor),
 }
 
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DialogueId(pub u64);
 
+/// A quest that a character is involved in, as shown in the quest log of
+/// their player.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuestInfo {
+    pub id: QuestId,
+    /// The name of whoever the quest has to be reported back to, if known
+    pub contact: Option<String>,
+    /// What needs to be done to complete the quest
+    pub objective: Content,
+    /// The name of the site the quest leads to, if any
+    pub target_site: Option<String>,
+    /// Where the quest leads to, if anywhere
+    pub marker: Option<Marker>,
+    /// What gets paid out when the quest succeeds
+    pub reward: Option<(ItemResource, u32)>,
+    /// The time before which the quest has to be completed
+    pub timeout: Option<Time>,
+    /// `None` while the quest is in progress, otherwise whether it succeeded
+    pub resolution: Option<bool>,
+}
+
 // `IS_VALIDATED` denotes whether the server has validated this dialogue as
 // fulfilled. For example, a dialogue could promise to give the receiver items
 // from their inventory.
@@ -420,6 +445,32 @@ pub enum TerrainResource {
     Ore, // Iron, copper, etc.
 }
 
+/// The kinds of wildlife that rtsim keeps track of the populations of, per
+/// chunk.
+// Note: the `serde(name = "...")` is to minimise the length of field
+// identifiers for the sake of rtsim persistence
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, enum_map::Enum)]
+pub enum WildlifeKind {
+    /// Peaceful animals, which predators feed on.
+    #[serde(rename = "0")]
+    Prey,
+    /// Aggressive animals that hunt others.
+    #[serde(rename = "1")]
+    Predator,
+}
+
+impl WildlifeKind {
+    /// Classify an animal by its alignment. Wildlife that isn't hostile is
+    /// treated as prey.
+    pub fn from_alignment(alignment: &comp::Alignment) -> Option<Self> {
+        match alignment {
+            comp::Alignment::Wild => Some(Self::Prey),
+            comp::Alignment::Enemy => Some(Self::Predator),
+            _ => None,
+        }
+    }
+}
+
 /// Like [`TerrainResource`], but for tracking inventory items in rtsim for the
 /// sake of questing, trade, etc.
 ///
@@ -435,6 +486,14 @@ pub enum TerrainResource {
 pub enum ItemResource {
     #[serde(rename = "0")]
     Coin,
+    #[serde(rename = "1")]
+    Food,
+    #[serde(rename = "2")]
+    Wood,
+    #[serde(rename = "3")]
+    Stone,
+    #[serde(rename = "4")]
+    Ore,
 }
 
 impl ItemResource {
@@ -442,10 +501,39 @@ impl ItemResource {
     // TODO: Return (Arc<ItemDef>, f32) to allow for an exchange rate
     // TODO: Have this function take an `impl Rng` so that it can be stochastic
     pub fn to_equivalent_item_def(&self) -> Arc<ItemDef> {
-        match self {
-            Self::Coin => Arc::<ItemDef>::load_cloned("common.items.utility.coins").unwrap(),
+        let specifier = match self {
+            Self::Coin => "common.items.utility.coins",
+            Self::Food => "common.items.food.apple",
+            Self::Wood => "common.items.log.wood",
+            Self::Stone => "common.items.crafting_ing.rock",
+            Self::Ore => "common.items.mineral.ore.iron",
+        };
+        Arc::<ItemDef>::load_cloned(specifier).unwrap()
+    }
+
+    /// The resource that best represents a good traded by site economies, if
+    /// any.
+    pub fn from_good(good: Good) -> Option<Self> {
+        match good {
+            Good::Coin => Some(Self::Coin),
+            Good::Food | Good::Flour | Good::Meat => Some(Self::Food),
+            Good::Wood => Some(Self::Wood),
+            Good::Stone => Some(Self::Stone),
+            Good::Ingredients => Some(Self::Ore),
+            _ => None,
         }
     }
+
+    /// The name of the resource, as used in dialogue.
+    pub fn localize(&self) -> Content {
+        Content::localized(match self {
+            Self::Coin => "npc-goods-coin",
+            Self::Food => "npc-goods-food",
+            Self::Wood => "npc-goods-wood",
+            Self::Stone => "npc-goods-stone",
+            Self::Ore => "npc-goods-ore",
+        })
+    }
 }
 
 // Note: the `serde(name = "...")` is to minimise the length of field
@@ -493,6 +581,37 @@ pub enum Profession {
     Captain,
 }
 
+/// An order given to a companion by the actor that hired them.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum CompanionOrder {
+    /// Follow the hirer around.
+    #[default]
+    Follow,
+    /// Stay close to a position, fighting anything hostile that comes near.
+    Guard(Vec3<f32>),
+    /// Wait at a position until told otherwise.
+    Wait(Vec3<f32>),
+}
+
+/// A companion hired by a character, as kept with the character so that the
+/// companion can be restored should rtsim lose track of them.
+#[derive(Clone, Debug)]
+pub struct Companion {
+    /// The uid of the rtsim NPC of the companion.
+    pub uid: u64,
+    pub seed: u32,
+    pub body: comp::Body,
+    /// The rank of the companion as an adventurer, which determines their
+    /// loadout.
+    pub rank: u32,
+    pub exp: u32,
+    pub order: CompanionOrder,
+    /// How much longer the contract of the companion runs for, in seconds.
+    pub contract_left: f64,
+    /// The rtsim tick at which the companion was last known to rtsim.
+    pub rtsim_tick: u64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct WorldSettings {
     pub start_time: f64,
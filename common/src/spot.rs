@@ -1,8 +1,8 @@
 use common_assets::{AssetCombined, AssetHandle, Ron};
 use lazy_static::lazy_static;
+use serde::Deserialize;
 
-use crate::terrain::BiomeKind;
-use strum::EnumIter;
+use crate::{lottery::LootSpec, terrain::BiomeKind};
 
 /// Spots are localised structures that spawn in the world. Conceptually, they
 /// fit somewhere between the tree generator and the site generator: an attempt
@@ -10,53 +10,29 @@ use strum::EnumIter;
 /// They are not globally visible to the game: this means that they do not
 /// appear on the map, and cannot interact with rtsim (much).
 ///
-/// To add a new spot, one must:
-///
-/// 1. Add a new variant to the [`Spot`] enum.
-/// 2. Add a new entry to [`veloren-world::layer::spot::SpotGenerate::generate`]
-///    that tells the system where to generate your new spot.
-/// 3. Add a new arm to the `match` expression in
-///    [`veloren-world::layer::spot::apply_spots_to`] that tells the generator
-///    how to generate a spot, including the base structure that composes the
-///    spot and the entities that should be spawned there.
-///
-/// Only add spots with randomly spawned NPCs here. Spots that only use
-/// EntitySpawner blocks can be added in assets/world/manifests/spots.ron
-#[derive(Copy, Clone, Debug, EnumIter, PartialEq)]
-pub enum Spot {
-    DwarvenGrave,
-    SaurokAltar,
-    MyrmidonTemple,
-    GnarlingTotem,
-    WitchHouse,
-    GnomeSpring,
-    WolfBurrow,
-    Igloo,
-    //BanditCamp,
-    //EnchantedRock,
-    //TowerRuin,
-    //WellOfLight,
-    //MerchantOutpost,
-    //RuinedHuntingCabin, <-- Bears!
-    // *Random world objects*
-    LionRock,
-    TreeStumpForest,
-    DesertBones,
-    Arch,
-    AirshipCrash,
-    FruitTree,
-    Shipwreck,
-    Shipwreck2,
-    FallenTree,
-    GraveSmall,
-    JungleTemple,
-    SaurokTotem,
-    JungleOutpost,
-    #[strum(disabled)]
-    RonFile(&'static SpotProperties),
+/// Spots are defined in `assets/world/manifests/spots.ron`, see
+/// [`SpotProperties`] for what a definition consists of. Adding a new spot only
+/// requires adding an entry there.
+#[derive(Copy, Clone, Debug)]
+pub struct Spot(&'static SpotProperties);
+
+impl PartialEq for Spot {
+    fn eq(&self, other: &Self) -> bool { std::ptr::eq(self.0, other.0) }
+}
+
+impl Spot {
+    /// All the spots that are defined.
+    pub fn iter() -> impl Iterator<Item = Self> { RON_SPOT_PROPERTIES.0.iter().map(Self) }
+
+    /// Find a spot by its name.
+    pub fn from_name(name: &str) -> Option<Self> { Self::iter().find(|spot| spot.0.name == name) }
+
+    pub fn properties(&self) -> &'static SpotProperties { self.0 }
+
+    pub fn name(&self) -> &'static str { &self.0.name }
 }
 
-#[derive(serde::Deserialize, Clone, Debug, PartialEq)]
+#[derive(Deserialize, Clone, Debug, PartialEq)]
 pub enum SpotCondition {
     MaxGradient(f32),
     Biome(Vec<BiomeKind>),
@@ -75,12 +51,43 @@ pub enum SpotCondition {
     Any(Vec<SpotCondition>),
 }
 
-#[derive(serde::Deserialize, Clone, Debug, PartialEq)]
+/// Entities that spawn around a spot.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct SpotEntity {
+    /// The entity config to spawn the entities from.
+    pub entity: String,
+    /// The smallest and largest number of entities to spawn, inclusive.
+    pub count: (u32, u32),
+    /// Loot dropped by the entities, instead of that of their entity config.
+    #[serde(default)]
+    pub loot: Option<LootSpec<String>>,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
 pub struct SpotProperties {
+    /// The name of the spot, as used by the `/spot` command.
+    pub name: String,
+    /// The manifest of base structures that the spot is made of, relative to
+    /// `world.manifests`. One of them is chosen for each spot.
     pub base_structures: String,
+    /// How many times the spot is attempted to be placed per square kilometre
+    /// of world area.
     pub freq: f32,
+    /// Where the spot may be placed.
     pub condition: SpotCondition,
+    /// Whether trees and other trivial structures may spawn around the spot.
     pub spawn: bool,
+    /// The maximum distance from the centre of the spot that entities will
+    /// spawn at.
+    #[serde(default = "SpotProperties::default_entity_radius")]
+    pub entity_radius: f32,
+    /// The entities that spawn around the spot, from closest to furthest.
+    #[serde(default)]
+    pub entities: Vec<SpotEntity>,
+}
+
+impl SpotProperties {
+    fn default_entity_radius() -> f32 { 1.0 }
 }
 
 pub type RonSpots = Ron<Vec<SpotProperties>>;
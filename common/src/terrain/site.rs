@@ -22,6 +22,7 @@ pub enum DungeonKindMeta {
     Myrmidon,
     VampireCastle,
     DwarvenMine,
+    Procedural,
 }
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
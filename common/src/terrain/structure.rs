@@ -1,4 +1,4 @@
-use super::{BlockKind, SpriteCfg, StructureSprite};
+use super::{Block, BlockKind, SpriteCfg, StructureSprite, sprite};
 use crate::{
     assets::{self, AssetCache, AssetExt, AssetHandle, BoxedError, DotVox, Ron, SharedString},
     make_case_elim,
@@ -8,8 +8,13 @@ use crate::{
 use common_i18n::Content;
 use dot_vox::DotVoxData;
 use hashbrown::HashMap;
-use serde::Deserialize;
-use std::{num::NonZeroU8, sync::Arc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    num::NonZeroU8,
+    sync::Arc,
+};
 use vek::*;
 
 make_case_elim!(
@@ -61,6 +66,8 @@ make_case_elim!(
         RedwoodWood = 39,
         SpriteWithCfg(sprite: StructureSprite, sprite_cfg: SpriteCfg) = 40,
         Choice(block_table: Vec<(f32, StructureBlock)>) = 41,
+        Block(block: Block) = 42,
+        BlockWithCfg(block: Block, sprite_cfg: SpriteCfg) = 43,
     }
 );
 
@@ -250,6 +257,254 @@ fn default_custom_indices() -> HashMap<u8, StructureBlock> {
         .collect()
 }
 
+/// A block of a captured structure, stored exactly as it was in the world.
+///
+/// Manifests are read back as the [`StructureBlock`] variants of the same
+/// names, so captured structures can be used like any other.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum CapturedBlock {
+    Block(Block),
+    BlockWithCfg(Block, SpriteCfg),
+}
+
+impl CapturedBlock {
+    pub fn block(&self) -> Block {
+        match self {
+            Self::Block(block) | Self::BlockWithCfg(block, _) => *block,
+        }
+    }
+
+    pub fn sprite_cfg(&self) -> Option<&SpriteCfg> {
+        match self {
+            Self::Block(_) => None,
+            Self::BlockWithCfg(_, sprite_cfg) => Some(sprite_cfg),
+        }
+    }
+}
+
+/// A structure manifest entry, in the format that [`Structure::load_group`]
+/// reads, for a captured structure.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CapturedSpec {
+    pub specifier: String,
+    pub center: [i32; 3],
+    pub custom_indices: BTreeMap<u8, CapturedBlock>,
+}
+
+#[derive(Debug)]
+pub enum CaptureError {
+    /// Part of the region could not be read, usually because it isn't loaded.
+    OutOfBounds(Vec3<i32>),
+    /// The region is larger than `.vox` files allow along some axis.
+    TooLarge,
+    /// The region has more distinct blocks than fit in a `.vox` palette.
+    TooManyBlocks,
+}
+
+/// A region of the world captured as a structure, which can be saved as a
+/// `.vox` model with a manifest, and placed back into the world.
+///
+/// Every distinct block takes up an entry of the palette, as do sprites with
+/// a [`SpriteCfg`], since those can't be compared. Empty air is left out, such
+/// that placing the structure keeps whatever was there before.
+pub struct CapturedStructure {
+    center: Vec3<i32>,
+    base: BaseStructure<Option<CapturedBlock>>,
+}
+
+impl CapturedStructure {
+    /// The largest size of a captured structure along any axis.
+    pub const MAX_SIZE: u32 = 256;
+
+    /// Capture the blocks of `vol` within `aabb`. The center of the structure
+    /// is in the middle of its bottom layer.
+    pub fn capture<V: ReadVol<Vox = Block>>(
+        vol: &V,
+        aabb: Aabb<i32>,
+        sprite_cfg_at: impl Fn(Vec3<i32>) -> Option<SpriteCfg>,
+    ) -> Result<Self, CaptureError> {
+        let size = Vec3::<i32>::from(aabb.size()).map(|e| e.max(0) as u32);
+        if size.reduce_max() > Self::MAX_SIZE {
+            return Err(CaptureError::TooLarge);
+        }
+
+        let mut palette = std::array::from_fn(|_| None);
+        let mut indices = HashMap::<Block, NonZeroU8>::new();
+        let mut next_index = 1u8..=255;
+        let mut vol_out = Dyna::filled(size, None, ());
+        for rpos in (0..size.x as i32).flat_map(|x| {
+            (0..size.y as i32)
+                .flat_map(move |y| (0..size.z as i32).map(move |z| Vec3::new(x, y, z)))
+        }) {
+            let wpos = aabb.min + rpos;
+            let block = *vol.get(wpos).map_err(|_| CaptureError::OutOfBounds(wpos))?;
+            if block == Block::empty() {
+                continue;
+            }
+
+            let index = match sprite_cfg_at(wpos) {
+                Some(sprite_cfg) => {
+                    let index = next_index.next().ok_or(CaptureError::TooManyBlocks)?;
+                    palette[index as usize] = Some(CapturedBlock::BlockWithCfg(block, sprite_cfg));
+                    index
+                },
+                None => match indices.get(&block) {
+                    Some(index) => index.get(),
+                    None => {
+                        let index = next_index.next().ok_or(CaptureError::TooManyBlocks)?;
+                        palette[index as usize] = Some(CapturedBlock::Block(block));
+                        indices.insert(block, NonZeroU8::new(index).unwrap());
+                        index
+                    },
+                },
+            };
+            let _ = vol_out.set(rpos, NonZeroU8::new(index));
+        }
+
+        Ok(Self {
+            center: Vec3::new(size.x as i32 / 2, size.y as i32 / 2, 0),
+            base: BaseStructure {
+                vol: vol_out,
+                palette,
+            },
+        })
+    }
+
+    /// Rebuild a captured structure from its model and manifest entry.
+    pub fn from_vox(dot_vox_data: &DotVoxData, spec: CapturedSpec) -> Self {
+        let mut base = load_base_structure(dot_vox_data, |_| None);
+        for (index, block) in spec.custom_indices {
+            base.palette[index as usize] = Some(block);
+        }
+        Self {
+            center: Vec3::from(spec.center),
+            base,
+        }
+    }
+
+    pub fn size(&self) -> Vec3<u32> { self.base.vol.size() }
+
+    /// The manifest entry of the structure, given the specifier of its model.
+    pub fn spec(&self, specifier: String) -> CapturedSpec {
+        CapturedSpec {
+            specifier,
+            center: self.center.into_array(),
+            custom_indices: self
+                .base
+                .palette
+                .iter()
+                .enumerate()
+                .filter_map(|(index, block)| Some((index as u8, block.clone()?)))
+                .collect(),
+        }
+    }
+
+    /// The blocks of the structure relative to its center, after mirroring
+    /// it along the x axis (if `mirror` is set) and then rotating it by
+    /// `rotation` quarter turns counter-clockwise. The orientation of sprites
+    /// is transformed along with their position.
+    pub fn blocks(
+        &self,
+        rotation: u8,
+        mirror: bool,
+    ) -> impl Iterator<Item = (Vec3<i32>, Block, Option<&SpriteCfg>)> + '_ {
+        let size = self.size().map(|e| e as i32);
+        (0..size.x)
+            .flat_map(move |x| {
+                (0..size.y).flat_map(move |y| (0..size.z).map(move |z| Vec3::new(x, y, z)))
+            })
+            .filter_map(move |pos| {
+                let index = (*self.base.vol.get(pos).ok()?)?;
+                let block = self.base.palette[index.get() as usize].as_ref()?;
+
+                let mut rpos = pos - self.center;
+                if mirror {
+                    rpos.x = -rpos.x;
+                }
+                for _ in 0..rotation % 4 {
+                    rpos = Vec3::new(-rpos.y, rpos.x, rpos.z);
+                }
+                Some((
+                    rpos,
+                    transform_block(block.block(), rotation, mirror),
+                    block.sprite_cfg(),
+                ))
+            })
+    }
+
+    /// Write the structure as a MagicaVoxel `.vox` model, which
+    /// [`Structure::load_group`] can load along with the manifest entry from
+    /// [`Self::spec`].
+    pub fn write_vox(&self, mut out: impl Write) -> io::Result<()> {
+        let size = self.size();
+        let mut voxels = Vec::new();
+        for z in 0..size.z as i32 {
+            for y in 0..size.y as i32 {
+                for x in 0..size.x as i32 {
+                    if let Ok(Some(index)) = self.base.vol.get(Vec3::new(x, y, z)) {
+                        voxels.extend([x as u8, y as u8, z as u8, index.get()]);
+                    }
+                }
+            }
+        }
+
+        let size_content = [size.x, size.y, size.z]
+            .iter()
+            .flat_map(|e| e.to_le_bytes())
+            .collect::<Vec<_>>();
+        let mut xyzi_content = ((voxels.len() / 4) as u32).to_le_bytes().to_vec();
+        xyzi_content.extend(voxels);
+        // Entry `i` of the palette is used by voxels with index `i + 1`, and the
+        // last entry is unused
+        let rgba_content = self.base.palette[1..]
+            .iter()
+            .map(|block| {
+                let col = block
+                    .as_ref()
+                    .and_then(|block| block.block().get_color())
+                    .unwrap_or(Rgb::broadcast(128));
+                [col.r, col.g, col.b, 255]
+            })
+            .chain([[0; 4]])
+            .flatten()
+            .collect::<Vec<_>>();
+
+        let mut children = Vec::new();
+        write_vox_chunk(&mut children, b"SIZE", &size_content, &[])?;
+        write_vox_chunk(&mut children, b"XYZI", &xyzi_content, &[])?;
+        write_vox_chunk(&mut children, b"RGBA", &rgba_content, &[])?;
+
+        out.write_all(b"VOX ")?;
+        out.write_all(&150u32.to_le_bytes())?;
+        write_vox_chunk(&mut out, b"MAIN", &[], &children)
+    }
+}
+
+fn write_vox_chunk(
+    out: &mut impl Write,
+    id: &[u8; 4],
+    content: &[u8],
+    children: &[u8],
+) -> io::Result<()> {
+    out.write_all(id)?;
+    out.write_all(&(content.len() as u32).to_le_bytes())?;
+    out.write_all(&(children.len() as u32).to_le_bytes())?;
+    out.write_all(content)?;
+    out.write_all(children)
+}
+
+/// Turn the sprite of a block along with the structure that it is part of.
+fn transform_block(mut block: Block, rotation: u8, mirror: bool) -> Block {
+    if let Ok(sprite::Ori(ori)) = block.get_attr::<sprite::Ori>() {
+        let ori = if mirror { (8 - ori) % 8 } else { ori };
+        let _ = block.set_attr(sprite::Ori((ori + rotation % 4 * 2) % 8));
+    }
+    if mirror && let Ok(sprite::MirrorX(mirrored)) = block.get_attr::<sprite::MirrorX>() {
+        let _ = block.set_attr(sprite::MirrorX(!mirrored));
+    }
+    block
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,7 +512,7 @@ mod tests {
         assets,
         generation::tests::validate_entity_config,
         lottery::{LootSpec, tests::validate_loot_spec},
-        terrain::Block,
+        terrain::SpriteKind,
     };
 
     pub fn validate_sprite_and_cfg(sprite: StructureSprite, sprite_cfg: &SpriteCfg) {
@@ -358,6 +613,8 @@ Sprite in question: {sprite:?}
             | StructureBlock::TerracottaKeyhole { .. } => {},
             // TODO: requires access to i18n for validation
             StructureBlock::Sign { .. } => {},
+            // Captured from the world, so these are valid as they are
+            StructureBlock::Block { .. } | StructureBlock::BlockWithCfg { .. } => {},
         }
     }
 
@@ -389,4 +646,68 @@ Sprite in question: {sprite:?}
             }
         }
     }
+
+    fn captured_blocks(structure: &CapturedStructure) -> Vec<(Vec3<i32>, Block, Option<String>)> {
+        structure
+            .blocks(1, true)
+            .map(|(pos, block, cfg)| (pos, block, cfg.and_then(|cfg| cfg.loot_table.clone())))
+            .collect()
+    }
+
+    #[test]
+    fn captured_structures_round_trip() {
+        let stone = Block::new(BlockKind::Rock, Rgb::new(100, 100, 100));
+        let chest = Block::air(SpriteKind::Chest);
+        let mut vol = Dyna::<Block, ()>::filled(Vec3::new(4, 3, 2), Block::empty(), ());
+        let _ = vol.set(Vec3::new(0, 0, 0), stone);
+        let _ = vol.set(Vec3::new(3, 2, 0), stone);
+        let _ = vol.set(Vec3::new(1, 1, 1), chest);
+
+        let captured = CapturedStructure::capture(
+            &vol,
+            Aabb {
+                min: Vec3::zero(),
+                max: Vec3::new(4, 3, 2),
+            },
+            |pos| {
+                (pos == Vec3::new(1, 1, 1)).then(|| SpriteCfg {
+                    loot_table: Some("common.loot_tables.dungeon.sahagin.chest".to_string()),
+                    ..SpriteCfg::default()
+                })
+            },
+        )
+        .unwrap();
+        let mut bytes = Vec::new();
+        captured.write_vox(&mut bytes).unwrap();
+        let dot_vox_data = dot_vox::load_bytes(&bytes).unwrap();
+        let loaded = CapturedStructure::from_vox(&dot_vox_data, captured.spec("test".to_string()));
+
+        assert_eq!(loaded.size(), Vec3::new(4, 3, 2));
+        assert_eq!(captured_blocks(&loaded).len(), 3);
+        assert_eq!(captured_blocks(&loaded), captured_blocks(&captured));
+    }
+
+    #[test]
+    fn captured_structures_are_transformed() {
+        let stone = Block::new(BlockKind::Rock, Rgb::new(100, 100, 100));
+        let mut vol = Dyna::<Block, ()>::filled(Vec3::new(4, 3, 1), Block::empty(), ());
+        // One block east of the center
+        let _ = vol.set(Vec3::new(3, 1, 0), stone);
+        let captured = CapturedStructure::capture(
+            &vol,
+            Aabb {
+                min: Vec3::zero(),
+                max: Vec3::new(4, 3, 1),
+            },
+            |_| None,
+        )
+        .unwrap();
+
+        let pos = |rotation, mirror| captured.blocks(rotation, mirror).next().unwrap().0;
+        assert_eq!(pos(0, false), Vec3::new(1, 0, 0));
+        assert_eq!(pos(1, false), Vec3::new(0, 1, 0));
+        assert_eq!(pos(2, false), Vec3::new(-1, 0, 0));
+        assert_eq!(pos(0, true), Vec3::new(-1, 0, 0));
+        assert_eq!(pos(1, true), Vec3::new(0, -1, 0));
+    }
 }
@@ -175,6 +175,9 @@ impl<V: RectRasterableVol> VolGrid2d<V> {
     #[inline(always)]
     pub fn chunk_size() -> Vec2<u32> { V::RECT_SIZE }
 
+    /// The base 2 logarithm of the size of the world, in chunks.
+    pub fn map_size_lg(&self) -> MapSizeLg { self.map_size_lg }
+
     pub fn insert(&mut self, key: Vec2<i32>, chunk: Arc<V>) -> Option<Arc<V>> {
         self.chunks.insert(key, chunk)
     }
@@ -9,9 +9,20 @@ pub enum PluginError {
     Encoding(Box<DecodeError>),
     PluginModuleError(String, String, PluginModuleError),
     ProcessExit,
+    Watch(notify::Error),
+    /// A reload changed or removed assets which may already be loaded, only
+    /// code and new assets can be reloaded
+    AssetsChanged(Vec<std::path::PathBuf>),
 }
 
 #[derive(Debug)]
 pub enum PluginModuleError {
     Wasmtime(wasmtime::Error),
+    /// The call used up its fuel, e.g. because it was stuck in a loop
+    OutOfFuel,
+    /// The call was interrupted for running longer than its time budget
+    TimedOut,
+    /// The module trapped or exceeded its budget too often and won't be
+    /// called anymore
+    Disabled,
 }
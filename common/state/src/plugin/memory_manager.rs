@@ -5,7 +5,8 @@ use common::{
 };
 use core::ptr::NonNull;
 use specs::{
-    Component, Entities, Entity, Read, ReadStorage, WriteStorage, storage::GenericReadStorage,
+    Component, Entities, Entity, Read, ReadStorage, World, WorldExt, WriteStorage,
+    storage::GenericReadStorage,
 };
 use tracing::error;
 
@@ -17,6 +18,37 @@ pub struct EcsWorld<'a, 'b> {
     pub id_maps: &'b Read<'a, IdMaps>,
 }
 
+impl<'a, 'b> EcsWorld<'a, 'b> {
+    /// Build the view of the world given to plugins from storages that have
+    /// already been fetched, e.g. by a system.
+    pub fn new(
+        entities: &'b Entities<'a>,
+        health: impl Into<EcsComponentAccess<'a, 'b, Health>>,
+        uid: impl Into<EcsComponentAccess<'a, 'b, Uid>>,
+        player: impl Into<EcsComponentAccess<'a, 'b, Player>>,
+        id_maps: &'b Read<'a, IdMaps>,
+    ) -> Self {
+        Self {
+            entities,
+            health: health.into(),
+            uid: uid.into(),
+            player: player.into(),
+            id_maps,
+        }
+    }
+}
+
+/// Run `f` with the view of the world given to plugins, fetching what it needs
+/// from `ecs`.
+pub fn with_ecs_world<T>(ecs: &World, f: impl FnOnce(&EcsWorld) -> T) -> T {
+    let entities = ecs.entities();
+    let health = ecs.read_storage::<Health>();
+    let uid = ecs.read_storage::<Uid>();
+    let player = ecs.read_storage::<Player>();
+    let id_maps = ecs.read_resource::<IdMaps>().into();
+    f(&EcsWorld::new(&entities, &health, &uid, &player, &id_maps))
+}
+
 pub enum EcsComponentAccess<'a, 'b, T: Component> {
     Read(&'b ReadStorage<'a, T>),
     ReadOwned(ReadStorage<'a, T>),
@@ -1,22 +1,36 @@
 pub mod errors;
 pub mod memory_manager;
 pub mod module;
+pub mod storage;
 
 use bincode::error::DecodeError;
-use common::{assets::ASSETS_PATH, event::PluginHash, uid::Uid};
+use common::{
+    assets::ASSETS_PATH,
+    cmd::PluginCommandData,
+    combat::DamageSource,
+    comp::BuffKind,
+    event::PluginHash,
+    terrain::{Block, TerrainChunk},
+    uid::Uid,
+};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     fs,
     io::{Read, Write},
     path::{Path, PathBuf},
+    sync::{Arc, Mutex, mpsc},
+    time::{Duration, Instant},
 };
-use tracing::{error, info};
+use tracing::{error, info, warn};
+use vek::{Vec2, Vec3};
 
 use self::{
     errors::{PluginError, PluginModuleError},
     memory_manager::EcsWorld,
     module::PluginModule,
+    storage::{PluginStorage, StorageChange},
 };
 
 use sha2::Digest;
@@ -26,6 +40,89 @@ pub struct PluginData {
     name: String,
     modules: HashSet<PathBuf>,
     dependencies: HashSet<String>,
+    /// Host functions modifying the world which this plugin may call
+    #[serde(default)]
+    capabilities: HashSet<Capability>,
+}
+
+/// Capabilities a plugin has to declare in its `plugin.toml` before it is
+/// allowed to call the matching world modifying host function
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Capability {
+    GiveItem,
+    Teleport,
+    ApplyBuff,
+    SetBlock,
+    SpawnNpc,
+    /// Modify chunks after they were generated
+    Worldgen,
+}
+
+/// Upper limit for the amount of items given by a single call
+pub const MAX_GIVE_AMOUNT: u32 = 2000;
+
+/// Altitudes that plugins may set blocks at, so that a single edit can't make
+/// a chunk grow to an arbitrary height
+pub const PLUGIN_BLOCK_ALTS: std::ops::Range<i32> = -1024..4096;
+
+/// World modifications requested by a plugin. They are queued during the
+/// plugin call and applied by the server afterwards.
+#[derive(Clone, Debug)]
+pub enum PluginAction {
+    GiveItem {
+        target: Uid,
+        item: String,
+        amount: u32,
+    },
+    Teleport {
+        target: Uid,
+        position: Vec3<f32>,
+    },
+    ApplyBuff {
+        target: Uid,
+        kind: BuffKind,
+        strength: f32,
+        duration: Option<f32>,
+    },
+    SetBlock {
+        position: Vec3<i32>,
+        block: Block,
+    },
+    SpawnNpc {
+        entity_config: String,
+        position: Vec3<f32>,
+    },
+}
+
+/// Terrain properties of a column handed to the worldgen hook
+#[derive(Clone, Copy, Debug)]
+pub struct ColumnInfo {
+    pub alt: f32,
+    pub water_alt: f32,
+    pub temp: f32,
+    pub humidity: f32,
+    pub rockiness: f32,
+    pub tree_density: f32,
+}
+
+/// Looks up the [`ColumnInfo`] of a world position, `None` outside of the
+/// world
+pub type ColumnInfoFn = Arc<dyn Fn(Vec2<i32>) -> Option<ColumnInfo> + Send + Sync>;
+
+/// Message from one half of a plugin to its other half, queued during the
+/// plugin call and sent by the server or client afterwards.
+#[derive(Clone, Debug)]
+pub enum PluginMessage {
+    ToClient {
+        player: Uid,
+        channel: String,
+        data: Vec<u8>,
+    },
+    ToServer {
+        channel: String,
+        data: Vec<u8>,
+    },
 }
 
 fn compute_hash(data: &[u8]) -> PluginHash {
@@ -69,12 +166,42 @@ pub fn find_cached(base_dir: &Path, hash: &PluginHash) -> Result<PathBuf, std::i
     }
 }
 
+/// Entity lifecycle and combat events forwarded to plugins which export the
+/// `entity-events` interface
+#[derive(Clone, Debug)]
+pub enum EntityEvent {
+    Spawn(Uid),
+    Despawn(Uid),
+    Damage {
+        target: Uid,
+        attacker: Option<Uid>,
+        amount: f32,
+        source: Option<DamageSource>,
+        precise: bool,
+    },
+    Death {
+        entity: Uid,
+        killer: Option<Uid>,
+    },
+    PlayerLeave {
+        name: String,
+        player: Uid,
+    },
+}
+
 pub struct Plugin {
     data: PluginData,
+    /// Sorted by module path, so the state of a module can be matched up
+    /// again after a hot reload
     modules: Vec<PluginModule>,
     hash: PluginHash,
     path: PathBuf,
     data_buf: Vec<u8>,
+    /// The hashes of the assets in the archive, everything but the manifest
+    /// and the modules
+    assets: HashMap<PathBuf, PluginHash>,
+    /// Bare `.wasm` modules are only run locally and never sent to clients
+    local_only: bool,
 }
 
 impl Plugin {
@@ -108,16 +235,24 @@ impl Plugin {
         )
         .map_err(PluginError::Toml)?;
 
-        let modules = data
-            .modules
-            .iter()
+        let mut module_paths = data.modules.iter().collect::<Vec<_>>();
+        module_paths.sort();
+        let modules = module_paths
+            .into_iter()
             .map(|path| {
                 let wasm_data = files.remove(path).ok_or(PluginError::NoSuchModule)?;
-                PluginModule::new(data.name.to_owned(), &wasm_data).map_err(|e| {
-                    PluginError::PluginModuleError(data.name.to_owned(), "<init>".to_owned(), e)
-                })
+                PluginModule::new(data.name.to_owned(), &wasm_data, &data.capabilities).map_err(
+                    |e| {
+                        PluginError::PluginModuleError(data.name.to_owned(), "<init>".to_owned(), e)
+                    },
+                )
             })
             .collect::<Result<_, _>>()?;
+        files.remove(Path::new("plugin.toml"));
+        let assets = files
+            .into_iter()
+            .map(|(path, data)| (path, compute_hash(&data)))
+            .collect();
 
         let data_buf = fs::read(&path_buf).map_err(PluginError::Io)?;
 
@@ -127,9 +262,82 @@ impl Plugin {
             hash: shasum,
             path: path_buf,
             data_buf,
+            assets,
+            local_only: false,
+        })
+    }
+
+    /// Load a single `.wasm` module as a plugin without manifest, it can't
+    /// use any capabilities and won't be sent to clients
+    pub fn from_wasm_path(path_buf: PathBuf) -> Result<Self, PluginError> {
+        let wasm_data = fs::read(&path_buf).map_err(PluginError::Io)?;
+        let file_name = path_buf
+            .file_name()
+            .map(PathBuf::from)
+            .ok_or(PluginError::NoSuchModule)?;
+        let name = path_buf
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .ok_or(PluginError::NoSuchModule)?;
+        let data = PluginData {
+            name,
+            modules: HashSet::from([file_name]),
+            dependencies: HashSet::new(),
+            capabilities: HashSet::new(),
+        };
+        let module = PluginModule::new(data.name.to_owned(), &wasm_data, &data.capabilities)
+            .map_err(|e| {
+                PluginError::PluginModuleError(data.name.to_owned(), "<init>".to_owned(), e)
+            })?;
+
+        Ok(Plugin {
+            data,
+            modules: vec![module],
+            hash: compute_hash(&wasm_data),
+            path: path_buf,
+            data_buf: wasm_data,
+            assets: HashMap::new(),
+            local_only: true,
         })
     }
 
+    /// Load either a `.plugin.tar` archive or a bare `.wasm` module
+    pub fn from_file(path_buf: PathBuf) -> Result<Self, PluginError> {
+        if is_wasm_module(&path_buf) {
+            Self::from_wasm_path(path_buf)
+        } else {
+            Self::from_path(path_buf)
+        }
+    }
+
+    pub fn name(&self) -> &str { &self.data.name }
+
+    pub fn module_count(&self) -> usize { self.modules.len() }
+
+    pub fn set_budget(&mut self, budget: PluginBudget) {
+        self.modules
+            .iter_mut()
+            .for_each(|module| module.set_budget(budget));
+    }
+
+    fn set_storage(&mut self, storage: &Arc<Mutex<PluginStorage>>) {
+        self.modules
+            .iter_mut()
+            .for_each(|module| module.set_storage(Arc::clone(storage)));
+    }
+
+    /// A plugin counts as disabled once any of its modules got disabled
+    pub fn is_disabled(&mut self) -> bool {
+        self.modules.iter_mut().any(|module| module.is_disabled())
+    }
+
+    pub fn total_faults(&mut self) -> u64 {
+        self.modules
+            .iter_mut()
+            .map(|module| module.total_faults())
+            .sum()
+    }
+
     pub fn load_event(
         &mut self,
         ecs: &EcsWorld,
@@ -140,6 +348,14 @@ impl Plugin {
             .try_for_each(|module| module.load_event(ecs, mode))
     }
 
+    /// Commands registered by all modules of this plugin
+    pub fn commands(&self) -> Vec<PluginCommandData> {
+        self.modules
+            .iter()
+            .flat_map(|module| module.commands())
+            .collect()
+    }
+
     pub fn command_event(
         &mut self,
         ecs: &EcsWorld,
@@ -162,6 +378,79 @@ impl Plugin {
         result
     }
 
+    pub fn entity_event(
+        &mut self,
+        ecs: &EcsWorld,
+        event: &EntityEvent,
+    ) -> Result<(), PluginModuleError> {
+        self.modules
+            .iter_mut()
+            .try_for_each(|module| module.entity_event(ecs, event))
+    }
+
+    /// Take all world modifications this plugin requested since the last call
+    pub fn take_actions(&mut self) -> Vec<PluginAction> {
+        self.modules
+            .iter_mut()
+            .flat_map(|module| module.take_actions())
+            .collect()
+    }
+
+    /// Take all messages this plugin sent since the last call
+    pub fn take_messages(&mut self) -> Vec<PluginMessage> {
+        self.modules
+            .iter_mut()
+            .flat_map(|module| module.take_messages())
+            .collect()
+    }
+
+    pub fn message_event(
+        &mut self,
+        ecs: &EcsWorld,
+        sender: Option<Uid>,
+        channel: &str,
+        data: &[u8],
+    ) -> Result<(), PluginModuleError> {
+        self.modules
+            .iter_mut()
+            .try_for_each(|module| module.message_event(ecs, sender, channel, data))
+    }
+
+    pub fn chunk_generated(
+        &mut self,
+        key: Vec2<i32>,
+        chunk: &mut TerrainChunk,
+        column_info: &ColumnInfoFn,
+    ) -> Result<(), PluginModuleError> {
+        self.modules
+            .iter_mut()
+            .try_for_each(|module| module.chunk_generated(key, chunk, column_info))
+    }
+
+    /// Collect the state each module wants to keep across a hot reload
+    pub fn save_state(&mut self, ecs: &EcsWorld) -> Vec<Option<Vec<u8>>> {
+        self.modules
+            .iter_mut()
+            .map(|module| {
+                module.save_state(ecs).unwrap_or_else(|e| {
+                    warn!(?e, "Plugin '{}' failed to save its state", module.name());
+                    None
+                })
+            })
+            .collect()
+    }
+
+    /// Hand state saved by [`Plugin::save_state`] to the matching modules
+    pub fn restore_state(&mut self, ecs: &EcsWorld, state: Vec<Option<Vec<u8>>>) {
+        for (module, state) in self.modules.iter_mut().zip(state) {
+            if let Some(state) = state
+                && let Err(e) = module.restore_state(ecs, &state)
+            {
+                warn!(?e, "Plugin '{}' failed to restore its state", module.name());
+            }
+        }
+    }
+
     /// get the path to the plugin file
     pub fn path(&self) -> &Path { self.path.as_path() }
 
@@ -194,9 +483,82 @@ impl Plugin {
     }
 }
 
+/// Limits for the work a single call into a plugin module may do
+#[derive(Clone, Copy, Debug)]
+pub struct PluginBudget {
+    /// Fuel available to each call, roughly the number of wasm instructions
+    pub fuel: u64,
+    /// Calls taking longer than this get interrupted and count as overrun
+    pub time: Duration,
+    /// Maximum size of the linear memory of a module in bytes
+    pub memory: usize,
+    /// Traps or overruns in a row after which a module gets disabled
+    pub max_faults: u32,
+}
+
+impl Default for PluginBudget {
+    fn default() -> Self {
+        Self {
+            fuel: 500_000_000,
+            time: Duration::from_millis(100),
+            memory: 256 << 20,
+            max_faults: 3,
+        }
+    }
+}
+
+/// Health of a loaded plugin, see [`PluginMgr::fault_stats`]
+pub struct PluginFaultStats {
+    pub name: String,
+    /// Traps and overruns since the plugin was loaded
+    pub faults: u64,
+    pub disabled: bool,
+}
+
+/// Persistent storage of all plugins, see [`PluginMgr::enable_storage`]
+struct StorageState {
+    plugins: HashMap<String, Arc<Mutex<PluginStorage>>>,
+    quota: usize,
+}
+
+impl StorageState {
+    fn get_or_create(&mut self, plugin: &str) -> &Arc<Mutex<PluginStorage>> {
+        let quota = self.quota;
+        self.plugins
+            .entry(plugin.to_owned())
+            .or_insert_with(|| Arc::new(Mutex::new(PluginStorage::new(HashMap::new(), quota))))
+    }
+}
+
 #[derive(Default)]
 pub struct PluginMgr {
     plugins: Vec<Plugin>,
+    budget: PluginBudget,
+    storage: Option<StorageState>,
+    /// Directory the plugins were loaded from, if any
+    dir: Option<PathBuf>,
+    watcher: Option<PluginWatcher>,
+}
+
+/// Delay after the last change to a file before it gets reloaded, so we
+/// don't pick up partially written plugins
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Watches the plugin directory for changed plugin files
+struct PluginWatcher {
+    _watcher: RecommendedWatcher,
+    changes: Mutex<mpsc::Receiver<PathBuf>>,
+    pending: HashMap<PathBuf, Instant>,
+}
+
+fn is_wasm_module(path: &Path) -> bool { path.extension().is_some_and(|ext| ext == "wasm") }
+
+fn is_plugin_file(path: &Path) -> bool {
+    is_wasm_module(path)
+        || path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|s| s.ends_with(".plugin.tar"))
 }
 
 impl PluginMgr {
@@ -218,21 +580,19 @@ impl PluginMgr {
     }
 
     pub fn from_dir<P: AsRef<Path>>(path: P) -> Result<Self, PluginError> {
+        let dir = path.as_ref().to_path_buf();
         let plugins = fs::read_dir(path)
             .map_err(PluginError::Io)?
             .filter_map(|e| e.ok())
             .map(|entry| {
                 if entry.file_type().map(|ft| ft.is_file()).unwrap_or(false)
-                    && entry
-                        .path()
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .map(|s| s.ends_with(".plugin.tar"))
-                        .unwrap_or(false)
+                    && is_plugin_file(&entry.path())
                 {
                     info!("Loading plugin at {:?}", entry.path());
-                    Plugin::from_path(entry.path()).map(|plugin| {
-                        if let Err(e) = common::assets::register_tar(entry.path()) {
+                    Plugin::from_file(entry.path()).map(|plugin| {
+                        if !plugin.local_only
+                            && let Err(e) = common::assets::register_tar(entry.path())
+                        {
                             error!("Plugin {:?} tar error {e:?}", entry.path());
                         }
                         Some(plugin)
@@ -255,12 +615,228 @@ impl PluginMgr {
             );
         }
 
-        Ok(Self { plugins })
+        Ok(Self {
+            plugins,
+            budget: PluginBudget::default(),
+            storage: None,
+            dir: Some(dir),
+            watcher: None,
+        })
+    }
+
+    /// Start watching the plugin directory, changed files are reported by
+    /// [`PluginMgr::changed_files`]
+    pub fn watch(&mut self) -> Result<(), PluginError> {
+        let Some(dir) = &self.dir else {
+            return Ok(());
+        };
+        let (send, recv) = mpsc::channel();
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+                Ok(event) => {
+                    if matches!(
+                        event.kind,
+                        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                    ) {
+                        event
+                            .paths
+                            .into_iter()
+                            .filter(|path| is_plugin_file(path))
+                            .for_each(|path| {
+                                let _ = send.send(path);
+                            });
+                    }
+                },
+                Err(e) => error!(?e, "Plugin watcher error"),
+            })
+            .map_err(PluginError::Watch)?;
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(PluginError::Watch)?;
+        info!("Watching {:?} for plugin changes", dir);
+        self.watcher = Some(PluginWatcher {
+            _watcher: watcher,
+            changes: Mutex::new(recv),
+            pending: HashMap::new(),
+        });
+        Ok(())
+    }
+
+    /// Plugin files which changed and have settled since the last call
+    pub fn changed_files(&mut self) -> Vec<PathBuf> {
+        let Some(watcher) = &mut self.watcher else {
+            return Vec::new();
+        };
+        let now = Instant::now();
+        if let Ok(changes) = watcher.changes.get_mut() {
+            for path in changes.try_iter() {
+                watcher.pending.insert(path, now);
+            }
+        }
+        let settled = watcher
+            .pending
+            .iter()
+            .filter(|(_, changed)| now.duration_since(**changed) >= RELOAD_DEBOUNCE)
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        settled.iter().for_each(|path| {
+            watcher.pending.remove(path);
+        });
+        settled
+    }
+
+    /// Load the plugin at `path` into a fresh instance, replacing the plugin
+    /// previously loaded from there. The old instance stays active if
+    /// loading fails.
+    ///
+    /// Assets that were already loaded are cached, so a reload may add assets
+    /// but fails if it changes or removes any that the old version had. Those
+    /// need a restart of the server.
+    ///
+    /// Clients which already received the old plugin keep using it until they
+    /// reconnect.
+    pub fn reload(
+        &mut self,
+        ecs: &EcsWorld,
+        mode: common::resources::GameMode,
+        path: &Path,
+    ) -> Result<(), PluginError> {
+        let mut plugin = Plugin::from_file(path.to_path_buf())?;
+        plugin.set_budget(self.budget);
+        if let Some(storage) = &mut self.storage {
+            plugin.set_storage(storage.get_or_create(&plugin.data.name));
+        }
+        if let Some(old) = self.plugins.iter().find(|old| old.path == path) {
+            let mut changed = old
+                .assets
+                .iter()
+                .filter(|(asset, hash)| plugin.assets.get(*asset) != Some(*hash))
+                .map(|(asset, _)| asset.clone())
+                .collect::<Vec<_>>();
+            if !changed.is_empty() {
+                changed.sort();
+                return Err(PluginError::AssetsChanged(changed));
+            }
+        }
+        plugin.load_event(ecs, mode).map_err(|e| {
+            PluginError::PluginModuleError(plugin.data.name.clone(), "load".to_owned(), e)
+        })?;
+        // Make assets that were added since the last load available
+        if !plugin.local_only
+            && let Err(e) = common::assets::register_tar(path.to_path_buf())
+        {
+            error!("Plugin {:?} tar error {e:?}", path);
+        }
+        if let Some(old) = self.plugins.iter_mut().find(|old| old.path == path) {
+            if old.data.modules == plugin.data.modules {
+                let state = old.save_state(ecs);
+                plugin.restore_state(ecs, state);
+            }
+            info!("Reloaded plugin '{}' from {:?}", plugin.data.name, path);
+            *old = plugin;
+        } else {
+            info!("Loaded new plugin '{}' from {:?}", plugin.data.name, path);
+            self.plugins.push(plugin);
+        }
+        Ok(())
+    }
+
+    /// Remove the plugin with the given name, returns whether it was loaded
+    pub fn unload(&mut self, name: &str) -> bool {
+        let count = self.plugins.len();
+        self.plugins.retain(|plugin| plugin.data.name != name);
+        count != self.plugins.len()
+    }
+
+    /// Remove the plugin loaded from `path`, e.g. because the file was deleted
+    pub fn unload_path(&mut self, path: &Path) -> bool {
+        let count = self.plugins.len();
+        self.plugins.retain(|plugin| plugin.path != path);
+        count != self.plugins.len()
+    }
+
+    /// Limit the work plugins may do per call, applies to already loaded
+    /// plugins as well
+    pub fn set_budget(&mut self, budget: PluginBudget) {
+        self.budget = budget;
+        self.plugins
+            .iter_mut()
+            .for_each(|plugin| plugin.set_budget(budget));
+    }
+
+    /// Give plugins access to persistent storage, `entries` are the stored
+    /// values of each plugin by name. Only the server should do this.
+    pub fn enable_storage(
+        &mut self,
+        entries: HashMap<String, HashMap<String, Vec<u8>>>,
+        quota: usize,
+    ) {
+        let mut storage = StorageState {
+            plugins: entries
+                .into_iter()
+                .map(|(plugin, entries)| {
+                    let storage = PluginStorage::new(entries, quota);
+                    if storage.size() > quota {
+                        warn!(
+                            "Stored data of plugin '{plugin}' exceeds the quota, it can only \
+                             delete entries"
+                        );
+                    }
+                    (plugin, Arc::new(Mutex::new(storage)))
+                })
+                .collect(),
+            quota,
+        };
+        for plugin in self.plugins.iter_mut() {
+            plugin.set_storage(storage.get_or_create(&plugin.data.name));
+        }
+        self.storage = Some(storage);
+    }
+
+    /// Take the storage changes of all plugins which still have to be
+    /// persisted, together with the name of the plugin
+    pub fn take_storage_changes(&mut self) -> Vec<(String, StorageChange)> {
+        let Some(storage) = &self.storage else {
+            return Vec::new();
+        };
+        storage
+            .plugins
+            .iter()
+            .flat_map(|(plugin, storage)| {
+                storage
+                    .lock()
+                    .map(|mut storage| storage.take_changes())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(move |change| (plugin.clone(), change))
+            })
+            .collect()
+    }
+
+    /// Fault counters of all loaded plugins, for metrics
+    pub fn fault_stats(&mut self) -> Vec<PluginFaultStats> {
+        self.plugins
+            .iter_mut()
+            .map(|plugin| PluginFaultStats {
+                name: plugin.data.name.clone(),
+                faults: plugin.total_faults(),
+                disabled: plugin.is_disabled(),
+            })
+            .collect()
+    }
+
+    /// Iterate over all loaded plugins
+    pub fn plugins(&self) -> impl Iterator<Item = &Plugin> { self.plugins.iter() }
+
+    /// Find a loaded plugin by name
+    pub fn find_by_name(&self, name: &str) -> Option<&Plugin> {
+        self.plugins.iter().find(|plugin| plugin.data.name == name)
     }
 
     /// Add a plugin received from the server
     pub fn load_server_plugin(&mut self, path: PathBuf) -> Result<PluginHash, PluginError> {
-        Plugin::from_path(path.clone()).map(|plugin| {
+        Plugin::from_path(path.clone()).map(|mut plugin| {
+            plugin.set_budget(self.budget);
             if let Err(e) = common::assets::register_tar(path.clone()) {
                 error!("Plugin {:?} tar error {e:?}", path.as_path());
             }
@@ -279,9 +855,13 @@ impl PluginMgr {
         self.load_server_plugin(path)
     }
 
-    /// list all registered plugins
+    /// list all registered plugins which are offered to clients
     pub fn plugin_list(&self) -> Vec<PluginHash> {
-        self.plugins.iter().map(|plugin| plugin.hash).collect()
+        self.plugins
+            .iter()
+            .filter(|plugin| !plugin.local_only)
+            .map(|plugin| plugin.hash)
+            .collect()
     }
 
     /// retrieve a specific plugin
@@ -322,6 +902,129 @@ impl PluginMgr {
         result
     }
 
+    /// Commands registered by all plugins, sorted by keyword
+    pub fn commands(&self) -> Vec<PluginCommandData> {
+        let mut commands = self
+            .plugins
+            .iter()
+            .flat_map(Plugin::commands)
+            .collect::<Vec<_>>();
+        commands.sort_by(|a, b| a.keyword.cmp(&b.keyword));
+        commands.dedup_by(|a, b| a.keyword == b.keyword);
+        commands
+    }
+
+    /// The command a plugin registered under `name`
+    pub fn find_command(&self, name: &str) -> Option<PluginCommandData> {
+        self.plugins
+            .iter()
+            .flat_map(Plugin::commands)
+            .find(|command| command.keyword == name)
+    }
+
+    /// Notify all plugins about an entity event, failures are only logged
+    pub fn entity_event(&mut self, ecs: &EcsWorld, event: &EntityEvent) {
+        self.plugins.iter_mut().for_each(|plugin| {
+            if let Err(e) = plugin.entity_event(ecs, event) {
+                warn!(
+                    ?e,
+                    ?event,
+                    "Plugin '{}' failed to handle event",
+                    plugin.data.name
+                );
+            }
+        });
+    }
+
+    /// Take the pending world modifications of all plugins together with the
+    /// name of the requesting plugin
+    pub fn take_actions(&mut self) -> Vec<(String, PluginAction)> {
+        self.plugins
+            .iter_mut()
+            .flat_map(|plugin| {
+                let name = plugin.data.name.clone();
+                plugin
+                    .take_actions()
+                    .into_iter()
+                    .map(move |action| (name.clone(), action))
+            })
+            .collect()
+    }
+
+    /// Whether any plugin wants to modify generated chunks
+    pub fn has_worldgen_hooks(&self) -> bool {
+        self.plugins
+            .iter()
+            .flat_map(|plugin| plugin.modules.iter())
+            .any(PluginModule::has_worldgen_hook)
+    }
+
+    /// Let plugins modify a freshly generated chunk. Plugins run ordered by
+    /// name so that the result doesn't depend on the order they were loaded
+    /// in, failures are only logged.
+    pub fn chunk_generated(
+        &mut self,
+        key: Vec2<i32>,
+        chunk: &mut TerrainChunk,
+        column_info: &ColumnInfoFn,
+    ) {
+        let mut plugins = self.plugins.iter_mut().collect::<Vec<_>>();
+        plugins.sort_by(|a, b| a.data.name.cmp(&b.data.name));
+        for plugin in plugins {
+            if let Err(e) = plugin.chunk_generated(key, chunk, column_info) {
+                warn!(
+                    ?e,
+                    ?key,
+                    "Plugin '{}' failed to modify generated chunk",
+                    plugin.data.name
+                );
+            }
+        }
+    }
+
+    /// Take the messages of all plugins together with the name of the sending
+    /// plugin
+    pub fn take_messages(&mut self) -> Vec<(String, PluginMessage)> {
+        self.plugins
+            .iter_mut()
+            .flat_map(|plugin| {
+                let name = plugin.data.name.clone();
+                plugin
+                    .take_messages()
+                    .into_iter()
+                    .map(move |message| (name.clone(), message))
+            })
+            .collect()
+    }
+
+    /// Deliver a message to the plugin with the given name, failures are only
+    /// logged
+    pub fn message_event(
+        &mut self,
+        ecs: &EcsWorld,
+        plugin: &str,
+        sender: Option<Uid>,
+        channel: &str,
+        data: &[u8],
+    ) {
+        let Some(plugin) = self
+            .plugins
+            .iter_mut()
+            .find(|candidate| candidate.data.name == plugin)
+        else {
+            tracing::debug!(?plugin, "Dropping message for unknown plugin");
+            return;
+        };
+        if let Err(e) = plugin.message_event(ecs, sender, channel, data) {
+            warn!(
+                ?e,
+                ?channel,
+                "Plugin '{}' failed to handle message",
+                plugin.data.name
+            );
+        }
+    }
+
     pub fn create_body(&mut self, name: &str) -> Option<module::Body> {
         let mut result = None;
         self.plugins.iter_mut().for_each(|plugin| {
@@ -351,6 +1054,38 @@ impl PluginMgr {
 /// Error returned by plugin based server commands
 pub enum CommandResults {
     UnknownCommand,
-    HostError(wasmtime::Error),
+    HostError(PluginModuleError),
     PluginError(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_capabilities() {
+        let data = toml::de::from_str::<PluginData>(
+            r#"
+            name = "example"
+            modules = ["example.wasm"]
+            dependencies = []
+            capabilities = ["give-item", "spawn-npc"]
+            "#,
+        )
+        .unwrap();
+        assert!(data.capabilities.contains(&Capability::GiveItem));
+        assert!(data.capabilities.contains(&Capability::SpawnNpc));
+        assert!(!data.capabilities.contains(&Capability::SetBlock));
+
+        // Older manifests without capabilities are still accepted
+        let data = toml::de::from_str::<PluginData>(
+            r#"
+            name = "example"
+            modules = ["example.wasm"]
+            dependencies = []
+            "#,
+        )
+        .unwrap();
+        assert!(data.capabilities.is_empty());
+    }
+}
@@ -1,19 +1,31 @@
 use std::{
     io,
     pin::Pin,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, OnceLock},
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
 use super::{
-    CommandResults,
+    Capability, ColumnInfoFn, CommandResults, EntityEvent, MAX_GIVE_AMOUNT, PLUGIN_BLOCK_ALTS,
+    PluginAction, PluginBudget, PluginMessage,
     errors::PluginModuleError,
     memory_manager::{EcsAccessManager, EcsWorld},
+    storage::{PluginStorage, StorageError},
 };
+use common::{
+    cmd::{PluginArgumentSpec, PluginCommandData, Requirement, ServerChatCommand},
+    terrain::{
+        Block, BlockKind, CoordinateConversions, TerrainChunk, TerrainChunkMeta, TerrainChunkSize,
+    },
+    vol::{ReadVol, RectVolSize, WriteVol},
+};
+use common_net::msg::{MAX_PLUGIN_MESSAGE_SIZE, MAX_PLUGIN_MESSAGES_PER_TICK};
 use hashbrown::{HashMap, HashSet};
 use tokio::io::AsyncWrite;
+use vek::{Vec2, Vec3};
 use wasmtime::{
-    Config, Engine, Store,
+    Config, Engine, Store, StoreLimits, StoreLimitsBuilder, Trap,
     component::{Component, HasSelf, Linker},
 };
 use wasmtime_wasi::{
@@ -35,6 +47,7 @@ wasmtime::component::bindgen!({
     with: {
         "veloren:plugin/types@0.0.1": types_mod::veloren::plugin::types,
         "veloren:plugin/information@0.0.1/entity": Entity,
+        "veloren:plugin/terrain@0.0.1/canvas": Canvas,
     },
 });
 
@@ -59,16 +72,77 @@ mod server_plugin {
     });
 }
 
+mod entity_events_hooks {
+    wasmtime::component::bindgen!({
+        path: "../../plugin/wit/veloren.wit",
+        world: "entity-events-hooks",
+        with: {
+            "veloren:plugin/types@0.0.1": super::types_mod::veloren::plugin::types,
+        },
+    });
+}
+
+mod hot_reload_hooks {
+    wasmtime::component::bindgen!({
+        path: "../../plugin/wit/veloren.wit",
+        world: "hot-reload-hooks",
+    });
+}
+
+mod message_hooks {
+    wasmtime::component::bindgen!({
+        path: "../../plugin/wit/veloren.wit",
+        world: "message-hooks",
+    });
+}
+
+mod worldgen_hooks {
+    wasmtime::component::bindgen!({
+        path: "../../plugin/wit/veloren.wit",
+        world: "worldgen-hooks",
+        with: {
+            "veloren:plugin/types@0.0.1": super::types_mod::veloren::plugin::types,
+            "veloren:plugin/terrain@0.0.1": super::veloren::plugin::terrain,
+        },
+    });
+}
+
 pub struct Entity {
     uid: common::uid::Uid,
 }
 
+/// A generated chunk lent to the worldgen hook of a plugin
+pub struct Canvas {
+    key: Vec2<i32>,
+    chunk: TerrainChunk,
+    /// Blocks set by the plugin by position relative to the chunk, only
+    /// written to the chunk if the hook succeeded
+    edits: HashMap<Vec3<i32>, Block>,
+    column_info: ColumnInfoFn,
+}
+
+impl Canvas {
+    /// Position relative to the chunk, `None` outside of it or of the
+    /// altitudes plugins may edit
+    fn relative_pos(&self, position: (i32, i32, i32)) -> Option<Vec3<i32>> {
+        let wpos = Vec3::from(position);
+        let rpos = wpos - Vec3::from(self.key.cpos_to_wpos());
+        (rpos.x >= 0
+            && rpos.y >= 0
+            && rpos.x < TerrainChunkSize::RECT_SIZE.x as i32
+            && rpos.y < TerrainChunkSize::RECT_SIZE.y as i32
+            && PLUGIN_BLOCK_ALTS.contains(&rpos.z))
+        .then_some(rpos)
+    }
+}
+
 pub use animation::Body;
+use entity_events_hooks::exports::veloren::plugin::entity_events;
 use exports::veloren::plugin::animation;
 pub use types_mod::veloren::plugin::types::{
     self, CharacterState, Dependency, Skeleton, Transform,
 };
-use veloren::plugin::{actions, information};
+use veloren::plugin::{actions, information, storage, terrain};
 
 type StoreType = wasmtime::Store<WasiHostCtx>;
 
@@ -138,17 +212,21 @@ impl PluginWrapper {
         }
     }
 
-    fn create_body(&self, store: &mut StoreType, bodytype: i32) -> Option<animation::Body> {
+    fn create_body(
+        &self,
+        store: &mut StoreType,
+        bodytype: i32,
+    ) -> wasmtime::Result<Option<animation::Body>> {
         match self {
             PluginWrapper::Full(pl) => {
                 let body_iface = pl.veloren_plugin_animation().body();
-                body_iface.call_constructor(store, bodytype).ok()
+                body_iface.call_constructor(store, bodytype).map(Some)
             },
             PluginWrapper::Animation(pl) => {
                 let body_iface = pl.veloren_plugin_animation().body();
-                body_iface.call_constructor(store, bodytype).ok()
+                body_iface.call_constructor(store, bodytype).map(Some)
             },
-            PluginWrapper::Server(_) => None,
+            PluginWrapper::Server(_) => Ok(None),
         }
     }
 
@@ -158,25 +236,52 @@ impl PluginWrapper {
         body: animation::Body,
         dep: types::Dependency,
         time: f32,
-    ) -> Option<types::Skeleton> {
+    ) -> wasmtime::Result<Option<types::Skeleton>> {
         match self {
             PluginWrapper::Full(pl) => {
                 let body_iface = pl.veloren_plugin_animation().body();
-                body_iface.call_update_skeleton(store, body, dep, time).ok()
+                body_iface
+                    .call_update_skeleton(store, body, dep, time)
+                    .map(Some)
             },
             PluginWrapper::Animation(pl) => {
                 let body_iface = pl.veloren_plugin_animation().body();
-                body_iface.call_update_skeleton(store, body, dep, time).ok()
+                body_iface
+                    .call_update_skeleton(store, body, dep, time)
+                    .map(Some)
             },
-            PluginWrapper::Server(_) => None,
+            PluginWrapper::Server(_) => Ok(None),
         }
     }
 }
 
+fn damage_source(source: common::combat::DamageSource) -> entity_events::DamageSource {
+    use common::combat::DamageSource;
+    match source {
+        DamageSource::Buff(_) => entity_events::DamageSource::Buff,
+        DamageSource::Melee => entity_events::DamageSource::Melee,
+        DamageSource::Projectile => entity_events::DamageSource::Projectile,
+        DamageSource::Explosion => entity_events::DamageSource::Explosion,
+        DamageSource::Falling => entity_events::DamageSource::Falling,
+        DamageSource::Shockwave => entity_events::DamageSource::Shockwave,
+        DamageSource::Energy => entity_events::DamageSource::Energy,
+        DamageSource::Other => entity_events::DamageSource::Other,
+    }
+}
+
 /// This structure represent the WASM State of the plugin.
 pub struct PluginModule {
     ecs: Arc<EcsAccessManager>,
     plugin: PluginWrapper,
+    /// Only present if the plugin exports the optional `entity-events`
+    entity_events: Option<entity_events_hooks::EntityEventsHooks>,
+    /// Only present if the plugin exports the optional `hot-reload`
+    hot_reload: Option<hot_reload_hooks::HotReloadHooks>,
+    /// Only present if the plugin exports the optional `messages`
+    messages: Option<message_hooks::MessageHooks>,
+    /// Only present if the plugin exports the optional `worldgen` and has
+    /// the capability for it
+    worldgen: Option<worldgen_hooks::WorldgenHooks>,
     store: Mutex<wasmtime::Store<WasiHostCtx>>,
     name: String,
 }
@@ -185,8 +290,233 @@ struct WasiHostCtx {
     preview2_ctx: WasiCtx,
     preview2_table: wasmtime::component::ResourceTable,
     ecs: Arc<EcsAccessManager>,
-    registered_commands: HashSet<String>,
+    name: String,
+    registered_commands: HashMap<String, PluginCommandData>,
     registered_bodies: HashMap<String, types::BodyIndex>,
+    capabilities: HashSet<Capability>,
+    /// Set by the load event, world modifications are refused before that
+    game_mode: Option<common::resources::GameMode>,
+    pending_actions: Vec<PluginAction>,
+    pending_messages: Vec<PluginMessage>,
+    /// Only available on the server
+    storage: Option<Arc<Mutex<PluginStorage>>>,
+    limits: StoreLimits,
+    budget: PluginBudget,
+    /// Calls in a row which trapped or overran the budget
+    faults: u32,
+    total_faults: u64,
+    disabled: bool,
+}
+
+/// Longest command name and argument label accepted from plugins
+const MAX_COMMAND_LABEL_LEN: usize = 32;
+/// Most arguments a plugin command may have
+const MAX_COMMAND_ARGS: usize = 16;
+
+/// Convert a command spec of a plugin, `None` if it is invalid or would shadow
+/// a built-in command
+fn command_data(spec: actions::CommandSpec) -> Option<PluginCommandData> {
+    let valid_label = |label: &str| {
+        !label.is_empty()
+            && label.len() <= MAX_COMMAND_LABEL_LEN
+            && !label.contains(char::is_whitespace)
+    };
+    if !valid_label(&spec.name)
+        || spec.name.parse::<ServerChatCommand>().is_ok()
+        || spec.arguments.len() > MAX_COMMAND_ARGS
+    {
+        return None;
+    }
+    let last = spec.arguments.len().saturating_sub(1);
+    let args = spec
+        .arguments
+        .into_iter()
+        .enumerate()
+        .map(|(i, arg)| {
+            if !valid_label(&arg.label) {
+                return None;
+            }
+            let req = if arg.optional {
+                Requirement::Optional
+            } else {
+                Requirement::Required
+            };
+            Some(match arg.kind {
+                actions::ArgumentKind::PlayerName => PluginArgumentSpec::PlayerName(req),
+                actions::ArgumentKind::EntityTarget => PluginArgumentSpec::EntityTarget(req),
+                actions::ArgumentKind::SiteName => PluginArgumentSpec::SiteName(req),
+                actions::ArgumentKind::Float(x) => PluginArgumentSpec::Float(arg.label, x, req),
+                actions::ArgumentKind::Integer(x) => PluginArgumentSpec::Integer(arg.label, x, req),
+                actions::ArgumentKind::Any => PluginArgumentSpec::Any(arg.label, req),
+                actions::ArgumentKind::Command => PluginArgumentSpec::Command(req),
+                actions::ArgumentKind::Message if i == last => PluginArgumentSpec::Message(req),
+                actions::ArgumentKind::Message => return None,
+                actions::ArgumentKind::Choice(options) => {
+                    PluginArgumentSpec::Enum(arg.label, options, req)
+                },
+                actions::ArgumentKind::Boolean(x) => {
+                    PluginArgumentSpec::Boolean(arg.label, x.to_string(), req)
+                },
+                actions::ArgumentKind::Flag => PluginArgumentSpec::Flag(arg.label),
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(PluginCommandData {
+        keyword: spec.name,
+        args,
+        description: spec.description,
+        needs_role: spec.role.map(|role| match role {
+            actions::AdminRole::Moderator => common::comp::AdminRole::Moderator,
+            actions::AdminRole::Admin => common::comp::AdminRole::Admin,
+        }),
+    })
+}
+
+fn store_limits(budget: &PluginBudget) -> StoreLimits {
+    StoreLimitsBuilder::new()
+        .memory_size(budget.memory)
+        .trap_on_grow_failure(true)
+        .build()
+}
+
+/// Granularity at which the time budget of calls is enforced
+const EPOCH_TICK: Duration = Duration::from_millis(5);
+
+/// The engine shared by all plugin modules. Its epoch is advanced every
+/// [`EPOCH_TICK`] by a background thread, which interrupts calls running past
+/// their deadline.
+fn engine() -> Result<&'static Engine, PluginModuleError> {
+    static ENGINE: OnceLock<Engine> = OnceLock::new();
+    if let Some(engine) = ENGINE.get() {
+        return Ok(engine);
+    }
+    let mut config = Config::new();
+    config.wasm_component_model(true);
+    config.consume_fuel(true);
+    config.epoch_interruption(true);
+    let engine = Engine::new(&config).map_err(PluginModuleError::Wasmtime)?;
+    Ok(ENGINE.get_or_init(|| {
+        let ticker = engine.clone();
+        std::thread::Builder::new()
+            .name("plugin-epoch".into())
+            .spawn(move || {
+                loop {
+                    std::thread::sleep(EPOCH_TICK);
+                    ticker.increment_epoch();
+                }
+            })
+            .expect("Failed to spawn the plugin epoch thread");
+        engine
+    }))
+}
+
+/// Number of epoch ticks after which a call gets interrupted
+fn epoch_deadline(budget: &PluginBudget) -> u64 {
+    budget.time.as_nanos().div_ceil(EPOCH_TICK.as_nanos()) as u64 + 1
+}
+
+/// Run a call into the plugin with the fuel and time of its budget. Traps and
+/// calls that get interrupted for taking longer than the time budget count as
+/// faults, after too many faults in a row the module gets disabled and refuses
+/// all further calls.
+fn guarded<T>(
+    store: &mut StoreType,
+    call: impl FnOnce(&mut StoreType) -> wasmtime::Result<T>,
+) -> Result<T, PluginModuleError> {
+    if store.data().disabled {
+        return Err(PluginModuleError::Disabled);
+    }
+    let budget = store.data().budget;
+    store
+        .set_fuel(budget.fuel)
+        .map_err(PluginModuleError::Wasmtime)?;
+    store.set_epoch_deadline(epoch_deadline(&budget));
+
+    let start = Instant::now();
+    let result = call(store).map_err(|e| match e.downcast_ref::<Trap>() {
+        Some(Trap::OutOfFuel) => PluginModuleError::OutOfFuel,
+        Some(Trap::Interrupt) => PluginModuleError::TimedOut,
+        _ => PluginModuleError::Wasmtime(e),
+    });
+
+    let ctx = store.data_mut();
+    let fault = match &result {
+        Err(PluginModuleError::TimedOut) => {
+            Some(format!("call interrupted after {:?}", start.elapsed()))
+        },
+        Err(e) => Some(format!("{e:?}")),
+        Ok(_) => None,
+    };
+    if let Some(fault) = fault {
+        ctx.faults += 1;
+        ctx.total_faults += 1;
+        if ctx.faults >= budget.max_faults {
+            ctx.disabled = true;
+            tracing::error!(
+                "Plugin {} disabled after {} faults in a row, last one: {fault}",
+                ctx.name,
+                ctx.faults
+            );
+        } else {
+            tracing::warn!("Plugin {} exceeded its budget: {fault}", ctx.name);
+        }
+    } else {
+        ctx.faults = 0;
+    }
+    result
+}
+
+impl WasiHostCtx {
+    /// Check that this plugin declared `capability` and runs on the server
+    fn require(&self, capability: Capability) -> Result<(), types::Error> {
+        let on_server = matches!(
+            self.game_mode,
+            Some(common::resources::GameMode::Server | common::resources::GameMode::Singleplayer)
+        );
+        if !on_server {
+            // Clients never get to modify the world, declaring the capability
+            // wouldn't help
+            tracing::debug!(
+                "Plugin {} used {capability:?}, which is only available on the server",
+                self.name
+            );
+            Err(types::Error::PermissionDenied)
+        } else if !self.capabilities.contains(&capability) {
+            tracing::warn!(
+                "Plugin {} used {capability:?} without declaring it in plugin.toml",
+                self.name
+            );
+            Err(types::Error::PermissionDenied)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_message_limits(&self, data: &[u8]) -> Result<(), types::Error> {
+        if data.len() > MAX_PLUGIN_MESSAGE_SIZE
+            || self.pending_messages.len() >= MAX_PLUGIN_MESSAGES_PER_TICK
+        {
+            tracing::warn!(
+                "Plugin {} exceeded the message limits, dropping message",
+                self.name
+            );
+            Err(types::Error::LimitExceeded)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn existing_uid(&self, uid: actions::Uid) -> Result<common::uid::Uid, types::Error> {
+        let uid = common::uid::Uid(uid);
+        self.ecs.with(|world| {
+            let world = world.ok_or(types::Error::EcsPointerNotAvailable)?;
+            world
+                .id_maps
+                .uid_entity(uid)
+                .map(|_| uid)
+                .ok_or(types::Error::EcsEntityNotFound)
+        })
+    }
 }
 
 impl WasiView for WasiHostCtx {
@@ -205,7 +535,21 @@ impl types::Host for WasiHostCtx {}
 impl actions::Host for WasiHostCtx {
     fn register_command(&mut self, name: String) {
         tracing::info!("Plugin registers /{name}");
-        self.registered_commands.insert(name);
+        let command = PluginCommandData {
+            keyword: name.clone(),
+            args: vec![PluginArgumentSpec::Message(Requirement::Optional)],
+            description: format!("Command of the plugin {}", self.name),
+            needs_role: None,
+        };
+        self.registered_commands.insert(name, command);
+    }
+
+    fn register_command_spec(&mut self, spec: actions::CommandSpec) -> Result<(), types::Error> {
+        let command = command_data(spec).ok_or(types::Error::InvalidArgument)?;
+        tracing::info!("Plugin registers /{}", command.keyword);
+        self.registered_commands
+            .insert(command.keyword.clone(), command);
+        Ok(())
     }
 
     fn player_send_message(&mut self, uid: actions::Uid, text: String) {
@@ -215,6 +559,238 @@ impl actions::Host for WasiHostCtx {
     fn register_animation(&mut self, name: String, id: types::BodyIndex) {
         let _ = self.registered_bodies.insert(name, id);
     }
+
+    fn give_item(
+        &mut self,
+        uid: actions::Uid,
+        item: String,
+        amount: u32,
+    ) -> Result<(), types::Error> {
+        self.require(Capability::GiveItem)?;
+        if amount == 0 {
+            return Err(types::Error::InvalidArgument);
+        }
+        if amount > MAX_GIVE_AMOUNT {
+            return Err(types::Error::LimitExceeded);
+        }
+        let target = self.existing_uid(uid)?;
+        self.pending_actions.push(PluginAction::GiveItem {
+            target,
+            item,
+            amount,
+        });
+        Ok(())
+    }
+
+    fn teleport(&mut self, uid: actions::Uid, position: types::Vec3) -> Result<(), types::Error> {
+        self.require(Capability::Teleport)?;
+        let target = self.existing_uid(uid)?;
+        self.pending_actions.push(PluginAction::Teleport {
+            target,
+            position: position.into(),
+        });
+        Ok(())
+    }
+
+    fn apply_buff(
+        &mut self,
+        uid: actions::Uid,
+        buff: String,
+        strength: f32,
+        duration: Option<f32>,
+    ) -> Result<(), types::Error> {
+        self.require(Capability::ApplyBuff)?;
+        let kind = common::cmd::BUFF_PARSER
+            .get(&buff)
+            .copied()
+            .filter(|kind| kind.is_simple())
+            .ok_or(types::Error::InvalidArgument)?;
+        let target = self.existing_uid(uid)?;
+        self.pending_actions.push(PluginAction::ApplyBuff {
+            target,
+            kind,
+            strength,
+            duration,
+        });
+        Ok(())
+    }
+
+    fn set_block(
+        &mut self,
+        position: (i32, i32, i32),
+        block: String,
+        color: (u8, u8, u8),
+    ) -> Result<(), types::Error> {
+        self.require(Capability::SetBlock)?;
+        if !PLUGIN_BLOCK_ALTS.contains(&position.2) {
+            return Err(types::Error::InvalidArgument);
+        }
+        let kind = block
+            .parse::<common::terrain::BlockKind>()
+            .map_err(|_| types::Error::InvalidArgument)?;
+        self.pending_actions.push(PluginAction::SetBlock {
+            position: position.into(),
+            block: common::terrain::Block::new(kind, color.into()),
+        });
+        Ok(())
+    }
+
+    fn spawn_npc(
+        &mut self,
+        entity_config: String,
+        position: types::Vec3,
+    ) -> Result<(), types::Error> {
+        self.require(Capability::SpawnNpc)?;
+        self.pending_actions.push(PluginAction::SpawnNpc {
+            entity_config,
+            position: position.into(),
+        });
+        Ok(())
+    }
+
+    fn send_to_client(
+        &mut self,
+        player: actions::Uid,
+        channel: String,
+        data: Vec<u8>,
+    ) -> Result<(), types::Error> {
+        if !matches!(
+            self.game_mode,
+            Some(common::resources::GameMode::Server | common::resources::GameMode::Singleplayer)
+        ) {
+            return Err(types::Error::PermissionDenied);
+        }
+        self.check_message_limits(&data)?;
+        let player = self.existing_uid(player)?;
+        self.pending_messages.push(PluginMessage::ToClient {
+            player,
+            channel,
+            data,
+        });
+        Ok(())
+    }
+
+    fn send_to_server(&mut self, channel: String, data: Vec<u8>) -> Result<(), types::Error> {
+        if !matches!(
+            self.game_mode,
+            Some(common::resources::GameMode::Client | common::resources::GameMode::Singleplayer)
+        ) {
+            return Err(types::Error::PermissionDenied);
+        }
+        self.check_message_limits(&data)?;
+        self.pending_messages
+            .push(PluginMessage::ToServer { channel, data });
+        Ok(())
+    }
+}
+
+/// Longest key accepted by the storage interface
+const MAX_STORAGE_KEY_LEN: usize = 256;
+
+impl WasiHostCtx {
+    fn with_storage<T>(
+        &self,
+        f: impl FnOnce(&mut PluginStorage) -> Result<T, types::Error>,
+    ) -> Result<T, types::Error> {
+        let storage = self
+            .storage
+            .as_ref()
+            .ok_or(types::Error::PermissionDenied)?;
+        let mut storage = storage.lock().map_err(|_| types::Error::RuntimeError)?;
+        f(&mut storage)
+    }
+}
+
+impl storage::Host for WasiHostCtx {
+    fn get(&mut self, key: String) -> Result<Option<Vec<u8>>, types::Error> {
+        self.with_storage(|storage| Ok(storage.get(&key).map(<[u8]>::to_vec)))
+    }
+
+    fn set(&mut self, key: String, value: Vec<u8>) -> Result<(), types::Error> {
+        if key.is_empty() || key.len() > MAX_STORAGE_KEY_LEN {
+            return Err(types::Error::InvalidArgument);
+        }
+        self.with_storage(|storage| {
+            storage.set(key, value).map_err(|e| match e {
+                StorageError::QuotaExceeded => types::Error::LimitExceeded,
+            })
+        })
+    }
+
+    fn delete(&mut self, key: String) -> Result<bool, types::Error> {
+        self.with_storage(|storage| Ok(storage.delete(&key)))
+    }
+
+    fn list_keys(&mut self, prefix: String) -> Result<Vec<String>, types::Error> {
+        self.with_storage(|storage| Ok(storage.keys(&prefix)))
+    }
+}
+
+impl terrain::Host for WasiHostCtx {}
+
+impl terrain::HostCanvas for WasiHostCtx {
+    fn get_block(
+        &mut self,
+        self_: wasmtime::component::Resource<terrain::Canvas>,
+        position: (i32, i32, i32),
+    ) -> Option<terrain::BlockInfo> {
+        let canvas = self.preview2_table.get(&self_).ok()?;
+        let rpos = canvas.relative_pos(position)?;
+        let block = match canvas.edits.get(&rpos) {
+            Some(block) => *block,
+            None => *canvas.chunk.get(rpos).ok()?,
+        };
+        Some(terrain::BlockInfo {
+            kind: block.kind().to_string(),
+            color: block.get_color().map(Into::into),
+        })
+    }
+
+    fn set_block(
+        &mut self,
+        self_: wasmtime::component::Resource<terrain::Canvas>,
+        position: (i32, i32, i32),
+        block: String,
+        color: (u8, u8, u8),
+    ) -> Result<(), types::Error> {
+        let kind = block
+            .parse::<BlockKind>()
+            .map_err(|_| types::Error::InvalidArgument)?;
+        let canvas = self
+            .preview2_table
+            .get_mut(&self_)
+            .map_err(|_err| types::Error::RuntimeError)?;
+        let rpos = canvas
+            .relative_pos(position)
+            .ok_or(types::Error::InvalidArgument)?;
+        canvas.edits.insert(rpos, Block::new(kind, color.into()));
+        Ok(())
+    }
+
+    fn column_info(
+        &mut self,
+        self_: wasmtime::component::Resource<terrain::Canvas>,
+        x: i32,
+        y: i32,
+    ) -> Option<terrain::ColumnInfo> {
+        let canvas = self.preview2_table.get(&self_).ok()?;
+        (canvas.column_info)(Vec2::new(x, y)).map(|info| terrain::ColumnInfo {
+            alt: info.alt,
+            water_alt: info.water_alt,
+            temp: info.temp,
+            humidity: info.humidity,
+            rockiness: info.rockiness,
+            tree_density: info.tree_density,
+        })
+    }
+
+    fn drop(
+        &mut self,
+        _rep: wasmtime::component::Resource<terrain::Canvas>,
+    ) -> wasmtime::Result<()> {
+        // The canvas is only lent to the plugin, the host takes it back itself
+        Ok(())
+    }
 }
 
 impl information::HostEntity for WasiHostCtx {
@@ -361,14 +937,14 @@ impl StdoutStream for LogStream {
 
 impl PluginModule {
     /// This function takes bytes from a WASM File and compile them
-    pub fn new(name: String, wasm_data: &[u8]) -> Result<Self, PluginModuleError> {
+    pub fn new(
+        name: String,
+        wasm_data: &[u8],
+        capabilities: &std::collections::HashSet<Capability>,
+    ) -> Result<Self, PluginModuleError> {
         let ecs = Arc::new(EcsAccessManager::default());
 
-        // configure the wasm runtime
-        let mut config = Config::new();
-        config.wasm_component_model(true);
-
-        let engine = Engine::new(&config).map_err(PluginModuleError::Wasmtime)?;
+        let engine = engine()?;
         // create a WASI environment (std implementing system calls)
         let wasi = WasiCtxBuilder::new()
             .stdout(LogStream(name.clone(), tracing::Level::INFO))
@@ -378,18 +954,34 @@ impl PluginModule {
             preview2_ctx: wasi,
             preview2_table: wasmtime_wasi::ResourceTable::new(),
             ecs: Arc::clone(&ecs),
-            registered_commands: HashSet::new(),
+            name: name.clone(),
+            registered_commands: HashMap::new(),
             registered_bodies: HashMap::new(),
+            capabilities: capabilities.iter().copied().collect(),
+            game_mode: None,
+            pending_actions: Vec::new(),
+            pending_messages: Vec::new(),
+            storage: None,
+            limits: store_limits(&PluginBudget::default()),
+            budget: PluginBudget::default(),
+            faults: 0,
+            total_faults: 0,
+            disabled: false,
         };
         // the store contains all data of a wasm instance
-        let mut store = Store::new(&engine, host_ctx);
+        let mut store = Store::new(engine, host_ctx);
+        store.limiter(|ctx| &mut ctx.limits);
+        store
+            .set_fuel(PluginBudget::default().fuel)
+            .map_err(PluginModuleError::Wasmtime)?;
+        store.set_epoch_deadline(epoch_deadline(&PluginBudget::default()));
 
         // load wasm from binary
         let module =
-            Component::from_binary(&engine, wasm_data).map_err(PluginModuleError::Wasmtime)?;
+            Component::from_binary(engine, wasm_data).map_err(PluginModuleError::Wasmtime)?;
 
         // register WASI and Veloren methods with the runtime
-        let mut linker = Linker::new(&engine);
+        let mut linker = Linker::new(engine);
         wasmtime_wasi::p2::add_to_linker_sync(&mut linker).map_err(PluginModuleError::Wasmtime)?;
         Plugin::add_to_linker::<_, HasSelf<_>>(&mut linker, |x| x)
             .map_err(PluginModuleError::Wasmtime)?;
@@ -406,9 +998,28 @@ impl PluginModule {
             },
         }
         .map_err(PluginModuleError::Wasmtime)?;
+        let entity_events = entity_events_hooks::EntityEventsHooks::new(&mut store, &instance).ok();
+        let hot_reload = hot_reload_hooks::HotReloadHooks::new(&mut store, &instance).ok();
+        let messages = message_hooks::MessageHooks::new(&mut store, &instance).ok();
+        let worldgen = worldgen_hooks::WorldgenHooks::new(&mut store, &instance)
+            .ok()
+            .filter(|_| {
+                let allowed = capabilities.contains(&Capability::Worldgen);
+                if !allowed {
+                    tracing::warn!(
+                        "Plugin {name} exports worldgen without declaring it in plugin.toml, \
+                         ignoring it"
+                    );
+                }
+                allowed
+            });
 
         Ok(Self {
             plugin,
+            entity_events,
+            hot_reload,
+            messages,
+            worldgen,
             ecs,
             store: store.into(),
             name,
@@ -417,17 +1028,82 @@ impl PluginModule {
 
     pub fn name(&self) -> &str { &self.name }
 
+    /// Limits for all further calls into this module
+    pub fn set_budget(&mut self, budget: PluginBudget) {
+        let ctx = self.store.get_mut().unwrap().data_mut();
+        ctx.limits = store_limits(&budget);
+        ctx.budget = budget;
+    }
+
+    /// Give this module access to the persistent storage of its plugin
+    pub fn set_storage(&mut self, storage: Arc<Mutex<PluginStorage>>) {
+        self.store.get_mut().unwrap().data_mut().storage = Some(storage);
+    }
+
+    /// Whether this module was disabled for exceeding its budget too often
+    pub fn is_disabled(&mut self) -> bool { self.store.get_mut().unwrap().data().disabled }
+
+    /// Number of traps and overruns since the module was loaded
+    pub fn total_faults(&mut self) -> u64 { self.store.get_mut().unwrap().data().total_faults }
+
     // Implementation of the commands called from veloren and provided in plugins
     pub fn load_event(
         &mut self,
         ecs: &EcsWorld,
         mode: common::resources::GameMode,
     ) -> Result<(), PluginModuleError> {
-        self.ecs
-            .execute_with(ecs, || {
-                self.plugin.load_event(self.store.get_mut().unwrap(), mode)
+        let store = self.store.get_mut().unwrap();
+        store.data_mut().game_mode = Some(mode);
+        self.ecs.execute_with(ecs, || {
+            guarded(store, |store| self.plugin.load_event(store, mode))
+        })
+    }
+
+    /// Commands this module registered
+    pub fn commands(&self) -> Vec<PluginCommandData> {
+        self.store
+            .lock()
+            .unwrap()
+            .data()
+            .registered_commands
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Take the world modifications requested since the last call
+    pub fn take_actions(&mut self) -> Vec<PluginAction> {
+        std::mem::take(&mut self.store.get_mut().unwrap().data_mut().pending_actions)
+    }
+
+    /// Take the messages sent to the other half of this plugin since the last
+    /// call
+    pub fn take_messages(&mut self) -> Vec<PluginMessage> {
+        std::mem::take(&mut self.store.get_mut().unwrap().data_mut().pending_messages)
+    }
+
+    /// Deliver a message from the other half of this plugin
+    pub fn message_event(
+        &mut self,
+        ecs: &EcsWorld,
+        sender: Option<common::uid::Uid>,
+        channel: &str,
+        data: &[u8],
+    ) -> Result<(), PluginModuleError> {
+        let Some(hooks) = &self.messages else {
+            return Ok(());
+        };
+        let store = self.store.get_mut().unwrap();
+        self.ecs.execute_with(ecs, || {
+            guarded(store, |store| {
+                hooks.veloren_plugin_messages().call_on_message(
+                    store,
+                    sender.map(|uid| uid.0),
+                    channel,
+                    data,
+                )
             })
-            .map_err(PluginModuleError::Wasmtime)
+        })
     }
 
     pub fn command_event(
@@ -443,15 +1119,15 @@ impl PluginModule {
             .unwrap()
             .data()
             .registered_commands
-            .contains(name)
+            .contains_key(name)
         {
             return Err(CommandResults::UnknownCommand);
         }
+        let store = self.store.get_mut().unwrap();
         self.ecs.execute_with(ecs, || {
-            match self
-                .plugin
-                .command_event(self.store.get_mut().unwrap(), name, args, player.0)
-            {
+            match guarded(store, |store| {
+                self.plugin.command_event(store, name, args, player.0)
+            }) {
                 Err(err) => Err(CommandResults::HostError(err)),
                 Ok(result) => result.map_err(CommandResults::PluginError),
             }
@@ -464,12 +1140,12 @@ impl PluginModule {
         name: &str,
         uuid: common::uuid::Uuid,
     ) -> types::JoinResult {
+        let store = self.store.get_mut().unwrap();
         self.ecs.execute_with(ecs, || {
-            match self.plugin.player_join_event(
-                self.store.get_mut().unwrap(),
-                name,
-                uuid.as_u64_pair(),
-            ) {
+            match guarded(store, |store| {
+                self.plugin
+                    .player_join_event(store, name, uuid.as_u64_pair())
+            }) {
                 Ok(value) => {
                     tracing::info!("JoinResult {value:?}");
                     value
@@ -482,10 +1158,139 @@ impl PluginModule {
         })
     }
 
+    pub fn entity_event(
+        &mut self,
+        ecs: &EcsWorld,
+        event: &EntityEvent,
+    ) -> Result<(), PluginModuleError> {
+        let Some(hooks) = &self.entity_events else {
+            return Ok(());
+        };
+        let store = self.store.get_mut().unwrap();
+        self.ecs.execute_with(ecs, || {
+            guarded(store, |store| {
+                let hooks = hooks.veloren_plugin_entity_events();
+                match event {
+                    EntityEvent::Spawn(uid) => hooks.call_spawn(store, uid.0),
+                    EntityEvent::Despawn(uid) => hooks.call_despawn(store, uid.0),
+                    EntityEvent::Damage {
+                        target,
+                        attacker,
+                        amount,
+                        source,
+                        precise,
+                    } => hooks.call_damage(store, entity_events::DamageInfo {
+                        target: target.0,
+                        attacker: attacker.map(|uid| uid.0),
+                        amount: *amount,
+                        source: source.map(damage_source),
+                        precise: *precise,
+                    }),
+                    EntityEvent::Death { entity, killer } => {
+                        hooks.call_death(store, entity.0, killer.map(|uid| uid.0))
+                    },
+                    EntityEvent::PlayerLeave { name, player } => {
+                        hooks.call_player_leave(store, name, player.0)
+                    },
+                }
+            })
+        })
+    }
+
+    /// Ask the plugin for the state it wants to keep across a hot reload,
+    /// `None` if it doesn't support this
+    pub fn save_state(&mut self, ecs: &EcsWorld) -> Result<Option<Vec<u8>>, PluginModuleError> {
+        let Some(hooks) = &self.hot_reload else {
+            return Ok(None);
+        };
+        let store = self.store.get_mut().unwrap();
+        self.ecs
+            .execute_with(ecs, || {
+                guarded(store, |store| {
+                    hooks.veloren_plugin_hot_reload().call_save_state(store)
+                })
+            })
+            .map(Some)
+    }
+
+    /// Hand the state saved by the previous instance to this one
+    pub fn restore_state(&mut self, ecs: &EcsWorld, state: &[u8]) -> Result<(), PluginModuleError> {
+        let Some(hooks) = &self.hot_reload else {
+            return Ok(());
+        };
+        let store = self.store.get_mut().unwrap();
+        self.ecs.execute_with(ecs, || {
+            guarded(store, |store| {
+                hooks
+                    .veloren_plugin_hot_reload()
+                    .call_restore_state(store, state)
+            })
+        })
+    }
+
+    /// Whether this module wants to modify generated chunks
+    pub fn has_worldgen_hook(&self) -> bool { self.worldgen.is_some() }
+
+    /// Let the plugin modify a freshly generated chunk. The blocks it set are
+    /// only written to the chunk if the call succeeded, other world
+    /// modifications and messages are dropped as the hook may only touch its
+    /// chunk.
+    pub fn chunk_generated(
+        &mut self,
+        key: Vec2<i32>,
+        chunk: &mut TerrainChunk,
+        column_info: &ColumnInfoFn,
+    ) -> Result<(), PluginModuleError> {
+        let Some(hooks) = &self.worldgen else {
+            return Ok(());
+        };
+        let store = self.store.get_mut().unwrap();
+        let pending_actions = store.data().pending_actions.len();
+        let pending_messages = store.data().pending_messages.len();
+        // Lend the chunk to the plugin, an empty chunk takes its place meanwhile
+        let placeholder =
+            TerrainChunk::new(0, Block::empty(), Block::empty(), TerrainChunkMeta::void());
+        let canvas = store
+            .data_mut()
+            .preview2_table
+            .push(Canvas {
+                key,
+                chunk: std::mem::replace(chunk, placeholder),
+                edits: HashMap::new(),
+                column_info: Arc::clone(column_info),
+            })
+            .map_err(|e| PluginModuleError::Wasmtime(e.into()))?;
+        let result = guarded(store, |store| {
+            hooks.veloren_plugin_worldgen().call_on_chunk_generated(
+                store,
+                (key.x, key.y),
+                wasmtime::component::Resource::new_borrow(canvas.rep()),
+            )
+        });
+        let ctx = store.data_mut();
+        ctx.pending_actions.truncate(pending_actions);
+        ctx.pending_messages.truncate(pending_messages);
+        let canvas = ctx
+            .preview2_table
+            .delete(canvas)
+            .map_err(|e| PluginModuleError::Wasmtime(e.into()))?;
+        *chunk = canvas.chunk;
+        if result.is_ok() {
+            for (rpos, block) in canvas.edits {
+                let _ = chunk.set(rpos, block);
+            }
+        }
+        result
+    }
+
     pub fn create_body(&mut self, bodytype: &str) -> Option<animation::Body> {
         let store = self.store.get_mut().unwrap();
         let bodytype = store.data().registered_bodies.get(bodytype).copied();
-        bodytype.and_then(|bd| self.plugin.create_body(store, bd))
+        bodytype.and_then(|bd| {
+            guarded(store, |store| self.plugin.create_body(store, bd))
+                .ok()
+                .flatten()
+        })
     }
 
     pub fn update_skeleton(
@@ -494,7 +1299,10 @@ impl PluginModule {
         dep: &types::Dependency,
         time: f32,
     ) -> Option<types::Skeleton> {
-        self.plugin
-            .update_skeleton(self.store.get_mut().unwrap(), *body, *dep, time)
+        guarded(self.store.get_mut().unwrap(), |store| {
+            self.plugin.update_skeleton(store, *body, *dep, time)
+        })
+        .ok()
+        .flatten()
     }
 }
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+/// A change to the storage of a plugin which still has to be persisted,
+/// `value` is `None` if the key was deleted
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageChange {
+    pub key: String,
+    pub value: Option<Vec<u8>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum StorageError {
+    /// Storing the value would exceed the quota of the plugin
+    QuotaExceeded,
+}
+
+/// Persistent key value entries of a single plugin, shared by all of its
+/// modules.
+///
+/// Changes are only recorded here, the server writes them to its database
+/// after the plugin call returned.
+#[derive(Debug, Default)]
+pub struct PluginStorage {
+    entries: HashMap<String, Vec<u8>>,
+    changes: Vec<StorageChange>,
+    /// Sum of the sizes of all keys and values
+    size: usize,
+    quota: usize,
+}
+
+fn entry_size(key: &str, value: &[u8]) -> usize { key.len() + value.len() }
+
+impl PluginStorage {
+    pub fn new(entries: HashMap<String, Vec<u8>>, quota: usize) -> Self {
+        let size = entries
+            .iter()
+            .map(|(key, value)| entry_size(key, value))
+            .sum();
+        Self {
+            entries,
+            changes: Vec::new(),
+            size,
+            quota,
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&[u8]> { self.entries.get(key).map(Vec::as_slice) }
+
+    pub fn set(&mut self, key: String, value: Vec<u8>) -> Result<(), StorageError> {
+        let old_size = self
+            .entries
+            .get(&key)
+            .map_or(0, |old| entry_size(&key, old));
+        let new_size = self.size - old_size + entry_size(&key, &value);
+        if new_size > self.quota {
+            return Err(StorageError::QuotaExceeded);
+        }
+        self.size = new_size;
+        self.changes.push(StorageChange {
+            key: key.clone(),
+            value: Some(value.clone()),
+        });
+        self.entries.insert(key, value);
+        Ok(())
+    }
+
+    /// Returns whether the key existed
+    pub fn delete(&mut self, key: &str) -> bool {
+        if let Some(old) = self.entries.remove(key) {
+            self.size -= entry_size(key, &old);
+            self.changes.push(StorageChange {
+                key: key.to_owned(),
+                value: None,
+            });
+            true
+        } else {
+            false
+        }
+    }
+
+    /// All keys starting with `prefix`, sorted
+    pub fn keys(&self, prefix: &str) -> Vec<String> {
+        let mut keys = self
+            .entries
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+
+    pub fn size(&self) -> usize { self.size }
+
+    /// Take the changes made since the last call
+    pub fn take_changes(&mut self) -> Vec<StorageChange> { std::mem::take(&mut self.changes) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quota() {
+        let mut storage = PluginStorage::new(HashMap::new(), 10);
+        assert_eq!(storage.set("a".into(), vec![0; 4]), Ok(()));
+        assert_eq!(storage.size(), 5);
+        // Overwriting only counts the difference
+        assert_eq!(storage.set("a".into(), vec![0; 9]), Ok(()));
+        assert_eq!(
+            storage.set("b".into(), vec![0; 1]),
+            Err(StorageError::QuotaExceeded)
+        );
+        assert!(storage.delete("a"));
+        assert_eq!(storage.size(), 0);
+        assert_eq!(storage.set("b".into(), vec![0; 1]), Ok(()));
+
+        assert_eq!(storage.take_changes(), vec![
+            StorageChange {
+                key: "a".into(),
+                value: Some(vec![0; 4]),
+            },
+            StorageChange {
+                key: "a".into(),
+                value: Some(vec![0; 9]),
+            },
+            StorageChange {
+                key: "a".into(),
+                value: None,
+            },
+            StorageChange {
+                key: "b".into(),
+                value: Some(vec![0; 1]),
+            },
+        ]);
+        assert!(storage.take_changes().is_empty());
+    }
+
+    #[test]
+    fn keys_by_prefix() {
+        let storage = PluginStorage::new(
+            HashMap::from([
+                ("score/bob".to_owned(), vec![1]),
+                ("score/alice".to_owned(), vec![2]),
+                ("config".to_owned(), vec![3]),
+            ]),
+            1024,
+        );
+        assert_eq!(storage.keys("score/"), vec!["score/alice", "score/bob"]);
+        assert_eq!(storage.keys("").len(), 3);
+        assert_eq!(storage.get("config"), Some(&[3][..]));
+    }
+}
@@ -1,10 +1,8 @@
 #[cfg(feature = "plugins")]
 use crate::plugin::PluginMgr;
 #[cfg(feature = "plugins")]
-use crate::plugin::memory_manager::EcsWorld;
+use crate::plugin::memory_manager::with_ecs_world;
 use crate::{BuildArea, NoDurabilityArea};
-#[cfg(feature = "plugins")]
-use common::uid::IdMaps;
 use common::{
     calendar::Calendar,
     comp::{self, gizmos::RtsimGizmos},
@@ -19,7 +17,7 @@ use common::{
     },
     shared_server_config::ServerConstants,
     slowjob::SlowJobPool,
-    terrain::{Block, MapSizeLg, TerrainChunk, TerrainGrid},
+    terrain::{Block, MapSizeLg, SpriteCfg, TerrainChunk, TerrainGrid},
     tether,
     time::DayPeriod,
     trade::Trades,
@@ -54,11 +52,18 @@ const SECONDS_TO_MILLISECONDS: f64 = 1000.0;
 #[derive(Default)]
 pub struct BlockChange {
     blocks: HashMap<Vec3<i32>, Block>,
+    sprite_cfgs: HashMap<Vec3<i32>, SpriteCfg>,
 }
 
 impl BlockChange {
     pub fn set(&mut self, pos: Vec3<i32>, block: Block) { self.blocks.insert(pos, block); }
 
+    /// Set the configuration of the sprite at `pos`. This is applied after the
+    /// blocks, so it can go along with setting the sprite itself.
+    pub fn set_sprite_cfg(&mut self, pos: Vec3<i32>, sprite_cfg: SpriteCfg) {
+        self.sprite_cfgs.insert(pos, sprite_cfg);
+    }
+
     pub fn try_set(&mut self, pos: Vec3<i32>, block: Block) -> Option<()> {
         if !self.blocks.contains_key(&pos) {
             self.blocks.insert(pos, block);
@@ -72,7 +77,10 @@ impl BlockChange {
     /// this tick.
     pub fn can_set_block(&self, pos: Vec3<i32>) -> bool { !self.blocks.contains_key(&pos) }
 
-    pub fn clear(&mut self) { self.blocks.clear(); }
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+        self.sprite_cfgs.clear();
+    }
 }
 
 #[derive(Default)]
@@ -367,22 +375,15 @@ impl State {
 
         // Load plugins from asset directory
         #[cfg(feature = "plugins")]
-        ecs.insert({
-            let ecs_world = EcsWorld {
-                entities: &ecs.entities(),
-                health: ecs.read_component().into(),
-                uid: ecs.read_component().into(),
-                id_maps: &ecs.read_resource::<IdMaps>().into(),
-                player: ecs.read_component().into(),
-            };
-            if let Err(e) = plugin_mgr.load_event(&ecs_world, game_mode) {
+        ecs.insert(with_ecs_world(&ecs, |ecs_world| {
+            if let Err(e) = plugin_mgr.load_event(ecs_world, game_mode) {
                 tracing::debug!(?e, "Failed to run plugin init");
                 tracing::info!("Plugins disabled, enable debug logging for more information.");
                 PluginMgr::default()
             } else {
                 plugin_mgr
             }
-        });
+        }));
 
         ecs
     }
@@ -527,6 +528,15 @@ impl State {
         self.ecs.write_resource::<BlockChange>().set(pos, block);
     }
 
+    /// Set the configuration of a sprite in this state's terrain. Sprite
+    /// configurations aren't part of block updates, so the chunk is sent to
+    /// clients again.
+    pub fn set_sprite_cfg(&self, pos: Vec3<i32>, sprite_cfg: SpriteCfg) {
+        self.ecs
+            .write_resource::<BlockChange>()
+            .set_sprite_cfg(pos, sprite_cfg);
+    }
+
     /// Set a block in this state's terrain (used to delete temporary summoned
     /// sprites after a timeout).
     pub fn schedule_set_block(
@@ -675,7 +685,22 @@ impl State {
             block_update(&self.ecs, updated_blocks);
         }
 
-        self.ecs.write_resource::<TerrainChanges>().modified_blocks = modified_blocks;
+        let mut terrain_changes = self.ecs.write_resource::<TerrainChanges>();
+        let sprite_cfgs = std::mem::take(&mut self.ecs.write_resource::<BlockChange>().sprite_cfgs);
+        for (wpos, sprite_cfg) in sprite_cfgs {
+            let key = TerrainGrid::chunk_key(wpos);
+            // Take the chunk out of the grid, so that it only gets copied if it is
+            // shared with something else
+            if let Some(mut chunk) = terrain.remove(key) {
+                Arc::make_mut(&mut chunk)
+                    .meta_mut()
+                    .set_sprite_cfg_at(TerrainGrid::chunk_offs(wpos), sprite_cfg);
+                terrain.insert(key, chunk);
+                terrain_changes.modified_chunks.insert(key);
+            }
+        }
+
+        terrain_changes.modified_blocks = modified_blocks;
     }
 
     /// Execute a single tick, simulating the game state by the given duration.
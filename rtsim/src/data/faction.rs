@@ -1,6 +1,10 @@
-use crate::data::Sentiments;
-use common::rtsim::Actor;
+use crate::data::{ReportId, Reports, Sentiment, Sentiments};
 pub use common::rtsim::FactionId;
+use common::{
+    resources::TimeOfDay,
+    rtsim::{Actor, NpcId, SiteId},
+};
+use hashbrown::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use slotmap::HopSlotMap;
 use std::ops::{Deref, DerefMut};
@@ -14,22 +18,114 @@ pub struct Faction {
 
     #[serde(default)]
     pub sentiments: Sentiments,
+    /// The diplomatic stance toward other factions. Factions that aren't
+    /// listed are [`Relation::Neutral`]. Relations are kept symmetric by
+    /// [`crate::rule::diplomacy`].
+    #[serde(default)]
+    pub relations: HashMap<FactionId, Relation>,
+    /// The site the faction is governed from, used to refer to the faction in
+    /// speech and on the map.
+    #[serde(default)]
+    pub seat: Option<SiteId>,
+    /// The raid the faction is currently carrying out, if any.
+    #[serde(default)]
+    pub raid: Option<Raid>,
 }
 
 impl Faction {
+    pub fn relation_to(&self, other: FactionId) -> Relation {
+        self.relations.get(&other).copied().unwrap_or_default()
+    }
+
+    pub fn is_hostile_to(&self, other: FactionId) -> bool {
+        self.relation_to(other) == Relation::Hostile
+    }
+
     pub fn cleanup(&mut self) {
         self.sentiments
             .cleanup(crate::data::sentiment::FACTION_MAX_SENTIMENTS);
+        self.relations
+            .retain(|_, relation| *relation != Relation::Neutral);
     }
 }
 
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Relation {
+    Allied,
+    #[default]
+    Neutral,
+    Hostile,
+}
+
+impl Relation {
+    /// The relation two factions should have, given the current relation and
+    /// the sentiments they feel toward one-another.
+    ///
+    /// Relations only change once sentiments cross a threshold well beyond the
+    /// one required to go back, so that factions don't flip-flop between
+    /// relations.
+    pub fn next(self, a_to_b: &Sentiment, b_to_a: &Sentiment) -> Self {
+        let either = |threshold| a_to_b.is(threshold) || b_to_a.is(threshold);
+        let both = |threshold| a_to_b.is(threshold) && b_to_a.is(threshold);
+        match self {
+            // It only takes one side to start a war...
+            Self::Neutral | Self::Allied if either(Sentiment::ENEMY) => Self::Hostile,
+            // ...but both need to want peace
+            Self::Hostile if !either(Sentiment::RIVAL) => Self::Neutral,
+            Self::Allied if !both(Sentiment::ALLY) => Self::Neutral,
+            Self::Neutral if both(Sentiment::FRIEND) => Self::Allied,
+            relation => relation,
+        }
+    }
+}
+
+/// A raiding party sent by a faction to capture a site of a hostile faction.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Raid {
+    pub from: SiteId,
+    pub target: SiteId,
+    pub raiders: Vec<NpcId>,
+    pub started: TimeOfDay,
+}
+
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct Factions {
     pub factions: HopSlotMap<FactionId, Faction>,
+    /// The [`crate::data::Report`]s that have already been taken into account
+    /// for the relations between factions.
+    #[serde(default)]
+    pub known_reports: HashSet<ReportId>,
 }
 
 impl Factions {
     pub fn create(&mut self, faction: Faction) -> FactionId { self.factions.insert(faction) }
+
+    /// The relation between two factions. A faction is always allied with
+    /// itself.
+    pub fn relation(&self, a: FactionId, b: FactionId) -> Relation {
+        if a == b {
+            Relation::Allied
+        } else {
+            self.factions
+                .get(a)
+                .map_or(Relation::Neutral, |faction| faction.relation_to(b))
+        }
+    }
+
+    /// The raid the given NPC takes part in, if any.
+    pub fn raid_of(&self, npc_id: NpcId, faction: Option<FactionId>) -> Option<&Raid> {
+        self.factions
+            .get(faction?)?
+            .raid
+            .as_ref()
+            .filter(|raid| raid.raiders.contains(&npc_id))
+    }
+
+    pub fn cleanup(&mut self, reports: &Reports) {
+        // Forget reports that have been forgotten anyway
+        self.known_reports
+            .retain(|report| reports.contains_key(*report));
+    }
 }
 
 impl Deref for Factions {
@@ -41,3 +137,35 @@ impl Deref for Factions {
 impl DerefMut for Factions {
     fn deref_mut(&mut self) -> &mut Self::Target { &mut self.factions }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentiment(value: f32) -> Sentiment {
+        let mut sentiment = Sentiment::default();
+        sentiment.change_by(value, 1.0);
+        sentiment
+    }
+
+    #[test]
+    fn relation_hysteresis() {
+        let neutral = sentiment(0.0);
+        let ally = sentiment(0.4);
+        let friend = sentiment(0.7);
+        let rival = sentiment(-0.4);
+        let enemy = sentiment(-0.7);
+
+        // One side is enough to start a war, but both need to want peace to end it
+        assert_eq!(Relation::Neutral.next(&enemy, &neutral), Relation::Hostile);
+        assert_eq!(Relation::Hostile.next(&rival, &neutral), Relation::Hostile);
+        assert_eq!(Relation::Hostile.next(&neutral, &ally), Relation::Neutral);
+
+        // Alliances need both sides, and survive a cooling of sentiments
+        assert_eq!(Relation::Neutral.next(&friend, &ally), Relation::Neutral);
+        assert_eq!(Relation::Neutral.next(&friend, &friend), Relation::Allied);
+        assert_eq!(Relation::Allied.next(&ally, &ally), Relation::Allied);
+        assert_eq!(Relation::Allied.next(&ally, &neutral), Relation::Neutral);
+        assert_eq!(Relation::Allied.next(&enemy, &friend), Relation::Hostile);
+    }
+}
@@ -0,0 +1,336 @@
+//! Upgrading of rtsim data written by older versions of the game.
+//!
+//! Migrations operate on the raw MessagePack representation of the data, so
+//! they can refer to fields and types that no longer exist in the code. Each
+//! migration upgrades the data by a single version, and they are chained
+//! together to bring old data up to [`CURRENT_VERSION`].
+//!
+//! When making a change that would break the loading of existing data:
+//!
+//! 1. Increment [`CURRENT_VERSION`]
+//! 2. Add a migration from the previous version to [`MIGRATIONS`]
+//! 3. Add a `data.dat` written by the previous version to
+//!    `rtsim/tests/fixtures`, named after its version (e.g: `v10.dat`)
+
+use super::CURRENT_VERSION;
+use rmpv::Value;
+use std::fmt;
+
+/// The oldest version of rtsim data that can be migrated. Older data gets
+/// purged.
+pub const OLDEST_MIGRATABLE_VERSION: u32 = 9;
+
+/// Upgrades data from one version to the next.
+pub type Migration = fn(&mut Value) -> Result<(), MigrationError>;
+
+/// `MIGRATIONS[i]` upgrades data of version `OLDEST_MIGRATABLE_VERSION + i` to
+/// the version after it.
+pub const MIGRATIONS: &[Migration] = &[v9_to_v10];
+
+/// Version 10 requires every NPC to have a health fraction and a list of known
+/// reports. NPCs without them start out healthy and unaware of any reports.
+fn v9_to_v10(data: &mut Value) -> Result<(), MigrationError> {
+    let invalid = |reason: &str| MigrationError::Invalid {
+        version: 9,
+        reason: reason.to_string(),
+    };
+    let Some(npcs) = field_mut(data, "npcs")? else {
+        return Ok(());
+    };
+    let Some(slots) = field_mut(npcs, "npcs")? else {
+        return Ok(());
+    };
+    let slots = match slots {
+        Value::Array(slots) => slots,
+        _ => return Err(invalid("npcs are not a list of slots")),
+    };
+    for slot in slots {
+        // Slots of removed NPCs have no value
+        if let Some(npc) = field_mut(slot, "value")?.filter(|npc| !npc.is_nil()) {
+            if field(npc, "health_fraction")?.is_none() {
+                set_field(npc, "health_fraction", Value::from(1.0f32))?;
+            }
+            if field(npc, "known_reports")?.is_none() {
+                set_field(npc, "known_reports", Value::Array(Vec::new()))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+const _: () = assert!(
+    OLDEST_MIGRATABLE_VERSION + MIGRATIONS.len() as u32 == CURRENT_VERSION,
+    "There must be a migration for every version since the oldest migratable one"
+);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationError {
+    /// The data is not a map of named fields, as written by
+    /// [`super::Data::write_to`].
+    NotAMap,
+    /// The data predates [`OLDEST_MIGRATABLE_VERSION`].
+    TooOld(u32),
+    /// The data was written by a newer version of the game.
+    TooNew(u32),
+    /// A migration failed to make sense of the data.
+    Invalid { version: u32, reason: String },
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotAMap => write!(f, "rtsim data is not a map of fields"),
+            Self::TooOld(version) => write!(
+                f,
+                "rtsim data version {version} is older than the oldest migratable version \
+                 {OLDEST_MIGRATABLE_VERSION}"
+            ),
+            Self::TooNew(version) => write!(
+                f,
+                "rtsim data version {version} is newer than the current version {CURRENT_VERSION}"
+            ),
+            Self::Invalid { version, reason } => {
+                write!(
+                    f,
+                    "failed to migrate rtsim data from version {version}: {reason}"
+                )
+            },
+        }
+    }
+}
+
+/// The version of the given data. Data written before versioning was introduced
+/// has no version field and is considered to be version 0.
+pub fn version_of(data: &Value) -> Result<u32, MigrationError> {
+    Ok(field(data, "version")?
+        .and_then(Value::as_u64)
+        .map_or(0, |version| version as u32))
+}
+
+/// Upgrade data to [`CURRENT_VERSION`], returning the version it had before.
+pub fn migrate(data: &mut Value) -> Result<u32, MigrationError> {
+    migrate_with(data, OLDEST_MIGRATABLE_VERSION, MIGRATIONS)
+}
+
+fn migrate_with(
+    data: &mut Value,
+    oldest: u32,
+    migrations: &[Migration],
+) -> Result<u32, MigrationError> {
+    let current = oldest + migrations.len() as u32;
+    let original = version_of(data)?;
+    if original < oldest {
+        return Err(MigrationError::TooOld(original));
+    } else if original > current {
+        return Err(MigrationError::TooNew(original));
+    }
+
+    let pending = &migrations[(original - oldest) as usize..];
+    for (version, migration) in (original..).zip(pending) {
+        migration(data)?;
+        set_field(data, "version", Value::from(version + 1))?;
+    }
+    Ok(original)
+}
+
+fn entries(data: &Value) -> Result<&[(Value, Value)], MigrationError> {
+    data.as_map()
+        .map(Vec::as_slice)
+        .ok_or(MigrationError::NotAMap)
+}
+
+fn entries_mut(data: &mut Value) -> Result<&mut Vec<(Value, Value)>, MigrationError> {
+    match data {
+        Value::Map(entries) => Ok(entries),
+        _ => Err(MigrationError::NotAMap),
+    }
+}
+
+/// Get the field `name` of a map.
+pub fn field<'a>(data: &'a Value, name: &str) -> Result<Option<&'a Value>, MigrationError> {
+    Ok(entries(data)?
+        .iter()
+        .find(|(key, _)| key.as_str() == Some(name))
+        .map(|(_, value)| value))
+}
+
+/// Get the field `name` of a map for modification.
+pub fn field_mut<'a>(
+    data: &'a mut Value,
+    name: &str,
+) -> Result<Option<&'a mut Value>, MigrationError> {
+    Ok(entries_mut(data)?
+        .iter_mut()
+        .find(|(key, _)| key.as_str() == Some(name))
+        .map(|(_, value)| value))
+}
+
+/// Set the field `name` of a map, adding it if it doesn't exist yet.
+pub fn set_field(data: &mut Value, name: &str, value: Value) -> Result<(), MigrationError> {
+    match field_mut(data, name)? {
+        Some(old) => *old = value,
+        None => entries_mut(data)?.push((Value::from(name), value)),
+    }
+    Ok(())
+}
+
+/// Remove the field `name` of a map, returning its value.
+pub fn remove_field(data: &mut Value, name: &str) -> Result<Option<Value>, MigrationError> {
+    let entries = entries_mut(data)?;
+    Ok(entries
+        .iter()
+        .position(|(key, _)| key.as_str() == Some(name))
+        .map(|idx| entries.remove(idx).1))
+}
+
+/// Rename the field `from` of a map to `to`, if it exists.
+pub fn rename_field(data: &mut Value, from: &str, to: &str) -> Result<(), MigrationError> {
+    if let Some((key, _)) = entries_mut(data)?
+        .iter_mut()
+        .find(|(key, _)| key.as_str() == Some(from))
+    {
+        *key = Value::from(to);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(version: Option<u32>) -> Value {
+        let mut entries = vec![(Value::from("tick"), Value::from(42))];
+        if let Some(version) = version {
+            entries.push((Value::from("version"), Value::from(version)));
+        }
+        Value::Map(entries)
+    }
+
+    fn rename_tick(data: &mut Value) -> Result<(), MigrationError> {
+        rename_field(data, "tick", "ticks")
+    }
+
+    fn double_ticks(data: &mut Value) -> Result<(), MigrationError> {
+        let ticks = field_mut(data, "ticks")?.ok_or_else(|| MigrationError::Invalid {
+            version: 4,
+            reason: "missing ticks".to_string(),
+        })?;
+        *ticks = Value::from(ticks.as_u64().unwrap_or(0) * 2);
+        Ok(())
+    }
+
+    #[test]
+    fn chain() {
+        let migrations: &[Migration] = &[rename_tick, double_ticks];
+
+        let mut old = data(Some(3));
+        assert_eq!(migrate_with(&mut old, 3, migrations), Ok(3));
+        assert_eq!(version_of(&old), Ok(5));
+        assert_eq!(field(&old, "tick"), Ok(None));
+        assert_eq!(field(&old, "ticks"), Ok(Some(&Value::from(84))));
+
+        // Only the migrations after the version of the data are applied
+        let mut newer = Value::Map(vec![
+            (Value::from("version"), Value::from(4)),
+            (Value::from("ticks"), Value::from(1)),
+        ]);
+        assert_eq!(migrate_with(&mut newer, 3, migrations), Ok(4));
+        assert_eq!(field(&newer, "ticks"), Ok(Some(&Value::from(2))));
+
+        let mut current = data(Some(5));
+        assert_eq!(migrate_with(&mut current, 3, migrations), Ok(5));
+        assert_eq!(current, data(Some(5)));
+    }
+
+    #[test]
+    fn unmigratable() {
+        let migrations: &[Migration] = &[rename_tick, double_ticks];
+
+        assert_eq!(
+            migrate_with(&mut data(None), 3, migrations),
+            Err(MigrationError::TooOld(0))
+        );
+        assert_eq!(
+            migrate_with(&mut data(Some(2)), 3, migrations),
+            Err(MigrationError::TooOld(2))
+        );
+        assert_eq!(
+            migrate_with(&mut data(Some(6)), 3, migrations),
+            Err(MigrationError::TooNew(6))
+        );
+        assert_eq!(
+            migrate_with(&mut Value::from(3), 3, migrations),
+            Err(MigrationError::NotAMap)
+        );
+        // The failing migration is reported
+        assert!(matches!(
+            migrate_with(&mut data(Some(4)), 3, migrations),
+            Err(MigrationError::Invalid { version: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn v9_npcs_get_health_and_reports() {
+        let npc = |entries: Vec<(&str, Value)>| {
+            Value::Map(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (Value::from(key), value))
+                    .collect(),
+            )
+        };
+        let slot = |value: Value| npc(vec![("value", value), ("version", Value::from(1))]);
+        let mut data = npc(vec![
+            ("version", Value::from(9)),
+            (
+                "npcs",
+                npc(vec![
+                    ("uid_counter", Value::from(3)),
+                    (
+                        "npcs",
+                        Value::Array(vec![
+                            slot(npc(vec![("seed", Value::from(1))])),
+                            slot(Value::Nil),
+                            slot(npc(vec![("health_fraction", Value::from(0.5f32))])),
+                        ]),
+                    ),
+                ]),
+            ),
+        ]);
+        assert_eq!(migrate(&mut data), Ok(9));
+        assert_eq!(version_of(&data), Ok(CURRENT_VERSION));
+
+        let slots = field(field(&data, "npcs").unwrap().unwrap(), "npcs")
+            .unwrap()
+            .unwrap()
+            .as_array()
+            .unwrap();
+        let npc_field = |i: usize, name| {
+            field(field(&slots[i], "value").unwrap().unwrap(), name)
+                .unwrap()
+                .cloned()
+        };
+        assert_eq!(npc_field(0, "health_fraction"), Some(Value::from(1.0f32)));
+        assert_eq!(
+            npc_field(0, "known_reports"),
+            Some(Value::Array(Vec::new()))
+        );
+        assert_eq!(field(&slots[1], "value"), Ok(Some(&Value::Nil)));
+        // Existing values are kept
+        assert_eq!(npc_field(2, "health_fraction"), Some(Value::from(0.5f32)));
+    }
+
+    #[test]
+    fn fields() {
+        let mut data = data(Some(1));
+        set_field(&mut data, "version", Value::from(2)).unwrap();
+        set_field(&mut data, "name", Value::from("world")).unwrap();
+        assert_eq!(version_of(&data), Ok(2));
+        assert_eq!(
+            remove_field(&mut data, "name"),
+            Ok(Some(Value::from("world")))
+        );
+        assert_eq!(remove_field(&mut data, "name"), Ok(None));
+        assert_eq!(field(&data, "tick"), Ok(Some(&Value::from(42))));
+    }
+}
@@ -1,6 +1,7 @@
 pub mod airship;
 pub mod architect;
 pub mod faction;
+pub mod migrate;
 pub mod nature;
 pub mod npc;
 pub mod quest;
@@ -9,7 +10,7 @@ pub mod sentiment;
 pub mod site;
 
 pub use self::{
-    faction::{Faction, FactionId, Factions},
+    faction::{Faction, FactionId, Factions, Relation},
     nature::Nature,
     npc::{Npc, NpcId, Npcs},
     quest::Quests,
@@ -21,6 +22,7 @@ use airship::AirshipSim;
 use architect::Architect;
 use common::resources::TimeOfDay;
 use enum_map::{EnumArray, EnumMap, enum_map};
+use migrate::MigrationError;
 use serde::{Deserialize, Serialize, de, ser};
 use std::{
     cmp::PartialEq,
@@ -28,12 +30,14 @@ use std::{
     io::{Read, Write},
     marker::PhantomData,
 };
+use tracing::{info, warn};
 
 /// The current version of rtsim data.
 ///
 /// Note that this number does *not* need incrementing on every change: most
 /// field removals/additions are fine. This number should only be incremented
-/// when we wish to perform a *hard purge* of rtsim data.
+/// when existing data can no longer be loaded, alongside a migration (see
+/// [`migrate`]).
 pub const CURRENT_VERSION: u32 = 10;
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -71,6 +75,7 @@ pub struct Data {
 
 pub enum ReadError {
     Load(rmp_serde::decode::Error),
+    Migrate(MigrationError),
     // Preserve old data
     VersionMismatch(Box<Data>),
 }
@@ -79,6 +84,7 @@ impl fmt::Debug for ReadError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Load(err) => err.fmt(f),
+            Self::Migrate(err) => err.fmt(f),
             Self::VersionMismatch(_) => write!(f, "VersionMismatch"),
         }
     }
@@ -96,16 +102,48 @@ impl Data {
         id
     }
 
-    pub fn from_reader<R: Read>(reader: R) -> Result<Box<Self>, ReadError> {
-        rmp_serde::decode::from_read(reader)
-            .map_err(ReadError::Load)
-            .and_then(|data: Data| {
-                if data.version == CURRENT_VERSION {
-                    Ok(Box::new(data))
-                } else {
-                    Err(ReadError::VersionMismatch(Box::new(data)))
+    /// Read data, migrating it to [`CURRENT_VERSION`] if it was written by an
+    /// older version.
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Box<Self>, ReadError> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(|err| ReadError::Load(rmp_serde::decode::Error::InvalidDataRead(err)))?;
+
+        // Most of the time the data is up to date, so avoid the cost of going through
+        // the raw representation
+        if let Ok(data) = rmp_serde::from_slice::<Data>(&bytes)
+            && data.version == CURRENT_VERSION
+        {
+            return Ok(Box::new(data));
+        }
+
+        let mut value = rmpv::decode::read_value(&mut bytes.as_slice())
+            .map_err(|err| ReadError::Load(rmp_serde::decode::Error::Syntax(err.to_string())))?;
+        match migrate::migrate(&mut value) {
+            Ok(version) => {
+                if version != CURRENT_VERSION {
+                    info!("Migrated rtsim data from version {version} to {CURRENT_VERSION}");
                 }
-            })
+                bytes.clear();
+                rmpv::encode::write_value(&mut bytes, &value).expect("Writing to a Vec can't fail");
+                rmp_serde::from_slice(&bytes)
+                    .map(Box::new)
+                    .map_err(ReadError::Load)
+            },
+            Err(
+                err @ (MigrationError::TooOld(_)
+                | MigrationError::TooNew(_)
+                | MigrationError::NotAMap),
+            ) => {
+                warn!("Rtsim data can't be migrated: {err}");
+                match rmp_serde::from_slice(&bytes) {
+                    Ok(data) => Err(ReadError::VersionMismatch(Box::new(data))),
+                    Err(err) => Err(ReadError::Load(err)),
+                }
+            },
+            Err(err) => Err(ReadError::Migrate(err)),
+        }
     }
 
     pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), WriteError> {
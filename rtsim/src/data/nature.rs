@@ -1,4 +1,7 @@
-use common::{grid::Grid, rtsim::TerrainResource};
+use common::{
+    grid::Grid,
+    rtsim::{TerrainResource, WildlifeKind},
+};
 use enum_map::EnumMap;
 use serde::{Deserialize, Serialize};
 use vek::*;
@@ -20,6 +23,7 @@ impl Nature {
         Self {
             chunks: Grid::populate_from(world.sim().get_size().map(|e| e as i32), |_| Chunk {
                 res: EnumMap::<_, f32>::default().map(|_, _| 1.0),
+                wildlife: Chunk::full_wildlife(),
             }),
         }
     }
@@ -36,6 +40,19 @@ impl Nature {
     ) -> Option<&mut EnumMap<TerrainResource, f32>> {
         self.chunks.get_mut(key).map(|c| &mut c.res)
     }
+
+    #[inline]
+    pub fn chunk_wildlife(&self, key: Vec2<i32>) -> Option<&EnumMap<WildlifeKind, f32>> {
+        self.chunks.get(key).map(|c| &c.wildlife)
+    }
+
+    /// Take an amount of wildlife of the given kind out of a chunk, as
+    /// happens when it gets hunted.
+    pub fn hunt(&mut self, key: Vec2<i32>, kind: WildlifeKind, amount: f32) {
+        if let Some(chunk) = self.chunks.get_mut(key) {
+            chunk.wildlife[kind] = (chunk.wildlife[kind] - amount).max(0.0);
+        }
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -63,4 +80,22 @@ pub struct Chunk {
     #[serde(serialize_with = "crate::data::rugged_ser_enum_map::<_, _, _, 1>")]
     #[serde(deserialize_with = "crate::data::rugged_de_enum_map::<_, _, _, 1>")]
     pub res: EnumMap<TerrainResource, f32>,
+    /// The wildlife populations of this chunk, relative to what the chunk can
+    /// naturally support.
+    ///
+    /// Like `res`, this is a factor of what terrain generation would spawn: at
+    /// 1.0, wildlife spawns as often as it would in an untouched world, and at
+    /// 0.0 none spawns at all. Populations change as predators feed on prey,
+    /// as animals migrate between chunks with the seasons and as they get
+    /// hunted, see [`crate::rule::wildlife`].
+    #[serde(rename = "w", default = "Chunk::full_wildlife")]
+    #[serde(serialize_with = "crate::data::rugged_ser_enum_map::<_, _, _, 1>")]
+    #[serde(deserialize_with = "crate::data::rugged_de_enum_map::<_, _, _, 1>")]
+    pub wildlife: EnumMap<WildlifeKind, f32>,
+}
+
+impl Chunk {
+    fn full_wildlife() -> EnumMap<WildlifeKind, f32> {
+        EnumMap::<_, f32>::default().map(|_, _| 1.0)
+    }
 }
@@ -9,10 +9,11 @@ use common::{
     comp::{self, agent::FlightMode, item::ItemDef},
     grid::Grid,
     map::Marker,
-    resources::Time,
+    resources::{Time, TimeOfDay},
     rtsim::{
-        Actor, Dialogue, DialogueId, DialogueKind, FactionId, NpcAction, NpcActivity, NpcInput,
-        Personality, QuestId, ReportId, Response, Role, SiteId, TerrainResource,
+        Actor, CompanionOrder, Dialogue, DialogueId, DialogueKind, FactionId, NpcAction,
+        NpcActivity, NpcInput, Personality, QuestId, ReportId, Response, Role, SiteId,
+        TerrainResource,
     },
     store::Id,
     terrain::CoordinateConversions,
@@ -68,6 +69,7 @@ pub struct Controller {
     pub new_home: Option<Option<SiteId>>,
     pub look_dir: Option<Dir>,
     pub job: Option<Job>,
+    pub order: CompanionOrder,
     pub quests_to_create: Vec<(QuestId, Quest)>,
 }
 
@@ -78,6 +80,7 @@ impl Controller {
         self.activity = None;
         self.look_dir = None;
         self.job = npc.job.clone();
+        self.order = npc.order;
     }
 
     pub fn do_idle(&mut self) { self.activity = None; }
@@ -124,6 +127,9 @@ impl Controller {
         self.actions.push(NpcAction::Say(target.into(), content));
     }
 
+    /// Invite the target to trade with the NPC.
+    pub fn trade(&mut self, target: Actor) { self.actions.push(NpcAction::Trade(target)); }
+
     pub fn npc_dialogue(
         &mut self,
         target: NpcId,
@@ -150,14 +156,19 @@ impl Controller {
 
     pub fn set_newly_hired(&mut self, actor: Actor, expires: Time) {
         self.job = Some(Job::Hired(actor, expires));
+        self.order = CompanionOrder::default();
     }
 
     pub fn end_hiring(&mut self) {
         if matches!(self.job, Some(Job::Hired(..))) {
             self.job = None;
+            self.order = CompanionOrder::default();
         }
     }
 
+    /// Follow an order of the actor that hired the NPC.
+    pub fn set_order(&mut self, order: CompanionOrder) { self.order = order; }
+
     pub fn end_quest(&mut self) {
         if matches!(self.job, Some(Job::Quest(..))) {
             self.job = None;
@@ -305,6 +316,12 @@ pub struct Npc {
 
     #[serde(default)]
     pub job: Option<Job>,
+    /// What the NPC has been told to do by the actor that hired them.
+    #[serde(default)]
+    pub order: CompanionOrder,
+    /// Experience earned as a companion, see [`Npc::gain_exp`].
+    #[serde(default)]
+    pub exp: u32,
 
     // Unpersisted state
     #[serde(skip)]
@@ -359,6 +376,8 @@ impl Clone for Npc {
             personality: self.personality,
             sentiments: self.sentiments.clone(),
             job: self.job.clone(),
+            order: self.order,
+            exp: self.exp,
             // Not persisted
             chunk_pos: None,
             current_site: Default::default(),
@@ -372,7 +391,12 @@ impl Clone for Npc {
 }
 
 impl Npc {
+    /// The experience that an adventurer needs to go up one rank, per rank.
+    const EXP_PER_RANK: u32 = 20;
+    /// The highest rank of adventurers.
+    pub const MAX_RANK: u32 = 3;
     pub const PERM_ENTITY_CONFIG: u32 = 1;
+    const PERM_MEMORY: u32 = 2;
     const PERM_NAME: u32 = 0;
 
     pub fn new(seed: u32, wpos: Vec3<f32>, body: comp::Body, role: Role) -> Self {
@@ -386,6 +410,8 @@ impl Npc {
             personality: Default::default(),
             sentiments: Default::default(),
             job: None,
+            order: CompanionOrder::default(),
+            exp: 0,
             role,
             home: None,
             faction: None,
@@ -460,16 +486,45 @@ impl Npc {
         }
     }
 
-    pub fn cleanup(&mut self, reports: &Reports) {
+    /// Gain experience, going up in rank once there is enough of it. Only
+    /// adventurers have ranks, so only they gain experience. Returns whether
+    /// the NPC went up in rank.
+    pub fn gain_exp(&mut self, exp: u32) -> bool {
+        let Role::Civilised(Some(Profession::Adventurer(rank))) = &mut self.role else {
+            return false;
+        };
+        if *rank >= Self::MAX_RANK {
+            return false;
+        }
+
+        self.exp += exp;
+        let needed = Self::EXP_PER_RANK * (*rank + 1);
+        if self.exp >= needed {
+            self.exp -= needed;
+            *rank += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// How long the NPC remembers reports for, as a fraction of the time for
+    /// which rtsim remembers them.
+    pub fn memory(&self) -> f64 { self.rng(Self::PERM_MEMORY).random_range(0.3..1.0) }
+
+    pub fn cleanup(&mut self, reports: &Reports, time_of_day: TimeOfDay) {
         // Clear old or superfluous sentiments
         // TODO: It might be worth giving more important NPCs a higher sentiment
         // 'budget' than less important ones.
         self.sentiments
             .cleanup(crate::data::sentiment::NPC_MAX_SENTIMENTS);
         // Clear reports that have been forgotten
-        self.known_reports
-            .retain(|report| reports.contains_key(*report));
-        // TODO: Limit number of reports
+        reports.forget(
+            &mut self.known_reports,
+            time_of_day,
+            self.memory(),
+            crate::data::report::NPC_MAX_REPORTS,
+        );
         // TODO: Clear old inbox items
     }
 }
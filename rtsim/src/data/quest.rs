@@ -5,7 +5,8 @@ use common::{
 use hashbrown::{HashMap, HashSet};
 use itertools::Either;
 use serde::{Deserialize, Serialize};
-use std::sync::atomic::{AtomicU8, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU64, Ordering};
+use vek::*;
 
 /// The easiest way to think about quests is as a virtual Jira board (or,
 /// perhaps, a community jobs noticeboard).
@@ -66,6 +67,10 @@ impl Quests {
 
     pub fn get(&self, id: QuestId) -> Option<&Quest> { self.quests.get(&id) }
 
+    pub fn iter(&self) -> impl Iterator<Item = (QuestId, &Quest)> + '_ {
+        self.quests.iter().map(|(id, quest)| (*id, quest))
+    }
+
     pub fn related_to(&self, actor: impl Into<Actor>) -> impl Iterator<Item = QuestId> + '_ {
         match self.related_quests.get(&actor.into()) {
             Some(quests) => Either::Left(
@@ -138,6 +143,17 @@ pub struct Quest {
 
     outcome: QuestOutcome,
 
+    /// Whether the objective of the quest has been reached, for quests that
+    /// still need to be reported back to the arbiter afterwards (such as
+    /// exploring a place). Like the resolution, this is monotonic.
+    #[serde(default)]
+    reached: QuestFlag,
+
+    /// Whether the actor doing the quest gave up on it. The arbiter resolves
+    /// abandoned quests as failed.
+    #[serde(default)]
+    abandoned: QuestFlag,
+
     /// The only aspect of the quest that mutates over time. Resolving quests is
     /// monotonic: once resolved, they cannot be unresolved (to avoid the
     /// deposit being paid back twice, for example).
@@ -159,6 +175,8 @@ impl Quest {
             },
             timeout: None,
             outcome: QuestOutcome::default(),
+            reached: QuestFlag::default(),
+            abandoned: QuestFlag::default(),
             res: QuestRes(AtomicU8::new(0)),
         }
     }
@@ -170,6 +188,87 @@ impl Quest {
             kind: QuestKind::Slay { target, slayer },
             timeout: None,
             outcome: QuestOutcome::default(),
+            reached: QuestFlag::default(),
+            abandoned: QuestFlag::default(),
+            res: QuestRes(AtomicU8::new(0)),
+        }
+    }
+
+    /// Create a new fetch quest that requires the fetcher to bring an amount of
+    /// an item to the arbiter.
+    pub fn fetch(arbiter: Actor, fetcher: Actor, item: ItemResource, amount: f32) -> Self {
+        Self {
+            arbiter,
+            kind: QuestKind::Fetch {
+                fetcher,
+                item,
+                amount,
+            },
+            timeout: None,
+            outcome: QuestOutcome::default(),
+            reached: QuestFlag::default(),
+            abandoned: QuestFlag::default(),
+            res: QuestRes(AtomicU8::new(0)),
+        }
+    }
+
+    /// Create a new delivery quest that requires the deliverer to carry an
+    /// amount of an item to a recipient at another site.
+    ///
+    /// The arbiter holds the deposit and gets it back if the delivery fails.
+    /// The recipient confirms the delivery, so they may also resolve the quest
+    /// successfully, paying out the deposit on behalf of the arbiter.
+    pub fn deliver(
+        arbiter: Actor,
+        deliverer: Actor,
+        recipient: Actor,
+        to: SiteId,
+        item: ItemResource,
+        amount: f32,
+    ) -> Self {
+        Self {
+            arbiter,
+            kind: QuestKind::Deliver {
+                deliverer,
+                recipient,
+                to,
+                item,
+                amount,
+            },
+            timeout: None,
+            outcome: QuestOutcome::default(),
+            reached: QuestFlag::default(),
+            abandoned: QuestFlag::default(),
+            res: QuestRes(AtomicU8::new(0)),
+        }
+    }
+
+    /// Create a new explore quest that requires the explorer to visit a place
+    /// and then report back to the arbiter.
+    pub fn explore(arbiter: Actor, explorer: Actor, wpos: Vec2<i32>) -> Self {
+        Self {
+            arbiter,
+            kind: QuestKind::Explore { explorer, wpos },
+            timeout: None,
+            outcome: QuestOutcome::default(),
+            reached: QuestFlag::default(),
+            abandoned: QuestFlag::default(),
+            res: QuestRes(AtomicU8::new(0)),
+        }
+    }
+
+    /// Create a new hire quest, the contract of a companion working for the
+    /// actor that hired them. The deposit is the pay of the companion.
+    ///
+    /// The companion is considered to be the quest arbiter.
+    pub fn hire(companion: Actor, hirer: Actor) -> Self {
+        Self {
+            arbiter: companion,
+            kind: QuestKind::Hire { companion, hirer },
+            timeout: None,
+            outcome: QuestOutcome::default(),
+            reached: QuestFlag::default(),
+            abandoned: QuestFlag::default(),
             res: QuestRes(AtomicU8::new(0)),
         }
     }
@@ -205,9 +304,15 @@ impl Quest {
     ///
     /// The `requester` parameter is a sanity test: you should pass the actor
     /// that originated the request to resolve, and the function will ensure
-    /// that this matches the quest's designated arbiter.
+    /// that this matches the quest's designated arbiter (or, for successful
+    /// deliveries, the recipient).
     pub fn resolve(&self, requester: impl Into<Actor>, res: bool) -> Option<QuestOutcome> {
-        if self.arbiter != requester.into() {
+        let requester = requester.into();
+        let is_recipient = matches!(
+            self.kind,
+            QuestKind::Deliver { recipient, .. } if recipient == requester
+        );
+        if self.arbiter != requester && !(res && is_recipient) {
             // Actor that requested quest resolution did not match the designated arbiter,
             // resolution not permitted!
             None
@@ -227,6 +332,32 @@ impl Quest {
 
     pub fn resolution(&self) -> Option<bool> { self.res.get() }
 
+    /// The item held in deposit, see [`QuestOutcome::deposit`].
+    pub fn deposit(&self) -> Option<(ItemResource, f32)> { self.outcome.deposit }
+
+    /// Record that the objective of the quest has been reached.
+    pub fn mark_reached(&self) { self.reached.0.store(true, Ordering::Relaxed) }
+
+    pub fn is_reached(&self) -> bool { self.reached.0.load(Ordering::Relaxed) }
+
+    /// Give up on an unresolved quest. Only the actors taking part in the
+    /// quest, other than the arbiter, may do this. Returns whether the quest
+    /// was abandoned.
+    pub fn abandon(&self, requester: impl Into<Actor>) -> bool {
+        let requester = requester.into();
+        if requester != self.arbiter
+            && self.resolution().is_none()
+            && self.get_related_actors().contains(&requester)
+        {
+            self.abandoned.0.store(true, Ordering::Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn is_abandoned(&self) -> bool { self.abandoned.0.load(Ordering::Relaxed) }
+
     pub fn get_related_actors(&self) -> HashSet<Actor> {
         let mut related = HashSet::default();
         self.for_related_actors(|actor| {
@@ -250,6 +381,20 @@ impl Quest {
                 f(*target);
                 f(*slayer);
             },
+            QuestKind::Fetch { fetcher, .. } => f(*fetcher),
+            QuestKind::Deliver {
+                deliverer,
+                recipient,
+                ..
+            } => {
+                f(*deliverer);
+                f(*recipient);
+            },
+            QuestKind::Explore { explorer, .. } => f(*explorer),
+            QuestKind::Hire { companion, hirer } => {
+                f(*companion);
+                f(*hirer);
+            },
         }
     }
 }
@@ -268,6 +413,16 @@ impl QuestRes {
     }
 }
 
+#[derive(Default, Serialize, Deserialize)]
+struct QuestFlag(AtomicBool);
+
+impl Clone for QuestFlag {
+    fn clone(&self) -> Self {
+        // See `QuestRes::clone`
+        Self(AtomicBool::new(self.0.load(Ordering::Relaxed)))
+    }
+}
+
 impl Clone for QuestRes {
     fn clone(&self) -> Self {
         // This isn't strictly kosher in a multi-threaded context, but we assume that
@@ -308,4 +463,29 @@ pub enum QuestKind {
         target: Actor,
         slayer: Actor,
     },
+    /// Bring an amount of an item to the arbiter.
+    Fetch {
+        fetcher: Actor,
+        item: ItemResource,
+        amount: f32,
+    },
+    /// Carry an amount of an item, handed over when the quest was accepted, to
+    /// a recipient at another site.
+    Deliver {
+        deliverer: Actor,
+        recipient: Actor,
+        to: SiteId,
+        item: ItemResource,
+        amount: f32,
+    },
+    /// Visit a point of interest, then report back to the arbiter.
+    Explore {
+        explorer: Actor,
+        wpos: Vec2<i32>,
+    },
+    /// Work for the hirer as a companion until the contract ends.
+    Hire {
+        companion: Actor,
+        hirer: Actor,
+    },
 }
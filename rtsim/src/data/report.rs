@@ -1,8 +1,10 @@
+use crate::data::Relation;
 use common::{
     resources::TimeOfDay,
-    rtsim::{Actor, SiteId},
+    rtsim::{Actor, FactionId, SiteId},
     terrain::SpriteKind,
 };
+use hashbrown::HashSet;
 use serde::{Deserialize, Serialize};
 use slotmap::HopSlotMap;
 use std::ops::Deref;
@@ -10,6 +12,11 @@ use vek::*;
 
 pub use common::rtsim::ReportId;
 
+/// The number of reports that an NPC can know of at once.
+pub const NPC_MAX_REPORTS: usize = 64;
+/// The number of reports that a site can keep track of at once.
+pub const SITE_MAX_REPORTS: usize = 256;
+
 /// Represents a single piece of information known by an rtsim entity.
 ///
 /// Reports are the medium through which rtsim represents information sharing
@@ -43,8 +50,17 @@ impl Report {
             },
             // TODO: Could consider what was stolen here
             ReportKind::Theft { .. } => DAYS * 1.5,
+            ReportKind::Relation { .. } => DAYS * 5.0,
+            // People don't easily forget losing their home
+            ReportKind::SiteCaptured { .. } => DAYS * 10.0,
         }
     }
+
+    /// Whether the report is still remembered by somebody with the given
+    /// memory, as a fraction of the time for which rtsim remembers it.
+    pub fn is_remembered(&self, current_time: TimeOfDay, memory: f64) -> bool {
+        (current_time.0 - self.at_tod.0).max(0.0) < self.remember_for() * memory
+    }
 }
 
 #[derive(Copy, Clone, Serialize, Deserialize)]
@@ -52,6 +68,12 @@ pub enum ReportKind {
     Death {
         actor: Actor,
         killer: Option<Actor>,
+        /// The factions of the actor and the killer at the time of the death.
+        #[serde(default)]
+        factions: [Option<FactionId>; 2],
+        /// The site near which the death happened, if any.
+        #[serde(default)]
+        site: Option<SiteId>,
     },
     Theft {
         thief: Actor,
@@ -60,6 +82,17 @@ pub enum ReportKind {
         /// What was stolen.
         sprite: SpriteKind,
     },
+    /// The relation between two factions changed.
+    Relation {
+        factions: [FactionId; 2],
+        relation: Relation,
+    },
+    /// A site was captured by a raiding party.
+    SiteCaptured {
+        site: SiteId,
+        by: FactionId,
+        from: Option<FactionId>,
+    },
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
@@ -72,11 +105,37 @@ impl Reports {
 
     pub fn cleanup(&mut self, current_time: TimeOfDay) {
         // Forget reports that are too old
-        self.reports.retain(|_, report| {
-            (current_time.0 - report.at_tod.0).max(0.0) < report.remember_for()
-        });
+        self.reports
+            .retain(|_, report| report.is_remembered(current_time, 1.0));
         // TODO: Limit global number of reports
     }
+
+    /// Forget the known reports that have been forgotten globally or that are
+    /// too old for the given memory (see [`Report::is_remembered`]), and then
+    /// all but the `max` most recent ones.
+    pub fn forget(
+        &self,
+        known: &mut HashSet<ReportId>,
+        current_time: TimeOfDay,
+        memory: f64,
+        max: usize,
+    ) {
+        known.retain(|report| {
+            self.get(*report)
+                .is_some_and(|report| report.is_remembered(current_time, memory))
+        });
+        if known.len() > max {
+            let mut recent = known.iter().copied().collect::<Vec<_>>();
+            recent.sort_by(|a, b| {
+                self.reports[*b]
+                    .at_tod
+                    .0
+                    .total_cmp(&self.reports[*a].at_tod.0)
+            });
+            recent.truncate(max);
+            *known = recent.into_iter().collect();
+        }
+    }
 }
 
 impl Deref for Reports {
@@ -84,3 +143,38 @@ impl Deref for Reports {
 
     fn deref(&self) -> &Self::Target { &self.reports }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: f64 = 60.0 * 60.0 * 24.0;
+
+    #[test]
+    fn reports_are_forgotten() {
+        let mut reports = Reports::default();
+        let mut create = |days_ago: f64| {
+            reports.create(Report {
+                kind: ReportKind::Relation {
+                    factions: Default::default(),
+                    relation: Relation::Hostile,
+                },
+                at_tod: TimeOfDay(DAY * (10.0 - days_ago)),
+            })
+        };
+        let recent = create(0.5);
+        let older = create(1.0);
+        let old = create(4.0);
+        let now = TimeOfDay(DAY * 10.0);
+
+        let mut known = HashSet::from_iter([recent, older, old]);
+        reports.forget(&mut known, now, 1.0, 8);
+        assert_eq!(known.len(), 3);
+        // Relations are remembered for 5 days, so with half the memory the
+        // oldest report is forgotten
+        reports.forget(&mut known, now, 0.5, 8);
+        assert_eq!(known, HashSet::from_iter([recent, older]));
+        reports.forget(&mut known, now, 1.0, 1);
+        assert_eq!(known, HashSet::from_iter([recent]));
+    }
+}
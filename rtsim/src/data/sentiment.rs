@@ -65,6 +65,11 @@ impl Sentiments {
         self.map.entry(target.into()).or_default()
     }
 
+    /// Iterate over all of the targets that sentiments are felt toward.
+    pub fn iter(&self) -> impl Iterator<Item = (Target, &Sentiment)> {
+        self.map.iter().map(|(tgt, sentiment)| (*tgt, sentiment))
+    }
+
     /// Progressively decay the sentiment back to a neutral sentiment.
     ///
     /// Note that sentiment get decay gets slower the harsher the sentiment is.
@@ -151,7 +156,8 @@ impl Sentiment {
     /// generally try to harm the actor in any way they can.
     pub const VILLAIN: f32 = -0.8;
 
-    fn value(&self) -> f32 { self.positivity as f32 * (1.0 / 126.0) }
+    /// How positive the sentiment is, between -1 and 1.
+    pub fn value(&self) -> f32 { self.positivity as f32 * (1.0 / 126.0) }
 
     /// Change the sentiment toward the given target by the given amount,
     /// capping out at the given value.
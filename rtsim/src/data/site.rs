@@ -1,6 +1,7 @@
 use crate::data::{ReportId, Reports};
 pub use common::rtsim::SiteId;
 use common::{
+    resources::TimeOfDay,
     rtsim::{FactionId, NpcId},
     store::Id,
 };
@@ -60,11 +61,14 @@ impl Site {
         self
     }
 
-    pub fn cleanup(&mut self, reports: &Reports) {
+    pub fn cleanup(&mut self, reports: &Reports, time_of_day: TimeOfDay) {
         // Clear reports that have been forgotten
-        self.known_reports
-            .retain(|report| reports.contains_key(*report));
-        // TODO: Limit number of reports
+        reports.forget(
+            &mut self.known_reports,
+            time_of_day,
+            1.0,
+            crate::data::report::SITE_MAX_REPORTS,
+        );
     }
 
     pub fn is_loaded(&self) -> bool { self.count_loaded_chunks > 0 }
@@ -9,6 +9,9 @@ impl Faction {
             leader: None,
             good_or_evil: rng.random(),
             sentiments: Default::default(),
+            relations: Default::default(),
+            seat: None,
+            raid: None,
         }
     }
 }
@@ -19,6 +19,7 @@ use common::{
 use rand::prelude::*;
 use rand_chacha::ChaChaRng;
 use tracing::info;
+use vek::*;
 use world::{
     IndexRef, World, civ::airship_travel::AirshipSpawningLocation, site::PlotKind, util::seed_expan,
 };
@@ -153,6 +154,14 @@ impl Data {
             this.spawn_airship(spawning_location, &mut airship_rng);
         }
 
+        // Boats
+        // Put a boat (and captain) at the start of each water route. As with airships,
+        // the routes are not persisted, and boats are placed back onto them by the
+        // `migrate` module each time the server is started.
+        for route_index in 0..world.civs().water_routes.routes.len() {
+            this.spawn_boat(world, route_index, &mut rng);
+        }
+
         this.architect.wanted_population = wanted_population(world, index);
 
         info!(
@@ -205,4 +214,57 @@ impl Data {
 
         (npc_id, vehicle_id)
     }
+
+    /// Creates a boat and captain NPC at the start of a water route. Ferry
+    /// routes are sailed by sail boats and trade routes by galleons.
+    pub fn spawn_boat(
+        &mut self,
+        world: &World,
+        route_index: usize,
+        rng: &mut impl Rng,
+    ) -> (NpcId, NpcId) {
+        let (npc_wpos3d, body) = boat_spawning_location(world, route_index);
+        let water_routes = &world.civs().water_routes;
+        let dock = &water_routes.docks[water_routes.routes[route_index].docks[0]];
+
+        let vehicle_id = self.npcs.create_npc(Npc::new(
+            rng.random(),
+            npc_wpos3d,
+            Body::Ship(body),
+            Role::Vehicle,
+        ));
+
+        let species = comp::humanoid::ALL_SPECIES.choose(&mut *rng).unwrap();
+        let npc_id = self.npcs.create_npc(
+            Npc::new(
+                rng.random(),
+                npc_wpos3d,
+                Body::Humanoid(comp::humanoid::Body::random_with(rng, species)),
+                Role::Civilised(Some(Profession::Captain)),
+            )
+            .with_home(self.sites.world_site_map.get(&dock.site).copied())
+            .with_personality(Personality::random_good(rng)),
+        );
+
+        // The captain steers the boat
+        self.npcs
+            .mounts
+            .steer(vehicle_id, npc_id)
+            .expect("We just created these npcs!");
+
+        (npc_id, vehicle_id)
+    }
+}
+
+/// Where the boat of a water route starts out, on the water at its first dock,
+/// and the kind of boat that sails it.
+pub fn boat_spawning_location(world: &World, route_index: usize) -> (Vec3<f32>, comp::ship::Body) {
+    let water_routes = &world.civs().water_routes;
+    let route = &water_routes.routes[route_index];
+    let wpos = water_routes.docks[route.docks[0]].wpos();
+    let water_alt = world
+        .sim()
+        .get_interpolated(wpos.as_(), |chunk| chunk.water_alt)
+        .unwrap_or(0.0);
+    (wpos.with_z(water_alt), route.kind.boat())
 }
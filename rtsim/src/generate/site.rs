@@ -46,7 +46,8 @@ impl Site {
                 | SiteKind::Haniwa
                 | SiteKind::Adlet
                 | SiteKind::VampireCastle
-                | SiteKind::DwarvenMine,
+                | SiteKind::DwarvenMine
+                | SiteKind::ProceduralDungeon,
             ) => Some(false),
             // Neutral
             Some(SiteKind::GiantTree | SiteKind::GliderCourse | SiteKind::Bridge(..)) | None => {
@@ -201,7 +201,10 @@ impl RtState {
         self.start_rule::<rule::migrate::Migrate>();
         self.start_rule::<rule::architect::Architect>();
         self.start_rule::<rule::replenish_resources::ReplenishResources>();
+        self.start_rule::<rule::wildlife::Wildlife>();
         self.start_rule::<rule::report::ReportEvents>();
+        self.start_rule::<rule::companion::Companions>();
+        self.start_rule::<rule::diplomacy::Diplomacy>();
         self.start_rule::<rule::sync_npcs::SyncNpcs>();
         self.start_rule::<rule::simulate_npcs::SimulateNpcs>();
         self.start_rule::<rule::npc_ai::NpcAi>();
@@ -218,6 +221,18 @@ impl RtState {
         }
     }
 
+    pub fn rule<R: Rule>(&self) -> impl Deref<Target = R> + '_ {
+        self.rules
+            .get::<RuleState<R>>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "Tried to access rule '{}' but it does not exist",
+                    type_name::<R>()
+                )
+            })
+            .borrow()
+    }
+
     fn rule_mut<R: Rule>(&self) -> impl DerefMut<Target = R> + '_ {
         self.rules
             .get::<RuleState<R>>()
@@ -57,22 +57,27 @@ impl Rule for CleanUp {
             data.npcs
                 .iter_mut()
                 .filter(|(_, npc)| (npc.seed as u64 + ctx.event.tick).is_multiple_of(NPC_CLEANUP_TICK_SKIP))
-                .for_each(|(_, npc)| npc.cleanup(&data.reports));
+                .for_each(|(_, npc)| npc.cleanup(&data.reports, data.time_of_day));
 
             // Clean up factions
             data.factions
                 .iter_mut()
                 .filter(|(_, faction)| (faction.seed as u64 + ctx.event.tick).is_multiple_of(FACTION_CLEANUP_TICK_SKIP))
-                .for_each(|(_, faction)| faction.cleanup());
+                .for_each(|(_, faction)| {
+                    // Decay faction sentiments, so that old grudges are eventually forgotten
+                    faction.sentiments.decay(&mut rng, ctx.event.dt * FACTION_CLEANUP_TICK_SKIP as f32);
+                    faction.cleanup();
+                });
 
             // Clean up sites
             data.sites
                 .iter_mut()
                 .filter(|(_, site)| (site.seed as u64 + ctx.event.tick).is_multiple_of(SITE_CLEANUP_TICK_SKIP))
-                .for_each(|(_, site)| site.cleanup(&data.reports));
+                .for_each(|(_, site)| site.cleanup(&data.reports, data.time_of_day));
 
             // Clean up old reports
             data.reports.cleanup(data.time_of_day);
+            data.factions.cleanup(&data.reports);
         });
 
         Ok(Self)
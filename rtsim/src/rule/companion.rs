@@ -0,0 +1,49 @@
+use crate::{
+    RtState, Rule, RuleError,
+    event::{EventCtx, OnDeath},
+};
+use common::rtsim::Role;
+
+/// Companions that are near a kill made by themselves or by their hirer share
+/// in the experience, going up in rank as they gain it.
+pub struct Companions;
+
+impl Rule for Companions {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnDeath>(on_death);
+
+        Ok(Self)
+    }
+}
+
+fn on_death(ctx: EventCtx<Companions, OnDeath>) {
+    let data = &mut *ctx.state.data_mut();
+
+    let (Some(wpos), Some(killer)) = (ctx.event.wpos, ctx.event.killer) else {
+        return;
+    };
+    // Monsters are worth more than anything else
+    let exp = match ctx
+        .event
+        .actor
+        .npc()
+        .and_then(|npc_id| data.npcs.get(npc_id))
+    {
+        Some(npc) if matches!(npc.role, Role::Monster) => 5,
+        _ => 1,
+    };
+
+    let companions = data
+        .npcs
+        .nearby(None, wpos, 32.0)
+        .filter_map(|actor| actor.npc())
+        .collect::<Vec<_>>();
+    for npc_id in companions {
+        if let Some(npc) = data.npcs.get_mut(npc_id)
+            && let Some((hirer, _)) = npc.hired()
+            && (killer == hirer || killer.npc() == Some(npc_id))
+        {
+            npc.gain_exp(exp);
+        }
+    }
+}
@@ -0,0 +1,431 @@
+//! Relations between factions, and the raids that hostile factions send
+//! against one-another.
+//!
+//! Factions grow to like or dislike each other through the sentiments of
+//! their members and through the incidents recorded in [`Report`]s. Once these
+//! sentiments cross a threshold, the relation between the two factions
+//! changes (see [`Relation::next`]). Hostile factions send raiding parties to
+//! the sites of their enemies, and sites change hands when the raiders
+//! overwhelm the defenders.
+//!
+//! Everything here is damped: faction sentiments decay over time (see
+//! [`crate::rule::cleanup`]), a faction only carries out a single raid at a
+//! time, and allies are only drawn into a war up to the point of rivalry.
+
+use crate::{
+    RtState, Rule, RuleError,
+    data::{
+        Data, FactionId, Npc, Relation, Report, ReportId, ReportKind, Sentiment, faction::Raid,
+        sentiment::Target,
+    },
+    event::OnTick,
+};
+use common::{
+    comp,
+    rtsim::{NpcInput, Profession, SiteId},
+};
+use hashbrown::HashMap;
+use rand::prelude::*;
+use rand_chacha::ChaChaRng;
+use tracing::debug;
+
+/// Prevent updating the relations of every faction every tick
+const FACTION_DIPLOMACY_TICK_SKIP: u64 = 300;
+const INCIDENT_TICK_SKIP: u64 = 30;
+
+/// How much faction sentiments change in response to incidents between their
+/// members.
+const MURDER_SEVERITY: f32 = 0.1;
+const THEFT_SEVERITY: f32 = 0.02;
+const CAPTURE_SEVERITY: f32 = 0.4;
+/// How much the sentiments of members sway the sentiments of their faction
+/// each time relations are updated.
+const MEMBER_INFLUENCE: f32 = 0.02;
+/// The minimum number of member opinions before they sway the faction.
+const MIN_MEMBER_OPINIONS: u32 = 3;
+/// How much allies sour toward the enemies of their allies when a war starts.
+const ALLY_SOLIDARITY: f32 = 0.2;
+
+/// The chance that a faction starts a raid against one of its enemies each
+/// time its relations are updated.
+const RAID_CHANCE: f64 = 0.02;
+/// Sites further away than this, in blocks, are not worth raiding.
+const MAX_RAID_DIST: f32 = 3000.0;
+const MIN_RAIDERS: usize = 3;
+const MAX_RAIDERS: usize = 8;
+/// The number of in-game seconds after which an unsuccessful raid is given up.
+const RAID_TIMEOUT: f64 = 60.0 * 60.0 * 12.0;
+
+pub struct Diplomacy;
+
+impl Rule for Diplomacy {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnTick>(|ctx| {
+            let data = &mut *ctx.state.data_mut();
+            let mut rng = ChaChaRng::from_seed(rand::rng().random::<[u8; 32]>());
+
+            if ctx.event.tick.is_multiple_of(INCIDENT_TICK_SKIP) {
+                record_incidents(data);
+            }
+
+            let due = data
+                .factions
+                .iter()
+                .filter(|(_, faction)| {
+                    (faction.seed as u64 + ctx.event.tick)
+                        .is_multiple_of(FACTION_DIPLOMACY_TICK_SKIP)
+                })
+                .map(|(faction_id, _)| faction_id)
+                .collect::<Vec<_>>();
+
+            for faction_id in due {
+                update_seat(data, faction_id);
+                absorb_member_sentiments(data, faction_id);
+                update_relations(data, faction_id);
+                update_raid(data, faction_id, &mut rng);
+            }
+        });
+
+        Ok(Self)
+    }
+}
+
+/// Whether the NPC is fit to take part in raids, or to defend against them.
+fn is_fighter(npc: &Npc) -> bool {
+    matches!(npc.body, comp::Body::Humanoid(_))
+        && matches!(
+            npc.profession(),
+            Some(
+                Profession::Guard
+                    | Profession::Hunter
+                    | Profession::Adventurer(_)
+                    | Profession::Pirate(_)
+                    | Profession::Cultist
+            )
+        )
+}
+
+/// Sour the sentiments of factions toward the factions whose members wronged
+/// them.
+fn record_incidents(data: &mut Data) {
+    let new_reports = data
+        .reports
+        .iter()
+        .filter(|(report_id, _)| !data.factions.known_reports.contains(report_id))
+        .map(|(report_id, report)| (report_id, report.kind))
+        .collect::<Vec<_>>();
+
+    for (report_id, kind) in new_reports {
+        data.factions.known_reports.insert(report_id);
+
+        let (victim, offender, severity) = match kind {
+            ReportKind::Death {
+                factions: [victim, killer],
+                ..
+            } => (victim, killer, MURDER_SEVERITY),
+            ReportKind::Theft {
+                thief,
+                site: Some(site),
+                ..
+            } => (
+                data.sites.get(site).and_then(|site| site.faction),
+                thief
+                    .npc()
+                    .and_then(|thief| data.npcs.get(thief))
+                    .and_then(|thief| thief.faction),
+                THEFT_SEVERITY,
+            ),
+            ReportKind::SiteCaptured { by, from, .. } => (from, Some(by), CAPTURE_SEVERITY),
+            ReportKind::Theft { .. } | ReportKind::Relation { .. } => continue,
+        };
+
+        if let (Some(victim), Some(offender)) = (victim, offender)
+            && victim != offender
+            && let Some(faction) = data.factions.get_mut(victim)
+        {
+            faction
+                .sentiments
+                .toward_mut(offender)
+                .change_by(-severity, Sentiment::VILLAIN);
+        }
+    }
+}
+
+/// Make sure the faction is governed from one of the sites it holds.
+fn update_seat(data: &mut Data, faction_id: FactionId) {
+    let Some(faction) = data.factions.get(faction_id) else {
+        return;
+    };
+    if faction
+        .seat
+        .and_then(|seat| data.sites.get(seat))
+        .is_some_and(|seat| seat.faction == Some(faction_id))
+    {
+        return;
+    }
+
+    let seat = data
+        .sites
+        .iter()
+        .filter(|(_, site)| site.faction == Some(faction_id))
+        .max_by_key(|(_, site)| site.population.len())
+        .map(|(site_id, _)| site_id);
+    data.factions[faction_id].seat = seat;
+}
+
+/// The feelings that members have toward the members of other factions rub off
+/// on their faction.
+fn absorb_member_sentiments(data: &mut Data, faction_id: FactionId) {
+    let mut opinions = HashMap::<FactionId, (f32, u32)>::new();
+    for npc in data
+        .npcs
+        .values()
+        .filter(|npc| npc.faction == Some(faction_id) && !npc.is_dead())
+    {
+        for (target, sentiment) in npc.sentiments.iter() {
+            let other = match target {
+                Target::Faction(other) => Some(other),
+                Target::Npc(other) => data.npcs.get(other).and_then(|other| other.faction),
+                Target::Character(_) => None,
+            };
+            if let Some(other) = other
+                && other != faction_id
+            {
+                let (sum, count) = opinions.entry(other).or_default();
+                *sum += sentiment.value();
+                *count += 1;
+            }
+        }
+    }
+
+    let Some(faction) = data.factions.get_mut(faction_id) else {
+        return;
+    };
+    for (other, (sum, count)) in opinions {
+        let average = sum / count as f32;
+        // The faction comes to share the average opinion of its members, but never
+        // goes beyond it
+        if count >= MIN_MEMBER_OPINIONS && average.abs() >= Sentiment::POSITIVE {
+            faction
+                .sentiments
+                .toward_mut(other)
+                .change_by(average.signum() * MEMBER_INFLUENCE, average.abs());
+        }
+    }
+}
+
+fn update_relations(data: &mut Data, faction_id: FactionId) {
+    let others = data
+        .factions
+        .keys()
+        .filter(|other| *other != faction_id)
+        .collect::<Vec<_>>();
+
+    for other in others {
+        let (Some(faction), Some(other_faction)) =
+            (data.factions.get(faction_id), data.factions.get(other))
+        else {
+            continue;
+        };
+        let relation = faction.relation_to(other);
+        let next = relation.next(
+            faction.sentiments.toward(other),
+            other_faction.sentiments.toward(faction_id),
+        );
+        if next != relation {
+            set_relation(data, [faction_id, other], next);
+        }
+    }
+}
+
+fn set_relation(data: &mut Data, factions @ [a, b]: [FactionId; 2], relation: Relation) {
+    debug!(?a, ?b, ?relation, "Relation between factions changed");
+    for (faction, other) in [(a, b), (b, a)] {
+        if let Some(faction) = data.factions.get_mut(faction) {
+            faction.relations.insert(other, relation);
+        }
+    }
+
+    // Allies are drawn into the war, but not so far as to start one of their own
+    if relation == Relation::Hostile {
+        for (faction, enemy) in [(a, b), (b, a)] {
+            let allies = data
+                .factions
+                .keys()
+                .filter(|ally| {
+                    !factions.contains(ally)
+                        && data.factions.relation(*ally, faction) == Relation::Allied
+                })
+                .collect::<Vec<_>>();
+            for ally in allies {
+                data.factions[ally]
+                    .sentiments
+                    .toward_mut(enemy)
+                    .change_by(-ALLY_SOLIDARITY, Sentiment::RIVAL);
+            }
+        }
+    }
+
+    let report = data.reports.create(Report {
+        kind: ReportKind::Relation { factions, relation },
+        at_tod: data.time_of_day,
+    });
+    spread_report(data, report, &factions);
+}
+
+/// Let the members and sites of the given factions know about a report.
+fn spread_report(data: &mut Data, report: ReportId, factions: &[FactionId]) {
+    for npc in data.npcs.values_mut() {
+        if !npc.is_dead() && npc.faction.is_some_and(|f| factions.contains(&f)) {
+            npc.inbox.push_back(NpcInput::Report(report));
+        }
+    }
+    for site in data.sites.values_mut() {
+        if site.faction.is_some_and(|f| factions.contains(&f)) {
+            site.known_reports.insert(report);
+        }
+    }
+}
+
+fn update_raid(data: &mut Data, faction_id: FactionId, rng: &mut impl Rng) {
+    match data.factions.get(faction_id).map(|faction| &faction.raid) {
+        Some(Some(_)) => progress_raid(data, faction_id),
+        Some(None) if rng.random_bool(RAID_CHANCE) => plan_raid(data, faction_id, rng),
+        _ => {},
+    }
+}
+
+/// Send a raiding party from one of the faction's sites to a nearby site of an
+/// enemy.
+fn plan_raid(data: &mut Data, faction_id: FactionId, rng: &mut impl Rng) {
+    let faction = &data.factions[faction_id];
+    let Some((from, target)) = data
+        .sites
+        .iter()
+        .filter(|(_, site)| site.faction == Some(faction_id))
+        .filter_map(|(from, site)| {
+            let (target, _) = data
+                .sites
+                .iter()
+                .filter(|(_, other)| other.faction.is_some_and(|f| faction.is_hostile_to(f)))
+                .map(|(target, other)| (target, other.wpos.as_::<f32>().distance(site.wpos.as_())))
+                .filter(|(_, dist)| *dist < MAX_RAID_DIST)
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+            Some((from, target))
+        })
+        .choose(rng)
+    else {
+        return;
+    };
+
+    let mut raiders = data.sites[from]
+        .population
+        .iter()
+        .copied()
+        .filter(|npc_id| {
+            data.npcs.get(*npc_id).is_some_and(|npc| {
+                !npc.is_dead()
+                    && npc.faction == Some(faction_id)
+                    && npc.current_site == Some(from)
+                    && npc.job.is_none()
+                    && is_fighter(npc)
+            })
+        })
+        .collect::<Vec<_>>();
+    // Leave at least half of the fighters behind to defend the site
+    raiders.shuffle(rng);
+    raiders.truncate((raiders.len() / 2).min(MAX_RAIDERS));
+    if raiders.len() < MIN_RAIDERS {
+        return;
+    }
+
+    debug!(
+        ?faction_id,
+        ?from,
+        ?target,
+        raiders = raiders.len(),
+        "Starting raid"
+    );
+    data.factions[faction_id].raid = Some(Raid {
+        from,
+        target,
+        raiders,
+        started: data.time_of_day,
+    });
+}
+
+/// Check on an ongoing raid, capturing the target site once the raiders present
+/// outnumber the defenders.
+fn progress_raid(data: &mut Data, faction_id: FactionId) {
+    let Some(mut raid) = data.factions[faction_id].raid.take() else {
+        return;
+    };
+    raid.raiders.retain(|npc_id| {
+        data.npcs
+            .get(*npc_id)
+            .is_some_and(|npc| !npc.is_dead() && npc.faction == Some(faction_id))
+    });
+
+    let Some(site) = data.sites.get(raid.target) else {
+        return;
+    };
+    let defending_faction = site.faction;
+    let still_at_war = defending_faction.is_some_and(|defending_faction| {
+        data.factions[faction_id].is_hostile_to(defending_faction)
+    });
+    let timed_out = data.time_of_day.0 - raid.started.0 > RAID_TIMEOUT;
+    if !still_at_war || timed_out || raid.raiders.is_empty() {
+        debug!(?faction_id, target = ?raid.target, "Raid called off");
+        return;
+    }
+
+    let present = raid
+        .raiders
+        .iter()
+        .filter(|npc_id| data.npcs[**npc_id].current_site == Some(raid.target))
+        .count();
+    let defenders = site
+        .population
+        .iter()
+        .filter(|npc_id| {
+            data.npcs.get(**npc_id).is_some_and(|npc| {
+                !npc.is_dead()
+                    && npc.faction == defending_faction
+                    && npc.current_site == Some(raid.target)
+                    && is_fighter(npc)
+            })
+        })
+        .count();
+
+    if present >= MIN_RAIDERS && present > defenders * 2 {
+        capture_site(data, raid.target, faction_id);
+    } else {
+        data.factions[faction_id].raid = Some(raid);
+    }
+}
+
+fn capture_site(data: &mut Data, site_id: SiteId, by: FactionId) {
+    let site = &mut data.sites[site_id];
+    let from = site.faction.replace(by);
+    debug!(?site_id, ?by, ?from, "Site captured");
+
+    // The residents that remain submit to their new rulers
+    for npc_id in site.population.iter() {
+        if let Some(npc) = data.npcs.get_mut(*npc_id)
+            && npc.faction == from
+        {
+            npc.faction = Some(by);
+        }
+    }
+
+    let report = data.reports.create(Report {
+        kind: ReportKind::SiteCaptured {
+            site: site_id,
+            by,
+            from,
+        },
+        at_tod: data.time_of_day,
+    });
+    let factions = [Some(by), from].into_iter().flatten().collect::<Vec<_>>();
+    spread_report(data, report, &factions);
+}
@@ -6,8 +6,9 @@ use crate::{
         npc::Profession,
     },
     event::OnSetup,
-    generate::wanted_population,
+    generate::{boat_spawning_location, wanted_population},
 };
+use common::comp::{self, Body};
 use rand::prelude::*;
 use rand_chacha::ChaChaRng;
 use tracing::warn;
@@ -116,6 +117,21 @@ impl Rule for Migrate {
                 }
             }
 
+            // Boat captains sail the water routes, rather than airship routes
+            let (boat_captains, airship_captains): (Vec<_>, Vec<_>) =
+                airship_captains.into_iter().partition(|captain_id| {
+                    data.npcs
+                        .mounts
+                        .get_mount_link(*captain_id)
+                        .and_then(|mount_link| data.npcs.get(mount_link.mount))
+                        .is_some_and(|vehicle| {
+                            matches!(
+                                vehicle.body,
+                                Body::Ship(comp::ship::Body::SailBoat | comp::ship::Body::Galleon)
+                            )
+                        })
+                });
+
             /*
                First, get all the location where airships can spawn. All available spawning points for airships must be used.
                It does not matter that site ids may be moved around. A captain may be assigned to any site, and
@@ -180,6 +196,41 @@ impl Rule for Migrate {
                 );
             }
 
+            // Put a boat on each water route, reusing existing boats of the right kind
+            // where possible. Boats don't remember their route, so they all start again
+            // from the first dock of the route they are put on.
+            let mut boats = boat_captains
+                .into_iter()
+                .filter_map(|captain_id| {
+                    Some((
+                        captain_id,
+                        data.npcs.mounts.get_mount_link(captain_id)?.mount,
+                    ))
+                })
+                .collect::<Vec<_>>();
+            for route_index in 0..ctx.world.civs().water_routes.routes.len() {
+                let (wpos, body) = boat_spawning_location(ctx.world, route_index);
+                if let Some(i) = boats.iter().position(|(_, boat_id)| {
+                    data.npcs
+                        .get(*boat_id)
+                        .is_some_and(|boat| boat.body == Body::Ship(body))
+                }) {
+                    let (captain_id, boat_id) = boats.swap_remove(i);
+                    for npc_id in [captain_id, boat_id] {
+                        if let Some(npc) = data.npcs.get_mut(npc_id) {
+                            npc.wpos = wpos;
+                        }
+                    }
+                } else {
+                    data.spawn_boat(ctx.world, route_index, &mut rng);
+                }
+            }
+            // Boats left over have no route to sail
+            for (captain_id, boat_id) in boats {
+                data.npcs.remove(captain_id);
+                data.npcs.remove(boat_id);
+            }
+
             // Group the airship captains by route
             data.airship_sim
                 .configure_route_pilots(&ctx.world.civs().airships, &data.npcs);
@@ -1,11 +1,14 @@
 pub mod architect;
 pub mod cleanup;
+pub mod companion;
+pub mod diplomacy;
 pub mod migrate;
 pub mod npc_ai;
 pub mod replenish_resources;
 pub mod report;
 pub mod simulate_npcs;
 pub mod sync_npcs;
+pub mod wildlife;
 
 use super::RtState;
 use std::fmt;
@@ -6,14 +6,27 @@ pub fn general<S: State>(tgt: Actor, session: DialogueSession) -> impl Action<S>
 
         // Job-dependent responses
         match &ctx.npc.job {
-            // TODO: Implement hiring as a quest?
             Some(Job::Hired(by, _)) if *by == tgt => {
                 responses.push((
-                    Response::from(Content::localized("dialogue-cancel_hire")),
-                    session
+                    Response::from(Content::localized("dialogue-companion-orders")),
+                    dialogue::orders(session).boxed(),
+                ));
+                let dismiss = match quest::contract(ctx, tgt) {
+                    // The companion settles the contract once they notice it was abandoned
+                    Some(quest_id) => just(move |ctx, _| {
+                        if let Some(quest) = ctx.state.data().quests.get(quest_id) {
+                            quest.abandon(session.target);
+                        }
+                    })
+                    .boxed(),
+                    None => session
                         .say_statement(Content::localized("npc-dialogue-hire_cancelled"))
                         .then(just(move |ctx, _| ctx.controller.end_hiring()))
                         .boxed(),
+                };
+                responses.push((
+                    Response::from(Content::localized("dialogue-cancel_hire")),
+                    dismiss,
                 ));
             },
             Some(_) => {},
@@ -134,15 +147,107 @@ pub fn general<S: State>(tgt: Actor, session: DialogueSession) -> impl Action<S>
                         ));
                     }
                 },
+                QuestKind::Fetch {
+                    fetcher,
+                    item,
+                    amount,
+                } if quest.arbiter == Actor::Npc(ctx.npc_id) && *fetcher == tgt => {
+                    responses.push((
+                        Response::from(
+                            Content::localized("dialogue-question-quest-fetch-claim")
+                                .with_arg("item", item.localize()),
+                        ),
+                        quest::claim_items(session, quest_id, *item, *amount).boxed(),
+                    ));
+                },
+                QuestKind::Deliver {
+                    deliverer,
+                    recipient,
+                    item,
+                    amount,
+                    ..
+                } if *recipient == Actor::Npc(ctx.npc_id) && *deliverer == tgt => {
+                    responses.push((
+                        Response::from(
+                            Content::localized("dialogue-question-quest-deliver-claim")
+                                .with_arg("item", item.localize()),
+                        ),
+                        quest::claim_items(session, quest_id, *item, *amount).boxed(),
+                    ));
+                },
+                QuestKind::Explore { explorer, wpos }
+                    if quest.arbiter == Actor::Npc(ctx.npc_id) && *explorer == tgt =>
+                {
+                    if quest.is_reached() {
+                        responses.push((
+                            Response::from(Content::localized(
+                                "dialogue-question-quest-explore-claim",
+                            )),
+                            session
+                                .say_statement(Content::localized(
+                                    "npc-response-quest-explore-thanks",
+                                ))
+                                .then(now(move |ctx, _| {
+                                    if let Ok(deposit) =
+                                        quest::resolve_take_deposit(ctx, quest_id, true)
+                                    {
+                                        session
+                                            .say_statement_with_gift(
+                                                Content::localized("npc-response-quest-reward"),
+                                                deposit,
+                                            )
+                                            .boxed()
+                                    } else {
+                                        finish().boxed()
+                                    }
+                                }))
+                                .boxed(),
+                        ));
+                    } else {
+                        let place = ctx
+                            .world
+                            .civs()
+                            .pois
+                            .values()
+                            .find(|poi| poi.loc == *wpos)
+                            .map_or_else(|| "<unknown>".to_string(), |poi| poi.name.clone());
+                        responses.push((
+                            Response::from(Content::localized(
+                                "dialogue-question-quest-explore-where",
+                            )),
+                            session
+                                .give_marker(
+                                    Marker::at(wpos.as_())
+                                        .with_id(quest_id)
+                                        .with_label(
+                                            Content::localized("hud-map-explore-label")
+                                                .with_arg("place", place),
+                                        )
+                                        .with_quest_flag(true),
+                                )
+                                .then(session.say_statement(Content::localized(
+                                    "npc-response-quest-explore-where",
+                                )))
+                                .boxed(),
+                        ));
+                    }
+                },
                 _ => {},
             }
         }
 
+        // Topics written as dialogue trees
+        responses.extend(dialogue_tree::topics(ctx, session));
+
         // General informational questions
         responses.push((
             Response::from(Content::localized("dialogue-question-site")),
             dialogue::about_site(session).boxed(),
         ));
+        responses.push((
+            Response::from(Content::localized("dialogue-question-news")),
+            dialogue::news(session).boxed(),
+        ));
         responses.push((
             Response::from(Content::localized("dialogue-question-self")),
             dialogue::about_self(session).boxed(),
@@ -205,6 +310,36 @@ fn about_site<S: State>(session: DialogueSession) -> impl Action<S> {
     })
 }
 
+fn news<S: State>(session: DialogueSession) -> impl Action<S> {
+    now(move |ctx, _| {
+        let mut reports = {
+            let data = ctx.state.data();
+            ctx.known_reports
+                .iter()
+                .filter_map(|report_id| Some((*report_id, data.reports.get(*report_id)?.at_tod)))
+                .collect::<Vec<_>>()
+        };
+        // Most recent news first
+        reports.sort_by(|(_, a), (_, b)| b.0.total_cmp(&a.0));
+
+        let news = reports
+            .into_iter()
+            .filter_map(|(report_id, _)| tell_report_content(ctx, report_id))
+            .take(3)
+            .collect::<Vec<_>>();
+
+        if news.is_empty() {
+            session
+                .say_statement(Content::localized("npc-news-none"))
+                .boxed()
+        } else {
+            news.into_iter().fold(finish().boxed(), |action, content| {
+                action.then(session.say_statement(content)).boxed()
+            })
+        }
+    })
+}
+
 fn about_self<S: State>(session: DialogueSession) -> impl Action<S> {
     now(move |ctx, _| {
         let name = Content::localized("npc-info-self_name")
@@ -299,15 +434,47 @@ fn hire<S: State>(tgt: Actor, session: DialogueSession) -> impl Action<S> {
                             price_mul.saturating_mul(base_price),
                         )),
                     },
-                    session
-                        .say_statement(Content::localized("npc-response-accept_hire"))
-                        .then(just(move |ctx, _| {
-                            ctx.controller.set_newly_hired(
-                                tgt,
-                                ctx.time.add_days(days, &ctx.system_data.server_constants),
-                            );
-                        }))
-                        .boxed(),
+                    now(move |ctx, _| {
+                        let price = price_mul.saturating_mul(base_price) as f32;
+                        // The pay is held as the deposit of the contract until it ends
+                        match quest::create_deposit(
+                            ctx,
+                            ItemResource::Coin,
+                            price,
+                            just(|_, _| true),
+                        ) {
+                            Some(hold_pay) => hold_pay
+                                .and_then(move |held| {
+                                    now(move |ctx, _| {
+                                        if !held {
+                                            return session
+                                                .say_statement(Content::localized(
+                                                    "npc-response-decline_hire",
+                                                ))
+                                                .boxed();
+                                        }
+                                        let expires = ctx
+                                            .time
+                                            .add_days(days, &ctx.system_data.server_constants);
+                                        ctx.controller.set_newly_hired(tgt, expires);
+                                        quest::create_quest(
+                                            Quest::hire(Actor::Npc(ctx.npc_id), tgt)
+                                                .with_deposit(ItemResource::Coin, price)
+                                                .with_timeout(expires),
+                                        )
+                                        .then(session.say_statement(Content::localized(
+                                            "npc-response-accept_hire",
+                                        )))
+                                        .boxed()
+                                    })
+                                })
+                                .boxed(),
+                            None => session
+                                .say_statement(Content::localized("npc-response-decline_hire"))
+                                .boxed(),
+                        }
+                    })
+                    .boxed(),
                 ));
             }
             session
@@ -321,6 +488,34 @@ fn hire<S: State>(tgt: Actor, session: DialogueSession) -> impl Action<S> {
     })
 }
 
+/// Let the hirer of a companion tell them what to do.
+fn orders<S: State>(session: DialogueSession) -> impl Action<S> {
+    now(move |ctx, _| {
+        let wpos = ctx.npc.wpos;
+        let responses = [
+            ("dialogue-companion-follow", CompanionOrder::Follow),
+            ("dialogue-companion-guard", CompanionOrder::Guard(wpos)),
+            ("dialogue-companion-wait", CompanionOrder::Wait(wpos)),
+        ]
+        .into_iter()
+        .filter(|(_, order)| *order != ctx.npc.order)
+        .map(|(msg, order)| {
+            (
+                Response::from(Content::localized(msg)),
+                just(move |ctx, _| ctx.controller.set_order(order))
+                    .then(session.say_statement(Content::localized("npc-response-companion-order")))
+                    .boxed(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+        session.ask_question(
+            Content::localized("npc-question-companion-orders"),
+            responses,
+        )
+    })
+}
+
 fn directions<S: State>(session: DialogueSession) -> impl Action<S> {
     now(move |ctx, _| {
         let mut responses = Vec::new();
@@ -0,0 +1,325 @@
+//! Dialogue trees, loaded from `common.rtsim.dialogue`.
+//!
+//! Each tree is a topic that the player can bring up in the general dialogue of
+//! an NPC, if the conditions of the tree hold for that NPC. Trees are made of
+//! nodes, at each of which the NPC says something and the player may choose a
+//! response, which leads on to another node. Responses are only offered if
+//! their conditions hold, and both nodes and responses can have effects such
+//! as offering a quest or a trade. All text is given as i18n keys.
+
+use super::{quest, routine::ProfessionKind};
+use crate::{
+    ai::{Action, NpcCtx, State, finish, just, now},
+    data::{Sentiment, npc::DialogueSession},
+};
+use common::{
+    assets::{self, AssetExt, Ron},
+    comp::Content,
+    rtsim::{Actor, Response},
+    time::DayPeriod,
+};
+use hashbrown::HashMap;
+use serde::Deserialize;
+use tracing::warn;
+
+const DIALOGUE_TREES: &str = "common.rtsim.dialogue";
+
+/// A threshold of sentiment, see the constants of [`Sentiment`].
+#[derive(Copy, Clone, Debug, Deserialize)]
+enum SentimentLevel {
+    Hero,
+    Friend,
+    Ally,
+    Positive,
+    Negative,
+    Rival,
+    Enemy,
+    Villain,
+}
+
+impl SentimentLevel {
+    fn value(self) -> f32 {
+        match self {
+            Self::Hero => Sentiment::HERO,
+            Self::Friend => Sentiment::FRIEND,
+            Self::Ally => Sentiment::ALLY,
+            Self::Positive => Sentiment::POSITIVE,
+            Self::Negative => Sentiment::NEGATIVE,
+            Self::Rival => Sentiment::RIVAL,
+            Self::Enemy => Sentiment::ENEMY,
+            Self::Villain => Sentiment::VILLAIN,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+enum QuestState {
+    /// The player has a quest involving the NPC that is still in progress.
+    InProgress,
+    /// The player has successfully completed a quest involving the NPC.
+    Completed,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+enum Condition {
+    /// The NPC has the given profession.
+    Profession(ProfessionKind),
+    /// The NPC feels at least this strongly toward the player. Negative levels
+    /// require the sentiment to be at least as negative.
+    Sentiment(SentimentLevel),
+    Quest(QuestState),
+    TimeOfDay(DayPeriod),
+    Not(Box<Condition>),
+    Any(Vec<Condition>),
+}
+
+impl Condition {
+    fn holds(&self, ctx: &NpcCtx, tgt: Actor) -> bool {
+        match self {
+            Self::Profession(kind) => ctx
+                .npc
+                .profession()
+                .is_some_and(|profession| ProfessionKind::from(profession) == *kind),
+            Self::Sentiment(level) => ctx.sentiments.toward(tgt).is(level.value()),
+            Self::Quest(state) => {
+                let data = ctx.state.data();
+                data.quests
+                    .related_to(ctx.npc_id)
+                    .filter_map(|quest_id| data.quests.get(quest_id))
+                    .filter(|quest| quest.get_related_actors().contains(&tgt))
+                    .any(|quest| match state {
+                        QuestState::InProgress => {
+                            quest.resolution().is_none() && !quest.is_abandoned()
+                        },
+                        QuestState::Completed => quest.resolution() == Some(true),
+                    })
+            },
+            Self::TimeOfDay(period) => DayPeriod::from(ctx.time_of_day.0) == *period,
+            Self::Not(condition) => !condition.holds(ctx, tgt),
+            Self::Any(conditions) => conditions.iter().any(|c| c.holds(ctx, tgt)),
+        }
+    }
+}
+
+fn all_hold(conditions: &[Condition], ctx: &NpcCtx, tgt: Actor) -> bool {
+    conditions.iter().all(|c| c.holds(ctx, tgt))
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+enum Effect {
+    /// Offer the player a quest, as if they had asked for work.
+    StartQuest,
+    /// Invite the player to trade.
+    Trade,
+    /// Change the sentiment of the NPC toward the player, without going past
+    /// the given level.
+    ChangeSentiment { by: f32, up_to: SentimentLevel },
+}
+
+impl Effect {
+    fn action<S: State>(self, session: DialogueSession) -> Box<dyn Action<S>> {
+        match self {
+            Self::StartQuest => quest::quest_request(session).boxed(),
+            Self::Trade => just(move |ctx, _| ctx.controller.trade(session.target)).boxed(),
+            Self::ChangeSentiment { by, up_to } => just(move |ctx, _| {
+                ctx.sentiments
+                    .toward_mut(session.target)
+                    .change_by(by, up_to.value())
+            })
+            .boxed(),
+        }
+    }
+}
+
+fn apply_effects<S: State>(effects: &[Effect], session: DialogueSession) -> Box<dyn Action<S>> {
+    effects.iter().fold(finish().boxed(), |action, effect| {
+        action.then(effect.action(session)).boxed()
+    })
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct ResponseOption {
+    msg: String,
+    #[serde(default)]
+    conditions: Vec<Condition>,
+    #[serde(default)]
+    effects: Vec<Effect>,
+    /// The node to go to when this response is chosen. Without one, the
+    /// conversation ends.
+    #[serde(default)]
+    next: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Node {
+    /// What the NPC says. If there are responses to choose from, this is the
+    /// question that they answer.
+    msg: String,
+    /// Effects that take place when the node is reached, before the NPC
+    /// speaks.
+    #[serde(default)]
+    effects: Vec<Effect>,
+    #[serde(default)]
+    responses: Vec<ResponseOption>,
+    /// The node to go to after the NPC has spoken, if none of the responses
+    /// are available.
+    #[serde(default)]
+    next: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DialogueTree {
+    /// What the player says to bring up the topic.
+    topic: String,
+    /// Conditions under which the NPC offers to talk about the topic.
+    #[serde(default)]
+    conditions: Vec<Condition>,
+    start: String,
+    nodes: HashMap<String, Node>,
+}
+
+impl DialogueTree {
+    /// Check that every node that the tree refers to exists.
+    pub fn validate(&self) -> Result<(), String> {
+        let check = |id: &str, from: &str| {
+            if self.nodes.contains_key(id) {
+                Ok(())
+            } else {
+                Err(format!("{from} refers to missing node {id:?}"))
+            }
+        };
+
+        check(&self.start, "start")?;
+        for (node_id, node) in &self.nodes {
+            if let Some(next) = &node.next {
+                check(next, &format!("node {node_id:?}"))?;
+            }
+            for (i, response) in node.responses.iter().enumerate() {
+                if let Some(next) = &response.next {
+                    check(next, &format!("response {i} of node {node_id:?}"))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Walk the tree from the given node. The tree is looked up again at every
+/// node, so that changes to it are picked up while the game is running.
+fn goto_node<S: State>(
+    tree_id: String,
+    node_id: String,
+    session: DialogueSession,
+) -> impl Action<S> {
+    now(move |ctx, _| {
+        let tree = Ron::<DialogueTree>::load_expect(&tree_id).read();
+        let Some(node) = tree.0.nodes.get(&node_id) else {
+            warn!("Dialogue tree {tree_id} has no node {node_id:?}");
+            return finish().boxed();
+        };
+
+        let goto = |next: &Option<String>| -> Box<dyn Action<S>> {
+            match next {
+                Some(next) => goto_node(tree_id.clone(), next.clone(), session).boxed(),
+                None => finish().boxed(),
+            }
+        };
+
+        let responses = node
+            .responses
+            .iter()
+            .filter(|response| all_hold(&response.conditions, ctx, session.target))
+            .map(|response| {
+                (
+                    Response::from(Content::localized(&response.msg)),
+                    apply_effects(&response.effects, session)
+                        .then(goto(&response.next))
+                        .boxed(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let msg = Content::localized(&node.msg);
+        let say = if responses.is_empty() {
+            session.say_statement(msg).then(goto(&node.next)).boxed()
+        } else {
+            session.ask_question(msg, responses).boxed()
+        };
+
+        apply_effects(&node.effects, session).then(say).boxed()
+    })
+}
+
+/// The topics of the dialogue trees that the NPC is willing to talk about,
+/// as responses for the general dialogue.
+pub fn topics<S: State>(
+    ctx: &NpcCtx,
+    session: DialogueSession,
+) -> Vec<(Response, Box<dyn Action<S>>)> {
+    let trees = match assets::load_rec_dir::<Ron<DialogueTree>>(DIALOGUE_TREES) {
+        Ok(trees) => trees,
+        Err(err) => {
+            warn!("Failed to load dialogue trees: {err}");
+            return Vec::new();
+        },
+    };
+
+    trees
+        .read()
+        .ids()
+        .filter_map(|tree_id| {
+            let tree = Ron::<DialogueTree>::load(tree_id).ok()?.read();
+            all_hold(&tree.0.conditions, ctx, session.target).then(|| {
+                (
+                    Response::from(Content::localized(&tree.0.topic)),
+                    goto_node(tree_id.to_string(), tree.0.start.clone(), session).boxed(),
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_all_dialogue_trees() {
+        let trees = assets::load_rec_dir::<Ron<DialogueTree>>(DIALOGUE_TREES)
+            .expect("failed to load dialogue tree directory");
+        for tree_id in trees.read().ids() {
+            let tree = Ron::<DialogueTree>::load_cloned(tree_id)
+                .unwrap_or_else(|e| panic!("failed to load {tree_id}: {e:?}"))
+                .into_inner();
+            tree.validate()
+                .unwrap_or_else(|e| panic!("{tree_id} is broken: {e}"));
+        }
+    }
+
+    #[test]
+    fn missing_nodes_are_caught() {
+        let greet = Node {
+            msg: "npc-question-general".to_string(),
+            effects: Vec::new(),
+            responses: vec![ResponseOption {
+                msg: "dialogue-finish".to_string(),
+                conditions: Vec::new(),
+                effects: Vec::new(),
+                next: Some("farewell".to_string()),
+            }],
+            next: None,
+        };
+        let mut tree = DialogueTree {
+            topic: "dialogue-question-self".to_string(),
+            conditions: Vec::new(),
+            start: "greet".to_string(),
+            nodes: [("greet".to_string(), greet)].into_iter().collect(),
+        };
+        assert!(tree.validate().is_err());
+
+        tree.nodes.get_mut("greet").unwrap().responses[0].next = None;
+        assert!(tree.validate().is_ok());
+        tree.start = "farewell".to_string();
+        assert!(tree.validate().is_err());
+    }
+}
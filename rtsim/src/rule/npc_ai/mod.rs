@@ -27,8 +27,10 @@ mod airship_ai;
 #[cfg(feature = "airship_log")]
 mod airship_logger;
 pub mod dialogue;
+pub mod dialogue_tree;
 pub mod movement;
 pub mod quest;
+pub mod routine;
 pub mod util;
 
 use std::{collections::VecDeque, hash::BuildHasherDefault, sync::Arc};
@@ -41,7 +43,7 @@ use crate::{
         seq, until,
     },
     data::{
-        ReportKind, Sentiment, Sites,
+        Relation, ReportKind, Sentiment, Sites,
         npc::{Brain, DialogueSession, Job, PathData, SimulationMode},
         quest::{Quest, QuestKind},
     },
@@ -59,8 +61,8 @@ use common::{
     match_some,
     path::Path,
     rtsim::{
-        Actor, DialogueKind, ItemResource, NpcInput, PersonalityTrait, Profession, QuestId,
-        Response, Role, SiteId, TerrainResource,
+        Actor, CompanionOrder, DialogueKind, ItemResource, NpcInput, PersonalityTrait, Profession,
+        QuestId, ReportId, Response, Role, SiteId, TerrainResource,
     },
     spiral::Spiral2d,
     store::Id,
@@ -89,6 +91,7 @@ use self::{
     movement::{
         follow_actor, goto, goto_2d, goto_2d_flying, goto_actor, travel_to_point, travel_to_site,
     },
+    routine::{Activity, Workplace},
     util::do_dialogue,
 };
 
@@ -262,6 +265,69 @@ fn tell_site_content(ctx: &NpcCtx, site: SiteId) -> Option<Content> {
     }
 }
 
+/// What the NPC would say to pass on a report, if it's worth talking about.
+fn tell_report_content(ctx: &NpcCtx, report_id: ReportId) -> Option<Content> {
+    let data = ctx.state.data();
+    let report = data.reports.get(report_id)?;
+    let npc = |actor: Actor| data.npcs.get(actor.npc()?);
+
+    match report.kind {
+        ReportKind::Death {
+            actor,
+            killer: Some(killer),
+            site,
+            ..
+        } => {
+            let site = util::site_name(ctx, site)?;
+            match (npc(actor), killer) {
+                // Player deeds
+                (Some(victim), Actor::Character(_)) => match victim.role {
+                    Role::Monster => Some(
+                        Content::localized("npc-news-monster_slain")
+                            .with_arg("body", victim.body.localize_npc())
+                            .with_arg("site", site),
+                    ),
+                    Role::Civilised(_) => {
+                        Some(Content::localized("npc-news-murder").with_arg("site", site))
+                    },
+                    Role::Wild | Role::Vehicle => None,
+                },
+                // Monster attacks
+                (_, killer) => {
+                    let killer = npc(killer)
+                        .filter(|killer| matches!(killer.role, Role::Monster | Role::Wild))?;
+                    Some(
+                        Content::localized("npc-news-monster_attack")
+                            .with_arg("body", killer.body.localize_npc())
+                            .with_arg("site", site),
+                    )
+                },
+            }
+        },
+        ReportKind::Death { killer: None, .. } => None,
+        ReportKind::Theft { site, .. } => {
+            Some(Content::localized("npc-news-theft").with_arg("site", util::site_name(ctx, site)?))
+        },
+        ReportKind::Relation { factions, relation } => {
+            let key = match relation {
+                Relation::Hostile => "npc-news-war",
+                Relation::Neutral => "npc-news-peace",
+                Relation::Allied => "npc-news-alliance",
+            };
+            Some(
+                Content::localized(key)
+                    .with_arg("site_a", util::faction_site_name(ctx, factions[0])?)
+                    .with_arg("site_b", util::faction_site_name(ctx, factions[1])?),
+            )
+        },
+        ReportKind::SiteCaptured { site, by, .. } => Some(
+            Content::localized("npc-news-site_captured")
+                .with_arg("site", util::site_name(ctx, site)?)
+                .with_arg("by", util::faction_site_name(ctx, by)?),
+        ),
+    }
+}
+
 fn smalltalk_to<S: State>(tgt: Actor) -> impl Action<S> {
     now(move |ctx, _| {
         if matches!(tgt, Actor::Npc(_)) && ctx.rng.random_bool(0.2) {
@@ -270,14 +336,42 @@ fn smalltalk_to<S: State>(tgt: Actor) -> impl Action<S> {
             // some sort of 'bored of conversation' system
             idle().boxed()
         } else {
+            // Pass on news we've heard
+            let comment = if let Actor::Npc(tgt_npc) = tgt
+                && ctx.rng.random_bool(0.3)
+                && let Some(report_id) = ctx.known_reports.iter().copied().choose(&mut ctx.rng)
+                && let Some(content) = tell_report_content(ctx, report_id)
+            {
+                ctx.controller.npc_action(
+                    tgt_npc,
+                    just(move |ctx, _| ctx.inbox.push_back(NpcInput::Report(report_id))),
+                );
+                content
             // Mention nearby sites
-            let comment = if ctx.rng.random_bool(0.3)
+            } else if ctx.rng.random_bool(0.3)
                 && let Some(current_site) = ctx.npc.current_site
                 && let Some(current_site) = ctx.state.data().sites.get(current_site)
                 && let Some(mention_site) = current_site.nearby_sites_by_size.choose(&mut ctx.rng)
                 && let Some(content) = tell_site_content(ctx, *mention_site)
             {
                 content
+            // Mention wars our faction is fighting
+            } else if ctx.rng.random_bool(0.3)
+                && let Some(faction) = ctx.npc.faction
+                && let Some(enemy) = ctx.state.data().factions.get(faction).and_then(|faction| {
+                    faction
+                        .relations
+                        .iter()
+                        .filter(|(_, relation)| **relation == Relation::Hostile)
+                        .map(|(enemy, _)| *enemy)
+                        .choose(&mut ctx.rng)
+                })
+                && let Some(enemy_site) = util::faction_site_name(ctx, enemy)
+            {
+                Content::localized_with_args("npc-speech-at_war", [(
+                    "site",
+                    Content::Plain(enemy_site),
+                )])
             // Mention current site
             } else if ctx.rng.random_bool(0.3)
                 && let Some(current_site) = ctx.npc.current_site
@@ -613,6 +707,47 @@ fn pirate(is_leader: bool) -> impl Action<DefaultState> {
     })
 }
 
+/// Take part in a raid of our faction on a site of an enemy faction, until the
+/// raid is over (see [`crate::rule::diplomacy`]).
+fn raid(target: SiteId) -> impl Action<DefaultState> {
+    just(move |ctx, _| {
+        ctx.controller.say(
+            None,
+            Content::localized_with_args("npc-speech-raid_start", [(
+                "site",
+                util::site_name(ctx, target).unwrap_or_default(),
+            )]),
+        )
+    })
+    .then(travel_to_site(target, 0.8).debug(|| "travel to raid site"))
+    // Roam the site: defenders get attacked as they're encountered (see `check_for_enemies`)
+    .then(
+        now(move |ctx, _| {
+            if let Some(plaza_wpos) = choose_plaza(ctx, target) {
+                travel_to_point(plaza_wpos, 0.7)
+                    .stop_if(timeout(30.0))
+                    .map(|_, _| ())
+                    .l()
+            } else {
+                idle().repeat().stop_if(timeout(5.0)).map(|_, _| ()).r()
+            }
+        })
+        .repeat()
+        .debug(|| "raiding"),
+    )
+    .stop_if(move |ctx: &mut NpcCtx| util::raid_target(ctx) != Some(target))
+    .then(now(|ctx, _| {
+        if let Some(home) = ctx.npc.home {
+            travel_to_site(home, 0.6)
+                .debug(|| "traveling home from raid")
+                .l()
+        } else {
+            finish().r()
+        }
+    }))
+    .map(|_, _| ())
+}
+
 fn adventure() -> impl Action<DefaultState> {
     choose(|ctx, _| {
         // Choose a random site that's fairly close by
@@ -649,39 +784,109 @@ fn adventure() -> impl Action<DefaultState> {
     .debug(move || "adventure")
 }
 
+/// Distance from their post within which a guarding companion attacks
+/// monsters.
+const GUARD_RADIUS: f32 = 32.0;
+
+/// Stand guard at a post, attacking any monster that comes near it.
+fn guard<S: State>(post: Vec3<f32>) -> impl Action<S> {
+    now(move |ctx, _| {
+        if ctx.npc.wpos.xy().distance_squared(post.xy()) > 8.0f32.powi(2) {
+            return goto(post, 0.8, 4.0).boxed();
+        }
+
+        let data = ctx.state.data();
+        let monster = data
+            .npcs
+            .nearby(Some(ctx.npc_id), post, GUARD_RADIUS)
+            .find(|actor| match actor {
+                Actor::Npc(npc_id) => data
+                    .npcs
+                    .get(*npc_id)
+                    .is_some_and(|npc| matches!(npc.role, Role::Monster)),
+                Actor::Character(_) => false,
+            });
+        match monster {
+            Some(monster) => just(move |ctx, _| ctx.controller.attack(monster)).boxed(),
+            None => idle().boxed(),
+        }
+    })
+    .repeat()
+    .map(|_, _| ())
+    .debug(move || format!("guarding {post:?}"))
+}
+
+/// End the contract with the hirer and head back home, telling the hirer why
+/// if they are around. The companion keeps their pay unless it gets refunded.
+fn end_contract<S: State>(
+    ctx: &mut NpcCtx,
+    hirer: Actor,
+    success: bool,
+    refund: bool,
+    msg: &'static str,
+) -> impl Action<S> + use<S> {
+    ctx.controller.end_hiring();
+    let deposit = quest::contract(ctx, hirer)
+        .and_then(|quest_id| quest::resolve_take_deposit(ctx, quest_id, success).ok())
+        .flatten()
+        .filter(|_| refund);
+
+    let tell_hirer = if util::actor_exists(ctx, hirer) {
+        goto_actor(hirer, 2.0)
+            .then(do_dialogue(hirer, move |session| {
+                session.say_statement_with_gift(Content::localized(msg), deposit.clone())
+            }))
+            .boxed()
+    } else {
+        finish().boxed()
+    };
+    let home = ctx.npc.home;
+    tell_hirer.then(now(move |_, _| match home {
+        Some(home) => travel_to_site(home, 0.6).boxed(),
+        None => finish().boxed(),
+    }))
+}
+
 fn hired(tgt: Actor) -> impl Action<DefaultState> {
-    follow_actor(tgt, 5.0)
-        // Stop following if we're no longer hired
+    now(move |ctx, _| {
+        let order = ctx.npc.order;
+        let action = match order {
+            CompanionOrder::Follow => follow_actor(tgt, 5.0).boxed(),
+            CompanionOrder::Guard(post) => guard(post).boxed(),
+            CompanionOrder::Wait(wpos) => goto(wpos, 0.8, 4.0)
+                .then(idle().repeat())
+                .map(|_, _| ())
+                .boxed(),
+        };
+        // Stop when given new orders, so that they get followed
+        action.stop_if(move |ctx: &mut NpcCtx| ctx.npc.order != order)
+    })
+        // Stop if we're no longer hired
         .stop_if(move |ctx: &mut NpcCtx| ctx.npc.hired().is_none_or(|(a, _)| a != tgt))
         .debug(move|| format!("hired by {tgt:?}"))
         .interrupt_with(move |ctx, _| {
             // End hiring for various reasons
             if let Some((tgt, expires)) = ctx.npc.hired() {
-                // Hiring period has expired
+                // The contract has run its course
                 if ctx.time > expires {
-                    ctx.controller.end_hiring();
-                    // If the actor exists, tell them that the hiring is over
-                    if util::actor_exists(ctx, tgt) {
-                        return Some(goto_actor(tgt, 2.0)
-                            .then(do_dialogue(tgt, |session| {
-                                session.say_statement(Content::localized("npc-dialogue-hire_expired"))
-                            }))
-                            .boxed());
-                    }
+                    return Some(end_contract(ctx, tgt, true, false, "npc-dialogue-hire_expired").boxed());
                 }
 
+                // Unhappy companions leave, handing back their pay
                 if ctx.sentiments.toward(tgt).is(Sentiment::RIVAL) {
-                    ctx.controller.end_hiring();
-                    // If the actor exists, tell them that the hiring is over
-                    if util::actor_exists(ctx, tgt) {
-                        return Some(goto_actor(tgt, 2.0)
-                            .then(do_dialogue(tgt, |session| {
-                                session.say_statement(Content::localized(
-                                    "npc-dialogue-hire_cancelled_unhappy",
-                                ))
-                            }))
-                            .boxed());
-                    }
+                    return Some(end_contract(ctx, tgt, false, true, "npc-dialogue-hire_cancelled_unhappy").boxed());
+                }
+
+                // The hirer has dismissed us before the end of the contract
+                if quest::contract(ctx, tgt).is_some_and(|quest_id| {
+                    ctx.state.data().quests.get(quest_id).is_some_and(|quest| quest.is_abandoned())
+                }) {
+                    return Some(end_contract(ctx, tgt, false, false, "npc-dialogue-hire_cancelled").boxed());
+                }
+
+                // Only companions that follow the hirer around wait for them at taverns
+                if ctx.npc.order != CompanionOrder::Follow {
+                    return None;
                 }
 
                 let data = ctx.state.data();
@@ -770,6 +975,42 @@ fn find_farm(ctx: &mut NpcCtx, site: SiteId) -> Option<Vec2<f32>> {
     })
 }
 
+/// Find a house to stay in. NPCs return to the same house each time they're
+/// at home.
+fn find_house(ctx: &mut NpcCtx, site_id: SiteId) -> Option<Vec2<f32>> {
+    let is_home = ctx.npc.home == Some(site_id);
+    let seed = ctx.npc.seed;
+    ctx.state.data().sites.get(site_id).and_then(|site| {
+        let site = ctx.index.sites.get(site.world_site?);
+        let houses = site
+            .filter_plots(|p| matches!(p.meta(), Some(PlotKindMeta::House { .. })))
+            .collect::<Vec<_>>();
+        let house = if is_home && !houses.is_empty() {
+            houses[seed as usize % houses.len()]
+        } else {
+            *houses.choose(&mut ctx.rng)?
+        };
+
+        Some(site.tile_center_wpos(house.root_tile()).as_())
+    })
+}
+
+fn find_workplace(ctx: &mut NpcCtx, site: SiteId, workplace: Workplace) -> Option<Vec2<f32>> {
+    ctx.state.data().sites.get(site).and_then(|site| {
+        let site = ctx.index.sites.get(site.world_site?);
+        let plot = site
+            .filter_plots(|p| match workplace {
+                Workplace::Workshop => matches!(p.meta(), Some(PlotKindMeta::Workshop { .. })),
+                Workplace::FarmField => matches!(p.kind(), PlotKind::FarmField(_)),
+                Workplace::Tavern => matches!(p.kind(), PlotKind::Tavern(_)),
+                Workplace::Plaza => matches!(p.kind(), PlotKind::Plaza(_)),
+            })
+            .choose(&mut ctx.rng)?;
+
+        Some(site.tile_center_wpos(plot.root_tile()).as_())
+    })
+}
+
 fn choose_plaza(ctx: &mut NpcCtx, site: SiteId) -> Option<Vec2<f32>> {
     ctx.state.data().sites.get(site).and_then(|site| {
         let site = ctx.index.sites.get(site.world_site?);
@@ -784,6 +1025,26 @@ fn choose_plaza(ctx: &mut NpcCtx, site: SiteId) -> Option<Vec2<f32>> {
 
 const WALKING_SPEED: f32 = 0.35;
 
+/// Nobody is around to see what a simulated villager does once they got
+/// somewhere, so rather than performing `action` they travel to `wpos` and wait
+/// there until they get loaded.
+fn unless_simulated<A: Action<DefaultState>>(
+    wpos: Vec2<f32>,
+    action: impl FnOnce() -> A + Clone + Send + Sync + 'static,
+) -> impl Action<DefaultState> {
+    now(move |ctx, _| {
+        if matches!(ctx.npc.mode, SimulationMode::Simulated) {
+            travel_to_point(wpos, 0.5)
+                .then(idle().repeat())
+                .stop_if(|ctx: &mut NpcCtx| matches!(ctx.npc.mode, SimulationMode::Loaded))
+                .map(|_, _| ())
+                .l()
+        } else {
+            action().r()
+        }
+    })
+}
+
 fn villager(visiting_site: SiteId) -> impl Action<DefaultState> {
     choose(move |ctx, state: &mut DefaultState| {
         // Consider moving home if the home site gets too full
@@ -827,35 +1088,20 @@ fn villager(visiting_site: SiteId) -> impl Action<DefaultState> {
                 .then(travel_to_site(new_home, 0.5))
                 .then(just(move |ctx, _| ctx.controller.set_new_home(new_home))));
         }
-        let day_period = DayPeriod::from(ctx.time_of_day.0);
-        let is_weekend = (ctx.time_of_day.day() as u64).is_multiple_of(6);
-        let is_evening = day_period == DayPeriod::Evening;
-
-        let is_free_time = is_weekend || is_evening;
+        let profession = ctx.npc.profession();
+        let seed = ctx.npc.seed;
+        let activity = routine::activity(profession, seed, ctx.time_of_day);
+        // Whether the routine has moved on to another activity since
+        let activity_over =
+            move |ctx: &mut NpcCtx| routine::activity(profession, seed, ctx.time_of_day) != activity;
 
         let is_raining = ctx.system_data.weather_grid.is_raining(ctx.npc.wpos.xy());
 
-        // Go to a house if it's dark
-        if day_period.is_dark()
-            && !matches!(ctx.npc.profession(), Some(Profession::Guard))
-        {
+        // Go home to sleep
+        if activity == Activity::Sleep {
             return important(
                 now(move |ctx, _| {
-                    if let Some(house_wpos) = ctx
-                        .state
-                        .data()
-                        .sites
-                        .get(visiting_site)
-                        .and_then(|site| Some(ctx.index.sites.get(site.world_site?)))
-                        .and_then(|site| {
-                            // Find a house in the site we're visiting
-                            let house = site
-                                .plots()
-                                .filter(|p| matches!(p.kind().meta(), Some(PlotKindMeta::House { .. })))
-                                .choose(&mut ctx.rng)?;
-                            Some(site.tile_center_wpos(house.root_tile()).as_())
-                        })
-                    {
+                    if let Some(house_wpos) = find_house(ctx, visiting_site) {
                         just(|ctx, _| {
                             ctx.controller
                                 .say(None, Content::localized("npc-speech-night_time"))
@@ -863,7 +1109,7 @@ fn villager(visiting_site: SiteId) -> impl Action<DefaultState> {
                         .then(travel_to_point(house_wpos, 0.65))
                         .debug(|| "walk to house")
                         .then(socialize().repeat().map_state(|state: &mut DefaultState| &mut state.socialize_timer).debug(|| "wait in house"))
-                        .stop_if(|ctx: &mut NpcCtx| DayPeriod::from(ctx.time_of_day.0).is_light())
+                        .stop_if(activity_over)
                         .then(just(|ctx, _| {
                             ctx.controller
                                 .say(None, Content::localized("npc-speech-day_time"))
@@ -883,21 +1129,7 @@ fn villager(visiting_site: SiteId) -> impl Action<DefaultState> {
         {
             return important(
                 now(move |ctx, _| {
-                    if let Some(house_wpos) = ctx
-                        .state
-                        .data()
-                        .sites
-                        .get(visiting_site)
-                        .and_then(|site| Some(ctx.index.sites.get(site.world_site?)))
-                        .and_then(|site| {
-                            // Find a house in the site we're visiting
-                            let house = site
-                                .plots()
-                                .filter(|p| matches!(p.kind().meta(), Some(PlotKindMeta::House { .. })))
-                                .choose(&mut ctx.rng)?;
-                            Some(site.tile_center_wpos(house.root_tile()).as_())
-                        })
-                    {
+                    if let Some(house_wpos) = find_house(ctx, visiting_site) {
                         just(|ctx, _| {
                                 ctx.controller.say(None, Content::localized("npc-speech-seeking_shelter_rain"))
                         })
@@ -920,11 +1152,29 @@ fn villager(visiting_site: SiteId) -> impl Action<DefaultState> {
                 .debug(|| "find somewhere to wait (rain)"),
             );
         }
-        // Go do something fun on evenings and holidays, or on random days.
-        else if
-            // Ain't no rest for the wicked
-            !matches!(ctx.npc.profession(), Some(Profession::Guard | Profession::Chef))
-            && (matches!(day_period, DayPeriod::Evening) || is_free_time || ctx.rng.random_bool(0.05)) {
+        // Have a meal at the tavern
+        else if activity == Activity::Eat
+            && let Some(ws_id) = ctx.state.data().sites[visiting_site].world_site
+            && let Some((tavern, door_wpos)) = ctx
+                .index
+                .sites
+                .get(ws_id)
+                .plots
+                .iter()
+                .filter_map(|(pid, p)| match_some!(p.kind(), PlotKind::Tavern(t) => (pid, t.door_wpos.xy().as_::<f32>())))
+                .choose(&mut ctx.rng)
+        {
+            return casual(
+                unless_simulated(door_wpos, move || go_to_tavern(visiting_site, tavern))
+                .stop_if(activity_over)
+                .debug(|| "have a meal")
+                .map(|_, _| ()),
+            );
+        }
+        // Go do something fun in our free time (or when there's nowhere to eat), and sometimes
+        // skive off work.
+        else if matches!(activity, Activity::Leisure | Activity::Eat)
+            || (activity == Activity::Work && ctx.rng.random_bool(0.05)) {
             let mut fun_activities = Vec::new();
 
             if let Some(ws_id) = ctx.state.data().sites[visiting_site].world_site {
@@ -948,7 +1198,7 @@ fn villager(visiting_site: SiteId) -> impl Action<DefaultState> {
                     };
                     let look_dir = Dir::from_unnormalized(arena_center - seat);
                     // Walk to an arena seat, cheer, sit and dance
-                    let action = casual(just(move |ctx, _| ctx.controller.say(None, Content::localized("npc-speech-arena")))
+                    let action = casual(unless_simulated(seat.xy(), move || just(move |ctx, _| ctx.controller.say(None, Content::localized("npc-speech-arena")))
                             .then(goto_2d(seat.xy(), 0.6, 1.0).debug(|| "go to arena"))
                             // Turn toward the centre of the arena and watch the action!
                             .then(choose(move |ctx, _| if ctx.rng.random_bool(0.3) {
@@ -960,13 +1210,12 @@ fn villager(visiting_site: SiteId) -> impl Action<DefaultState> {
                             })
                                 .repeat()
                                 .stop_if(timeout(wait_time)))
-                            .map(|_, _| ())
-                            .boxed());
+                            .map(|_, _| ())));
                     fun_activities.push(action);
                 }
-                if let Some(tavern) = ws.plots.iter().filter_map(|(pid, p)| match_some!(p.kind(), PlotKind::Tavern(_) => pid)).choose(&mut ctx.rng) {
+                if let Some((tavern, door_wpos)) = ws.plots.iter().filter_map(|(pid, p)| match_some!(p.kind(), PlotKind::Tavern(t) => (pid, t.door_wpos.xy().as_::<f32>()))).choose(&mut ctx.rng) {
                     let wait_time = ctx.rng.random_range(100.0..300.0);
-                    let action = go_to_tavern(visiting_site, tavern).stop_if(timeout(wait_time)).map(|_, _| ());
+                    let action = unless_simulated(door_wpos, move || go_to_tavern(visiting_site, tavern)).stop_if(timeout(wait_time)).map(|_, _| ());
 
                     fun_activities.push(casual(action));
                 }
@@ -1080,14 +1329,31 @@ fn villager(visiting_site: SiteId) -> impl Action<DefaultState> {
 
             let face_dir = Dir::from_unnormalized((room_center - bar_pos).as_::<f32>().with_z(0.0)).unwrap_or_else(|| Dir::random_2d(&mut ctx.rng));
 
+            let door_wpos = tavern.door_wpos.xy().as_();
             return casual(
-                travel_to_point(tavern.door_wpos.xy().as_(), 0.5)
+                unless_simulated(door_wpos, move || travel_to_point(door_wpos, 0.5)
                     .then(goto(bar_pos.as_() + Vec2::new(0.5, 0.5), WALKING_SPEED, 2.0))
                     // TODO: Just dance there for now, in the future do other stuff.
                     .then(just(move |ctx, _| ctx.controller.do_dance(Some(face_dir))).repeat().stop_if(timeout(60.0)))
-                    .debug(|| "cook food").map(|_, _| ())
+                    .map(|_, _| ()))
+                    .debug(|| "cook food")
             )
         }
+        // Villagers without duties of their own work at their workplace
+        else if let Some(workplace) = routine::workplace(profession)
+            && let Some(workplace_wpos) = find_workplace(ctx, visiting_site, workplace)
+        {
+            return casual(
+                travel_to_point(workplace_wpos, 0.5)
+                    .debug(|| "walk to workplace")
+                    .then(socialize()
+                        .repeat()
+                        .map_state(|state: &mut DefaultState| &mut state.socialize_timer)
+                        .debug(|| "work at workplace"))
+                    .stop_if(activity_over)
+                    .map(|_, _| ()),
+            );
+        }
 
         // If nothing else needs doing, walk between plazas and socialize
         casual(now(move |ctx, _| {
@@ -1260,9 +1526,39 @@ fn pilot<S: State>(ship: common::comp::ship::Body) -> impl Action<S> {
     .map(|_, _| ())
 }
 
-fn captain<S: State>() -> impl Action<S> {
-    // For now just randomly travel the sea
-    now(|ctx, _| {
+/// Sail a boat along the water routes, waiting at each dock for a while before
+/// setting off again. Sail boats work as ferries and galleons as trading ships.
+fn captain<S: State>(boat: comp::ship::Body) -> impl Action<S> {
+    const DOCK_DIST: f32 = 64.0;
+
+    now(move |ctx, _| {
+        let water_routes = &ctx.world.civs().water_routes;
+        if let Some(dock) = water_routes.dock_near(ctx.npc.wpos.xy(), DOCK_DIST)
+            && let Some((route, reversed)) = water_routes
+                .routes_from(dock)
+                .filter(|(route, _)| water_routes.routes[*route].kind.boat() == boat)
+                .choose(&mut ctx.rng)
+        {
+            let sim = ctx.world.sim();
+            let waypoints = water_routes
+                .waypoints(route, reversed)
+                .into_iter()
+                .map(|wpos| {
+                    wpos.with_z(
+                        sim.get_interpolated(wpos.as_(), |chunk| chunk.water_alt)
+                            .unwrap_or(0.0),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let wait = ctx.rng.random_range(60.0..180.0);
+            return seq(waypoints.into_iter().map(|wpos| goto(wpos, 0.7, 16.0)))
+                .then(idle().repeat().stop_if(timeout(wait)))
+                .map(|_, _| ())
+                .debug(move || format!("sailing water route {route}"))
+                .boxed();
+        }
+
+        // Away from the water routes, just randomly travel the sea
         let chunk = ctx.npc.wpos.xy().as_().wpos_to_cpos();
         if let Some(chunk) = NEIGHBORS
             .into_iter()
@@ -1300,6 +1596,8 @@ fn check_inbox<S: State>(ctx: &mut NpcCtx) -> Option<impl Action<S> + use<S>> {
                 let Some(report) = data.reports.get(*report_id) else {
                     return false;
                 };
+                // Whether or not we react to it, we now know of the report and can pass it on
+                ctx.known_reports.insert(*report_id);
 
                 const REPORT_RESPONSE_TIME: f64 = 60.0 * 5.0;
 
@@ -1426,7 +1724,6 @@ fn check_inbox<S: State>(ctx: &mut NpcCtx) -> Option<impl Action<S> + use<S>> {
                         } else {
                             "npc-speech-witness_death"
                         };
-                        ctx.known_reports.insert(*report_id);
 
                         if ctx.time_of_day.0 - report.at_tod.0 < REPORT_RESPONSE_TIME {
                             action = Some(
@@ -1452,7 +1749,6 @@ fn check_inbox<S: State>(ctx: &mut NpcCtx) -> Option<impl Action<S> + use<S>> {
                             ctx.sentiments
                                 .toward_mut(thief)
                                 .change_by(-0.2, Sentiment::ENEMY);
-                            ctx.known_reports.insert(*report_id);
 
                             let phrase = if matches!(ctx.npc.profession(), Some(Profession::Farmer))
                                 && matches!(sprite.category(), sprite::Category::Plant)
@@ -1476,11 +1772,46 @@ fn check_inbox<S: State>(ctx: &mut NpcCtx) -> Option<impl Action<S> + use<S>> {
                     },
                     // We don't care about deaths of non-civilians
                     ReportKind::Death { .. } => false,
+                    ReportKind::Relation { factions, relation } => {
+                        if let Some(faction) = ctx.npc.faction
+                            && factions.contains(&faction)
+                            && let Some(other) = factions.into_iter().find(|f| *f != faction)
+                        {
+                            let phrase = match relation {
+                                Relation::Hostile => "npc-speech-war_declared",
+                                Relation::Neutral => "npc-speech-peace_made",
+                                Relation::Allied => "npc-speech-alliance_formed",
+                            };
+                            if ctx.time_of_day.0 - report.at_tod.0 < REPORT_RESPONSE_TIME
+                                && let Some(seat) = data.factions.get(other).and_then(|f| f.seat)
+                            {
+                                action = Some(announce_site_news(phrase, seat).r().r());
+                            }
+                        }
+                        false
+                    },
+                    ReportKind::SiteCaptured { site, by, from } => {
+                        let phrase = if ctx.npc.home == Some(site) {
+                            Some("npc-speech-site_occupied")
+                        } else if ctx.npc.faction == Some(by) {
+                            Some("npc-speech-site_conquered")
+                        } else if ctx.npc.faction.is_some() && ctx.npc.faction == from {
+                            Some("npc-speech-site_lost")
+                        } else {
+                            None
+                        };
+                        if let Some(phrase) = phrase {
+                            if ctx.time_of_day.0 - report.at_tod.0 < REPORT_RESPONSE_TIME {
+                                action = Some(announce_site_news(phrase, site).r().r());
+                            }
+                        }
+                        false
+                    },
                 }
             },
             NpcInput::Report(_) => false, // Reports we already know of are ignored
             NpcInput::Interaction(by) => {
-                action = Some(talk_to(*by).r());
+                action = Some(talk_to(*by).l().r());
                 false
             },
             // Dialogue inputs get retained because they're handled by specific conversation actions
@@ -1492,6 +1823,16 @@ fn check_inbox<S: State>(ctx: &mut NpcCtx) -> Option<impl Action<S> + use<S>> {
     action
 }
 
+/// Tell anybody nearby about news concerning a site.
+fn announce_site_news<S: State>(phrase: &'static str, site: SiteId) -> impl Action<S> {
+    just(move |ctx, _| {
+        if let Some(site) = util::site_name(ctx, site) {
+            ctx.controller
+                .say(None, Content::localized_with_args(phrase, [("site", site)]));
+        }
+    })
+}
+
 fn check_for_enemies<S: State>(ctx: &mut NpcCtx) -> Option<impl Action<S> + use<S>> {
     // TODO: Instead of checking all nearby actors every tick, it would be more
     // effective to have the actor grid generate a per-tick diff so that we only
@@ -1503,7 +1844,9 @@ fn check_for_enemies<S: State>(ctx: &mut NpcCtx) -> Option<impl Action<S> + use<
         .data()
         .npcs
         .nearby(Some(ctx.npc_id), ctx.npc.wpos, 24.0)
-        .find(|actor| ctx.sentiments.toward(*actor).is(Sentiment::ENEMY))
+        .find(|actor| {
+            ctx.sentiments.toward(*actor).is(Sentiment::ENEMY) || util::is_raid_enemy(ctx, *actor)
+        })
         .map(|enemy| just(move |ctx, _| ctx.controller.attack(enemy)))
 }
 
@@ -1511,7 +1854,10 @@ fn react_to_events<S: State>(ctx: &mut NpcCtx, _: &mut S) -> Option<impl Action<
     check_inbox::<S>(ctx)
         .map(Action::boxed)
         .or_else(|| check_for_enemies(ctx).map(Action::boxed))
-        .or_else(|| quest::check_for_timeouts(ctx).map(Action::boxed))
+        .or_else(|| {
+            quest::update_progress(ctx);
+            quest::check_for_timeouts(ctx).map(Action::boxed)
+        })
 }
 
 fn humanoid() -> impl Action<DefaultState> {
@@ -1527,8 +1873,8 @@ fn humanoid() -> impl Action<DefaultState> {
                             important(airship_ai::pilot_airship())
                         },
                         comp::Body::Ship(
-                            comp::ship::Body::SailBoat | comp::ship::Body::Galleon,
-                        ) => important(captain()),
+                            body @ (comp::ship::Body::SailBoat | comp::ship::Body::Galleon),
+                        ) => important(captain(body)),
                         _ => casual(idle()),
                     }
                 } else {
@@ -1544,7 +1890,8 @@ fn humanoid() -> impl Action<DefaultState> {
             important(
                 match job {
                     Job::Hired(tgt, _) => {
-                        if util::actor_exists(ctx, *tgt) {
+                        // Companions under contract keep working while their hirer is away
+                        if util::actor_exists(ctx, *tgt) || quest::contract(ctx, *tgt).is_some() {
                             hired(*tgt).boxed()
                         } else {
                             just(|ctx, _| ctx.controller.end_hiring()).boxed()
@@ -1567,6 +1914,8 @@ fn humanoid() -> impl Action<DefaultState> {
                 }
                 .interrupt_with(react_to_events),
             )
+        } else if let Some(target) = util::raid_target(ctx) {
+            important(raid(target).interrupt_with(react_to_events))
         } else {
             let action = match ctx.npc.profession() {
                 Some(Profession::Adventurer(_) | Profession::Merchant) => adventure().l().l(),
@@ -1,5 +1,8 @@
 use super::*;
-use common::comp::{Item, item::ItemBase};
+use common::comp::{
+    Inventory, Item,
+    item::{ItemBase, MaterialStatManifest, tool::AbilityMap},
+};
 
 /// Perform a deposit check, ensuring that the NPC has the given item and amount
 /// in their inventory. If they do, the provided action is performed to
@@ -114,6 +117,145 @@ pub fn create_quest<S: State>(quest: Quest) -> impl Action<S, QuestId> {
     })
 }
 
+/// The contract under which the NPC works for the hirer, if they have one that
+/// is still in force.
+pub fn contract(ctx: &NpcCtx, hirer: Actor) -> Option<QuestId> {
+    let data = ctx.state.data();
+    data.quests.related_to(ctx.npc_id).find(|quest_id| {
+        data.quests.get(*quest_id).is_some_and(|quest| {
+            quest.resolution().is_none()
+                && matches!(
+                    quest.kind,
+                    QuestKind::Hire { companion, hirer: h }
+                        if companion == Actor::Npc(ctx.npc_id) && h == hirer
+                )
+        })
+    })
+}
+
+/// Take an amount of an item from an actor's inventory and put it into the
+/// NPC's own inventory. Returns `false`, taking nothing, if the actor doesn't
+/// have enough of the item.
+pub fn take_items(ctx: &mut NpcCtx, from: Actor, item: ItemResource, amount: f32) -> bool {
+    let item_def = item.to_equivalent_item_def();
+    let amount = amount.ceil() as u32;
+    let Some(from) = ctx.system_data.id_maps.actor_entity(from) else {
+        return false;
+    };
+    let mut inventories = ctx.system_data.inventories.lock().unwrap();
+    let Some(items) = inventories
+        .get_mut(from)
+        .filter(|inv| inv.item_count(&item_def) >= amount as u64)
+        .and_then(|mut inv| {
+            inv.remove_item_amount(
+                &item_def,
+                amount,
+                &ctx.system_data.ability_map,
+                &ctx.system_data.msm,
+            )
+        })
+    else {
+        return false;
+    };
+    if let Some(npc_entity) = ctx.system_data.id_maps.rtsim_entity(ctx.npc_id)
+        && let Some(mut inv) = inventories.get_mut(npc_entity)
+    {
+        for item in items {
+            let _ = inv.push(item);
+        }
+    }
+    true
+}
+
+/// Put the goods of a delivery from the stock of the NPC's site into their
+/// inventory, so that they can be handed over with
+/// [`DialogueSession::say_statement_with_gift`].
+///
+/// Rtsim doesn't track site stock as items, so the goods are created here. The
+/// deposit for the delivery has already been taken by [`create_deposit`], so
+/// it is given back if the goods don't fit.
+fn take_delivery_from_stock(
+    ctx: &mut NpcCtx,
+    goods: (ItemResource, f32),
+    deposit: (ItemResource, f32),
+) -> Option<(Arc<ItemDef>, u32)> {
+    let npc_entity = ctx.system_data.id_maps.rtsim_entity(ctx.npc_id)?;
+    let mut inventories = ctx.system_data.inventories.lock().unwrap();
+    let mut inv = inventories.get_mut(npc_entity)?;
+    put_or_refund(
+        &mut inv,
+        goods,
+        deposit,
+        &ctx.system_data.ability_map,
+        &ctx.system_data.msm,
+    )
+}
+
+/// Put an amount of an item into an inventory. Nothing is put into it if not
+/// all of the items fit.
+fn put_into(
+    inv: &mut Inventory,
+    item: ItemResource,
+    amount: f32,
+    ability_map: &AbilityMap,
+    msm: &MaterialStatManifest,
+) -> Option<(Arc<ItemDef>, u32)> {
+    let item_def = item.to_equivalent_item_def();
+    let amount = amount.ceil() as u32;
+    if !inv.has_space_for(&item_def, amount) {
+        return None;
+    }
+    let mut item = Item::new_from_item_base(
+        ItemBase::Simple(item_def.clone()),
+        Vec::new(),
+        ability_map,
+        msm,
+    );
+    item.set_amount(amount).ok()?;
+    inv.push(item).ok()?;
+    Some((item_def, amount))
+}
+
+/// Put `goods` into an inventory, or if they don't fit, the `refund` instead.
+fn put_or_refund(
+    inv: &mut Inventory,
+    goods: (ItemResource, f32),
+    refund: (ItemResource, f32),
+    ability_map: &AbilityMap,
+    msm: &MaterialStatManifest,
+) -> Option<(Arc<ItemDef>, u32)> {
+    put_into(inv, goods.0, goods.1, ability_map, msm).or_else(|| {
+        put_into(inv, refund.0, refund.1, ability_map, msm);
+        None
+    })
+}
+
+/// The goods that the economy of a site has a need for, along with how much
+/// they're needed relative to the average good (`1.0` being average).
+///
+/// The most needed goods come first.
+fn site_needs(ctx: &NpcCtx, site_id: Option<SiteId>) -> Vec<(ItemResource, f32)> {
+    let Some(prices) = util::site_prices(ctx, site_id) else {
+        return Vec::new();
+    };
+    let mean_price = prices.values.values().sum::<f32>() / prices.values.len().max(1) as f32;
+    let mut needs = Vec::<(ItemResource, f32)>::new();
+    for (good, price) in &prices.values {
+        // Coins are what quests are paid with, not what they're about
+        if let Some(item) =
+            ItemResource::from_good(*good).filter(|item| *item != ItemResource::Coin)
+        {
+            let need = price / mean_price.max(0.0001);
+            match needs.iter_mut().find(|(i, _)| *i == item) {
+                Some((_, n)) => *n = n.max(need),
+                None => needs.push((item, need)),
+            }
+        }
+    }
+    needs.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    needs
+}
+
 pub fn quest_request<S: State>(session: DialogueSession) -> impl Action<S> {
     now(move |ctx, _| {
         let mut quests = Vec::new();
@@ -271,6 +413,235 @@ pub fn quest_request<S: State>(session: DialogueSession) -> impl Action<S> {
             );
         }
 
+        // Fetch quest: ask for the good the local site needs most
+        const FETCH_REWARD_ITEM: ItemResource = ItemResource::Coin;
+        const FETCH_COINS_PER_ITEM: f32 = 8.0;
+        if matches!(
+            ctx.npc.profession(),
+            Some(Profession::Farmer | Profession::Blacksmith | Profession::Merchant)
+        ) && let Some((fetch_item, need)) = site_needs(ctx, ctx.npc.current_site)
+            .into_iter()
+            .next()
+            // Only bother asking for goods that are actually scarce
+            .filter(|(_, need)| *need > 1.0)
+            && let fetch_amount = ctx.rng.random_range(5..=15) as f32
+            && let fetch_reward_amount =
+                (fetch_amount * FETCH_COINS_PER_ITEM * need.min(3.0)).round()
+            && let Some(accept_quest) = create_deposit(
+                ctx,
+                FETCH_REWARD_ITEM,
+                fetch_reward_amount,
+                session.ask_yes_no_question(
+                    Content::localized("npc-response-quest-fetch-ask")
+                        .with_arg("amount", fetch_amount as u64)
+                        .with_arg("item", fetch_item.localize())
+                        .with_arg("coins", fetch_reward_amount as u64),
+                ),
+            )
+        {
+            quests.push(
+                accept_quest
+                    .and_then(move |yes| {
+                        now(move |ctx, _| {
+                            if yes {
+                                let quest = Quest::fetch(
+                                    ctx.npc_id.into(),
+                                    session.target,
+                                    fetch_item,
+                                    fetch_amount,
+                                )
+                                .with_deposit(FETCH_REWARD_ITEM, fetch_reward_amount)
+                                .with_timeout(ctx.time.add_minutes(60.0));
+                                create_quest(quest.clone())
+                                    .then(session.say_statement(Content::localized(
+                                        "npc-response-quest-fetch-start",
+                                    )))
+                                    .boxed()
+                            } else {
+                                session
+                                    .say_statement(Content::localized(
+                                        "npc-response-quest-rejected",
+                                    ))
+                                    .boxed()
+                            }
+                        })
+                    })
+                    .boxed(),
+            );
+        }
+
+        // Delivery quest: send a good we have plenty of to a site that needs it
+        const DELIVER_REWARD_ITEM: ItemResource = ItemResource::Coin;
+        const DELIVER_AMOUNT: f32 = 10.0;
+        if matches!(ctx.npc.profession(), Some(Profession::Merchant))
+            && let Some((deliver_item, _)) = site_needs(ctx, ctx.npc.current_site).pop()
+            && let Some((dst_site_id, dst_site, dist)) = ctx
+                .state
+                .data()
+                .sites
+                .iter()
+                .map(|(site_id, site)| (site_id, site, site.wpos.as_().distance(ctx.npc.wpos.xy())))
+                .filter(|(site_id, _, dist)| Some(*site_id) != ctx.npc.current_site && (1000.0..5_000.0).contains(dist))
+                // Choose the site that needs the good the most
+                .filter_map(|(site_id, site, dist)| {
+                    let need = site_needs(ctx, Some(site_id))
+                        .into_iter()
+                        .find(|(item, _)| *item == deliver_item)?
+                        .1;
+                    Some((site_id, site, dist, need))
+                })
+                .filter(|(_, _, _, need)| *need > 1.0)
+                .max_by(|(_, _, _, a), (_, _, _, b)| a.total_cmp(b))
+                .map(|(site_id, site, dist, _)| (site_id, site, dist))
+            && let Some((recipient_id, recipient)) = dst_site
+                .population
+                .iter()
+                .filter_map(|npc_id| Some((*npc_id, ctx.state.data().npcs.get(*npc_id)?)))
+                .filter(|(_, npc)| matches!(npc.role, Role::Civilised(_)))
+                .choose(&mut ctx.rng)
+            && let recipient_name = recipient
+                .get_name()
+                .unwrap_or_else(|| "<unknown>".to_string())
+            && let Some(dst_site_name) = util::site_name(ctx, dst_site_id)
+            && let deliver_reward_amount = (dist / 25.0).round()
+            && let time_limit = 1.0 + dist as f64 / 80.0
+            && let Some(accept_quest) = create_deposit(
+                ctx,
+                DELIVER_REWARD_ITEM,
+                deliver_reward_amount,
+                session.ask_yes_no_question(
+                    Content::localized("npc-response-quest-deliver-ask")
+                        .with_arg("amount", DELIVER_AMOUNT as u64)
+                        .with_arg("item", deliver_item.localize())
+                        .with_arg("name", recipient_name.clone())
+                        .with_arg("dst", dst_site_name.clone())
+                        .with_arg("coins", deliver_reward_amount as u64)
+                        .with_arg("mins", time_limit as u64),
+                ),
+            )
+        {
+            let recipient_wpos = recipient.wpos.xy();
+            quests.push(
+                accept_quest
+                    .and_then(move |yes| {
+                        now(move |ctx, _| {
+                            // The deposit was only taken if the quest was accepted
+                            if yes
+                                && let Some(goods) = take_delivery_from_stock(
+                                    ctx,
+                                    (deliver_item, DELIVER_AMOUNT),
+                                    (DELIVER_REWARD_ITEM, deliver_reward_amount),
+                                )
+                            {
+                                let quest = Quest::deliver(
+                                    ctx.npc_id.into(),
+                                    session.target,
+                                    recipient_id.into(),
+                                    dst_site_id,
+                                    deliver_item,
+                                    DELIVER_AMOUNT,
+                                )
+                                .with_deposit(DELIVER_REWARD_ITEM, deliver_reward_amount)
+                                .with_timeout(ctx.time.add_minutes(time_limit));
+                                create_quest(quest.clone())
+                                    .then(
+                                        session.give_marker(
+                                            Marker::at(recipient_wpos)
+                                                .with_id(Actor::from(recipient_id))
+                                                .with_label(
+                                                    Content::localized("hud-map-deliver-label")
+                                                        .with_arg("name", recipient_name.clone())
+                                                        .with_arg("place", dst_site_name.clone()),
+                                                )
+                                                .with_quest_flag(true),
+                                        ),
+                                    )
+                                    .then(
+                                        session.say_statement_with_gift(
+                                            Content::localized("npc-response-quest-deliver-start")
+                                                .with_arg("name", recipient_name.clone()),
+                                            Some(goods),
+                                        ),
+                                    )
+                                    .boxed()
+                            } else {
+                                session
+                                    .say_statement(Content::localized(
+                                        "npc-response-quest-rejected",
+                                    ))
+                                    .boxed()
+                            }
+                        })
+                    })
+                    .boxed(),
+            );
+        }
+
+        // Explore quest
+        const EXPLORE_REWARD_ITEM: ItemResource = ItemResource::Coin;
+        if let Some(poi) = ctx
+            .world
+            .civs()
+            .pois
+            .values()
+            .filter(|poi| (500.0..3_000.0).contains(&poi.loc.as_().distance(ctx.npc.wpos.xy())))
+            // Temporarily, try to choose the same place for 15 minutes to avoid players asking many times
+            .choose(&mut ChaChaRng::from_seed([(ctx.time.0 / (60.0 * 15.0)) as u8; 32]))
+            && let poi_wpos = poi.loc
+            && let poi_name = poi.name.clone()
+            && let dist = poi_wpos.as_().distance(ctx.npc.wpos.xy())
+            && let explore_reward_amount = (dist / 20.0).round()
+            && let time_limit = 1.0 + dist as f64 / 40.0
+            && let Some(accept_quest) = create_deposit(
+                ctx,
+                EXPLORE_REWARD_ITEM,
+                explore_reward_amount,
+                session.ask_yes_no_question(
+                    Content::localized("npc-response-quest-explore-ask")
+                        .with_arg("place", poi_name.clone())
+                        .with_arg("coins", explore_reward_amount as u64)
+                        .with_arg("mins", time_limit as u64),
+                ),
+            )
+        {
+            quests.push(
+                accept_quest
+                    .and_then(move |yes| {
+                        now(move |ctx, _| {
+                            if yes {
+                                let quest =
+                                    Quest::explore(ctx.npc_id.into(), session.target, poi_wpos)
+                                        .with_deposit(EXPLORE_REWARD_ITEM, explore_reward_amount)
+                                        .with_timeout(ctx.time.add_minutes(time_limit));
+                                create_quest(quest.clone())
+                                    .and_then(move |quest_id| {
+                                        session.give_marker(
+                                            Marker::at(poi_wpos.as_())
+                                                .with_id(quest_id)
+                                                .with_label(
+                                                    Content::localized("hud-map-explore-label")
+                                                        .with_arg("place", poi_name.clone()),
+                                                )
+                                                .with_quest_flag(true),
+                                        )
+                                    })
+                                    .then(session.say_statement(Content::localized(
+                                        "npc-response-quest-explore-start",
+                                    )))
+                                    .boxed()
+                            } else {
+                                session
+                                    .say_statement(Content::localized(
+                                        "npc-response-quest-rejected",
+                                    ))
+                                    .boxed()
+                            }
+                        })
+                    })
+                    .boxed(),
+            );
+        }
+
         if quests.is_empty() {
             session
                 .say_statement(Content::localized("npc-response-quest-nothing"))
@@ -281,26 +652,93 @@ pub fn quest_request<S: State>(session: DialogueSession) -> impl Action<S> {
     })
 }
 
+/// Let the quester hand over the items a fetch or delivery quest asked for and,
+/// if they have them, pay them the deposit.
+pub fn claim_items<S: State>(
+    session: DialogueSession,
+    quest_id: QuestId,
+    item: ItemResource,
+    amount: f32,
+) -> impl Action<S> {
+    now(move |ctx, _| {
+        if take_items(ctx, session.target, item, amount) {
+            match resolve_take_deposit(ctx, quest_id, true) {
+                Ok(deposit) => session
+                    .say_statement(Content::localized("npc-response-quest-items-thanks"))
+                    .then(session.say_statement_with_gift(
+                        Content::localized("npc-response-quest-reward"),
+                        deposit,
+                    ))
+                    .boxed(),
+                Err(()) => finish().boxed(),
+            }
+        } else {
+            // The quester can try again once they have the items
+            session
+                .say_statement(
+                    Content::localized("npc-response-quest-items-missing")
+                        .with_arg("amount", amount.ceil() as u64)
+                        .with_arg("item", item.localize()),
+                )
+                .boxed()
+        }
+    })
+}
+
+/// Distance from the point of interest within which an explore quest counts
+/// as reached.
+const EXPLORE_RADIUS: f32 = 64.0;
+
+/// Keep track of the objectives reached for quests arbitrated by this NPC that
+/// can't be observed when they get reported back.
+pub fn update_progress(ctx: &NpcCtx) {
+    let data = ctx.state.data();
+    for quest_id in data.quests.related_to(ctx.npc_id) {
+        if let Some(quest) = data.quests.get(quest_id)
+            && quest.arbiter == Actor::Npc(ctx.npc_id)
+            && let QuestKind::Explore { explorer, wpos } = quest.kind
+            && !quest.is_reached()
+            && util::locate_actor(ctx, explorer).is_some_and(|explorer_wpos| {
+                explorer_wpos.xy().distance_squared(wpos.as_()) < EXPLORE_RADIUS.powi(2)
+            })
+        {
+            quest.mark_reached();
+        }
+    }
+}
+
 pub fn check_for_timeouts<S: State>(ctx: &mut NpcCtx) -> Option<impl Action<S> + use<S>> {
     let data = ctx.state.data();
     for quest_id in data.quests.related_to(ctx.npc_id) {
         let Some(quest) = data.quests.get(quest_id) else {
             continue;
         };
-        if let Some(timeout) = quest.timeout
-            // The quest has timed out...
-            && ctx.time > timeout
-            // ...so resolve it
-            && let Ok(Some(_)) = resolve_take_deposit(ctx, quest_id, false)
+        // Contracts are settled by the companion while the hirer is around, see
+        // `hired`. A contract that runs out while the hirer is away has still
+        // been served.
+        let served_contract = match quest.kind {
+            QuestKind::Hire { hirer, .. } if util::actor_exists(ctx, hirer) => continue,
+            QuestKind::Hire { .. } => !quest.is_abandoned(),
+            _ => false,
+        };
+        let timed_out = quest.timeout.is_some_and(|timeout| ctx.time > timeout);
+        if (timed_out || quest.is_abandoned())
+            // The quest has timed out or been abandoned, so resolve it
+            && let Ok(Some(_)) = resolve_take_deposit(ctx, quest_id, served_contract)
         {
             // Stop any job related to the quest
             if ctx.npc.job == Some(Job::Quest(quest_id)) {
                 ctx.controller.end_quest();
             }
+            if let QuestKind::Hire { hirer, .. } = quest.kind
+                && ctx.npc.hired().is_some_and(|(by, _)| by == hirer)
+            {
+                ctx.controller.end_hiring();
+            }
 
             // If needs be, inform the quester that they failed
             match quest.kind {
-                QuestKind::Escort { escorter, .. } => {
+                QuestKind::Escort { escorter, .. } if timed_out => {
                     return Some(
                         goto_actor(escorter, 2.0)
                             .then(do_dialogue(escorter, move |session| {
@@ -310,7 +748,12 @@ pub fn check_for_timeouts<S: State>(ctx: &mut NpcCtx) -> Option<impl Action<S> +
                             .boxed(),
                     );
                 },
-                QuestKind::Slay { .. } => {},
+                QuestKind::Escort { .. }
+                | QuestKind::Slay { .. }
+                | QuestKind::Fetch { .. }
+                | QuestKind::Deliver { .. }
+                | QuestKind::Explore { .. }
+                | QuestKind::Hire { .. } => {},
             }
         }
     }
@@ -358,3 +801,42 @@ pub fn escorted<S: State>(quest_id: QuestId, escorter: Actor, dst_site: SiteId)
         })
         .map(|_, _| ())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delivery_out_of_stock_refunds_deposit() {
+        let msm = &MaterialStatManifest::load().read();
+        let ability_map = &AbilityMap::load().read();
+        let coins = ItemResource::Coin.to_equivalent_item_def();
+        let wood = ItemResource::Wood.to_equivalent_item_def();
+
+        // What's left of the NPC's coins after the deposit was taken
+        let mut inv = Inventory::with_empty();
+        assert!(put_into(&mut inv, ItemResource::Coin, 5.0, ability_map, msm).is_some());
+
+        let goods = (ItemResource::Wood, 10.0);
+        let deposit = (ItemResource::Coin, 20.0);
+        assert_eq!(
+            put_or_refund(&mut inv, goods, deposit, ability_map, msm).map(|(_, amount)| amount),
+            Some(10)
+        );
+        assert_eq!(inv.item_count(&wood), 10);
+        assert_eq!(inv.item_count(&coins), 5);
+
+        // Without space for the goods, the deposit is given back instead
+        while inv.free_slots() > 0 {
+            assert!(
+                inv.push(Item::new_from_asset_expect(
+                    "common.items.debug.admin_stick"
+                ))
+                .is_ok()
+            );
+        }
+        assert!(put_or_refund(&mut inv, goods, deposit, ability_map, msm).is_none());
+        assert_eq!(inv.item_count(&wood), 10);
+        assert_eq!(inv.item_count(&coins), 25);
+    }
+}
@@ -0,0 +1,218 @@
+//! Daily routines of villagers, loaded from `common.rtsim.routines`.
+//!
+//! A routine divides the day into activities, such that villagers go to work in
+//! the morning, have their meals at the tavern and go home to sleep at night.
+//! Professions without a routine of their own follow the default one.
+
+use common::{
+    assets::{AssetExt, Ron},
+    resources::TimeOfDay,
+    rtsim::Profession,
+};
+use hashbrown::HashMap;
+use serde::Deserialize;
+
+const ROUTINES: &str = "common.rtsim.routines";
+
+const HOUR: f64 = 60.0 * 60.0;
+const DAY: f64 = HOUR * 24.0;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+pub enum Activity {
+    /// Go home and sleep.
+    Sleep,
+    /// Have a meal at the tavern.
+    Eat,
+    /// Carry out the duties of the villager's profession.
+    Work,
+    /// Spend time at the plazas, the arena or the tavern.
+    Leisure,
+}
+
+/// The kind of plot at which a villager works, for professions that aren't
+/// bound to a particular place otherwise.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+pub enum Workplace {
+    Workshop,
+    FarmField,
+    Tavern,
+    Plaza,
+}
+
+/// [`Profession`] without the data attached to some of its variants, to key
+/// routines and dialogue by.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub(super) enum ProfessionKind {
+    Farmer,
+    Hunter,
+    Merchant,
+    Guard,
+    Adventurer,
+    Blacksmith,
+    Chef,
+    Alchemist,
+    Pirate,
+    Cultist,
+    Herbalist,
+    Captain,
+}
+
+impl From<Profession> for ProfessionKind {
+    fn from(profession: Profession) -> Self {
+        match profession {
+            Profession::Farmer => Self::Farmer,
+            Profession::Hunter => Self::Hunter,
+            Profession::Merchant => Self::Merchant,
+            Profession::Guard => Self::Guard,
+            Profession::Adventurer(_) => Self::Adventurer,
+            Profession::Blacksmith => Self::Blacksmith,
+            Profession::Chef => Self::Chef,
+            Profession::Alchemist => Self::Alchemist,
+            Profession::Pirate(_) => Self::Pirate,
+            Profession::Cultist => Self::Cultist,
+            Profession::Herbalist => Self::Herbalist,
+            Profession::Captain => Self::Captain,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Routine {
+    /// The hours of the day at which each activity starts, in order. The last
+    /// activity of the day carries on past midnight.
+    schedule: Vec<(f64, Activity)>,
+    /// Work is replaced by leisure on every nth day.
+    #[serde(default)]
+    day_off_every: Option<u32>,
+    #[serde(default)]
+    workplace: Option<Workplace>,
+    /// Each villager shifts the schedule by up to this many hours, depending
+    /// on their seed, so that they don't all do the same thing at once.
+    #[serde(default)]
+    stagger: f64,
+}
+
+impl Routine {
+    pub fn activity_at(&self, seed: u32, time_of_day: TimeOfDay) -> Activity {
+        let shift = self.stagger * HOUR * (seed as f64 / (u32::MAX as f64 + 1.0));
+        let time_of_day = TimeOfDay(time_of_day.0 - shift);
+        let hour = time_of_day.day() / HOUR;
+        let activity = self
+            .schedule
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= hour)
+            .or_else(|| self.schedule.last())
+            .map_or(Activity::Leisure, |(_, activity)| *activity);
+
+        let day = (time_of_day.0 / DAY).floor() as i64;
+        if activity == Activity::Work
+            && self
+                .day_off_every
+                .is_some_and(|every| every > 0 && day.rem_euclid(every as i64) == 0)
+        {
+            Activity::Leisure
+        } else {
+            activity
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Routines {
+    default: Routine,
+    professions: HashMap<ProfessionKind, Routine>,
+}
+
+impl Routines {
+    fn of(&self, profession: Option<Profession>) -> &Routine {
+        profession
+            .and_then(|profession| self.professions.get(&ProfessionKind::from(profession)))
+            .unwrap_or(&self.default)
+    }
+}
+
+/// What a villager of the given profession and seed should be doing at the
+/// given time.
+pub fn activity(profession: Option<Profession>, seed: u32, time_of_day: TimeOfDay) -> Activity {
+    Ron::<Routines>::load_expect(ROUTINES)
+        .read()
+        .0
+        .of(profession)
+        .activity_at(seed, time_of_day)
+}
+
+/// Where a villager of the given profession works, if at a plot.
+pub fn workplace(profession: Option<Profession>) -> Option<Workplace> {
+    Ron::<Routines>::load_expect(ROUTINES)
+        .read()
+        .0
+        .of(profession)
+        .workplace
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: f64, hour: f64) -> TimeOfDay { TimeOfDay(day * DAY + hour * HOUR) }
+
+    #[test]
+    fn schedule_wraps_around_midnight() {
+        let routine = Routine {
+            schedule: vec![
+                (6.0, Activity::Work),
+                (12.0, Activity::Eat),
+                (22.0, Activity::Sleep),
+            ],
+            day_off_every: Some(3),
+            workplace: None,
+            stagger: 0.0,
+        };
+
+        assert_eq!(routine.activity_at(0, at(1.0, 2.0)), Activity::Sleep);
+        assert_eq!(routine.activity_at(0, at(1.0, 6.0)), Activity::Work);
+        assert_eq!(routine.activity_at(0, at(1.0, 12.5)), Activity::Eat);
+        assert_eq!(routine.activity_at(0, at(1.0, 23.0)), Activity::Sleep);
+        // Days off are spent at leisure, but villagers still eat and sleep
+        assert_eq!(routine.activity_at(0, at(3.0, 8.0)), Activity::Leisure);
+        assert_eq!(routine.activity_at(0, at(3.0, 12.5)), Activity::Eat);
+    }
+
+    #[test]
+    fn staggered_schedules() {
+        let routine = Routine {
+            schedule: vec![(0.0, Activity::Work), (8.0, Activity::Sleep)],
+            day_off_every: None,
+            workplace: None,
+            stagger: 24.0,
+        };
+        // Halfway through the seeds, the schedule is shifted by half a day
+        let half = u32::MAX / 2 + 1;
+
+        assert_eq!(routine.activity_at(0, at(1.0, 4.0)), Activity::Work);
+        assert_eq!(routine.activity_at(half, at(1.0, 4.0)), Activity::Sleep);
+        assert_eq!(routine.activity_at(half, at(1.0, 16.0)), Activity::Work);
+        // At any time, some villagers are at work and others asleep
+        let working = (0..24u32)
+            .filter(|i| routine.activity_at(i * (u32::MAX / 24), at(1.0, 10.0)) == Activity::Work)
+            .count();
+        assert!((1..24).contains(&working));
+    }
+
+    #[test]
+    fn routines_load() {
+        let routines = Ron::<Routines>::load_expect(ROUTINES).read();
+        for routine in core::iter::once(&routines.0.default).chain(routines.0.professions.values())
+        {
+            assert!(!routine.schedule.is_empty());
+            assert!(
+                routine
+                    .schedule
+                    .windows(2)
+                    .all(|pair| pair[0].0 < pair[1].0 && (0.0..24.0).contains(&pair[1].0)),
+                "schedules must be in order: {routine:?}"
+            );
+        }
+    }
+}
@@ -1,10 +1,68 @@
 use super::*;
+use crate::data::{FactionId, Npc};
+use common::trade::SitePrices;
 
 pub fn site_name(ctx: &NpcCtx, site_id: impl Into<Option<SiteId>>) -> Option<String> {
     let world_site = ctx.state.data().sites.get(site_id.into()?)?.world_site?;
     Some(ctx.index.sites.get(world_site).name()?.to_string())
 }
 
+/// The name of the site a faction is governed from.
+pub fn faction_site_name(ctx: &NpcCtx, faction: FactionId) -> Option<String> {
+    let seat = ctx.state.data().factions.get(faction)?.seat;
+    site_name(ctx, seat)
+}
+
+/// The site the NPC is raiding, if it takes part in a raid of its faction.
+pub fn raid_target(ctx: &NpcCtx) -> Option<SiteId> {
+    ctx.state
+        .data()
+        .factions
+        .raid_of(ctx.npc_id, ctx.npc.faction)
+        .map(|raid| raid.target)
+}
+
+/// Whether the NPC and the other actor are on opposing sides of a raid: either
+/// the NPC is raiding the home of the other actor, or the other actor is
+/// raiding the home of the NPC while it is there to defend it.
+pub fn is_raid_enemy(ctx: &NpcCtx, other: Actor) -> bool {
+    let data = ctx.state.data();
+    let Some(other_id) = other.npc() else {
+        return false;
+    };
+    let Some(other) = data.npcs.get(other_id) else {
+        return false;
+    };
+    let is_defender = |npc: &Npc, site: SiteId| {
+        npc.home == Some(site)
+            && data
+                .sites
+                .get(site)
+                .is_some_and(|site| site.faction.is_some() && site.faction == npc.faction)
+    };
+
+    if let Some(raid) = data.factions.raid_of(ctx.npc_id, ctx.npc.faction) {
+        ctx.npc.current_site == Some(raid.target) && is_defender(other, raid.target)
+    } else if let Some(raid) = data.factions.raid_of(other_id, other.faction) {
+        ctx.npc.current_site == Some(raid.target) && is_defender(ctx.npc, raid.target)
+    } else {
+        false
+    }
+}
+
+/// The prices of goods in the economy of the given site, if it has one.
+pub fn site_prices(ctx: &NpcCtx, site_id: impl Into<Option<SiteId>>) -> Option<SitePrices> {
+    let world_site = ctx.state.data().sites.get(site_id.into()?)?.world_site?;
+    Some(
+        ctx.index
+            .sites
+            .get(world_site)
+            .economy
+            .as_ref()?
+            .get_site_prices(),
+    )
+}
+
 pub fn locate_actor(ctx: &NpcCtx, actor: Actor) -> Option<Vec3<f32>> {
     match actor {
         Actor::Npc(npc_id) => ctx.state.data().npcs.get(npc_id).map(|npc| npc.wpos),
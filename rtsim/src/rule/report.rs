@@ -3,7 +3,7 @@ use crate::{
     data::{Report, report::ReportKind},
     event::{EventCtx, OnDeath, OnTheft},
 };
-use common::rtsim::NpcInput;
+use common::rtsim::{Actor, NpcInput};
 
 pub struct ReportEvents;
 
@@ -27,10 +27,28 @@ fn on_death(ctx: EventCtx<ReportEvents, OnDeath>) {
             .collect::<Vec<_>>();
 
         if !nearby.is_empty() {
+            let faction_of = |actor: Actor| data.npcs.get(actor.npc()?)?.faction;
+            let factions = [
+                faction_of(ctx.event.actor),
+                ctx.event.killer.and_then(faction_of),
+            ];
+            // Where the death happened, as far as the witnesses can tell
+            let site = ctx
+                .event
+                .actor
+                .npc()
+                .and_then(|npc_id| data.npcs.get(npc_id)?.current_site)
+                .or_else(|| {
+                    nearby
+                        .iter()
+                        .find_map(|npc_id| data.npcs.get(*npc_id)?.current_site)
+                });
             let report = data.reports.create(Report {
                 kind: ReportKind::Death {
                     actor: ctx.event.actor,
                     killer: ctx.event.killer,
+                    factions,
+                    site,
                 },
                 at_tod: data.time_of_day,
             });
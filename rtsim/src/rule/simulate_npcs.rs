@@ -2,11 +2,12 @@ use crate::{
     RtState, Rule, RuleError,
     data::{Sentiment, npc::SimulationMode},
     event::{EventCtx, OnHealthChange, OnHelped, OnMountVolume, OnTick},
+    rule::wildlife,
 };
 use common::{
     comp::{self, Body},
     mounting::{Volume, VolumePos},
-    rtsim::{Actor, NpcAction, NpcActivity},
+    rtsim::{Actor, NpcAction, NpcActivity, WildlifeKind},
     terrain::{CoordinateConversions, TerrainChunkSize},
     vol::RectVolSize,
 };
@@ -123,6 +124,7 @@ fn on_tick(ctx: EventCtx<SimulateNpcs, OnTick>) {
                     NpcAction::Say(_, _) => {}, // Currently, just swallow interactions
                     NpcAction::Attack(_) => {}, // TODO: Implement simulated combat
                     NpcAction::Dialogue(_, _) => {},
+                    NpcAction::Trade(_) => {},
                 }
             }
 
@@ -234,9 +236,16 @@ fn on_tick(ctx: EventCtx<SimulateNpcs, OnTick>) {
                         }
                     }
                 },
+                // Hunting depletes the prey of the area, even if we don't simulate the hunt
+                Some(NpcActivity::HuntAnimals) => {
+                    data.nature.hunt(
+                        npc.wpos.xy().as_().wpos_to_cpos(),
+                        WildlifeKind::Prey,
+                        wildlife::NPC_HUNT_RATE * ctx.event.dt,
+                    );
+                },
                 Some(
                     NpcActivity::Gather(_)
-                    | NpcActivity::HuntAnimals
                     | NpcActivity::Dance(_)
                     | NpcActivity::Cheer(_)
                     | NpcActivity::Sit(..)
@@ -295,5 +304,6 @@ fn on_tick(ctx: EventCtx<SimulateNpcs, OnTick>) {
 
         // Set job status
         npc.job = npc.controller.job.clone();
+        npc.order = npc.controller.order;
     }
 }
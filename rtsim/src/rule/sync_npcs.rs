@@ -8,6 +8,9 @@ use common::{
     terrain::CoordinateConversions,
 };
 
+/// How often NPCs exchange reports with the site they're staying at.
+const REPORT_EXCHANGE_TICK_SKIP: u64 = 600;
+
 pub struct SyncNpcs;
 
 impl Rule for SyncNpcs {
@@ -98,6 +101,7 @@ fn on_tick(ctx: EventCtx<SyncNpcs, OnTick>) {
     let data = &mut *ctx.state.data_mut();
     for (npc_id, npc) in data.npcs.npcs.iter_mut() {
         // Update the NPC's current site, if any
+        let last_site = npc.current_site;
         npc.current_site = ctx
             .world
             .sim()
@@ -109,19 +113,28 @@ fn on_tick(ctx: EventCtx<SyncNpcs, OnTick>) {
                     .find_map(|site| data.sites.world_site_map.get(site).copied())
             });
 
-        // Share known reports with current site, if it's our home
-        // TODO: Only share new reports
+        // Exchange known reports with the current site when arriving there, and
+        // every so often while staying, such that news travels with those who
+        // visit it
         if let Some(current_site) = npc.current_site
-            && Some(current_site) == npc.home
+            && (last_site != Some(current_site)
+                || (npc.seed as u64 + ctx.event.tick).is_multiple_of(REPORT_EXCHANGE_TICK_SKIP))
             && let Some(site) = data.sites.get_mut(current_site)
         {
             // TODO: Sites should have an inbox and their own AI code
             site.known_reports.extend(npc.known_reports.iter().copied());
+            let memory = npc.memory();
             npc.inbox.extend(
                 site.known_reports
                     .iter()
                     .copied()
                     .filter(|report| !npc.known_reports.contains(report))
+                    // Don't pick up news that the NPC would forget again right away
+                    .filter(|report| {
+                        data.reports
+                            .get(*report)
+                            .is_some_and(|report| report.is_remembered(data.time_of_day, memory))
+                    })
                     .map(NpcInput::Report),
             );
         }
@@ -0,0 +1,172 @@
+//! Wildlife populations, tracked per chunk in [`crate::data::nature::Chunk`].
+//!
+//! Prey grows back toward what a chunk can support, while predators feed on
+//! it, so that predators thrive where prey is plentiful and die out where it
+//! isn't. Animals also wander between neighbouring chunks, favouring warmer
+//! land in the winter and cooler land in the summer. Populations are depleted
+//! by hunting (see [`Nature::hunt`]), and since wildlife spawns according to
+//! them, an area that has been overhunted stays empty until animals have
+//! moved back in and bred.
+//!
+//! [`Nature::hunt`]: crate::data::nature::Nature::hunt
+
+use crate::{RtState, Rule, RuleError, event::OnTick};
+use common::rtsim::WildlifeKind;
+use enum_map::EnumMap;
+use vek::*;
+use world::util::CARDINALS;
+
+pub struct Wildlife;
+
+/// How many chunks should be updated per tick.
+pub const UPDATE_PER_TICK: usize = 8192;
+/// The highest population that a chunk can reach, relative to what it
+/// naturally supports. Prey can go beyond what the chunk naturally supports
+/// when there are few predators to keep it in check.
+pub const MAX_POPULATION: f32 = 2.0;
+/// How much of the population of a chunk one animal killed by a player or a
+/// loaded NPC accounts for.
+pub const KILL_DEPLETION: f32 = 0.1;
+/// How quickly simulated NPCs hunting in a chunk deplete its prey, per second.
+pub const NPC_HUNT_RATE: f32 = 1.0 / (60.0 * 30.0);
+
+/// How quickly prey breeds, per second.
+const PREY_GROWTH: f32 = 1.0 / (60.0 * 60.0 * 2.0);
+/// How quickly predators eat away at prey when there are more of them than
+/// the chunk naturally supports, per second.
+const PREDATION: f32 = 1.0 / (60.0 * 60.0 * 3.0);
+/// How quickly predator populations follow the prey that they feed on, per
+/// second.
+const PREDATOR_GROWTH: f32 = 1.0 / (60.0 * 60.0 * 3.0);
+/// How quickly animals wander to neighbouring chunks, per second.
+const MIGRATION: f32 = 1.0 / (60.0 * 60.0);
+/// How strongly the seasons drive animals toward land of a preferred
+/// temperature.
+const SEASONAL_PULL: f32 = 0.5;
+/// The length of a year, in in-game days.
+const YEAR_DAYS: f64 = 16.0;
+
+/// How far into the year it is, from -1.0 in the depths of winter to 1.0 at
+/// the height of summer.
+fn season(time_of_day: f64) -> f32 {
+    const DAY: f64 = 60.0 * 60.0 * 24.0;
+    (time_of_day / (YEAR_DAYS * DAY) * std::f64::consts::TAU).sin() as f32
+}
+
+/// Advance the populations of a chunk, not counting migration.
+fn breed(pop: &mut EnumMap<WildlifeKind, f32>, dt: f32) {
+    let prey = pop[WildlifeKind::Prey];
+    let predators = pop[WildlifeKind::Predator];
+    // Both populations settle at what the chunk naturally supports, unless
+    // something upsets the balance
+    pop[WildlifeKind::Prey] +=
+        (PREY_GROWTH * prey * (1.0 - prey) - PREDATION * prey * (predators - 1.0)) * dt;
+    pop[WildlifeKind::Predator] += PREDATOR_GROWTH * predators * (prey - predators) * dt;
+}
+
+impl Rule for Wildlife {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnTick>(|ctx| {
+            let mut data = ctx.state.data_mut();
+            let chunks = &mut data.nature.chunks;
+            let size = chunks.size();
+            let chunk_count = size.product() as usize;
+
+            // Every chunk gets updated once in this many ticks
+            let group_count = chunk_count / UPDATE_PER_TICK + 1;
+            let dt = ctx.event.dt * group_count as f32;
+
+            // Animals seek out land whose temperature suits the season
+            let season = season(ctx.event.time_of_day.0);
+            let appeal = |pos: Vec2<i32>| {
+                ctx.world.sim().get(pos).map_or(0.0, |chunk| {
+                    (1.0 - SEASONAL_PULL * season * chunk.temp).max(0.0)
+                })
+            };
+
+            let start = (ctx.event.tick as usize % group_count) * UPDATE_PER_TICK;
+            for idx in start..(start + UPDATE_PER_TICK).min(chunk_count) {
+                let pos = Vec2::new(idx as i32 % size.x, idx as i32 / size.x);
+                let here = appeal(pos);
+
+                // Animals move in from neighbours that find this chunk more appealing
+                // than their own, and out to neighbours that are more appealing
+                let mut inflow = EnumMap::<WildlifeKind, f32>::default();
+                let pop = chunks[pos].wildlife;
+                for dir in CARDINALS {
+                    if let Some(neighbour) = chunks.get(pos + dir) {
+                        let there = appeal(pos + dir);
+                        for (kind, flow) in &mut inflow {
+                            *flow += (neighbour.wildlife[kind] * here - pop[kind] * there)
+                                / CARDINALS.len() as f32;
+                        }
+                    }
+                }
+
+                let wildlife = &mut chunks[pos].wildlife;
+                breed(wildlife, dt);
+                for (kind, pop) in wildlife.iter_mut() {
+                    *pop = (*pop + inflow[kind] * MIGRATION * dt).clamp(0.0, MAX_POPULATION);
+                }
+            }
+        });
+
+        Ok(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn simulate(mut pop: EnumMap<WildlifeKind, f32>, hours: f32) -> EnumMap<WildlifeKind, f32> {
+        let dt = 10.0;
+        for _ in 0..(hours * 60.0 * 60.0 / dt) as usize {
+            breed(&mut pop, dt);
+            for (_, pop) in pop.iter_mut() {
+                *pop = pop.clamp(0.0, MAX_POPULATION);
+            }
+        }
+        pop
+    }
+
+    fn populations(prey: f32, predators: f32) -> EnumMap<WildlifeKind, f32> {
+        let mut pop = EnumMap::default();
+        pop[WildlifeKind::Prey] = prey;
+        pop[WildlifeKind::Predator] = predators;
+        pop
+    }
+
+    #[test]
+    fn untouched_populations_are_stable() {
+        let pop = simulate(populations(1.0, 1.0), 48.0);
+        assert!((pop[WildlifeKind::Prey] - 1.0).abs() < 0.01);
+        assert!((pop[WildlifeKind::Predator] - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn overhunting_has_lasting_consequences() {
+        // Depleted prey takes hours to breed back, and predators starve meanwhile
+        let pop = simulate(populations(0.05, 1.0), 1.0);
+        assert!(pop[WildlifeKind::Prey] < 0.5);
+        assert!(pop[WildlifeKind::Predator] < 1.0);
+
+        // Eventually, the balance gets restored
+        let pop = simulate(pop, 72.0);
+        assert!((pop[WildlifeKind::Prey] - 1.0).abs() < 0.1);
+        assert!((pop[WildlifeKind::Predator] - 1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn prey_booms_without_predators() {
+        let pop = simulate(populations(1.0, 0.0), 12.0);
+        assert!(pop[WildlifeKind::Prey] > 1.2);
+    }
+
+    #[test]
+    fn extinct_populations_stay_extinct_without_migration() {
+        let pop = simulate(populations(0.0, 0.0), 24.0);
+        assert_eq!(pop[WildlifeKind::Prey], 0.0);
+        assert_eq!(pop[WildlifeKind::Predator], 0.0);
+    }
+}
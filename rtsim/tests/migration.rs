@@ -0,0 +1,71 @@
+use std::{fs, path::PathBuf};
+use veloren_rtsim::data::{
+    CURRENT_VERSION, Data, ReadError,
+    migrate::{MIGRATIONS, OLDEST_MIGRATABLE_VERSION},
+};
+
+/// Saves written by older versions, named after their version (`v10.dat`)
+fn fixtures() -> Vec<(u32, PathBuf)> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut fixtures = fs::read_dir(&dir)
+        .expect("missing fixtures directory")
+        .map(|entry| entry.unwrap().path())
+        .filter_map(|path| {
+            let version = path
+                .file_stem()?
+                .to_str()?
+                .strip_prefix('v')?
+                .parse()
+                .ok()?;
+            Some((version, path))
+        })
+        .collect::<Vec<_>>();
+    fixtures.sort();
+    fixtures
+}
+
+#[test]
+fn fixtures_load() {
+    for (version, path) in fixtures() {
+        let result = Data::from_reader(fs::File::open(&path).unwrap());
+        if version >= OLDEST_MIGRATABLE_VERSION {
+            let data = result.unwrap_or_else(|err| panic!("{path:?} failed to load: {err:?}"));
+            assert_eq!(data.version, CURRENT_VERSION, "{path:?}");
+        } else {
+            // Too old to be migrated, but the data is handed back to decide what to do
+            assert!(
+                matches!(result, Err(ReadError::VersionMismatch(_))),
+                "{path:?} should be too old to migrate"
+            );
+        }
+    }
+}
+
+#[test]
+fn fixture_for_every_migration() {
+    let versions = fixtures()
+        .into_iter()
+        .map(|(version, _)| version)
+        .collect::<Vec<_>>();
+    for version in OLDEST_MIGRATABLE_VERSION..=CURRENT_VERSION {
+        assert!(
+            versions.contains(&version),
+            "missing a fixture for version {version}"
+        );
+    }
+    assert_eq!(
+        OLDEST_MIGRATABLE_VERSION + MIGRATIONS.len() as u32,
+        CURRENT_VERSION
+    );
+}
+
+#[test]
+fn round_trip() {
+    let (_, path) = fixtures().pop().expect("no fixtures");
+    let data = Data::from_reader(fs::File::open(path).unwrap()).unwrap();
+    let mut bytes = Vec::new();
+    data.write_to(&mut bytes).unwrap();
+    let reloaded = Data::from_reader(bytes.as_slice()).unwrap();
+    assert_eq!(reloaded.tick, data.tick);
+    assert_eq!(reloaded.version, CURRENT_VERSION);
+}
@@ -5,7 +5,7 @@
 use clap::{Parser, builder::ValueParser};
 use common::comp;
 use server::persistence::SqlLogMode;
-use std::{str::FromStr, sync::mpsc::Sender};
+use std::{path::PathBuf, str::FromStr, sync::mpsc::Sender};
 use tracing::error;
 
 // Custom value parser for case-insensitive parsing of AdminRole
@@ -117,6 +117,23 @@ pub struct BenchParams {
     pub duration: u32,
 }
 
+#[derive(Debug, Clone, Parser)]
+pub struct SoakRtsimParams {
+    /// Number of in-game days to simulate
+    #[arg(long, default_value_t = 7.0)]
+    pub days: f64,
+    /// Simulated time that passes with every tick (in seconds)
+    #[arg(long, default_value_t = 1.0 / 30.0)]
+    pub dt: f32,
+    /// In-game hours between samples
+    #[arg(long, default_value_t = 1.0)]
+    pub sample_hours: f64,
+    /// File to write the samples to, as JSON or, if the extension is `.csv`,
+    /// as CSV
+    #[arg(long, default_value = "rtsim_soak.json")]
+    pub output: PathBuf,
+}
+
 #[derive(Parser)]
 pub enum ArgvCommand {
     #[command(flatten)]
@@ -124,6 +141,11 @@ pub enum ArgvCommand {
     /// Load an area, run the server for some time, and then exit (useful for
     /// profiling).
     Bench(BenchParams),
+    /// Upgrade the saved rtsim data to the current version, and then exit
+    MigrateRtsim,
+    /// Run rtsim without clients for some number of in-game days, write
+    /// statistics about it to a file, and then exit
+    SoakRtsim(SoakRtsimParams),
 }
 
 #[derive(Parser)]
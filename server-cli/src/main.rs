@@ -20,6 +20,7 @@ mod web;
 use crate::{
     cli::{
         Admin, ArgvApp, ArgvCommand, BenchParams, Message, MessageReturn, SharedCommand, Shutdown,
+        SoakRtsimParams,
     },
     settings::Settings,
     shutdown_coordinator::ShutdownCoordinator,
@@ -140,6 +141,7 @@ fn main() -> io::Result<()> {
     };
 
     let mut bench = None;
+    let mut soak = None;
     if let Some(command) = app.command {
         match command {
             ArgvCommand::Shared(SharedCommand::Admin { command }) => {
@@ -189,6 +191,25 @@ fn main() -> io::Result<()> {
                 // annoying, might require a more involved refactor to get
                 // working nicely
             },
+            ArgvCommand::MigrateRtsim => {
+                return match server::rtsim::RtSim::migrate_file(server_data_dir.clone()) {
+                    Ok(backup_path) => {
+                        info!(
+                            "Rtsim data migrated, the original was kept at {}",
+                            backup_path.display()
+                        );
+                        Ok(())
+                    },
+                    Err(err) => Err(io::Error::other(err)),
+                };
+            },
+            ArgvCommand::SoakRtsim(params) => {
+                soak = Some(params);
+                // The soak test runs without clients, so don't listen for any.
+                server_settings.gameserver_protocols.clear();
+                server_settings.query_address = None;
+                server_settings.auth_server_address = None;
+            },
         };
     }
 
@@ -209,7 +230,6 @@ fn main() -> io::Result<()> {
     let protocols_and_addresses = server_settings.gameserver_protocols.clone();
     let web_port = &settings.web_address.port();
     // Create server
-    #[cfg_attr(not(feature = "worldgen"), expect(unused_mut))]
     let mut server = Server::new(
         server_settings,
         editable_settings,
@@ -220,6 +240,10 @@ fn main() -> io::Result<()> {
     )
     .expect("Failed to create server instance!");
 
+    if let Some(params) = soak {
+        return soak_rtsim(&mut server, params);
+    }
+
     let registry = Arc::clone(server.metrics_registry());
     let chat = server.chat_cache().clone();
     let metrics_shutdown = Arc::new(Notify::new());
@@ -284,6 +308,27 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+fn soak_rtsim(server: &mut Server, params: SoakRtsimParams) -> io::Result<()> {
+    let samples = server.soak_rtsim(&server::rtsim::soak::SoakParams {
+        days: params.days,
+        dt: params.dt,
+        sample_hours: params.sample_hours,
+    });
+
+    let file = io::BufWriter::new(std::fs::File::create(&params.output)?);
+    if params.output.extension().is_some_and(|ext| ext == "csv") {
+        server::rtsim::soak::write_csv(&samples, file)?;
+    } else {
+        server::rtsim::soak::write_json(&samples, file)?;
+    }
+    info!(
+        "Wrote {} rtsim soak samples to {}",
+        samples.len(),
+        params.output.display()
+    );
+    Ok(())
+}
+
 fn server_loop(
     mut server: Server,
     bench: Option<BenchParams>,
@@ -7,6 +7,8 @@ use common::{
     calendar::Calendar, generation::ChunkSupplement, resources::TimeOfDay, slowjob::SlowJobPool,
     terrain::TerrainChunk,
 };
+#[cfg(feature = "plugins")]
+use common_state::plugin::{ColumnInfo, ColumnInfoFn, PluginMgr};
 use hashbrown::{HashMap, hash_map::Entry};
 use rayon::iter::ParallelIterator;
 use specs::Entity as EcsEntity;
@@ -64,13 +66,17 @@ impl ChunkGenerator {
         // Get state for this chunk from rtsim
         #[cfg(feature = "worldgen")]
         let rtsim_resources = Some(rtsim.get_chunk_resources(key));
+        #[cfg(feature = "worldgen")]
+        let rtsim_wildlife = Some(rtsim.get_chunk_wildlife(key));
         #[cfg(not(feature = "worldgen"))]
         let rtsim_resources = None;
+        #[cfg(not(feature = "worldgen"))]
+        let rtsim_wildlife = None;
 
         slowjob_pool.spawn("CHUNK_GENERATOR", move || {
             let index = index.as_index_ref();
             let payload = world
-                .generate_chunk(index, key, rtsim_resources, || cancel.load(Ordering::Relaxed), Some(time))
+                .generate_chunk(index, key, rtsim_resources, rtsim_wildlife, || cancel.load(Ordering::Relaxed), Some(time))
                 // FIXME: Since only the first entity who cancels a chunk is notified, we end up
                 // delaying chunk re-requests for up to 3 seconds for other clients, which isn't
                 // great.  We *could* store all the other requesting clients here, but it could
@@ -98,6 +104,23 @@ impl ChunkGenerator {
         None
     }
 
+    /// Let plugins modify a freshly generated chunk. This runs on the main
+    /// thread before changes made by players are applied, plugins are
+    /// expected to only depend on the chunk so it looks the same each time it
+    /// is generated.
+    #[cfg(feature = "plugins")]
+    pub fn apply_plugin_hooks(
+        plugin_mgr: &mut PluginMgr,
+        world: &Arc<World>,
+        key: Vec2<i32>,
+        chunk: &mut TerrainChunk,
+    ) {
+        if !plugin_mgr.has_worldgen_hooks() {
+            return;
+        }
+        plugin_mgr.chunk_generated(key, chunk, &column_info_fn(world));
+    }
+
     pub fn pending_chunks(&self) -> impl Iterator<Item = Vec2<i32>> + '_ {
         self.pending_chunks.keys().copied()
     }
@@ -121,3 +144,26 @@ impl ChunkGenerator {
         });
     }
 }
+
+#[cfg(feature = "plugins")]
+fn column_info_fn(world: &Arc<World>) -> ColumnInfoFn {
+    #[cfg(feature = "worldgen")]
+    {
+        let world = Arc::clone(world);
+        Arc::new(move |wpos: Vec2<i32>| {
+            world.sim().get_wpos(wpos).map(|chunk| ColumnInfo {
+                alt: chunk.alt,
+                water_alt: chunk.water_alt,
+                temp: chunk.temp,
+                humidity: chunk.humidity,
+                rockiness: chunk.rockiness,
+                tree_density: chunk.tree_density,
+            })
+        })
+    }
+    #[cfg(not(feature = "worldgen"))]
+    {
+        let _ = world;
+        Arc::new(|_: Vec2<i32>| None::<ColumnInfo>)
+    }
+}
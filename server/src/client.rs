@@ -192,6 +192,8 @@ impl Client {
                     | ServerGeneral::InventoryUpdate(_, _)
                     | ServerGeneral::GroupInventoryUpdate(_, _)
                     | ServerGeneral::Dialogue(_, _)
+                    | ServerGeneral::QuestLog(_)
+                    | ServerGeneral::SiteHolders(_)
                     | ServerGeneral::SetViewDistance(_)
                     | ServerGeneral::Outcomes(_)
                     | ServerGeneral::Knockback(_)
@@ -203,7 +205,8 @@ impl Client {
                     | ServerGeneral::LocalWindUpdate(_)
                     | ServerGeneral::SpectatePosition(_)
                     | ServerGeneral::UpdateRecipes
-                    | ServerGeneral::Gizmos(_) => {
+                    | ServerGeneral::Gizmos(_)
+                    | ServerGeneral::PluginMessage { .. } => {
                         PreparedMsg::new(2, &g, &self.in_game_stream_params)
                     },
                     // Terrain
@@ -225,7 +228,8 @@ impl Client {
                     | ServerGeneral::Disconnect(_)
                     | ServerGeneral::Notification(_)
                     | ServerGeneral::SetPlayerRole(_)
-                    | ServerGeneral::PluginData(_) => {
+                    | ServerGeneral::PluginData(_)
+                    | ServerGeneral::PluginCommands(_) => {
                         PreparedMsg::new(3, &g, &self.general_stream_params)
                     },
                 }
@@ -19,7 +19,7 @@ use crate::{
     wiring::{self, OutputFormula},
 };
 #[cfg(feature = "worldgen")]
-use common::{cmd::SPOT_PARSER, spot::Spot};
+use common::spot::Spot;
 
 use assets::{AssetExt, Ron};
 use authc::Uuid;
@@ -196,6 +196,7 @@ fn do_command(
         ServerChatCommand::Outcome => handle_outcome,
         ServerChatCommand::PermitBuild => handle_permit_build,
         ServerChatCommand::Players => handle_players,
+        ServerChatCommand::Plugin => handle_plugin,
         ServerChatCommand::Poise => handle_poise,
         ServerChatCommand::Portal => handle_spawn_portal,
         ServerChatCommand::ResetRecipes => handle_reset_recipes,
@@ -217,6 +218,8 @@ fn do_command(
         ServerChatCommand::SkillPreset => handle_skill_preset,
         ServerChatCommand::Spawn => handle_spawn,
         ServerChatCommand::Spot => handle_spot,
+        ServerChatCommand::StructureCapture => handle_structure_capture,
+        ServerChatCommand::StructurePaste => handle_structure_paste,
         ServerChatCommand::Sudo => handle_sudo,
         ServerChatCommand::Tell => handle_tell,
         ServerChatCommand::Time => handle_time,
@@ -2820,6 +2823,41 @@ fn handle_players(
     Ok(())
 }
 
+#[cfg(feature = "plugins")]
+fn handle_plugin(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let (Some(plugin_action), name) = parse_cmd_args!(args, String, String) else {
+        return Err(action.help_content());
+    };
+    let msg = match (plugin_action.as_str(), name) {
+        ("list", _) => crate::plugin::list_plugins(server),
+        ("reload", Some(name)) => crate::plugin::reload_plugin(server, &name)?,
+        ("unload", Some(name)) => crate::plugin::unload_plugin(server, &name)?,
+        _ => return Err(action.help_content()),
+    };
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(ChatType::CommandInfo, Content::Plain(msg)),
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "plugins"))]
+fn handle_plugin(
+    _server: &mut Server,
+    _client: EcsEntity,
+    _target: EcsEntity,
+    _args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    Err(Content::Plain("Unsupported without plugins enabled".into()))
+}
+
 fn handle_spawn_portal(
     server: &mut Server,
     client: EcsEntity,
@@ -6491,7 +6529,8 @@ fn handle_spot(
         return Err(action.help_content());
     };
 
-    let maybe_spot_kind = SPOT_PARSER.get(&target_spot);
+    let target_spot = Spot::from_name(&target_spot)
+        .ok_or_else(|| Content::localized("command-spot-spot_not_found"))?;
 
     let target_pos = server
         .state
@@ -6508,16 +6547,10 @@ fn handle_spot(
         .filter(|chunk| world.sim().get(*chunk).is_some())
         .take(world.sim().map_size_lg().chunks_len())
         .find(|chunk| {
-            world.sim().get(*chunk).is_some_and(|chunk| {
-                if let Some(spot) = &chunk.spot {
-                    match spot {
-                        Spot::RonFile(spot) => spot.base_structures == target_spot,
-                        spot_kind => Some(spot_kind) == maybe_spot_kind,
-                    }
-                } else {
-                    false
-                }
-            })
+            world
+                .sim()
+                .get(*chunk)
+                .is_some_and(|chunk| chunk.spot == Some(target_spot))
         });
 
     if let Some(spot_chunk) = spot_chunk {
@@ -6547,3 +6580,73 @@ fn handle_spot(
 ) -> CmdResult<()> {
     Err(Content::localized("command-spot-world_feature"))
 }
+
+fn handle_structure_capture(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let (Some(name), Some(xlo), Some(xhi), Some(ylo), Some(yhi), Some(zlo), Some(zhi)) =
+        parse_cmd_args!(args, String, i32, i32, i32, i32, i32, i32)
+    else {
+        return Err(action.help_content());
+    };
+
+    // Both corners are part of the region
+    let aabb = Aabb {
+        min: Vec3::new(xlo, ylo, zlo),
+        max: Vec3::new(xhi, yhi, zhi),
+    }
+    .made_valid();
+    let aabb = Aabb {
+        min: aabb.min,
+        max: aabb.max + 1,
+    };
+    let structure = crate::structures::capture(&server.state, aabb)?;
+    crate::structures::save(server.data_dir().as_ref(), &name, &structure)?;
+
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(
+            ChatType::CommandInfo,
+            Content::localized_with_args("command-structure-captured", [("name", name)]),
+        ),
+    );
+    Ok(())
+}
+
+fn handle_structure_paste(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let (Some(name), rotation, mirror) = parse_cmd_args!(args, String, u8, bool) else {
+        return Err(action.help_content());
+    };
+
+    let pos = position(server, target, "target")?;
+    let structure = crate::structures::load(server.data_dir().as_ref(), &name)?;
+    let count = crate::structures::paste(
+        &server.state,
+        &structure,
+        pos.0.map(|e| e.floor() as i32),
+        rotation.unwrap_or(0),
+        mirror.unwrap_or(false),
+    );
+
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(
+            ChatType::CommandInfo,
+            Content::localized_with_args("command-structure-pasted", [
+                ("name", name),
+                ("count", count.to_string()),
+            ]),
+        ),
+    );
+    Ok(())
+}
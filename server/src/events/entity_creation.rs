@@ -86,6 +86,7 @@ pub fn handle_loaded_character_data(server: &mut Server, ev: UpdateCharacterData
         pets: ev.components.6,
         active_abilities: ev.components.7,
         map_marker: ev.components.8,
+        companion: ev.components.9,
     };
     if let Some(marker) = loaded_components.map_marker {
         server.notify_client(
@@ -104,6 +105,9 @@ pub fn handle_loaded_character_data(server: &mut Server, ev: UpdateCharacterData
         ServerGeneral::CharacterDataLoadResult(Err(err))
     } else {
         sys::subscription::initialize_region_subscription(server.state.ecs(), ev.entity);
+        // The character is in the world now
+        #[cfg(feature = "plugins")]
+        server.state.plugin_spawn_event(ev.entity);
         // We notify the client with the metadata result from the operation.
         ServerGeneral::CharacterDataLoadResult(Ok(ev.metadata))
     };
@@ -257,6 +261,9 @@ pub fn handle_create_npc(server: &mut Server, ev: CreateNpcEvent) -> EcsEntity {
         tame_pet(server.state.ecs(), pet_entity, new_entity);
     }
 
+    #[cfg(feature = "plugins")]
+    server.state.plugin_spawn_event(new_entity);
+
     new_entity
 }
 
@@ -309,6 +316,9 @@ pub fn handle_create_ship(server: &mut Server, ev: CreateShipEvent) {
             .add_rtsim(rtsim_entity, entity);
     }
 
+    #[cfg(feature = "plugins")]
+    server.state.plugin_spawn_event(entity);
+
     if let Some(driver) = ev.driver {
         let npc_entity = handle_create_npc(server, CreateNpcEvent {
             pos: ev.pos,
@@ -613,6 +623,8 @@ pub fn handle_create_object(
                     time,
                 )]))
                 .build();
+            #[cfg(feature = "plugins")]
+            state.plugin_spawn_event(crux);
 
             if let Some(owner) = state.ecs().read_resource::<IdMaps>().uid_entity(owner) {
                 let mut group_manager = state.ecs().write_resource::<comp::group::GroupManager>();
@@ -628,7 +640,7 @@ pub fn handle_create_object(
             }
         },
         _ => {
-            server
+            let _entity = server
                 .state
                 .create_object(pos, body)
                 .with(vel)
@@ -637,6 +649,8 @@ pub fn handle_create_object(
                 .maybe_with(light_emitter)
                 .maybe_with(stats)
                 .build();
+            #[cfg(feature = "plugins")]
+            server.state.plugin_spawn_event(_entity);
         },
     }
 }
@@ -17,7 +17,7 @@ use crate::{
     sys::terrain::{NpcData, SAFE_ZONE_RADIUS, SpawnEntityData},
 };
 #[cfg(feature = "worldgen")]
-use common::rtsim::{Actor, RtSimEntity};
+use common::rtsim::{Actor, RtSimEntity, WildlifeKind};
 use common::{
     CachedSpatialGrid, Damage, DamageKind, DamageSource, GroupTarget, RadiusEffect,
     assets::{AssetExt, Ron},
@@ -66,6 +66,8 @@ use common::{
     vol::ReadVol,
 };
 use common_net::{msg::ServerGeneral, sync::WorldSyncExt, synced_components::Heads};
+#[cfg(feature = "plugins")]
+use common_state::plugin::{EntityEvent, PluginMgr, memory_manager::EcsWorld};
 use common_state::{AreasContainer, BlockChange, NoDurabilityArea, ScheduledBlockChange};
 use hashbrown::HashSet;
 use rand::Rng;
@@ -203,8 +205,12 @@ pub struct HealthChangeEventData<'a> {
     rtsim: WriteExpect<'a, RtSim>,
     events: HealthChangeEvents<'a>,
     time: Read<'a, Time>,
-    #[cfg(feature = "worldgen")]
+    #[cfg(any(feature = "worldgen", feature = "plugins"))]
     id_maps: Read<'a, IdMaps>,
+    #[cfg(feature = "plugins")]
+    plugin_mgr: Write<'a, PluginMgr>,
+    #[cfg(feature = "plugins")]
+    players: ReadStorage<'a, Player>,
     #[cfg(feature = "worldgen")]
     world: ReadExpect<'a, Arc<World>>,
     #[cfg(feature = "worldgen")]
@@ -226,6 +232,8 @@ impl ServerEvent for HealthChangeEvent {
     fn handle(events: impl ExactSizeIterator<Item = Self>, mut data: Self::SystemData<'_>) {
         let mut emitters = data.events.get_emitters();
         let mut rng = rand::rng();
+        #[cfg(feature = "plugins")]
+        let mut plugin_events = Vec::new();
         for ev in events {
             if let Some((mut health, pos, uid, heads)) = (
                 &mut data.healths,
@@ -279,6 +287,20 @@ impl ServerEvent for HealthChangeEvent {
                     }
                 }
 
+                #[cfg(feature = "plugins")]
+                if let Some(uid) = uid
+                    && changed
+                    && ev.change.amount < 0.0
+                {
+                    plugin_events.push(EntityEvent::Damage {
+                        target: *uid,
+                        attacker: ev.change.by.map(|by| by.uid()),
+                        amount: -ev.change.amount,
+                        source: ev.change.cause,
+                        precise: ev.change.precise,
+                    });
+                }
+
                 if let (Some(pos), Some(uid)) = (pos, uid)
                     && changed
                 {
@@ -316,6 +338,20 @@ impl ServerEvent for HealthChangeEvent {
                 agent.inbox.push_back(AgentEvent::Hurt);
             }
         }
+
+        #[cfg(feature = "plugins")]
+        if !plugin_events.is_empty() {
+            let ecs_world = EcsWorld::new(
+                &data.entities,
+                &data.healths,
+                &data.uids,
+                &data.players,
+                &data.id_maps,
+            );
+            for event in plugin_events {
+                data.plugin_mgr.entity_event(&ecs_world, &event);
+            }
+        }
     }
 }
 
@@ -562,6 +598,8 @@ pub struct DestroyEventData<'a> {
     presences: ReadStorage<'a, Presence>,
     buff_events: Read<'a, EventBus<BuffEvent>>,
     masses: ReadStorage<'a, comp::Mass>,
+    #[cfg(feature = "plugins")]
+    plugin_mgr: Write<'a, PluginMgr>,
 }
 
 /// Handle an entity dying. If it is a player, it will send a message to all
@@ -579,6 +617,8 @@ impl ServerEvent for DestroyEvent {
         let mut buff_emitter = data.buff_events.emitter();
         let mut transform_emitter = data.transform_events.emitter();
         data.entities_died_last_tick.0.clear();
+        #[cfg(feature = "plugins")]
+        let mut plugin_events = Vec::new();
 
         for ev in events {
             // TODO: Investigate duplicate `Destroy` events (but don't remove this).
@@ -1079,6 +1119,19 @@ impl ServerEvent for DestroyEvent {
             let entity_as_actor =
                 |entity| entity_as_actor(entity, &data.rtsim_entities, &data.presences);
 
+            #[cfg(feature = "worldgen")]
+            let killer = ev
+                .cause
+                .by
+                .as_ref()
+                .and_then(
+                    |(DamageContributor::Solo(entity_uid)
+                     | DamageContributor::Group { entity_uid, .. })| {
+                        data.id_maps.uid_entity(*entity_uid)
+                    },
+                )
+                .and_then(entity_as_actor);
+
             #[cfg(feature = "worldgen")]
             if let Some(actor) = entity_as_actor(ev.entity)
                 // Skip the death hook for rtsim entities if they aren't deleted, otherwise
@@ -1091,23 +1144,47 @@ impl ServerEvent for DestroyEvent {
                     data.index.as_index_ref(),
                     actor,
                     data.positions.get(ev.entity).map(|p| p.0),
-                    ev.cause
-                        .by
-                        .as_ref()
-                        .and_then(
-                            |(DamageContributor::Solo(entity_uid)
-                             | DamageContributor::Group { entity_uid, .. })| {
-                                data.id_maps.uid_entity(*entity_uid)
-                            },
-                        )
-                        .and_then(entity_as_actor),
+                    killer,
                 );
+            } else if should_delete
+                // Wildlife hunted down by players and NPCs is gone from the area for good
+                && killer.is_some()
+                && !matches!(data.bodies.get(ev.entity), Some(Body::Humanoid(_)))
+                && let Some(kind) = data
+                    .alignments
+                    .get(ev.entity)
+                    .and_then(WildlifeKind::from_alignment)
+                && let Some(pos) = data.positions.get(ev.entity)
+            {
+                data.rtsim.hook_wildlife_death(pos.0, kind);
+            }
+
+            #[cfg(feature = "plugins")]
+            if let Some(uid) = data.uids.get(ev.entity) {
+                plugin_events.push(EntityEvent::Death {
+                    entity: *uid,
+                    killer: ev.cause.by.map(|by| by.uid()),
+                });
             }
 
             if should_delete {
                 delete_emitter.emit(DeleteEvent(ev.entity));
             }
         }
+
+        #[cfg(feature = "plugins")]
+        if !plugin_events.is_empty() {
+            let ecs_world = EcsWorld::new(
+                &data.entities,
+                &data.healths,
+                &data.uids,
+                &data.players,
+                &data.id_maps,
+            );
+            for event in plugin_events {
+                data.plugin_mgr.entity_event(&ecs_world, &event);
+            }
+        }
     }
 }
 
@@ -1,11 +1,20 @@
 use crate::client::Client;
 use common::event::RequestSiteInfoEvent;
+#[cfg(feature = "plugins")]
+use common::{
+    comp::{Health, Player},
+    uid::{IdMaps, Uid},
+};
+#[cfg(feature = "plugins")]
+use common_net::msg::{MAX_PLUGIN_MESSAGE_SIZE, MAX_PLUGIN_MESSAGES_PER_TICK};
 use common_net::msg::{ServerGeneral, world_msg::EconomyInfo};
 #[cfg(feature = "plugins")]
-use common_state::plugin::PluginMgr;
+use common_state::plugin::{PluginMgr, memory_manager::EcsWorld};
 #[cfg(feature = "worldgen")]
 use specs::ReadExpect;
 use specs::{DispatcherBuilder, ReadStorage};
+#[cfg(feature = "plugins")]
+use specs::{Entities, Read, Write};
 use std::collections::HashMap;
 #[cfg(feature = "worldgen")]
 use world::IndexOwned;
@@ -16,6 +25,8 @@ pub(super) fn register_event_systems(builder: &mut DispatcherBuilder) {
     event_dispatch::<RequestSiteInfoEvent>(builder, &[]);
     #[cfg(feature = "plugins")]
     event_dispatch::<common::event::RequestPluginsEvent>(builder, &[]);
+    #[cfg(feature = "plugins")]
+    event_dispatch::<common::event::PluginMessageEvent>(builder, &[]);
 }
 
 #[cfg(not(feature = "worldgen"))]
@@ -102,3 +113,40 @@ impl ServerEvent for common::event::RequestPluginsEvent {
         }
     }
 }
+
+/// Deliver messages from the client half of plugins to the server half
+#[cfg(feature = "plugins")]
+impl ServerEvent for common::event::PluginMessageEvent {
+    type SystemData<'a> = (
+        Entities<'a>,
+        Write<'a, PluginMgr>,
+        Read<'a, IdMaps>,
+        ReadStorage<'a, Health>,
+        ReadStorage<'a, Uid>,
+        ReadStorage<'a, Player>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (entities, mut plugin_mgr, id_maps, healths, uids, players): Self::SystemData<'_>,
+    ) {
+        let ecs_world = EcsWorld::new(&entities, &healths, &uids, &players, &id_maps);
+        let mut received = HashMap::new();
+        for ev in events {
+            let Some(sender) = uids.get(ev.entity) else {
+                continue;
+            };
+            let count = received.entry(ev.entity).or_insert(0);
+            *count += 1;
+            if *count > MAX_PLUGIN_MESSAGES_PER_TICK || ev.data.len() > MAX_PLUGIN_MESSAGE_SIZE {
+                tracing::debug!(
+                    ?sender,
+                    plugin = ev.plugin,
+                    "Dropping plugin message exceeding the limits"
+                );
+                continue;
+            }
+            plugin_mgr.message_event(&ecs_world, &ev.plugin, Some(*sender), &ev.channel, &ev.data);
+        }
+    }
+}
@@ -18,9 +18,9 @@ use common::{
     },
     consts::{MAX_INTERACT_RANGE, MAX_NPCINTERACT_RANGE, SOUND_TRAVEL_DIST_PER_VOLUME},
     event::{
-        CreateItemDropEvent, CreateSpriteEvent, DialogueEvent, EventBus, MineBlockEvent,
-        NpcInteractEvent, SetLanternEvent, SetPetStayEvent, SoundEvent, TamePetEvent,
-        ToggleSpriteLightEvent,
+        AbandonQuestEvent, CreateItemDropEvent, CreateSpriteEvent, DialogueEvent, EventBus,
+        MineBlockEvent, NpcInteractEvent, SetLanternEvent, SetPetStayEvent, SoundEvent,
+        TamePetEvent, ToggleSpriteLightEvent,
     },
     link::Is,
     mounting::Mount,
@@ -32,7 +32,7 @@ use common::{
     vol::ReadVol,
 };
 
-use crate::{Server, ServerGeneral, Time, client::Client};
+use crate::{Server, ServerGeneral, Time, client::Client, rtsim::RtSim};
 
 use crate::pet::tame_pet;
 use hashbrown::{HashMap, HashSet};
@@ -44,6 +44,7 @@ pub(super) fn register_event_systems(builder: &mut DispatcherBuilder) {
     event_dispatch::<SetLanternEvent>(builder, &[]);
     event_dispatch::<NpcInteractEvent>(builder, &[]);
     event_dispatch::<DialogueEvent>(builder, &[]);
+    event_dispatch::<AbandonQuestEvent>(builder, &[]);
     event_dispatch::<SetPetStayEvent>(builder, &[]);
     event_dispatch::<MineBlockEvent>(builder, &[]);
     event_dispatch::<SoundEvent>(builder, &[]);
@@ -225,6 +226,24 @@ impl ServerEvent for DialogueEvent {
     }
 }
 
+impl ServerEvent for AbandonQuestEvent {
+    type SystemData<'a> = (ReadExpect<'a, RtSim>, ReadStorage<'a, comp::Presence>);
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (rtsim, presences): Self::SystemData<'_>,
+    ) {
+        for ev in events {
+            if let Some(character_id) = presences
+                .get(ev.entity)
+                .and_then(|presence| presence.kind.character_id())
+            {
+                rtsim.hook_abandon_quest(character_id, ev.quest);
+            }
+        }
+    }
+}
+
 impl ServerEvent for SetPetStayEvent {
     type SystemData<'a> = (
         WriteStorage<'a, comp::Agent>,
@@ -13,6 +13,8 @@ use common::{
 use common_base::span;
 use common_net::msg::{PlayerListUpdate, ServerGeneral};
 use common_state::State;
+#[cfg(feature = "plugins")]
+use common_state::plugin::EntityEvent;
 use hashbrown::HashSet;
 use specs::{Builder, Entity as EcsEntity, Join, WorldExt};
 use tracing::{Instrument, debug, error, trace, warn};
@@ -294,6 +296,17 @@ pub fn handle_client_disconnect(
         )));
     }
 
+    #[cfg(feature = "plugins")]
+    if !already_disconnected {
+        let player_name = state
+            .read_storage::<comp::Player>()
+            .get(entity)
+            .map(|player| player.alias.clone());
+        if let Some((player, name)) = state.read_component_copied::<Uid>(entity).zip(player_name) {
+            state.plugin_entity_event(EntityEvent::PlayerLeave { name, player });
+        }
+    }
+
     // Sync the player's character data to the database
     if !skip_persistence {
         entity = persist_entity(state, entity);
@@ -399,6 +412,14 @@ pub(super) fn persist_entity(state: &mut State, entity: EcsEntity) -> EcsEntity
                         })
                         .collect();
 
+                    let companion =
+                        state
+                            .ecs()
+                            .try_fetch::<crate::rtsim::RtSim>()
+                            .and_then(|rtsim| {
+                                rtsim.companion_of(char_id, *state.ecs().read_resource::<Time>())
+                            });
+
                     character_updater.add_pending_logout_update((
                         char_id,
                         skill_set.clone(),
@@ -407,6 +428,7 @@ pub(super) fn persist_entity(state: &mut State, entity: EcsEntity) -> EcsEntity
                         waypoint,
                         active_abilities.clone(),
                         map_marker,
+                        companion,
                     ));
                 }
             },
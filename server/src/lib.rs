@@ -24,10 +24,12 @@ pub mod login_provider;
 pub mod metrics;
 pub mod persistence;
 mod pet;
+#[cfg(feature = "plugins")] mod plugin;
 pub mod presence;
 pub mod rtsim;
 pub mod settings;
 pub mod state_ext;
+pub mod structures;
 pub mod sys;
 #[cfg(feature = "persistent_world")]
 pub mod terrain_persistence;
@@ -135,10 +137,7 @@ use std::sync::RwLock;
 use crate::settings::Protocol;
 
 #[cfg(feature = "plugins")]
-use {
-    common::uid::IdMaps,
-    common_state::plugin::{PluginMgr, memory_manager::EcsWorld},
-};
+use common_state::plugin::{PluginMgr, memory_manager::with_ecs_world};
 
 use crate::{chat::ChatCache, persistence::character_loader::CharacterScreenResponseKind};
 use common::comp::Anchor;
@@ -293,6 +292,7 @@ impl Server {
         let physics_metrics = PhysicsMetrics::new(&registry).unwrap();
         let server_event_metrics = metrics::ServerEventMetrics::new(&registry).unwrap();
         let query_server_metrics = metrics::QueryServerMetrics::new(&registry).unwrap();
+        let plugin_metrics = metrics::PluginMetrics::new(&registry).unwrap();
 
         let battlemode_buffer = BattleModeBuffer::default();
 
@@ -300,7 +300,33 @@ impl Server {
 
         // Load plugins before generating the world.
         #[cfg(feature = "plugins")]
-        let plugin_mgr = PluginMgr::from_asset_or_default();
+        let plugin_mgr = {
+            let mut plugin_mgr = PluginMgr::from_asset_or_default();
+            plugin_mgr.set_budget(common_state::plugin::PluginBudget {
+                fuel: settings.plugins.fuel_per_call,
+                time: settings.plugins.call_time_budget,
+                memory: settings.plugins.memory_limit_mib << 20,
+                max_faults: settings.plugins.max_faults,
+            });
+            match persistence::plugin_storage::load_plugin_storage(
+                &database_settings.read().unwrap(),
+            ) {
+                Ok(entries) => {
+                    plugin_mgr.enable_storage(entries, settings.plugins.storage_quota_kib << 10)
+                },
+                Err(e) => error!(
+                    ?e,
+                    "Failed to load plugin storage, plugins can't store data"
+                ),
+            }
+            if let Err(e) = plugin_mgr.watch() {
+                warn!(
+                    ?e,
+                    "Failed to watch plugin directory, hot reloading is disabled"
+                );
+            }
+            plugin_mgr
+        };
 
         debug!("Generating world, seed: {}", settings.world_seed);
         #[cfg(feature = "worldgen")]
@@ -400,6 +426,7 @@ impl Server {
         state.ecs_mut().insert(physics_metrics);
         state.ecs_mut().insert(server_event_metrics);
         state.ecs_mut().insert(query_server_metrics);
+        state.ecs_mut().insert(plugin_metrics);
         if settings.experimental_terrain_persistence {
             #[cfg(feature = "persistent_world")]
             {
@@ -440,6 +467,14 @@ impl Server {
         state.ecs_mut().insert(CharacterUpdater::new(
             Arc::<RwLock<DatabaseSettings>>::clone(&database_settings),
         )?);
+        #[cfg(feature = "plugins")]
+        state
+            .ecs_mut()
+            .insert(persistence::plugin_storage::PluginStorageUpdater::new(
+                Arc::<RwLock<DatabaseSettings>>::clone(&database_settings),
+            ));
+        #[cfg(feature = "plugins")]
+        state.ecs_mut().insert(plugin::PendingSpawns::default());
 
         let ability_map = comp::item::tool::AbilityMap::<comp::AbilityItem>::load_expect_cloned(
             "common.abilities.ability_set_manifest",
@@ -475,6 +510,10 @@ impl Server {
         state.ecs_mut().register::<login_provider::PendingLogin>();
         state.ecs_mut().register::<RepositionOnChunkLoad>();
         state.ecs_mut().register::<RtSimEntity>();
+        state.ecs_mut().register::<rtsim::quest_log::QuestLog>();
+        state
+            .ecs_mut()
+            .register::<rtsim::site_holders::KnowsSiteHolders>();
 
         // Load banned words list
         let banned_words = settings.moderation.load_banned_words(data_dir);
@@ -866,9 +905,25 @@ impl Server {
         // Handle entity links (such as mounting)
         self.state.maintain_links();
 
+        // Reload plugins whose files changed on disk
+        #[cfg(feature = "plugins")]
+        plugin::reload_changed_plugins(self);
+
+        // Apply world modifications requested by plugins, they may emit events
+        #[cfg(feature = "plugins")]
+        plugin::apply_plugin_actions(self);
+        #[cfg(feature = "plugins")]
+        plugin::send_plugin_messages(self);
+        #[cfg(feature = "plugins")]
+        plugin::persist_plugin_storage(self);
+
         // Handle game events
         frontend_events.append(&mut self.handle_events());
 
+        // Tell plugins about the entities that were created while handling them
+        #[cfg(feature = "plugins")]
+        plugin::dispatch_spawns(self);
+
         let before_update_terrain_and_regions = Instant::now();
 
         // Apply terrain changes and update the region map after processing server
@@ -1105,6 +1160,7 @@ impl Server {
                                         pets,
                                         active_abilities,
                                         map_marker,
+                                        companion,
                                     } = character_data;
                                     let character_data = (
                                         body,
@@ -1116,6 +1172,7 @@ impl Server {
                                         pets,
                                         active_abilities,
                                         map_marker,
+                                        companion,
                                     );
                                     // TODO: Does this need to be a server event? E.g. we could
                                     // just handle it here.
@@ -1199,6 +1256,8 @@ impl Server {
 
         // 8) Update Metrics
         run_now::<sys::metrics::Sys>(self.state.ecs());
+        #[cfg(feature = "plugins")]
+        plugin::update_plugin_metrics(self);
 
         {
             // Report timing info
@@ -1384,15 +1443,33 @@ impl Server {
         } else {
             #[cfg(feature = "plugins")]
             {
-                let mut plugin_manager = self.state.ecs().write_resource::<PluginMgr>();
-                let ecs_world = EcsWorld {
-                    entities: &self.state.ecs().entities(),
-                    health: self.state.ecs().read_component().into(),
-                    uid: self.state.ecs().read_component().into(),
-                    id_maps: &self.state.ecs().read_resource::<IdMaps>().into(),
-                    player: self.state.ecs().read_component().into(),
-                };
-                let uid = if let Some(uid) = ecs_world.uid.get(entity).copied() {
+                // Check the role and arguments against the spec the plugin registered
+                let command = self
+                    .state
+                    .ecs()
+                    .read_resource::<PluginMgr>()
+                    .find_command(&name);
+                if let Some(command) = command {
+                    let error = if command.needs_role > self.entity_admin_role(entity) {
+                        Some(Content::localized_with_args("command-no-permission", [(
+                            "command_name",
+                            name.as_str(),
+                        )]))
+                    } else {
+                        command.check_args(&args).err().map(|error| {
+                            Content::Plain(format!("{error}\nUsage: {}", command.usage_string()))
+                        })
+                    };
+                    if let Some(error) = error {
+                        self.notify_client(
+                            entity,
+                            ServerGeneral::server_msg(comp::ChatType::CommandError, error),
+                        );
+                        return;
+                    }
+                }
+
+                let uid = if let Some(uid) = self.state.read_component_copied::<Uid>(entity) {
                     uid
                 } else {
                     self.notify_client(
@@ -1406,7 +1483,16 @@ impl Server {
                     );
                     return;
                 };
-                match plugin_manager.command_event(&ecs_world, &name, args.as_slice(), uid) {
+                let ecs = self.state.ecs();
+                let result = with_ecs_world(ecs, |ecs_world| {
+                    ecs.write_resource::<PluginMgr>().command_event(
+                        ecs_world,
+                        &name,
+                        args.as_slice(),
+                        uid,
+                    )
+                });
+                match result {
                     Err(common_state::plugin::CommandResults::UnknownCommand) => self
                         .notify_client(
                             entity,
@@ -1557,6 +1643,17 @@ impl Server {
             .is_some()
     }
 
+    /// Run rtsim without clients for the given number of in-game days, see
+    /// [`rtsim::soak`].
+    pub fn soak_rtsim(&mut self, params: &rtsim::soak::SoakParams) -> Vec<rtsim::soak::SoakSample> {
+        rtsim::soak::run(
+            self.state.ecs(),
+            &self.world,
+            self.index.as_index_ref(),
+            params,
+        )
+    }
+
     /// Sets the SQL log mode at runtime
     pub fn set_sql_log_mode(&mut self, sql_log_mode: SqlLogMode) {
         // Unwrap is safe here because we only perform a variable assignment with the
@@ -70,6 +70,11 @@ pub struct ServerEventMetrics {
     pub event_count: IntCounterVec,
 }
 
+pub struct PluginMetrics {
+    pub plugin_faults: IntGaugeVec,
+    pub plugins_disabled: IntGauge,
+}
+
 pub struct QueryServerMetrics {
     pub received_packets: IntCounter,
     pub dropped_packets: IntCounter,
@@ -439,6 +444,30 @@ impl ServerEventMetrics {
     }
 }
 
+impl PluginMetrics {
+    pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let plugin_faults = IntGaugeVec::new(
+            Opts::new(
+                "plugin_faults",
+                "number of traps and budget overruns of each plugin since it was loaded",
+            ),
+            &["plugin"],
+        )?;
+        let plugins_disabled = IntGauge::with_opts(Opts::new(
+            "plugins_disabled",
+            "number of plugins disabled for repeatedly trapping or exceeding their budget",
+        ))?;
+
+        registry.register(Box::new(plugin_faults.clone()))?;
+        registry.register(Box::new(plugins_disabled.clone()))?;
+
+        Ok(Self {
+            plugin_faults,
+            plugins_disabled,
+        })
+    }
+}
+
 impl QueryServerMetrics {
     pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
         let received_packets = IntCounter::with_opts(Opts::new(
@@ -0,0 +1,7 @@
+-- The companion that each character has hired, if any
+CREATE TABLE IF NOT EXISTS "companion" (
+    "character_id" INT NOT NULL,
+    "companion_data" TEXT NOT NULL,
+    PRIMARY KEY("character_id"),
+    FOREIGN KEY("character_id") REFERENCES "character"("character_id")
+);
@@ -2,8 +2,8 @@ use crate::persistence::{
     character::EntityId,
     error::PersistenceError,
     json_models::{
-        self, CharacterPosition, DatabaseAbilitySet, DatabaseItemProperties, GenericBody,
-        HumanoidBody,
+        self, CharacterPosition, DatabaseAbilitySet, DatabaseCompanion, DatabaseItemProperties,
+        GenericBody, HumanoidBody,
     },
     models::{AbilitySets, Character, Item, SkillGroup},
 };
@@ -23,6 +23,7 @@ use common::{
         skillset::{self, SkillGroupKind, SkillSet, skills::Skill},
     },
     resources::Time,
+    rtsim::Companion,
 };
 use core::{convert::TryFrom, num::NonZeroU64};
 use hashbrown::HashMap;
@@ -299,6 +300,40 @@ pub fn convert_waypoint_from_database_json(
     ))
 }
 
+pub fn convert_companion_to_database_json(
+    companion: &Companion,
+) -> Result<String, PersistenceError> {
+    let (body_variant, body_data) = convert_body_to_database_json(&companion.body)?;
+    let db_companion = DatabaseCompanion {
+        uid: companion.uid,
+        seed: companion.seed,
+        body_variant: body_variant.to_string(),
+        body_data,
+        rank: companion.rank,
+        exp: companion.exp,
+        order: companion.order.into(),
+        contract_left: companion.contract_left,
+        rtsim_tick: companion.rtsim_tick,
+    };
+    Ok(serde_json::to_string(&db_companion)?)
+}
+
+pub fn convert_companion_from_database_json(
+    companion_data: &str,
+) -> Result<Companion, PersistenceError> {
+    let db_companion = serde_json::de::from_str::<DatabaseCompanion>(companion_data)?;
+    Ok(Companion {
+        uid: db_companion.uid,
+        seed: db_companion.seed,
+        body: convert_body_from_database(&db_companion.body_variant, &db_companion.body_data)?,
+        rank: db_companion.rank,
+        exp: db_companion.exp,
+        order: db_companion.order.into(),
+        contract_left: db_companion.contract_left,
+        rtsim_tick: db_companion.rtsim_tick,
+    })
+}
+
 // Used to handle cases of modular items that are composed of components.
 // When called with the index of a component's parent item, it can get a mutable
 // reference to that parent item so that the component can be added to the
@@ -14,7 +14,8 @@ use crate::{
         character::conversions::{
             convert_active_abilities_from_database, convert_active_abilities_to_database,
             convert_body_from_database, convert_body_to_database_json,
-            convert_character_from_database, convert_hardcore_from_database,
+            convert_character_from_database, convert_companion_from_database_json,
+            convert_companion_to_database_json, convert_hardcore_from_database,
             convert_hardcore_to_database, convert_inventory_from_database_items,
             convert_items_to_database_items, convert_loadout_from_database_items,
             convert_recipe_book_from_database_items, convert_skill_groups_to_database,
@@ -31,9 +32,10 @@ use common::{
     comp::Content,
     event::{PermanentChange, UpdateCharacterMetadata},
     npc::NPC_NAMES,
+    rtsim::Companion,
 };
 use core::ops::Range;
-use rusqlite::{Connection, ToSql, Transaction, types::Value};
+use rusqlite::{Connection, OptionalExtension, ToSql, Transaction, types::Value};
 use std::{num::NonZeroU64, rc::Rc};
 use tracing::{debug, error, trace, warn};
 
@@ -271,6 +273,29 @@ pub fn load_character_data(
         })
         .collect::<Vec<(comp::Pet, comp::Body, comp::Stats)>>();
 
+    let mut stmt = connection.prepare_cached(
+        "
+        SELECT  companion_data
+        FROM    companion
+        WHERE   character_id = ?1",
+    )?;
+
+    let companion = stmt
+        .query_row([char_id.0], |row| row.get::<_, String>(0))
+        .optional()?
+        .and_then(
+            |companion_data| match convert_companion_from_database_json(&companion_data) {
+                Ok(companion) => Some(companion),
+                Err(e) => {
+                    warn!(
+                        "Failed to deserialize companion for character_id {}: {}",
+                        char_id.0, e
+                    );
+                    None
+                },
+            },
+        );
+
     let mut stmt = connection.prepare_cached(
         "
             SELECT  ability_sets
@@ -308,6 +333,7 @@ pub fn load_character_data(
             pets,
             active_abilities: convert_active_abilities_from_database(&ability_set_data),
             map_marker: char_map_marker,
+            companion,
         },
         UpdateCharacterMetadata {
             skill_set_persistence_load_error,
@@ -425,6 +451,7 @@ pub fn create_character(
         pets: _,
         active_abilities,
         map_marker,
+        companion: _,
     } = persisted_components;
 
     // Fetch new entity IDs for character, inventory, loadout, overflow items, and
@@ -735,6 +762,17 @@ pub fn delete_character(
     stmt.execute([&char_id.0])?;
     drop(stmt);
 
+    // Delete companion
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    companion
+        WHERE   character_id = ?1",
+    )?;
+
+    stmt.execute([&char_id.0])?;
+    drop(stmt);
+
     // Delete character
     let mut stmt = transaction.prepare_cached(
         "
@@ -929,6 +967,36 @@ fn get_pseudo_container_id(
     }
 }
 
+/// Stores the companion of the character, or removes it from the database if
+/// the character no longer has one.
+fn update_companion(
+    char_id: CharacterId,
+    companion: Option<Companion>,
+    transaction: &mut Transaction,
+) -> Result<(), PersistenceError> {
+    if let Some(companion) = companion {
+        let companion_data = convert_companion_to_database_json(&companion)?;
+        let mut stmt = transaction.prepare_cached(
+            "
+            REPLACE
+            INTO    companion (character_id,
+                               companion_data)
+            VALUES  (?1, ?2)",
+        )?;
+        stmt.execute([&char_id.0 as &dyn ToSql, &companion_data])?;
+    } else {
+        let mut stmt = transaction.prepare_cached(
+            "
+            DELETE
+            FROM    companion
+            WHERE   character_id = ?1",
+        )?;
+        stmt.execute([&char_id.0])?;
+    }
+
+    Ok(())
+}
+
 /// Stores new pets in the database, and removes pets from the database that the
 /// player no longer has. Currently there are no actual updates to pet data
 /// since we don't store any updatable data about pets in the database.
@@ -1069,11 +1137,14 @@ pub fn update(
     char_waypoint: Option<comp::Waypoint>,
     active_abilities: comp::ability::ActiveAbilities,
     map_marker: Option<comp::MapMarker>,
+    companion: Option<Companion>,
     transaction: &mut Transaction,
 ) -> Result<(), PersistenceError> {
     // Run pet persistence
     update_pets(char_id, pets, transaction)?;
 
+    update_companion(char_id, companion, transaction)?;
+
     let pseudo_containers = get_pseudo_containers(transaction, char_id)?;
     let mut upserts = Vec::new();
     // First, get all the entity IDs for any new items, and identify which
@@ -68,6 +68,47 @@ pub struct CharacterPosition {
     pub map_marker: Option<Vec2<i32>>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub enum CompanionOrder {
+    Follow,
+    Guard(Vec3<f32>),
+    Wait(Vec3<f32>),
+}
+
+impl From<common::rtsim::CompanionOrder> for CompanionOrder {
+    fn from(order: common::rtsim::CompanionOrder) -> Self {
+        match order {
+            common::rtsim::CompanionOrder::Follow => Self::Follow,
+            common::rtsim::CompanionOrder::Guard(wpos) => Self::Guard(wpos),
+            common::rtsim::CompanionOrder::Wait(wpos) => Self::Wait(wpos),
+        }
+    }
+}
+
+impl From<CompanionOrder> for common::rtsim::CompanionOrder {
+    fn from(order: CompanionOrder) -> Self {
+        match order {
+            CompanionOrder::Follow => Self::Follow,
+            CompanionOrder::Guard(wpos) => Self::Guard(wpos),
+            CompanionOrder::Wait(wpos) => Self::Wait(wpos),
+        }
+    }
+}
+
+/// A companion hired by the character, see [`common::rtsim::Companion`].
+#[derive(Serialize, Deserialize)]
+pub struct DatabaseCompanion {
+    pub uid: u64,
+    pub seed: u32,
+    pub body_variant: String,
+    pub body_data: String,
+    pub rank: u32,
+    pub exp: u32,
+    pub order: CompanionOrder,
+    pub contract_left: f64,
+    pub rtsim_tick: u64,
+}
+
 pub fn skill_group_to_db_string(skill_group: comp::skillset::SkillGroupKind) -> String {
     use comp::{item::tool::ToolKind, skillset::SkillGroupKind::*};
     let skill_group_string = match skill_group {
@@ -11,6 +11,7 @@ mod diesel_to_rusqlite;
 pub mod error;
 mod json_models;
 mod models;
+pub mod plugin_storage;
 
 use crate::persistence::character_updater::PetPersistenceData;
 use common::comp;
@@ -40,6 +41,7 @@ pub struct PersistedComponents {
     pub pets: Vec<PetPersistenceData>,
     pub active_abilities: comp::ActiveAbilities,
     pub map_marker: Option<comp::MapMarker>,
+    pub companion: Option<common::rtsim::Companion>,
 }
 
 pub type EditableComponents = (comp::Body,);
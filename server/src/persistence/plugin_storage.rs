@@ -0,0 +1,150 @@
+//! Persistent key value storage of plugins
+//!
+//! Plugins only read and write an in-memory copy of their entries, changes are
+//! written to the `plugin_storage` table in a background thread.
+
+use crate::persistence::{
+    ConnectionMode, DatabaseSettings, VelorenConnection, error::PersistenceError,
+    establish_connection,
+};
+use rusqlite::DropBehavior;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use tracing::{error, trace};
+
+/// Change to the storage of a plugin, `value` is `None` if the key was deleted
+pub struct PluginStorageChange {
+    pub plugin: String,
+    pub key: String,
+    pub value: Option<Vec<u8>>,
+}
+
+/// Stored entries of each plugin by plugin name
+pub type PluginStorageEntries = HashMap<String, HashMap<String, Vec<u8>>>;
+
+/// Load the stored entries of all plugins, this is done once at server
+/// startup
+pub fn load_plugin_storage(
+    settings: &DatabaseSettings,
+) -> Result<PluginStorageEntries, PersistenceError> {
+    let connection = establish_connection(settings, ConnectionMode::ReadOnly);
+    let mut stmt = connection.prepare_cached(
+        "
+        SELECT  plugin,
+                key,
+                value
+        FROM    plugin_storage",
+    )?;
+
+    let mut entries = PluginStorageEntries::new();
+    for row in stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Vec<u8>>(2)?,
+        ))
+    })? {
+        let (plugin, key, value) = row?;
+        entries.entry(plugin).or_default().insert(key, value);
+    }
+    Ok(entries)
+}
+
+/// A unidirectional messaging resource for saving plugin storage in a
+/// background thread.
+pub struct PluginStorageUpdater {
+    update_tx: Option<crossbeam_channel::Sender<Vec<PluginStorageChange>>>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl PluginStorageUpdater {
+    pub fn new(settings: Arc<RwLock<DatabaseSettings>>) -> Self {
+        let (update_tx, update_rx) = crossbeam_channel::unbounded::<Vec<PluginStorageChange>>();
+
+        let builder = std::thread::Builder::new().name("persistence_plugin_storage".into());
+        let handle = builder
+            .spawn(move || {
+                // Unwrap here is safe as there is no code that can panic when the write lock is
+                // taken that could cause the RwLock to become poisoned.
+                let mut conn =
+                    establish_connection(&settings.read().unwrap(), ConnectionMode::ReadWrite);
+                while let Ok(changes) = update_rx.recv() {
+                    conn.update_log_mode(&settings);
+                    if let Err(e) = execute_storage_update(changes, &mut conn) {
+                        error!(?e, "Error while persisting plugin storage");
+                    }
+                }
+            })
+            .unwrap();
+
+        Self {
+            update_tx: Some(update_tx),
+            handle: Some(handle),
+        }
+    }
+
+    /// Queue changes to be written to the database
+    pub fn update(&self, changes: Vec<PluginStorageChange>) {
+        if changes.is_empty() {
+            return;
+        }
+        if let Err(e) = self
+            .update_tx
+            .as_ref()
+            .expect("Tried to use updater after it was dropped")
+            .send(changes)
+        {
+            error!(?e, "Could not send plugin storage update");
+        }
+    }
+}
+
+impl Drop for PluginStorageUpdater {
+    fn drop(&mut self) {
+        drop(self.update_tx.take());
+        if let Err(e) = self.handle.take().unwrap().join() {
+            error!(?e, "Error from joining plugin storage update thread");
+        }
+    }
+}
+
+fn execute_storage_update(
+    changes: Vec<PluginStorageChange>,
+    connection: &mut VelorenConnection,
+) -> Result<(), PersistenceError> {
+    let mut transaction = connection.connection.transaction()?;
+    transaction.set_drop_behavior(DropBehavior::Rollback);
+    trace!("Transaction started for plugin storage update");
+
+    for change in changes {
+        match change.value {
+            Some(value) => {
+                let mut stmt = transaction.prepare_cached(
+                    "
+                    REPLACE
+                    INTO    plugin_storage (plugin,
+                                            key,
+                                            value)
+                    VALUES  (?1, ?2, ?3)",
+                )?;
+                stmt.execute(rusqlite::params![change.plugin, change.key, value])?;
+            },
+            None => {
+                let mut stmt = transaction.prepare_cached(
+                    "
+                    DELETE
+                    FROM    plugin_storage
+                    WHERE   plugin = ?1
+                    AND     key = ?2",
+                )?;
+                stmt.execute([change.plugin, change.key])?;
+            },
+        }
+    }
+
+    transaction.commit()?;
+    trace!("Commit for plugin storage update completed");
+    Ok(())
+}
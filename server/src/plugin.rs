@@ -0,0 +1,434 @@
+//! Server side handling of requests made by plugins.
+use crate::{
+    Server, StateExt,
+    client::Client,
+    metrics::PluginMetrics,
+    persistence::plugin_storage::{PluginStorageChange, PluginStorageUpdater},
+    sys::terrain::SpawnEntityData,
+};
+use common::{
+    assets::{AssetExt, Ron},
+    comp::{
+        self, Content, Inventory, Item,
+        buff::{Buff, BuffChange, BuffData, BuffSource, DestInfo},
+        inventory::item::{MaterialStatManifest, tool::AbilityMap},
+    },
+    event::{BuffEvent, CreateNpcEvent, EventBus},
+    generation::{EntityConfig, EntityInfo},
+    resources::{GameMode, Secs, Time},
+    terrain::{CoordinateConversions, TerrainChunkSize},
+    uid::{IdMaps, Uid},
+    vol::RectVolSize,
+};
+use common_net::msg::ServerGeneral;
+use common_state::plugin::{
+    EntityEvent, PLUGIN_BLOCK_ALTS, PluginAction, PluginMessage, PluginMgr,
+    memory_manager::{EcsWorld, with_ecs_world},
+};
+use specs::WorldExt;
+use std::fmt::Write;
+use tracing::{info, warn};
+use vek::*;
+
+/// Run `f` with the plugin manager and the view plugins get of the world
+fn with_plugin_mgr<T>(
+    server: &Server,
+    f: impl FnOnce(&mut PluginMgr, &EcsWorld, GameMode) -> T,
+) -> T {
+    let ecs = server.state.ecs();
+    let mode = *ecs.read_resource::<GameMode>();
+    with_ecs_world(ecs, |ecs_world| {
+        f(&mut ecs.write_resource::<PluginMgr>(), ecs_world, mode)
+    })
+}
+
+/// Reload plugins whose files were changed, and unload the ones whose files
+/// were removed, since the last tick.
+pub(crate) fn reload_changed_plugins(server: &mut Server) {
+    let changed = with_plugin_mgr(server, |plugin_mgr, ecs_world, mode| {
+        let changed = plugin_mgr.changed_files();
+        for path in changed.iter() {
+            if !path.exists() {
+                if plugin_mgr.unload_path(path) {
+                    info!(?path, "Plugin file removed, unloaded plugin");
+                }
+            } else if let Err(e) = plugin_mgr.reload(ecs_world, mode, path) {
+                warn!(
+                    ?e,
+                    ?path,
+                    "Failed to reload plugin, keeping the old version"
+                );
+            }
+        }
+        !changed.is_empty()
+    });
+    if changed {
+        send_plugin_commands(server);
+    }
+}
+
+/// Tell all clients about the commands plugins registered, after they were
+/// reloaded or unloaded
+fn send_plugin_commands(server: &Server) {
+    let commands = server.state.ecs().read_resource::<PluginMgr>().commands();
+    server
+        .state
+        .notify_players(ServerGeneral::PluginCommands(commands));
+}
+
+/// Report traps, overruns and disabled plugins
+pub(crate) fn update_plugin_metrics(server: &Server) {
+    let ecs = server.state.ecs();
+    let stats = ecs.write_resource::<PluginMgr>().fault_stats();
+    let metrics = ecs.read_resource::<PluginMetrics>();
+    // Drop the series of plugins that were unloaded since the last update
+    metrics.plugin_faults.reset();
+    for stat in stats.iter() {
+        metrics
+            .plugin_faults
+            .with_label_values(&[&stat.name])
+            .set(stat.faults as i64);
+    }
+    metrics
+        .plugins_disabled
+        .set(stats.iter().filter(|stat| stat.disabled).count() as i64);
+}
+
+/// Summary of the loaded plugins for the `/plugin list` command
+pub(crate) fn list_plugins(server: &Server) -> String {
+    let mut plugin_mgr = server.state.ecs().write_resource::<PluginMgr>();
+    let stats = plugin_mgr.fault_stats();
+    let mut list = String::from("Loaded plugins:");
+    for (plugin, stat) in plugin_mgr.plugins().zip(stats) {
+        let _ = write!(
+            list,
+            "\n{} ({} modules) from {:?}",
+            plugin.name(),
+            plugin.module_count(),
+            plugin.path(),
+        );
+        if stat.disabled {
+            let _ = write!(list, ", disabled after {} faults", stat.faults);
+        }
+    }
+    list
+}
+
+/// Reload a plugin by name for the `/plugin reload` command
+pub(crate) fn reload_plugin(server: &mut Server, name: &str) -> Result<String, Content> {
+    with_plugin_mgr(server, |plugin_mgr, ecs_world, mode| {
+        let path = plugin_mgr
+            .find_by_name(name)
+            .map(|plugin| plugin.path().to_path_buf())
+            .ok_or_else(|| Content::Plain(format!("No plugin named {name} is loaded")))?;
+        plugin_mgr
+            .reload(ecs_world, mode, &path)
+            .map(|()| format!("Reloaded plugin {name}"))
+            .map_err(|e| Content::Plain(format!("Failed to reload plugin {name}: {e:?}")))
+    })
+    .inspect(|_| send_plugin_commands(server))
+}
+
+/// Unload a plugin by name for the `/plugin unload` command
+pub(crate) fn unload_plugin(server: &mut Server, name: &str) -> Result<String, Content> {
+    if server
+        .state
+        .ecs()
+        .write_resource::<PluginMgr>()
+        .unload(name)
+    {
+        send_plugin_commands(server);
+        Ok(format!("Unloaded plugin {name}"))
+    } else {
+        Err(Content::Plain(format!("No plugin named {name} is loaded")))
+    }
+}
+
+/// Apply the world modifications plugins requested since the last tick.
+///
+/// The capabilities of each plugin have already been checked when the request
+/// was queued.
+pub(crate) fn apply_plugin_actions(server: &mut Server) {
+    let actions = server
+        .state
+        .ecs()
+        .write_resource::<PluginMgr>()
+        .take_actions();
+
+    for (plugin, action) in actions {
+        if let Err(error) = apply_action(server, action) {
+            warn!(?plugin, "Failed to apply plugin action: {error}");
+        }
+    }
+}
+
+/// Entities created during the current tick, which plugins are told about
+/// once the events of the tick have been handled. Entities are spawned from
+/// many places, and waiting lets them be fully set up first, for example with
+/// their pets tamed.
+#[derive(Default)]
+pub struct PendingSpawns(pub Vec<Uid>);
+
+/// Tell plugins about the entities that were spawned during the tick
+pub(crate) fn dispatch_spawns(server: &mut Server) {
+    let spawns = std::mem::take(&mut server.state.ecs().write_resource::<PendingSpawns>().0);
+    for uid in spawns {
+        server.state.plugin_entity_event(EntityEvent::Spawn(uid));
+    }
+}
+
+/// Hand changes plugins made to their storage to the database thread
+pub(crate) fn persist_plugin_storage(server: &mut Server) {
+    let ecs = server.state.ecs();
+    let changes = ecs
+        .write_resource::<PluginMgr>()
+        .take_storage_changes()
+        .into_iter()
+        .map(|(plugin, change)| PluginStorageChange {
+            plugin,
+            key: change.key,
+            value: change.value,
+        })
+        .collect();
+    ecs.read_resource::<PluginStorageUpdater>().update(changes);
+}
+
+/// Send the messages server plugins addressed to their client half
+pub(crate) fn send_plugin_messages(server: &mut Server) {
+    let ecs = server.state.ecs();
+    let messages = ecs.write_resource::<PluginMgr>().take_messages();
+    if messages.is_empty() {
+        return;
+    }
+    let id_maps = ecs.read_resource::<IdMaps>();
+    let clients = ecs.read_storage::<Client>();
+    for (plugin, message) in messages {
+        match message {
+            PluginMessage::ToClient {
+                player,
+                channel,
+                data,
+            } => {
+                if let Some(client) = id_maps
+                    .uid_entity(player)
+                    .and_then(|entity| clients.get(entity))
+                {
+                    client.send_fallible(ServerGeneral::PluginMessage {
+                        plugin,
+                        channel,
+                        data,
+                    });
+                }
+            },
+            // Only client side plugins talk to the server
+            PluginMessage::ToServer { .. } => {},
+        }
+    }
+}
+
+fn apply_action(server: &mut Server, action: PluginAction) -> Result<(), String> {
+    let ecs = server.state.ecs();
+    let find_entity = |uid| {
+        ecs.read_resource::<IdMaps>()
+            .uid_entity(uid)
+            .ok_or_else(|| format!("Entity {uid} no longer exists"))
+    };
+
+    match action {
+        PluginAction::GiveItem {
+            target,
+            item,
+            amount,
+        } => {
+            let target = find_entity(target)?;
+            let mut item = Item::new_from_asset(&item)
+                .map_err(|error| format!("Invalid item {item}: {error:?}"))?;
+            let mut inventories = ecs.write_storage::<Inventory>();
+            let mut inventory = inventories
+                .get_mut(target)
+                .ok_or_else(|| "Target has no inventory".to_string())?;
+            let given = if item.set_amount(amount).is_ok() {
+                inventory
+                    .push(item)
+                    .map_err(|_| "Inventory is full".to_string())?;
+                amount
+            } else {
+                // Each of these items takes a slot of its own, so no more are made
+                // than the inventory has room for
+                if amount as usize > inventory.capacity() {
+                    return Err(format!(
+                        "Can't give {amount} unstackable items to an inventory of {} slots",
+                        inventory.capacity()
+                    ));
+                }
+                let given = amount.min(inventory.free_slots() as u32);
+                let ability_map = ecs.read_resource::<AbilityMap>();
+                let msm = ecs.read_resource::<MaterialStatManifest>();
+                for _ in 0..given {
+                    inventory
+                        .push(item.duplicate(&ability_map, &msm))
+                        .map_err(|_| "Inventory is full".to_string())?;
+                }
+                given
+            };
+            drop(inventories);
+
+            if given > 0 {
+                let mut inventory_update = ecs.write_storage::<comp::InventoryUpdate>();
+                if let Some(update) = inventory_update.get_mut(target) {
+                    update.push(comp::InventoryUpdateEvent::Given);
+                } else {
+                    let _ = inventory_update.insert(
+                        target,
+                        comp::InventoryUpdate::new(comp::InventoryUpdateEvent::Given),
+                    );
+                }
+            }
+            if given < amount {
+                Err(format!("Inventory is full, gave {given} of {amount} items"))
+            } else {
+                Ok(())
+            }
+        },
+        PluginAction::Teleport { target, position } => {
+            let target = find_entity(target)?;
+            if !position.map(f32::is_finite).reduce_and() {
+                return Err(format!("Position {position} is not finite"));
+            }
+            // Keep the entity within the world, where there is terrain to load
+            let world_size = server.state.terrain().map_size_lg().chunks().as_::<f32>()
+                * TerrainChunkSize::RECT_SIZE.as_::<f32>();
+            let position = Vec3::new(
+                position.x.clamp(0.0, world_size.x - 1.0),
+                position.y.clamp(0.0, world_size.y - 1.0),
+                position
+                    .z
+                    .clamp(PLUGIN_BLOCK_ALTS.start as f32, PLUGIN_BLOCK_ALTS.end as f32),
+            );
+            server
+                .state
+                .position_mut(target, true, |pos| pos.0 = position)
+                .map_err(|error| format!("{error:?}"))
+        },
+        PluginAction::ApplyBuff {
+            target,
+            kind,
+            strength,
+            duration,
+        } => {
+            let target = find_entity(target)?;
+            let stats = ecs.read_storage::<comp::Stats>();
+            let masses = ecs.read_storage::<comp::Mass>();
+            let dest_info = DestInfo {
+                stats: stats.get(target),
+                mass: masses.get(target),
+            };
+            ecs.read_resource::<EventBus<BuffEvent>>()
+                .emit_now(BuffEvent {
+                    entity: target,
+                    buff_change: BuffChange::Add(Buff::new(
+                        kind,
+                        BuffData::new(strength, duration.map(|secs| Secs(secs as f64))),
+                        vec![],
+                        BuffSource::World,
+                        *ecs.read_resource::<Time>(),
+                        dest_info,
+                        None,
+                    )),
+                });
+            Ok(())
+        },
+        PluginAction::SetBlock { position, block } => {
+            // Edits of unloaded chunks would be lost, and would still be
+            // persisted
+            if server
+                .state
+                .terrain()
+                .get_key(position.wpos_to_cpos())
+                .is_none()
+            {
+                return Err(format!("Block {position} is not in a loaded chunk"));
+            }
+            server.state.set_block(position, block);
+            #[cfg(feature = "persistent_world")]
+            if let Some(terrain_persistence) = server
+                .state
+                .ecs()
+                .try_fetch_mut::<crate::TerrainPersistence>()
+                .as_mut()
+            {
+                terrain_persistence.set_block(position, block);
+            }
+            Ok(())
+        },
+        PluginAction::SpawnNpc {
+            entity_config,
+            position,
+        } => {
+            let config = Ron::<EntityConfig>::load(&entity_config)
+                .map_err(|error| format!("Invalid entity config {entity_config}: {error:?}"))?
+                .read();
+            let entity_info = EntityInfo::at(position).with_entity_config(
+                config.clone().into_inner(),
+                Some(&entity_config),
+                &mut rand::rng(),
+                None,
+            );
+            match SpawnEntityData::from_entity_info(entity_info) {
+                SpawnEntityData::Npc(data) => {
+                    let (npc_builder, _pos) = data.to_npc_builder();
+                    ecs.read_resource::<EventBus<CreateNpcEvent>>()
+                        .emit_now(CreateNpcEvent {
+                            pos: comp::Pos(position),
+                            ori: comp::Ori::default(),
+                            npc: npc_builder,
+                        });
+                    Ok(())
+                },
+                SpawnEntityData::Special(_, _) => {
+                    Err(format!("Special entity {entity_config} can't be spawned"))
+                },
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{
+        comp::PickupItem,
+        resources::ProgramTime,
+        terrain::{MapSizeLg, TerrainChunk},
+    };
+    use common_state::State;
+    use std::sync::Arc;
+    use vek::*;
+
+    #[test]
+    fn item_drops_are_announced_as_spawns() {
+        let mut state = State::server(
+            State::pools(GameMode::Server),
+            MapSizeLg::new(Vec2::new(1, 1)).unwrap(),
+            Arc::new(TerrainChunk::water(0)),
+            |_| {},
+            PluginMgr::default(),
+        );
+        state.ecs_mut().insert(PendingSpawns::default());
+
+        let item = Item::new_from_asset_expect("common.items.food.apple");
+        let entity = state
+            .create_item_drop(
+                comp::Pos(Vec3::zero()),
+                comp::Ori::default(),
+                comp::Vel(Vec3::zero()),
+                PickupItem::new(item, ProgramTime(0.0), false),
+                None,
+            )
+            .expect("There is nothing to merge the drop into");
+        let uid = state
+            .read_component_copied::<Uid>(entity)
+            .expect("Item drops are synced");
+        assert_eq!(state.ecs().read_resource::<PendingSpawns>().0, vec![uid]);
+    }
+}
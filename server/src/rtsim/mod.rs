@@ -1,12 +1,20 @@
 pub mod event;
+pub mod quest_log;
 pub mod rule;
+pub mod site_holders;
+pub mod soak;
 pub mod tick;
 
 use atomicwrites::{AtomicFile, OverwriteBehavior};
 use common::{
+    character::CharacterId,
     grid::Grid,
     mounting::VolumePos,
-    rtsim::{Actor, NpcId, RtSimEntity, TerrainResource, WorldSettings},
+    resources::Time,
+    rtsim::{
+        Actor, Companion, NpcId, Profession, QuestId, Role, RtSimEntity, TerrainResource,
+        WildlifeKind, WorldSettings,
+    },
     terrain::{CoordinateConversions, SpriteKind},
 };
 use common_ecs::{System, dispatch};
@@ -15,7 +23,12 @@ use crossbeam_channel::{Receiver, Sender, unbounded};
 use enum_map::EnumMap;
 use rtsim::{
     RtState,
-    data::{Data, ReadError, npc::SimulationMode},
+    data::{
+        Data, Npc, ReadError,
+        architect::TrackedPopulation,
+        npc::{Job, SimulationMode},
+        quest::{Quest, QuestKind},
+    },
     event::{OnDeath, OnHealthChange, OnHelped, OnMountVolume, OnSetup, OnTheft},
 };
 use specs::DispatcherBuilder;
@@ -73,8 +86,8 @@ impl RtSim {
                                     break 'load *data;
                                 }
                             },
-                            Err(ReadError::Load(err)) => {
-                                error!("Rtsim data failed to load: {}", err);
+                            Err(err @ (ReadError::Load(_) | ReadError::Migrate(_))) => {
+                                error!("Rtsim data failed to load: {:?}", err);
                                 info!("Old rtsim data will now be moved to a backup file");
                                 let mut i = 0;
                                 loop {
@@ -137,6 +150,25 @@ impl RtSim {
         Ok(this)
     }
 
+    /// Upgrade the rtsim data in `data_dir` to the current version without
+    /// starting the server, keeping the original file as a backup. Returns the
+    /// path of the backup.
+    pub fn migrate_file(data_dir: PathBuf) -> Result<PathBuf, String> {
+        let file_path = Self::get_file_path(data_dir);
+        let file = File::open(&file_path)
+            .map_err(|err| format!("Failed to open {}: {err}", file_path.display()))?;
+        let data = Data::from_reader(io::BufReader::new(file))
+            .map_err(|err| format!("Failed to migrate {}: {err:?}", file_path.display()))?;
+
+        let backup_path = file_path.with_extension("dat_backup");
+        fs::copy(&file_path, &backup_path)
+            .map_err(|err| format!("Failed to back up {}: {err}", file_path.display()))?;
+        AtomicFile::new(&file_path, OverwriteBehavior::AllowOverwrite)
+            .write(|file| data.write_to(io::BufWriter::new(file)))
+            .map_err(|err| format!("Failed to write {}: {err}", file_path.display()))?;
+        Ok(backup_path)
+    }
+
     fn get_file_path(mut data_dir: PathBuf) -> PathBuf {
         let mut path = std::env::var("VELOREN_RTSIM")
             .map(PathBuf::from)
@@ -148,6 +180,105 @@ impl RtSim {
         path
     }
 
+    /// Give up on a quest on behalf of a character. The arbiter of the quest
+    /// resolves it as failed the next time it gets simulated.
+    pub fn hook_abandon_quest(&self, character_id: CharacterId, quest_id: QuestId) {
+        if let Some(quest) = self.state.data().quests.get(quest_id)
+            && !quest.abandon(Actor::Character(character_id))
+        {
+            debug!(
+                ?quest_id,
+                "Character tried to abandon a quest it can't abandon"
+            );
+        }
+    }
+
+    /// The companion that a character has hired, if any, as it should be
+    /// persisted with the character.
+    pub fn companion_of(&self, character_id: CharacterId, time: Time) -> Option<Companion> {
+        let data = self.state.data();
+        let hirer = Actor::Character(character_id);
+        data.quests
+            .related_to(hirer)
+            .filter_map(|quest_id| data.quests.get(quest_id))
+            .find_map(|quest| match quest.kind {
+                QuestKind::Hire {
+                    companion: Actor::Npc(npc_id),
+                    hirer: h,
+                } if h == hirer => data.npcs.get(npc_id),
+                _ => None,
+            })
+            .filter(|npc| !npc.is_dead())
+            .and_then(|npc| {
+                let (_, expires) = npc.hired().filter(|(by, _)| *by == hirer)?;
+                Some(Companion {
+                    uid: npc.uid,
+                    seed: npc.seed,
+                    body: npc.body,
+                    rank: match npc.profession() {
+                        Some(Profession::Adventurer(rank)) => rank,
+                        _ => 0,
+                    },
+                    exp: npc.exp,
+                    order: npc.order,
+                    contract_left: (expires.0 - time.0).max(0.0),
+                    rtsim_tick: data.tick,
+                })
+            })
+    }
+
+    /// Bring the companion of a character that has just logged in to them. If
+    /// rtsim has lost track of the companion, because its data was reset since
+    /// the character last played, the companion gets restored from what was
+    /// kept with the character.
+    pub fn hook_character_login(
+        &mut self,
+        character_id: CharacterId,
+        companion: Option<Companion>,
+        wpos: Vec3<f32>,
+        time: Time,
+    ) {
+        let Some(companion) = companion else {
+            return;
+        };
+        let hirer = Actor::Character(character_id);
+        let data = self.state.get_data_mut();
+
+        if let Some(npc) = data.npcs.values_mut().find(|npc| npc.uid == companion.uid) {
+            // Companions that are far away catch up with their hirer
+            if npc.hired().is_some_and(|(by, _)| by == hirer)
+                && matches!(npc.mode, SimulationMode::Simulated)
+                && matches!(companion.order, common::rtsim::CompanionOrder::Follow)
+            {
+                npc.wpos = wpos;
+            }
+        } else if data.tick < companion.rtsim_tick {
+            let home = data
+                .sites
+                .iter()
+                .min_by_key(|(_, site)| site.wpos.as_().distance_squared(wpos.xy()) as i64)
+                .map(|(site_id, _)| site_id);
+            let role = Role::Civilised(Some(Profession::Adventurer(companion.rank)));
+            let mut npc = Npc::new(companion.seed, wpos, companion.body, role).with_home(home);
+            npc.exp = companion.exp;
+            npc.order = companion.order;
+            let expires = Time(time.0 + companion.contract_left);
+            npc.job = Some(Job::Hired(hirer, expires));
+            let npc_id = data.spawn_npc(npc);
+            data.architect
+                .population
+                .add(TrackedPopulation::Adventurers, 1);
+
+            // The companion was paid when first hired, so the new contract has no deposit
+            let quest_id = data.quests.register();
+            data.quests.create(
+                quest_id,
+                Quest::hire(Actor::Npc(npc_id), hirer).with_timeout(expires),
+            );
+            info!(?character_id, ?npc_id, "Restored companion of character");
+        }
+    }
+
     pub fn hook_character_mount_volume(
         &mut self,
         world: &World,
@@ -296,6 +427,16 @@ impl RtSim {
         );
     }
 
+    /// Deplete the wildlife of the chunk in which an animal of the given kind
+    /// was hunted down.
+    pub fn hook_wildlife_death(&mut self, wpos: Vec3<f32>, kind: WildlifeKind) {
+        self.state.get_data_mut().nature.hunt(
+            wpos.xy().as_().wpos_to_cpos(),
+            kind,
+            rtsim::rule::wildlife::KILL_DEPLETION,
+        );
+    }
+
     pub fn hook_rtsim_actor_helped(
         &mut self,
         world: &World,
@@ -349,6 +490,17 @@ impl RtSim {
             .unwrap_or_default()
     }
 
+    /// The wildlife populations of a chunk, relative to what it naturally
+    /// supports.
+    pub fn get_chunk_wildlife(&self, key: Vec2<i32>) -> EnumMap<WildlifeKind, f32> {
+        self.state
+            .data()
+            .nature
+            .chunk_wildlife(key)
+            .copied()
+            .unwrap_or_else(|| EnumMap::from_fn(|_| 1.0))
+    }
+
     pub fn state(&self) -> &RtState { &self.state }
 
     pub fn set_should_purge(&mut self, should_purge: bool) {
@@ -380,4 +532,6 @@ pub struct LoadedChunkState {
 
 pub fn add_server_systems(dispatch_builder: &mut DispatcherBuilder) {
     dispatch::<tick::Sys>(dispatch_builder, &[&common_systems::phys::Sys::sys_name()]);
+    dispatch::<quest_log::Sys>(dispatch_builder, &[&tick::Sys::sys_name()]);
+    dispatch::<site_holders::Sys>(dispatch_builder, &[&tick::Sys::sys_name()]);
 }
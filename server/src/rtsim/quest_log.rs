@@ -0,0 +1,277 @@
+//! Keeps the quest logs of players in sync with the rtsim quests their
+//! characters take part in.
+use super::RtSim;
+use crate::{Tick, client::Client};
+use common::{
+    character::CharacterId,
+    comp::{Content, Presence},
+    map::Marker,
+    rtsim::{Actor, QuestId, QuestInfo, SiteId},
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::ServerGeneral;
+use rtsim::data::{
+    Data,
+    quest::{Quest, QuestKind},
+};
+use specs::{Component, Entities, Join, Read, ReadExpect, ReadStorage, WriteStorage};
+use std::sync::Arc;
+use vek::*;
+use world::{IndexOwned, IndexRef, World};
+
+/// How often quest logs are checked for changes, in ticks
+const QUEST_LOG_SYNC_INTERVAL: u64 = 30;
+
+/// The quests last sent to the client of a character
+pub struct QuestLog {
+    character_id: CharacterId,
+    sent: Vec<QuestInfo>,
+}
+
+impl Component for QuestLog {
+    type Storage = specs::HashMapStorage<Self>;
+}
+
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, Tick>,
+        ReadExpect<'a, RtSim>,
+        ReadExpect<'a, Arc<World>>,
+        ReadExpect<'a, IndexOwned>,
+        ReadStorage<'a, Presence>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, QuestLog>,
+    );
+
+    const NAME: &'static str = "rtsim::quest_log";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (entities, tick, rtsim, world, index, presences, clients, mut quest_logs): Self::SystemData,
+    ) {
+        if tick.0 % QUEST_LOG_SYNC_INTERVAL != 0 {
+            return;
+        }
+
+        let data = rtsim.state().data();
+        for (entity, presence, client) in (&entities, &presences, &clients).join() {
+            let Some(character_id) = presence.kind.character_id() else {
+                continue;
+            };
+            let Ok(entry) = quest_logs.entry(entity) else {
+                continue;
+            };
+            let log = entry.or_insert_with(|| QuestLog {
+                character_id,
+                sent: Vec::new(),
+            });
+            if log.character_id != character_id {
+                log.character_id = character_id;
+                log.sent.clear();
+            }
+
+            let actor = Actor::Character(character_id);
+            let mut ids = data.quests.related_to(actor).collect::<Vec<_>>();
+            // Quests that were resolved since the last update get sent once more, so that
+            // the player learns how they ended
+            for info in &log.sent {
+                if info.resolution.is_none() && !ids.contains(&info.id) {
+                    ids.push(info.id);
+                }
+            }
+            ids.sort_by_key(|id| id.0);
+
+            let quests = ids
+                .into_iter()
+                .filter_map(|id| {
+                    let quest = data.quests.get(id)?;
+                    Some(quest_info(&data, &world, index.as_index_ref(), id, quest))
+                })
+                .collect::<Vec<_>>();
+
+            if quests != log.sent {
+                client.send_fallible(ServerGeneral::QuestLog(quests.clone()));
+                log.sent = quests;
+            }
+        }
+    }
+}
+
+fn site_name(data: &Data, index: IndexRef, site_id: SiteId) -> Option<String> {
+    let world_site = data.sites.get(site_id)?.world_site?;
+    Some(index.sites.get(world_site).name()?.to_string())
+}
+
+fn actor_name(data: &Data, actor: Actor) -> Option<String> {
+    data.npcs.get(actor.npc()?)?.get_name()
+}
+
+fn actor_wpos(data: &Data, actor: Actor) -> Option<Vec2<f32>> {
+    Some(data.npcs.get(actor.npc()?)?.wpos.xy())
+}
+
+/// Describe a quest for the quest log of a player taking part in it
+fn quest_info(
+    data: &Data,
+    world: &World,
+    index: IndexRef,
+    id: QuestId,
+    quest: &Quest,
+) -> QuestInfo {
+    let contact = actor_name(data, quest.arbiter);
+    let unknown = || "<unknown>".to_string();
+    // Marks where to report back to the arbiter once the objective is done
+    let contact_marker = || {
+        actor_wpos(data, quest.arbiter).map(|wpos| {
+            Marker::at(wpos)
+                .with_id(quest.arbiter)
+                .with_label(
+                    Content::localized("hud-map-character-label")
+                        .with_arg("name", contact.clone().unwrap_or_else(unknown)),
+                )
+                .with_quest_flag(true)
+        })
+    };
+
+    let (objective, target_site, marker) = match &quest.kind {
+        QuestKind::Escort { escortee, to, .. } => {
+            let place = site_name(data, index, *to);
+            let name = actor_name(data, *escortee).unwrap_or_else(unknown);
+            let site = data.sites.get(*to);
+            let marker = site.map(|site| {
+                Marker::at(site.wpos.as_())
+                    .with_id(id)
+                    .with_label(
+                        Content::localized("hud-map-escort-label")
+                            .with_arg("name", name.clone())
+                            .with_arg("place", place.clone().unwrap_or_else(unknown)),
+                    )
+                    .with_quest_flag(true)
+            });
+            (
+                Content::localized("hud-quest-objective-escort")
+                    .with_arg("name", name)
+                    .with_arg("place", place.clone().unwrap_or_else(unknown)),
+                place,
+                marker,
+            )
+        },
+        QuestKind::Slay { target, .. } => {
+            match target.npc().and_then(|npc_id| data.npcs.get(npc_id)) {
+                Some(target_npc) => (
+                    Content::localized("hud-quest-objective-slay")
+                        .with_arg("body", target_npc.body.localize_npc()),
+                    None,
+                    Some(
+                        Marker::at(target_npc.wpos.xy())
+                            .with_id(*target)
+                            .with_label(
+                                Content::localized("hud-map-creature-label")
+                                    .with_arg("body", target_npc.body.localize_npc()),
+                            )
+                            .with_quest_flag(true),
+                    ),
+                ),
+                // The target is dead, only the reward is left to claim
+                None => (
+                    Content::localized("hud-quest-objective-report")
+                        .with_arg("name", contact.clone().unwrap_or_else(unknown)),
+                    None,
+                    contact_marker(),
+                ),
+            }
+        },
+        QuestKind::Fetch { item, amount, .. } => (
+            Content::localized("hud-quest-objective-fetch")
+                .with_arg("amount", amount.ceil() as u64)
+                .with_arg("item", item.localize())
+                .with_arg("name", contact.clone().unwrap_or_else(unknown)),
+            None,
+            contact_marker(),
+        ),
+        QuestKind::Deliver {
+            recipient,
+            to,
+            item,
+            amount,
+            ..
+        } => {
+            let place = site_name(data, index, *to);
+            let name = actor_name(data, *recipient).unwrap_or_else(unknown);
+            let marker = actor_wpos(data, *recipient).map(|wpos| {
+                Marker::at(wpos)
+                    .with_id(*recipient)
+                    .with_label(
+                        Content::localized("hud-map-deliver-label")
+                            .with_arg("name", name.clone())
+                            .with_arg("place", place.clone().unwrap_or_else(unknown)),
+                    )
+                    .with_quest_flag(true)
+            });
+            (
+                Content::localized("hud-quest-objective-deliver")
+                    .with_arg("amount", amount.ceil() as u64)
+                    .with_arg("item", item.localize())
+                    .with_arg("name", name)
+                    .with_arg("place", place.clone().unwrap_or_else(unknown)),
+                place,
+                marker,
+            )
+        },
+        QuestKind::Explore { .. } if quest.is_reached() => (
+            Content::localized("hud-quest-objective-report")
+                .with_arg("name", contact.clone().unwrap_or_else(unknown)),
+            None,
+            contact_marker(),
+        ),
+        QuestKind::Explore { wpos, .. } => {
+            let place = world
+                .civs()
+                .pois
+                .values()
+                .find(|poi| poi.loc == *wpos)
+                .map_or_else(unknown, |poi| poi.name.clone());
+            (
+                Content::localized("hud-quest-objective-explore").with_arg("place", place.clone()),
+                None,
+                Some(
+                    Marker::at(wpos.as_())
+                        .with_id(id)
+                        .with_label(
+                            Content::localized("hud-map-explore-label").with_arg("place", place),
+                        )
+                        .with_quest_flag(true),
+                ),
+            )
+        },
+        QuestKind::Hire { .. } => (
+            Content::localized("hud-quest-objective-hire")
+                .with_arg("name", contact.clone().unwrap_or_else(unknown)),
+            None,
+            contact_marker(),
+        ),
+    };
+
+    QuestInfo {
+        id,
+        contact,
+        objective,
+        target_site,
+        marker,
+        // The deposit of a contract is the pay of the companion, not a reward
+        reward: quest
+            .deposit()
+            .filter(|_| !matches!(quest.kind, QuestKind::Hire { .. }))
+            .map(|(item, amount)| (item, amount.floor() as u32)),
+        timeout: quest.timeout,
+        // Abandoned quests are as good as failed, even if the arbiter hasn't noticed yet
+        resolution: quest
+            .resolution()
+            .or_else(|| quest.is_abandoned().then_some(false)),
+    }
+}
@@ -0,0 +1,89 @@
+//! Lets players know which faction holds each site, so that sites changing
+//! hands are reflected on their world map.
+use super::RtSim;
+use crate::{Tick, client::Client};
+use common::{
+    comp::{Content, Presence},
+    trade::SiteId,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::ServerGeneral;
+use rtsim::data::Data;
+use specs::{Component, Entities, Join, NullStorage, Read, ReadExpect, ReadStorage, WriteStorage};
+use world::{IndexOwned, IndexRef};
+
+/// How often site holders are checked for changes, in ticks
+const SITE_HOLDERS_SYNC_INTERVAL: u64 = 300;
+
+/// Marks clients that were sent the current holders of sites
+#[derive(Default)]
+pub struct KnowsSiteHolders;
+
+impl Component for KnowsSiteHolders {
+    type Storage = NullStorage<Self>;
+}
+
+#[derive(Default)]
+pub struct Sys {
+    holders: Vec<(SiteId, Content)>,
+}
+
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, Tick>,
+        ReadExpect<'a, RtSim>,
+        ReadExpect<'a, IndexOwned>,
+        ReadStorage<'a, Presence>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, KnowsSiteHolders>,
+    );
+
+    const NAME: &'static str = "rtsim::site_holders";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        job: &mut Job<Self>,
+        (entities, tick, rtsim, index, presences, clients, mut knows): Self::SystemData,
+    ) {
+        if tick.0 % SITE_HOLDERS_SYNC_INTERVAL != 0 {
+            return;
+        }
+
+        let holders = site_holders(&rtsim.state().data(), index.as_index_ref());
+        if holders != job.own.holders {
+            job.own.holders = holders;
+            knows.clear();
+        }
+
+        let mut informed = Vec::new();
+        for (entity, _, client, ()) in (&entities, &presences, &clients, !&knows).join() {
+            client.send_fallible(ServerGeneral::SiteHolders(job.own.holders.clone()));
+            informed.push(entity);
+        }
+        for entity in informed {
+            let _ = knows.insert(entity, KnowsSiteHolders);
+        }
+    }
+}
+
+/// Describe who holds the sites that aren't the seat of their faction
+fn site_holders(data: &Data, index: IndexRef) -> Vec<(SiteId, Content)> {
+    let site_name =
+        |site: &rtsim::data::Site| Some(index.sites.get(site.world_site?).name()?.to_string());
+    data.sites
+        .iter()
+        .filter_map(|(site_id, site)| {
+            let seat = data.factions.get(site.faction?)?.seat?;
+            if seat == site_id {
+                return None;
+            }
+            let seat_name = site_name(data.sites.get(seat)?)?;
+            Some((
+                site.world_site?.id(),
+                Content::localized_with_args("hud-map-site-held_by", [("site", seat_name)]),
+            ))
+        })
+        .collect()
+}
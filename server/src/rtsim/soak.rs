@@ -0,0 +1,482 @@
+//! Headless soak tests of rtsim.
+//!
+//! These run the simulation for many in-game days as fast as possible and
+//! without any clients, such that every NPC is simulated, and sample statistics
+//! along the way. This makes it possible to spot long-term problems, such as
+//! populations collapsing, the architect spawning without bound or airships
+//! drifting away from their routes, without running a real server for hours.
+
+use super::RtSim;
+use common::{
+    comp::{self, gizmos::RtsimGizmos},
+    resources::{Time, TimeOfDay},
+    rtsim::{Actor, Profession, QuestId, Role},
+    shared_server_config::ServerConstants,
+    uid::IdMaps,
+    weather::WeatherGrid,
+};
+use rtsim::{
+    RtState, Rule, RuleError,
+    ai::NpcSystemData,
+    data::{Data, Npc, Relation},
+    event::OnDeath,
+};
+use serde::Serialize;
+use specs::{Read, ReadExpect, ReadStorage, WriteExpect, WriteStorage};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    sync::Mutex,
+};
+use tracing::info;
+use world::{IndexRef, World};
+
+const HOUR: f64 = 60.0 * 60.0;
+const DAY: f64 = HOUR * 24.0;
+
+#[derive(Copy, Clone, Debug)]
+pub struct SoakParams {
+    /// How many in-game days to simulate.
+    pub days: f64,
+    /// The real time, in seconds, that passes with every tick.
+    pub dt: f32,
+    /// How many in-game hours pass between samples.
+    pub sample_hours: f64,
+}
+
+/// The state of rtsim at some point during a soak test. Counts of events, such
+/// as deaths and quests, are totals since the start of the test.
+#[derive(Clone, Debug, Serialize)]
+pub struct SoakSample {
+    pub day: f64,
+    pub population: usize,
+    /// The living NPCs by profession, or by role for those without one.
+    pub groups: BTreeMap<&'static str, usize>,
+    /// The population that the architect keeps track of, and the population
+    /// that it is trying to reach.
+    pub architect_population: u32,
+    pub architect_wanted_population: u32,
+    /// Deaths that the architect has yet to respawn.
+    pub pending_respawns: usize,
+    /// The deaths so far, by what killed them.
+    pub deaths: BTreeMap<&'static str, u32>,
+    pub quests_created: usize,
+    pub quests_succeeded: usize,
+    pub quests_failed: usize,
+    pub factions: usize,
+    pub sites_held: usize,
+    pub wars: usize,
+    pub alliances: usize,
+    pub raids: usize,
+    /// Airships that are assigned to a route.
+    pub airships: usize,
+    /// How far the airship furthest from any dock is from its nearest dock.
+    pub airship_max_dock_distance: f32,
+}
+
+/// Counts the deaths that happen during a soak test, and remembers the quests
+/// that existed before it such that only the ones created or resolved during
+/// the test are counted.
+#[derive(Default)]
+struct SoakStats {
+    deaths: BTreeMap<&'static str, u32>,
+    initial_quests: HashMap<QuestId, Option<bool>>,
+}
+
+impl Rule for SoakStats {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnDeath>(|ctx| {
+            let data = ctx.state.data();
+            let cause = match ctx.event.killer {
+                None => "unknown",
+                Some(Actor::Character(_)) => "player",
+                Some(Actor::Npc(killer)) => data.npcs.get(killer).map_or("unknown", group),
+            };
+            *ctx.rule.deaths.entry(cause).or_default() += 1;
+        });
+
+        Ok(Self {
+            deaths: BTreeMap::new(),
+            initial_quests: rtstate
+                .data()
+                .quests
+                .iter()
+                .map(|(id, quest)| (id, quest.resolution()))
+                .collect(),
+        })
+    }
+}
+
+/// The group that an NPC counts toward in the statistics.
+fn group(npc: &Npc) -> &'static str {
+    match &npc.role {
+        Role::Civilised(Some(profession)) => match profession {
+            Profession::Farmer => "farmer",
+            Profession::Hunter => "hunter",
+            Profession::Merchant => "merchant",
+            Profession::Guard => "guard",
+            Profession::Adventurer(_) => "adventurer",
+            Profession::Blacksmith => "blacksmith",
+            Profession::Chef => "chef",
+            Profession::Alchemist => "alchemist",
+            Profession::Pirate(_) => "pirate",
+            Profession::Cultist => "cultist",
+            Profession::Herbalist => "herbalist",
+            Profession::Captain => "captain",
+        },
+        Role::Civilised(None) => "civilian",
+        Role::Wild => "wild",
+        Role::Monster => "monster",
+        Role::Vehicle => "vehicle",
+    }
+}
+
+fn sample(data: &Data, stats: &SoakStats, world: &World, day: f64) -> SoakSample {
+    let living = data.npcs.values().filter(|npc| !npc.is_dead());
+    let mut groups = BTreeMap::new();
+    for npc in living.clone() {
+        *groups.entry(group(npc)).or_default() += 1;
+    }
+
+    let quests_created = data
+        .quests
+        .iter()
+        .filter(|(id, _)| !stats.initial_quests.contains_key(id))
+        .count();
+    // Quests that were resolved during the test, by whether they succeeded
+    let resolved = data.quests.iter().filter_map(|(id, quest)| {
+        quest
+            .resolution()
+            .filter(|_| stats.initial_quests.get(&id).is_none_or(Option::is_none))
+    });
+
+    let relations = data
+        .factions
+        .values()
+        .flat_map(|faction| faction.relations.values());
+    let count_relations = |relation: Relation| {
+        // Relations are symmetric, so every pair is counted twice
+        relations.clone().filter(|r| **r == relation).count() / 2
+    };
+
+    let docks = &world.civs().airships.airship_docks;
+    let pilots = data
+        .airship_sim
+        .assigned_routes
+        .keys()
+        .filter_map(|pilot| data.npcs.get(*pilot))
+        .collect::<Vec<_>>();
+    let airship_max_dock_distance = pilots
+        .iter()
+        .filter_map(|pilot| {
+            docks
+                .iter()
+                .map(|dock| dock.center.distance(pilot.wpos.xy()))
+                .min_by(f32::total_cmp)
+        })
+        .fold(0.0, f32::max);
+
+    SoakSample {
+        day,
+        population: living.count(),
+        groups,
+        architect_population: data.architect.population.total(),
+        architect_wanted_population: data.architect.wanted_population.total(),
+        pending_respawns: data.architect.deaths.len(),
+        deaths: stats.deaths.clone(),
+        quests_created,
+        quests_succeeded: resolved.clone().filter(|succeeded| *succeeded).count(),
+        quests_failed: resolved.filter(|succeeded| !*succeeded).count(),
+        factions: data.factions.len(),
+        sites_held: data
+            .sites
+            .values()
+            .filter(|site| site.faction.is_some())
+            .count(),
+        wars: count_relations(Relation::Hostile),
+        alliances: count_relations(Relation::Allied),
+        raids: data
+            .factions
+            .values()
+            .filter(|faction| faction.raid.is_some())
+            .count(),
+        airships: pilots.len(),
+        airship_max_dock_distance,
+    }
+}
+
+/// Run rtsim for the given number of in-game days, starting from the state of
+/// the server, and sample statistics along the way. The soaked state is saved
+/// next to the rtsim data of the server, rather than over it.
+pub fn run(
+    ecs: &specs::World,
+    world: &World,
+    index: IndexRef,
+    params: &SoakParams,
+) -> Vec<SoakSample> {
+    let mut rtsim = ecs.write_resource::<RtSim>();
+    rtsim.file_path.set_extension("soak_dat");
+    rtsim.save_thread = None;
+    rtsim.state.start_rule::<SoakStats>();
+
+    let day_cycle_coefficient = ecs.read_resource::<ServerConstants>().day_cycle_coefficient;
+    let mut time_of_day = *ecs.read_resource::<TimeOfDay>();
+    let mut time = *ecs.read_resource::<Time>();
+    let (
+        positions,
+        id_maps,
+        server_constants,
+        weather_grid,
+        rtsim_gizmos,
+        ability_map,
+        msm,
+        inventories,
+    ) = ecs.system_data::<(
+        ReadStorage<comp::Pos>,
+        Read<IdMaps>,
+        ReadExpect<ServerConstants>,
+        ReadExpect<WeatherGrid>,
+        WriteExpect<RtsimGizmos>,
+        ReadExpect<comp::tool::AbilityMap>,
+        ReadExpect<comp::item::MaterialStatManifest>,
+        WriteStorage<comp::Inventory>,
+    )>();
+    let mut system_data = NpcSystemData {
+        positions,
+        id_maps,
+        server_constants,
+        weather_grid,
+        rtsim_gizmos,
+        ability_map,
+        msm,
+        inventories: Mutex::new(inventories),
+    };
+
+    let start = time_of_day.0;
+    let mut next_sample = start;
+    let mut samples = Vec::new();
+    loop {
+        let day = (time_of_day.0 - start) / DAY;
+        if time_of_day.0 >= next_sample {
+            let stats = rtsim.state.rule::<SoakStats>();
+            samples.push(sample(&rtsim.state.data(), &stats, world, day));
+            info!("Soaked rtsim for {day:.2} of {} days", params.days);
+            next_sample += params.sample_hours * HOUR;
+        }
+        if day >= params.days {
+            break;
+        }
+
+        time_of_day.0 += params.dt as f64 * day_cycle_coefficient;
+        time.0 += params.dt as f64;
+        rtsim
+            .state
+            .tick(&mut system_data, world, index, time_of_day, time, params.dt);
+    }
+
+    samples
+}
+
+/// Write the samples as CSV, with one row per sample and one column per group
+/// of NPCs and cause of death that appears in any of the samples.
+pub fn write_csv(samples: &[SoakSample], mut out: impl io::Write) -> io::Result<()> {
+    let mut groups = samples
+        .iter()
+        .flat_map(|sample| sample.groups.keys().copied())
+        .collect::<Vec<_>>();
+    groups.sort_unstable();
+    groups.dedup();
+    let mut causes = samples
+        .iter()
+        .flat_map(|sample| sample.deaths.keys().copied())
+        .collect::<Vec<_>>();
+    causes.sort_unstable();
+    causes.dedup();
+
+    write!(
+        out,
+        "day,population,architect_population,architect_wanted_population,pending_respawns,\
+         quests_created,quests_succeeded,quests_failed,factions,sites_held,wars,alliances,raids,\
+         airships,airship_max_dock_distance"
+    )?;
+    for group in &groups {
+        write!(out, ",population_{group}")?;
+    }
+    for cause in &causes {
+        write!(out, ",deaths_by_{cause}")?;
+    }
+    writeln!(out)?;
+
+    for s in samples {
+        write!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            s.day,
+            s.population,
+            s.architect_population,
+            s.architect_wanted_population,
+            s.pending_respawns,
+            s.quests_created,
+            s.quests_succeeded,
+            s.quests_failed,
+            s.factions,
+            s.sites_held,
+            s.wars,
+            s.alliances,
+            s.raids,
+            s.airships,
+            s.airship_max_dock_distance,
+        )?;
+        for group in &groups {
+            write!(out, ",{}", s.groups.get(group).copied().unwrap_or(0))?;
+        }
+        for cause in &causes {
+            write!(out, ",{}", s.deaths.get(cause).copied().unwrap_or(0))?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Write the samples as a JSON array.
+pub fn write_json(samples: &[SoakSample], out: impl io::Write) -> io::Result<()> {
+    serde_json::to_writer_pretty(out, samples).map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::comp::humanoid;
+    use rtsim::data::{Quest, nature::Nature};
+    use vek::{Vec2, Vec3};
+
+    fn npc(profession: Option<Profession>) -> Npc {
+        Npc::new(
+            0,
+            Vec3::zero(),
+            comp::Body::Humanoid(humanoid::Body::random()),
+            Role::Civilised(profession),
+        )
+    }
+
+    #[test]
+    fn sample_counts() {
+        let (world, _) = World::empty();
+        let mut data = Data {
+            version: rtsim::data::CURRENT_VERSION,
+            nature: Nature::generate(&world),
+            npcs: Default::default(),
+            sites: Default::default(),
+            factions: Default::default(),
+            reports: Default::default(),
+            architect: Default::default(),
+            quests: Default::default(),
+            tick: 0,
+            time_of_day: TimeOfDay(0.0),
+            should_purge: false,
+            airship_sim: Default::default(),
+        };
+        let guard = data.spawn_npc(npc(Some(Profession::Guard)));
+        data.spawn_npc(npc(Some(Profession::Guard)));
+        data.spawn_npc(npc(None));
+        let mut dead = npc(Some(Profession::Farmer));
+        dead.health_fraction = 0.0;
+        data.spawn_npc(dead);
+
+        let quest = |data: &mut Data| {
+            let id = data.quests.register();
+            data.quests
+                .create(id, Quest::explore(guard.into(), guard.into(), Vec2::zero()));
+            id
+        };
+        // Quests from before the test only count once they are resolved during it
+        let old_resolved = quest(&mut data);
+        let old_succeeded = quest(&mut data);
+        quest(&mut data);
+        data.quests.get(old_resolved).unwrap().resolve(guard, false);
+        let initial_quests = data
+            .quests
+            .iter()
+            .map(|(id, quest)| (id, quest.resolution()))
+            .collect();
+        data.quests.get(old_succeeded).unwrap().resolve(guard, true);
+
+        let succeeded = quest(&mut data);
+        let failed = quest(&mut data);
+        quest(&mut data);
+        data.quests.get(succeeded).unwrap().resolve(guard, true);
+        data.quests.get(failed).unwrap().resolve(guard, false);
+
+        let stats = SoakStats {
+            deaths: BTreeMap::from([("farmer", 2), ("player", 1)]),
+            initial_quests,
+        };
+        let sample = sample(&data, &stats, &world, 1.5);
+
+        assert_eq!(sample.day, 1.5);
+        // The dead farmer doesn't count toward the population
+        assert_eq!(sample.population, 3);
+        assert_eq!(
+            sample.groups,
+            BTreeMap::from([("civilian", 1), ("guard", 2)])
+        );
+        assert_eq!(sample.deaths, stats.deaths);
+        assert_eq!(sample.quests_created, 3);
+        assert_eq!(sample.quests_succeeded, 2);
+        assert_eq!(sample.quests_failed, 1);
+        assert_eq!(sample.airships, 0);
+    }
+
+    fn empty_sample(day: f64) -> SoakSample {
+        SoakSample {
+            day,
+            population: 0,
+            groups: BTreeMap::new(),
+            architect_population: 0,
+            architect_wanted_population: 0,
+            pending_respawns: 0,
+            deaths: BTreeMap::new(),
+            quests_created: 0,
+            quests_succeeded: 0,
+            quests_failed: 0,
+            factions: 0,
+            sites_held: 0,
+            wars: 0,
+            alliances: 0,
+            raids: 0,
+            airships: 0,
+            airship_max_dock_distance: 0.0,
+        }
+    }
+
+    #[test]
+    fn csv_has_a_column_for_every_group_and_cause() {
+        let samples = [
+            SoakSample {
+                population: 3,
+                groups: BTreeMap::from([("guard", 2), ("farmer", 1)]),
+                ..empty_sample(0.0)
+            },
+            SoakSample {
+                population: 2,
+                groups: BTreeMap::from([("guard", 2)]),
+                deaths: BTreeMap::from([("wild", 1)]),
+                quests_created: 4,
+                ..empty_sample(0.5)
+            },
+        ];
+        let mut out = Vec::new();
+        write_csv(&samples, &mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines, [
+            "day,population,architect_population,architect_wanted_population,pending_respawns,\
+             quests_created,quests_succeeded,quests_failed,factions,sites_held,wars,alliances,\
+             raids,airships,airship_max_dock_distance,population_farmer,population_guard,\
+             deaths_by_wild",
+            "0,3,0,0,0,0,0,0,0,0,0,0,0,0,0,1,2,0",
+            "0.5,2,0,0,0,4,0,0,0,0,0,0,0,0,0,0,2,1",
+        ]);
+    }
+}
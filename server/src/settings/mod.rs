@@ -119,6 +119,35 @@ impl Default for GameplaySettings {
     }
 }
 
+/// Limits for the work plugins may do in a single call, plugins exceeding them
+/// too often in a row get disabled
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PluginSettings {
+    /// Wasm fuel available to each call, roughly the number of instructions
+    pub fuel_per_call: u64,
+    /// Calls taking longer than this get interrupted and count as overrun
+    pub call_time_budget: Duration,
+    /// Maximum memory of a plugin module in MiB
+    pub memory_limit_mib: usize,
+    /// Traps or overruns in a row after which a plugin gets disabled
+    pub max_faults: u32,
+    /// Space each plugin may use in the persistent plugin storage in KiB
+    pub storage_quota_kib: usize,
+}
+
+impl Default for PluginSettings {
+    fn default() -> Self {
+        Self {
+            fuel_per_call: 500_000_000,
+            call_time_budget: Duration::from_millis(100),
+            memory_limit_mib: 256,
+            max_faults: 3,
+            storage_quota_kib: 1024,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ModerationSettings {
     #[serde(default)]
@@ -209,6 +238,9 @@ pub struct Settings {
 
     #[serde(default)]
     pub world: WorldSettings,
+
+    #[serde(default)]
+    pub plugins: PluginSettings,
 }
 
 impl Default for Settings {
@@ -238,6 +270,7 @@ impl Default for Settings {
             gameplay: GameplaySettings::default(),
             moderation: ModerationSettings::default(),
             world: WorldSettings::default(),
+            plugins: PluginSettings::default(),
         }
     }
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "plugins")]
+use crate::plugin::PendingSpawns;
 #[cfg(feature = "worldgen")]
 use crate::rtsim::RtSim;
 use crate::{
@@ -36,6 +38,8 @@ use common_net::{
     sync::WorldSyncExt,
 };
 use common_state::State;
+#[cfg(feature = "plugins")]
+use common_state::plugin::{EntityEvent, PluginMgr, memory_manager::with_ecs_world};
 use specs::{
     Builder, Entity as EcsEntity, EntityBuilder as EcsEntityBuilder, Join, WorldExt, WriteStorage,
     storage::{GenericReadStorage, GenericWriteStorage},
@@ -162,6 +166,13 @@ pub trait StateExt {
     ) -> Result<(), specs::error::WrongGeneration>;
     /// Get the given entity as an [`Actor`], if it is one.
     fn entity_as_actor(&self, entity: EcsEntity) -> Option<Actor>;
+    /// Forward an entity lifecycle or combat event to all loaded plugins
+    #[cfg(feature = "plugins")]
+    fn plugin_entity_event(&self, event: EntityEvent);
+    /// Announce a newly created entity to plugins as spawned, once the events
+    /// of the current tick have been handled and it is fully set up.
+    #[cfg(feature = "plugins")]
+    fn plugin_spawn_event(&self, entity: EcsEntity);
     /// Mutate the position of an entity or, if the entity is mounted, the
     /// mount.
     ///
@@ -294,27 +305,29 @@ impl StateExt for State {
             }),
             _ => None,
         };
-        Some(
-            self.ecs_mut()
-                .create_entity_synced()
-                .with(world_item)
-                .with(pos)
-                .with(ori)
-                .with(vel)
-                .with(item_body.orientation(&mut rand::rng()))
-                .with(item_body.mass())
-                .with(item_body.density())
-                .with(body.collider())
-                .with(body)
-                .with(Object::DeleteAfter {
-                    spawned_at,
-                    // Delete the item drop after 5 minutes
-                    timeout: Duration::from_secs(300),
-                })
-                .maybe_with(loot_owner)
-                .maybe_with(light_emitter)
-                .build(),
-        )
+        let entity = self
+            .ecs_mut()
+            .create_entity_synced()
+            .with(world_item)
+            .with(pos)
+            .with(ori)
+            .with(vel)
+            .with(item_body.orientation(&mut rand::rng()))
+            .with(item_body.mass())
+            .with(item_body.density())
+            .with(body.collider())
+            .with(body)
+            .with(Object::DeleteAfter {
+                spawned_at,
+                // Delete the item drop after 5 minutes
+                timeout: Duration::from_secs(300),
+            })
+            .maybe_with(loot_owner)
+            .maybe_with(light_emitter)
+            .build();
+        #[cfg(feature = "plugins")]
+        self.plugin_spawn_event(entity);
+        Some(entity)
     }
 
     fn create_ship<F: FnOnce(comp::ship::Body) -> comp::Collider>(
@@ -603,6 +616,7 @@ impl StateExt for State {
             pets,
             active_abilities,
             map_marker,
+            companion,
         } = components;
 
         if let Some(player_uid) = self.read_component_copied::<Uid>(entity) {
@@ -707,6 +721,17 @@ impl StateExt for State {
                 error!("Player has no pos, cannot load {} pets", pets.len());
             }
 
+            if let Some(player_pos) = player_pos
+                && let Some(Presence {
+                    kind: PresenceKind::Character(char_id),
+                    ..
+                }) = self.ecs().read_storage::<Presence>().get(entity)
+                && let Some(mut rtsim) = self.ecs().try_fetch_mut::<crate::rtsim::RtSim>()
+            {
+                let time = *self.ecs().read_resource::<Time>();
+                rtsim.hook_character_login(*char_id, companion, player_pos.0, time);
+            }
+
             let settings = self.ecs().read_resource::<Settings>();
             let mut char_battle_mode = settings.gameplay.battle_mode.default_mode();
             let presences = self.ecs().read_storage::<Presence>();
@@ -1132,6 +1157,21 @@ impl StateExt for State {
             .unzip();
         let maybe_rtsim = self.read_component_copied::<RtSimEntity>(entity);
 
+        // Entities deleted in the tick they were created in were never announced to
+        // plugins, so they are not told about the despawn either
+        #[cfg(feature = "plugins")]
+        if let Some(uid) = maybe_uid {
+            let announced = {
+                let mut pending = self.ecs().write_resource::<PendingSpawns>();
+                let pending_count = pending.0.len();
+                pending.0.retain(|pending| *pending != uid);
+                pending.0.len() == pending_count
+            };
+            if announced {
+                self.plugin_entity_event(EntityEvent::Despawn(uid));
+            }
+        }
+
         self.mut_resource::<IdMaps>().remove_entity(
             Some(entity),
             maybe_uid,
@@ -1162,6 +1202,22 @@ impl StateExt for State {
         }
     }
 
+    #[cfg(feature = "plugins")]
+    fn plugin_entity_event(&self, event: EntityEvent) {
+        let ecs = self.ecs();
+        with_ecs_world(ecs, |ecs_world| {
+            ecs.write_resource::<PluginMgr>()
+                .entity_event(ecs_world, &event)
+        });
+    }
+
+    #[cfg(feature = "plugins")]
+    fn plugin_spawn_event(&self, entity: EcsEntity) {
+        if let Some(uid) = self.read_component_copied::<Uid>(entity) {
+            self.ecs().write_resource::<PendingSpawns>().0.push(uid);
+        }
+    }
+
     fn position_mut<T>(
         &mut self,
         entity: EcsEntity,
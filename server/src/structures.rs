@@ -0,0 +1,277 @@
+//! Structures captured from the world, see [`CapturedStructure`].
+//!
+//! Captures are saved in the `structures` folder of the data directory, which
+//! is laid out like the asset folder. Pointing `VELOREN_ASSETS_OVERRIDE` at it
+//! makes a capture named `name` available to worldgen as
+//! `Structure::load_group("captured.name")`.
+
+use common::{
+    assets::{BoxedError, DotVox, FileAsset},
+    comp::Content,
+    terrain::structure::{CaptureError, CapturedSpec, CapturedStructure},
+};
+use common_state::State;
+use std::{
+    fmt, fs,
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+};
+use vek::*;
+
+const STRUCTURES_DIR: &str = "structures";
+
+#[derive(Debug)]
+pub enum StructureError {
+    InvalidName(String),
+    DoesNotExist(String),
+    Capture(CaptureError),
+    Io(io::Error),
+    Ron(ron::Error),
+    Vox(BoxedError),
+}
+
+impl From<StructureError> for Content {
+    fn from(value: StructureError) -> Self {
+        match value {
+            StructureError::InvalidName(name) => {
+                Content::localized_with_args("command-structure-invalid", [("name", name)])
+            },
+            StructureError::DoesNotExist(name) => {
+                Content::localized_with_args("command-structure-not-found", [("name", name)])
+            },
+            StructureError::Capture(CaptureError::OutOfBounds(pos)) => {
+                Content::localized_with_args("command-structure-unloaded", [(
+                    "pos",
+                    format!("{pos}"),
+                )])
+            },
+            StructureError::Capture(CaptureError::TooLarge) => Content::localized_with_args(
+                "command-structure-too-large",
+                [("max", CapturedStructure::MAX_SIZE.to_string())],
+            ),
+            StructureError::Capture(CaptureError::TooManyBlocks) => {
+                Content::localized("command-structure-too-many-blocks")
+            },
+            err @ (StructureError::Io(_) | StructureError::Ron(_) | StructureError::Vox(_)) => {
+                Content::localized_with_args("command-structure-io", [("error", err.to_string())])
+            },
+        }
+    }
+}
+
+impl fmt::Display for StructureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidName(name) => write!(
+                f,
+                "Structure name '{name}' is invalid. Names may only contain lowercase ASCII, \
+                 digits and underscores"
+            ),
+            Self::DoesNotExist(name) => write!(f, "Structure '{name}' does not exist"),
+            Self::Capture(err) => write!(f, "Failed to capture structure: {err:?}"),
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Ron(err) => write!(f, "Failed to read the manifest: {err}"),
+            Self::Vox(err) => write!(f, "Failed to read the model: {err}"),
+        }
+    }
+}
+
+impl From<CaptureError> for StructureError {
+    fn from(err: CaptureError) -> Self { Self::Capture(err) }
+}
+
+impl From<io::Error> for StructureError {
+    fn from(err: io::Error) -> Self { Self::Io(err) }
+}
+
+impl From<ron::Error> for StructureError {
+    fn from(err: ron::Error) -> Self { Self::Ron(err) }
+}
+
+/// The files of a captured structure, and the specifier of its model.
+struct StructurePaths {
+    specifier: String,
+    vox: PathBuf,
+    manifest: PathBuf,
+}
+
+impl StructurePaths {
+    fn new(data_dir: &Path, name: &str) -> Result<Self, StructureError> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(StructureError::InvalidName(name.to_string()));
+        }
+
+        let root = data_dir.join(STRUCTURES_DIR).join("world");
+        Ok(Self {
+            specifier: format!("world.structure.captured.{name}"),
+            vox: root.join(format!("structure/captured/{name}.vox")),
+            manifest: root.join(format!("manifests/captured/{name}.ron")),
+        })
+    }
+}
+
+/// Capture the blocks and sprites of the loaded terrain within `aabb`.
+pub fn capture(state: &State, aabb: Aabb<i32>) -> Result<CapturedStructure, StructureError> {
+    let terrain = state.terrain();
+    Ok(CapturedStructure::capture(&*terrain, aabb, |wpos| {
+        terrain.sprite_cfg_at(wpos).cloned()
+    })?)
+}
+
+/// Save a captured structure as a `.vox` model and a manifest. Existing
+/// structures with the same name are overwritten.
+pub fn save(
+    data_dir: &Path,
+    name: &str,
+    structure: &CapturedStructure,
+) -> Result<(), StructureError> {
+    let paths = StructurePaths::new(data_dir, name)?;
+    for path in [&paths.vox, &paths.manifest] {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+    }
+
+    structure.write_vox(BufWriter::new(fs::File::create(&paths.vox)?))?;
+    let manifest = ron::ser::to_string_pretty(
+        &vec![structure.spec(paths.specifier)],
+        ron::ser::PrettyConfig::default(),
+    )?;
+    fs::write(&paths.manifest, manifest)?;
+    Ok(())
+}
+
+/// Load a structure that was saved with [`save`].
+pub fn load(data_dir: &Path, name: &str) -> Result<CapturedStructure, StructureError> {
+    let paths = StructurePaths::new(data_dir, name)?;
+    if !paths.vox.exists() || !paths.manifest.exists() {
+        return Err(StructureError::DoesNotExist(name.to_string()));
+    }
+
+    let dot_vox = DotVox::from_bytes(fs::read(&paths.vox)?.into()).map_err(StructureError::Vox)?;
+    let spec = ron::from_str::<Vec<CapturedSpec>>(&fs::read_to_string(&paths.manifest)?)
+        .map_err(|err| err.code)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| StructureError::DoesNotExist(name.to_string()))?;
+    Ok(CapturedStructure::from_vox(&dot_vox.0, spec))
+}
+
+/// Place a structure with its center at `origin`, after mirroring and rotating
+/// it as in [`CapturedStructure::blocks`]. Returns the number of blocks placed.
+pub fn paste(
+    state: &State,
+    structure: &CapturedStructure,
+    origin: Vec3<i32>,
+    rotation: u8,
+    mirror: bool,
+) -> usize {
+    let mut count = 0;
+    for (rpos, block, sprite_cfg) in structure.blocks(rotation, mirror) {
+        let wpos = origin + rpos;
+        state.set_block(wpos, block);
+        if let Some(sprite_cfg) = sprite_cfg {
+            state.set_sprite_cfg(wpos, sprite_cfg.clone());
+        }
+        #[cfg(feature = "persistent_world")]
+        if let Some(terrain_persistence) = state
+            .ecs()
+            .try_fetch_mut::<crate::TerrainPersistence>()
+            .as_mut()
+        {
+            terrain_persistence.set_block(wpos, block);
+            if let Some(sprite_cfg) = sprite_cfg {
+                terrain_persistence.set_sprite_cfg(wpos, sprite_cfg.clone());
+            }
+        }
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{
+        assets::{AssetCache, source::FileSystem},
+        terrain::{
+            Block, BlockKind, SpriteCfg, SpriteKind,
+            structure::{StructureBlock, StructuresGroup},
+        },
+        vol::{ReadVol, WriteVol},
+        volumes::dyna::Dyna,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A fresh data directory that is removed again when dropped, such that
+    /// concurrent test runs don't interfere with each other.
+    struct TempDataDir(PathBuf);
+
+    impl TempDataDir {
+        fn new() -> Self {
+            static COUNTER: AtomicUsize = AtomicUsize::new(0);
+            Self(std::env::temp_dir().join(format!(
+                "veloren_structures_test_{}_{}",
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            )))
+        }
+    }
+
+    impl Drop for TempDataDir {
+        fn drop(&mut self) { let _ = fs::remove_dir_all(&self.0); }
+    }
+
+    #[test]
+    fn saved_structures_load_as_groups() {
+        let temp_dir = TempDataDir::new();
+        let data_dir = &temp_dir.0;
+        let stone = Block::new(BlockKind::Rock, Rgb::new(100, 100, 100));
+        let chest = Block::air(SpriteKind::Chest);
+        let loot_table = "common.loot_tables.dungeon.sahagin.chest";
+        let mut vol = Dyna::<Block, ()>::filled(Vec3::new(4, 3, 2), Block::empty(), ());
+        let _ = vol.set(Vec3::new(0, 0, 0), stone);
+        let _ = vol.set(Vec3::new(1, 1, 1), chest);
+        let captured = CapturedStructure::capture(
+            &vol,
+            Aabb {
+                min: Vec3::zero(),
+                max: Vec3::new(4, 3, 2),
+            },
+            |pos| {
+                (pos == Vec3::new(1, 1, 1)).then(|| SpriteCfg {
+                    loot_table: Some(loot_table.to_string()),
+                    ..SpriteCfg::default()
+                })
+            },
+        )
+        .unwrap();
+        save(data_dir, "test", &captured).unwrap();
+
+        // The structures folder is laid out like the asset folder, see the module docs
+        let cache =
+            AssetCache::with_source(FileSystem::new(data_dir.join(STRUCTURES_DIR)).unwrap());
+        let group = cache.load::<StructuresGroup>("world.manifests.captured.test");
+
+        let group = group.unwrap().read();
+        assert_eq!(group.len(), 1);
+        let structure = &group[0];
+        assert_eq!(structure.get_bounds(), Aabb {
+            min: Vec3::new(-2, -1, 0),
+            max: Vec3::new(2, 2, 2),
+        });
+        assert!(matches!(
+            structure.get(Vec3::new(-2, -1, 0)),
+            Ok(StructureBlock::Block(block)) if *block == stone
+        ));
+        assert!(matches!(
+            structure.get(Vec3::new(-1, 0, 1)),
+            Ok(StructureBlock::BlockWithCfg(block, cfg))
+                if *block == chest && cfg.loot_table.as_deref() == Some(loot_table)
+        ));
+    }
+}
@@ -7,7 +7,9 @@ use common::{
             AgentEvent, AwarenessState, DEFAULT_INTERACTION_TIME, TRADE_INTERACTION_TIME, Target,
             TimerAction,
         },
-        body, is_downed,
+        body,
+        invite::InviteKind,
+        is_downed,
     },
     consts::MAX_INTERACT_RANGE,
     interaction::InteractionKind,
@@ -655,6 +657,17 @@ fn handle_rtsim_actions(bdata: &mut BehaviorData) -> bool {
                     warn!("NPC dialogue sent to non-existent target entity");
                 }
             },
+            NpcAction::Trade(target) => {
+                if let Some(target) = bdata.read_data.id_maps.actor_entity(target)
+                    && let Some(target_uid) = bdata.read_data.uids.get(target)
+                {
+                    bdata
+                        .controller
+                        .push_initiate_invite(*target_uid, InviteKind::Trade);
+                } else {
+                    warn!("NPC trade offered to non-existent target entity");
+                }
+            },
         }
         true
     } else {
@@ -51,6 +51,8 @@ event_emitters! {
         update_map_marker: event::UpdateMapMarkerEvent,
         client_disconnect: event::ClientDisconnectEvent,
         set_battle_mode: event::SetBattleModeEvent,
+        plugin_message: event::PluginMessageEvent,
+        abandon_quest: event::AbandonQuestEvent,
     }
 }
 
@@ -262,6 +264,21 @@ impl Sys {
                     battle_mode,
                 });
             },
+            ClientGeneral::PluginMessage {
+                plugin,
+                channel,
+                data,
+            } => {
+                emitters.emit(event::PluginMessageEvent {
+                    entity,
+                    plugin,
+                    channel,
+                    data,
+                });
+            },
+            ClientGeneral::AbandonQuest(quest) => {
+                emitters.emit(event::AbandonQuestEvent { entity, quest });
+            },
             ClientGeneral::RequestCharacterList
             | ClientGeneral::CreateCharacter { .. }
             | ClientGeneral::EditCharacter { .. }
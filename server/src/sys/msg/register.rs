@@ -415,6 +415,12 @@ impl<'a> System<'a> for Sys {
                             player_list.clone(),
                         )))?;
 
+                        // Send the commands of server plugins for completion and /help
+                        #[cfg(feature = "plugins")]
+                        client.send(ServerGeneral::PluginCommands(
+                            read_data.plugin_mgr.commands(),
+                        ))?;
+
                         Ok(())
                     }() {
                         trace!(?e, "failed to process register");
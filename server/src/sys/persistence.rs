@@ -1,14 +1,15 @@
-use crate::{persistence::character_updater, sys::SysScheduler};
+use crate::{persistence::character_updater, rtsim::RtSim, sys::SysScheduler};
 use common::{
     comp::{
         ActiveAbilities, Alignment, Body, Inventory, MapMarker, Presence, PresenceKind, SkillSet,
         Stats, Waypoint,
         pet::{Pet, is_tameable},
     },
+    resources::Time,
     uid::Uid,
 };
 use common_ecs::{Job, Origin, Phase, System};
-use specs::{Join, LendJoin, ReadStorage, Write, WriteExpect};
+use specs::{Join, LendJoin, Read, ReadStorage, Write, WriteExpect};
 use tracing::error;
 
 #[derive(Default)]
@@ -27,6 +28,8 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Pet>,
         ReadStorage<'a, Stats>,
         ReadStorage<'a, ActiveAbilities>,
+        Read<'a, Time>,
+        Option<Read<'a, RtSim>>,
         WriteExpect<'a, character_updater::CharacterUpdater>,
         Write<'a, SysScheduler<Self>>,
     );
@@ -49,6 +52,8 @@ impl<'a> System<'a> for Sys {
             pets,
             stats,
             active_abilities,
+            time,
+            rtsim,
             mut updater,
             mut scheduler,
         ): Self::SystemData,
@@ -107,6 +112,9 @@ impl<'a> System<'a> for Sys {
                                     waypoint.cloned(),
                                     active_abilities.clone(),
                                     map_marker.cloned(),
+                                    rtsim
+                                        .as_ref()
+                                        .and_then(|rtsim| rtsim.companion_of(id, *time)),
                                 ))
                             },
                             PresenceKind::Spectator | PresenceKind::Possessor => None,
@@ -32,6 +32,8 @@ use common::{
 use common_ecs::{Job, Origin, Phase, System};
 use common_net::msg::ServerGeneral;
 use common_state::TerrainChanges;
+#[cfg(feature = "plugins")]
+use common_state::plugin::PluginMgr;
 use comp::Behavior;
 use core::cmp::Reverse;
 use itertools::Itertools;
@@ -78,6 +80,8 @@ pub struct Data<'a> {
     terrain_changes: Write<'a, TerrainChanges>,
     chunk_requests: Write<'a, Vec<ChunkRequest>>,
     rtsim: RtSimData<'a>,
+    #[cfg(feature = "plugins")]
+    plugin_mgr: Write<'a, PluginMgr>,
     #[cfg(feature = "persistent_world")]
     terrain_persistence: TerrainPersistenceData<'a>,
     positions: WriteStorage<'a, Pos>,
@@ -130,7 +134,10 @@ impl<'a> System<'a> for Sys {
         // Also, send the chunk data to anybody that is close by.
         let mut new_chunks = Vec::new();
         'insert_terrain_chunks: while let Some((key, res)) = data.chunk_generator.recv_new_chunk() {
-            #[cfg_attr(not(feature = "persistent_world"), expect(unused_mut))]
+            #[cfg_attr(
+                not(any(feature = "persistent_world", feature = "plugins")),
+                expect(unused_mut)
+            )]
             let (mut chunk, supplement) = match res {
                 Ok((chunk, supplement)) => (chunk, supplement),
                 Err(Some(entity)) => {
@@ -147,6 +154,10 @@ impl<'a> System<'a> for Sys {
                 },
             };
 
+            // Let plugins modify the chunk before changes made by players are applied
+            #[cfg(feature = "plugins")]
+            ChunkGenerator::apply_plugin_hooks(&mut data.plugin_mgr, &data.world, key, &mut chunk);
+
             // Apply changes from terrain persistence to this chunk
             #[cfg(feature = "persistent_world")]
             if let Some(terrain_persistence) = data.terrain_persistence.as_mut() {
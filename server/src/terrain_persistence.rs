@@ -5,7 +5,7 @@ use bincode::{
     serde::{decode_from_std_read, encode_to_vec},
 };
 use common::{
-    terrain::{Block, TerrainChunk},
+    terrain::{Block, SpriteCfg, TerrainChunk},
     vol::{RectRasterableVol, WriteVol},
 };
 use hashbrown::HashMap;
@@ -79,6 +79,12 @@ impl TerrainPersistence {
             }
         }
 
+        for (rpos, sprite_cfg) in loaded_chunk.chunk.sprite_cfgs() {
+            terrain_chunk
+                .meta_mut()
+                .set_sprite_cfg_at(rpos, sprite_cfg.clone());
+        }
+
         // Reset any unchanged blocks (this is an optimisation only)
         for rpos in resets {
             loaded_chunk.chunk.reset_block(rpos);
@@ -217,14 +223,28 @@ impl TerrainPersistence {
             .xy()
             .map2(TerrainChunk::RECT_SIZE, |e, sz| e.div_euclid(sz as i32));
         let loaded_chunk = self.load_chunk(key);
-        let old_block = loaded_chunk
-            .chunk
-            .blocks
-            .insert(pos - key * TerrainChunk::RECT_SIZE.map(|e| e as i32), block);
+        let rpos = pos - key * TerrainChunk::RECT_SIZE.map(|e| e as i32);
+        let old_block = loaded_chunk.chunk.blocks.insert(rpos, block);
         if old_block != Some(block) {
             loaded_chunk.modified = true;
+            // The configuration was for the sprite that was there before
+            loaded_chunk.chunk.sprite_cfgs.remove(&rpos);
         }
     }
+
+    /// Set the configuration of the sprite at `pos`. This should come after
+    /// setting the block of the sprite, which clears the configuration.
+    pub fn set_sprite_cfg(&mut self, pos: Vec3<i32>, sprite_cfg: SpriteCfg) {
+        let key = pos
+            .xy()
+            .map2(TerrainChunk::RECT_SIZE, |e, sz| e.div_euclid(sz as i32));
+        let loaded_chunk = self.load_chunk(key);
+        loaded_chunk.chunk.sprite_cfgs.insert(
+            pos - key * TerrainChunk::RECT_SIZE.map(|e| e as i32),
+            sprite_cfg,
+        );
+        loaded_chunk.modified = true;
+    }
 }
 
 impl Drop for TerrainPersistence {
@@ -234,6 +254,7 @@ impl Drop for TerrainPersistence {
 #[derive(Default, Serialize, Deserialize, Clone)]
 pub struct Chunk {
     blocks: HashMap<Vec3<i32>, Block>,
+    sprite_cfgs: HashMap<Vec3<i32>, SpriteCfg>,
 }
 
 impl Chunk {
@@ -247,6 +268,10 @@ impl Chunk {
         self.blocks.iter().map(|(k, b)| (*k, *b))
     }
 
+    fn sprite_cfgs(&self) -> impl Iterator<Item = (Vec3<i32>, &SpriteCfg)> + '_ {
+        self.sprite_cfgs.iter().map(|(k, cfg)| (*k, cfg))
+    }
+
     fn reset_block(&mut self, rpos: Vec3<i32>) { self.blocks.remove(&rpos); }
 
     /// Get the number of blocks this chunk contains
@@ -358,12 +383,17 @@ mod version {
     /// The newest supported raw format type. This should be changed every time
     /// a new raw format is added.
     // Step [3]
-    pub type Current = V3;
+    pub type Current = V4;
 
     type LoadChunkFn<R> = fn(R) -> Result<Chunk, (&'static str, Box<DecodeError>)>;
     fn loaders<'a, R: io::Read + Clone>() -> &'a [LoadChunkFn<R>] {
         // Step [4]
-        &[load_raw::<V3, _>, load_raw::<V2, _>, load_raw::<V1, _>]
+        &[
+            load_raw::<V4, _>,
+            load_raw::<V3, _>,
+            load_raw::<V2, _>,
+            load_raw::<V1, _>,
+        ]
     }
 
     // Convert back to current
@@ -371,18 +401,54 @@ mod version {
     impl From<Chunk> for Current {
         fn from(chunk: Chunk) -> Self {
             Self {
-                version: version_magic(3),
+                version: version_magic(4),
                 blocks: chunk
                     .blocks
                     .into_iter()
                     .map(|(pos, b)| (pos.x as u8, pos.y as u8, pos.z as i16, b.to_u32()))
                     .collect(),
+                sprite_cfgs: chunk
+                    .sprite_cfgs
+                    .into_iter()
+                    .map(|(pos, cfg)| (pos.x as u8, pos.y as u8, pos.z as i16, cfg))
+                    .collect(),
             }
         }
     }
 
-    /// Version 3 of the raw chunk format.
+    /// Version 4 of the raw chunk format.
     #[derive(Serialize, Deserialize)]
+    pub struct V4 {
+        #[serde(deserialize_with = "version::<_, 4>")]
+        pub version: u64,
+        pub blocks: Vec<(u8, u8, i16, u32)>,
+        pub sprite_cfgs: Vec<(u8, u8, i16, SpriteCfg)>,
+    }
+
+    impl From<V4> for Chunk {
+        fn from(v4: V4) -> Self {
+            Self {
+                blocks: v4
+                    .blocks
+                    .into_iter()
+                    .map(|(x, y, z, b)| {
+                        (
+                            Vec3::new(x as i32, y as i32, z as i32),
+                            Block::from_u32(b).unwrap_or_else(Block::empty),
+                        )
+                    })
+                    .collect(),
+                sprite_cfgs: v4
+                    .sprite_cfgs
+                    .into_iter()
+                    .map(|(x, y, z, cfg)| (Vec3::new(x as i32, y as i32, z as i32), cfg))
+                    .collect(),
+            }
+        }
+    }
+
+    /// Version 3 of the raw chunk format.
+    #[derive(Deserialize)]
     pub struct V3 {
         #[serde(deserialize_with = "version::<_, 3>")]
         pub version: u64,
@@ -402,6 +468,7 @@ mod version {
                         )
                     })
                     .collect(),
+                sprite_cfgs: HashMap::default(),
             }
         }
     }
@@ -422,6 +489,7 @@ mod version {
                     .into_iter()
                     .map(|(x, y, z, b)| (Vec3::new(x as i32, y as i32, z as i32), b))
                     .collect(),
+                sprite_cfgs: HashMap::default(),
             }
         }
     }
@@ -433,7 +501,12 @@ mod version {
     }
 
     impl From<V1> for Chunk {
-        fn from(v1: V1) -> Self { Self { blocks: v1.blocks } }
+        fn from(v1: V1) -> Self {
+            Self {
+                blocks: v1.blocks,
+                sprite_cfgs: HashMap::default(),
+            }
+        }
     }
 
     // Utility things
@@ -2,7 +2,7 @@ use common::{
     calendar::Calendar,
     generation::ChunkSupplement,
     resources::TimeOfDay,
-    rtsim::TerrainResource,
+    rtsim::{TerrainResource, WildlifeKind},
     terrain::{
         Block, BlockKind, MapSizeLg, SpriteKind, TerrainChunk, TerrainChunkMeta, TerrainChunkSize,
     },
@@ -52,6 +52,7 @@ impl World {
         _index: IndexRef,
         chunk_pos: Vec2<i32>,
         _rtsim_resources: Option<EnumMap<TerrainResource, f32>>,
+        _rtsim_wildlife: Option<EnumMap<WildlifeKind, f32>>,
         // TODO: misleading name
         mut _should_continue: impl FnMut() -> bool,
         _time: Option<(TimeOfDay, Calendar)>,
@@ -79,7 +79,7 @@ impl ClientChatCommand {
             },
             ClientChatCommand::ExperimentalShader => cmd(
                 vec![Enum(
-                    "Shader",
+                    "Shader".into(),
                     ExperimentalShader::iter()
                         .map(|item| item.to_string())
                         .collect(),
@@ -108,7 +108,7 @@ impl ClientChatCommand {
                 cmd(vec![], Content::localized("command-waypoint-desc"), None)
             },
             ClientChatCommand::Wiki => cmd(
-                vec![Any("topic", Optional)],
+                vec![Any("topic".into(), Optional)],
                 Content::localized("command-wiki-desc"),
                 None,
             ),
@@ -179,6 +179,8 @@ impl FromStr for ClientChatCommand {
 pub enum ChatCommandKind {
     Client(ClientChatCommand),
     Server(ServerChatCommand),
+    /// A command registered by a server plugin
+    Plugin(PluginCommandData),
 }
 
 impl FromStr for ChatCommandKind {
@@ -254,6 +256,7 @@ fn preproccess_command(
     let mut cmd_args = match command {
         ChatCommandKind::Client(cmd) => cmd.data().args,
         ChatCommandKind::Server(cmd) => cmd.data().args,
+        ChatCommandKind::Plugin(cmd) => cmd.data().args,
     };
     let client = &mut session_state.client.borrow_mut();
     let ecs = client.state().ecs();
@@ -387,7 +390,16 @@ pub fn run_command(
     mut args: Vec<String>,
 ) -> CommandResult {
     let command = ChatCommandKind::from_str(cmd)
-        .map_err(|_| invalid_command_message(&session_state.client.borrow(), cmd.to_string()))?;
+        .ok()
+        .or_else(|| {
+            session_state
+                .client
+                .borrow()
+                .plugin_command(cmd)
+                .cloned()
+                .map(ChatCommandKind::Plugin)
+        })
+        .ok_or_else(|| invalid_command_message(&session_state.client.borrow(), cmd.to_string()))?;
 
     preproccess_command(session_state, &command, &mut args)?;
 
@@ -400,6 +412,13 @@ pub fn run_command(
             Ok(None) // The server will provide a response when the command is
             // run
         },
+        ChatCommandKind::Plugin(cmd) => {
+            session_state
+                .client
+                .borrow_mut()
+                .send_command(cmd.keyword, args);
+            Ok(None)
+        },
         ChatCommandKind::Client(cmd) => run_client_command(session_state, global_state, cmd, args),
     }
 }
@@ -556,6 +575,11 @@ fn handle_help(
         Ok(Some(cmd.help_content()))
     } else if let Some(cmd) = parse_cmd_args!(&args, ClientChatCommand) {
         Ok(Some(cmd.help_content()))
+    } else if let Some(cmd) = args
+        .first()
+        .and_then(|arg| session_state.client.borrow().plugin_command(arg).cloned())
+    {
+        Ok(Some(cmd.help_content()))
     } else {
         let client = &mut session_state.client.borrow_mut();
 
@@ -573,6 +597,13 @@ fn handle_help(
         let server_commands = ServerChatCommand::iter()
             .filter(|cmd| cmd.needs_role() <= entity_role)
             .map(|cmd| i18n.get_content(&cmd.help_content()))
+            .chain(
+                client
+                    .plugin_commands()
+                    .iter()
+                    .filter(|cmd| cmd.needs_role <= entity_role)
+                    .map(|cmd| i18n.get_content(&cmd.help_content())),
+            )
             .join("\n");
 
         let additional_shortcuts = ServerChatCommand::iter()
@@ -831,9 +862,9 @@ impl TabComplete for ArgumentSpec {
             },
             // No specific completion for arbitrary 'Any' arguments
             ArgumentSpec::Any(_, _) => vec![],
-            ArgumentSpec::Command(_) => complete_command(part, ""),
+            ArgumentSpec::Command(_) => complete_command(part, "", client.plugin_commands()),
             ArgumentSpec::Message(_) => complete_player(part, client),
-            ArgumentSpec::SubCommand => complete_command(part, ""),
+            ArgumentSpec::SubCommand => complete_command(part, "", client.plugin_commands()),
             ArgumentSpec::Enum(_, strings, _) => strings
                 .iter()
                 .filter(|string| string.starts_with(part)) // Filter by partial input
@@ -934,12 +965,17 @@ fn nth_word(line: &str, n: usize) -> Option<usize> {
     None
 }
 
-/// Returns a list of [`ClientChatCommand`] and [`ServerChatCommand`] names that
-/// start with the given partial input.
-fn complete_command(part: &str, prefix: &str) -> Vec<String> {
+/// Returns a list of [`ClientChatCommand`], [`ServerChatCommand`] and plugin
+/// command names that start with the given partial input.
+fn complete_command(
+    part: &str,
+    prefix: &str,
+    plugin_commands: &[PluginCommandData],
+) -> Vec<String> {
     ServerChatCommand::iter_with_keywords()
         .map(|(kwd, _)| kwd)
         .chain(ClientChatCommand::iter_with_keywords().map(|(kwd, _)| kwd))
+        .chain(plugin_commands.iter().map(|cmd| cmd.keyword.as_str()))
         .filter(|kwd| kwd.starts_with(part))
         .map(|kwd| format!("{}{}", prefix, kwd))
         .collect()
@@ -976,7 +1012,7 @@ pub fn complete(line: &str, client: &Client, i18n: &Localization, cmd_prefix: &s
             // Completing chat command name. This is the start of the line so the prefix
             // will be part of it
             let word = word.strip_prefix(cmd_prefix).unwrap_or(word);
-            return complete_command(word, cmd_prefix);
+            return complete_command(word, cmd_prefix, client.plugin_commands());
         }
 
         // Try to parse the command to get its argument specifications
@@ -986,7 +1022,7 @@ pub fn complete(line: &str, client: &Client, i18n: &Localization, cmd_prefix: &s
             } else if let Ok(cmd) = cmd.parse::<ClientChatCommand>() {
                 Some(cmd.data().args)
             } else {
-                None
+                client.plugin_command(cmd).map(|cmd| cmd.data().args)
             }
         };
 
@@ -1035,12 +1071,12 @@ fn verify_cmd_list_sorted() {
 
 #[test]
 fn test_complete_command() {
-    assert_eq!(complete_command("mu", "/"), vec!["/mute".to_string()]);
-    assert_eq!(complete_command("unba", "/"), vec![
+    assert_eq!(complete_command("mu", "/", &[]), vec!["/mute".to_string()]);
+    assert_eq!(complete_command("unba", "/", &[]), vec![
         "/unban".to_string(),
         "/unban_ip".to_string()
     ]);
-    assert_eq!(complete_command("make_", "/"), vec![
+    assert_eq!(complete_command("make_", "/", &[]), vec![
         "/make_block".to_string(),
         "/make_npc".to_string(),
         "/make_sprite".to_string(),
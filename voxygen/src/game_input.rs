@@ -103,6 +103,8 @@ pub enum GameInput {
     Crafting,
     #[strum(serialize = "gameinput-diary")]
     Diary,
+    #[strum(serialize = "gameinput-questlog")]
+    QuestLog,
     #[strum(serialize = "gameinput-settings")]
     Settings,
     #[strum(serialize = "gameinput-controls")]
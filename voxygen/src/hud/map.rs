@@ -117,6 +117,7 @@ widget_ids! {
 
 const SHOW_ECONOMY: bool = false; // turn this display off (for 0.9) until we have an improved look
 
+#[derive(Clone)]
 pub struct ExtraMarker {
     pub recv_pos: Vec2<f32>,
     pub marker: Marker,
@@ -1028,6 +1029,7 @@ impl Widget for Map<'_> {
                     MarkerKind::Cultist => i18n.get_msg("hud-map-cultist"),
                     MarkerKind::Sahagin => i18n.get_msg("hud-map-sahagin"),
                     MarkerKind::Myrmidon => i18n.get_msg("hud-map-myrmidon"),
+                    MarkerKind::ProceduralDungeon => i18n.get_msg("hud-map-procedural_dungeon"),
                     MarkerKind::DwarvenMine => i18n.get_msg("hud-map-df_mine"),
                     MarkerKind::VampireCastle => i18n.get_msg("hud-map-vampire_castle"),
                     MarkerKind::Character => i18n.get_msg("hud-map-character"),
@@ -1048,6 +1050,7 @@ impl Widget for Map<'_> {
                 MarkerKind::Cultist => (Some(5), i18n.get_msg("hud-map-cultist")),
                 MarkerKind::Sahagin => (Some(2), i18n.get_msg("hud-map-sahagin")),
                 MarkerKind::Myrmidon => (Some(4), i18n.get_msg("hud-map-myrmidon")),
+                MarkerKind::ProceduralDungeon => (None, i18n.get_msg("hud-map-procedural_dungeon")),
                 MarkerKind::DwarvenMine => (Some(5), i18n.get_msg("hud-map-df_mine")),
                 MarkerKind::VampireCastle => (Some(3), i18n.get_msg("hud-map-vampire_castle")),
                 MarkerKind::Character => (None, i18n.get_msg("hud-map-character")),
@@ -1055,7 +1058,12 @@ impl Widget for Map<'_> {
             let desc = if let Some(site_id) = marker.site
                 && let Some(site) = self.client.sites().get(&site_id)
             {
-                desc.into_owned() + &get_site_economy(site)
+                let holder = site
+                    .holder
+                    .as_ref()
+                    .map(|holder| format!("\n{}", i18n.get_content(holder)))
+                    .unwrap_or_default();
+                desc.into_owned() + &holder + &get_site_economy(site)
             } else {
                 desc.into_owned()
             };
@@ -1073,6 +1081,7 @@ impl Widget for Map<'_> {
                 MarkerKind::Cultist => self.imgs.mmap_site_cultist,
                 MarkerKind::Sahagin => self.imgs.mmap_site_sahagin,
                 MarkerKind::Myrmidon => self.imgs.mmap_site_myrmidon,
+                MarkerKind::ProceduralDungeon => self.imgs.mmap_site_dungeon,
                 MarkerKind::DwarvenMine => self.imgs.mmap_site_mine,
                 MarkerKind::VampireCastle => self.imgs.mmap_site_vampire_castle,
 
@@ -1100,6 +1109,7 @@ impl Widget for Map<'_> {
                 MarkerKind::Cultist => self.imgs.mmap_site_cultist_hover,
                 MarkerKind::Sahagin => self.imgs.mmap_site_sahagin_hover,
                 MarkerKind::Myrmidon => self.imgs.mmap_site_myrmidon_hover,
+                MarkerKind::ProceduralDungeon => self.imgs.mmap_site_dungeon_hover,
                 MarkerKind::DwarvenMine => self.imgs.mmap_site_mine_hover,
                 MarkerKind::VampireCastle => self.imgs.mmap_site_vampire_castle_hover,
                 MarkerKind::Bridge => self.imgs.mmap_site_bridge_hover,
@@ -1126,6 +1136,7 @@ impl Widget for Map<'_> {
                     | MarkerKind::Cultist
                     | MarkerKind::Sahagin
                     | MarkerKind::Myrmidon
+                    | MarkerKind::ProceduralDungeon
                     | MarkerKind::DwarvenMine => match difficulty {
                         Some(0) => QUALITY_LOW,
                         Some(1) => QUALITY_COMMON,
@@ -1158,6 +1169,7 @@ impl Widget for Map<'_> {
                 | MarkerKind::Cultist
                 | MarkerKind::Sahagin
                 | MarkerKind::Myrmidon
+                | MarkerKind::ProceduralDungeon
                 | MarkerKind::Terracotta
                 | MarkerKind::Adlet
                 | MarkerKind::VampireCastle => show_dungeons,
@@ -1233,6 +1245,7 @@ impl Widget for Map<'_> {
                     | MarkerKind::Cultist
                     | MarkerKind::Sahagin
                     | MarkerKind::Myrmidon
+                    | MarkerKind::ProceduralDungeon
                     | MarkerKind::Terracotta
                     | MarkerKind::Adlet
                     | MarkerKind::VampireCastle => {
@@ -763,6 +763,7 @@ impl Widget for MiniMap<'_> {
                     MarkerKind::Cultist => self.imgs.mmap_site_cultist_bg,
                     MarkerKind::Sahagin => self.imgs.mmap_site_sahagin_bg,
                     MarkerKind::Myrmidon => self.imgs.mmap_site_myrmidon_bg,
+                    MarkerKind::ProceduralDungeon => self.imgs.mmap_site_dungeon_bg,
                     MarkerKind::DwarvenMine => self.imgs.mmap_site_mine_bg,
                     MarkerKind::VampireCastle => self.imgs.mmap_site_vampire_castle_bg,
                     MarkerKind::Character => self.imgs.mmap_character,
@@ -799,6 +800,7 @@ impl Widget for MiniMap<'_> {
                     MarkerKind::Cultist => self.imgs.mmap_site_cultist,
                     MarkerKind::Sahagin => self.imgs.mmap_site_sahagin,
                     MarkerKind::Myrmidon => self.imgs.mmap_site_myrmidon,
+                    MarkerKind::ProceduralDungeon => self.imgs.mmap_site_dungeon,
                     MarkerKind::DwarvenMine => self.imgs.mmap_site_mine,
                     MarkerKind::VampireCastle => self.imgs.mmap_site_vampire_castle,
                     MarkerKind::Character => self.imgs.mmap_character,
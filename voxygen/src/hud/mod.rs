@@ -54,7 +54,7 @@ use map::Map;
 use minimap::{MiniMap, VoxelMinimap};
 use popup::Popup;
 use prompt_dialog::PromptDialog;
-use quest::Quest;
+use quest::{Quest, QuestLog, QuestTracker};
 use serde::{Deserialize, Serialize};
 use settings_window::{SettingsTab, SettingsWindow};
 use skillbar::Skillbar;
@@ -336,6 +336,8 @@ widget_ids! {
         small_window,
         social_window,
         quest_window,
+        quest_log_window,
+        quest_tracker,
         crafting_window,
         settings_window,
         group_window,
@@ -764,6 +766,7 @@ pub enum Event {
     AcknowledgePersistenceLoadError,
     MapMarkerEvent(MapMarkerChange),
     Dialogue(EcsEntity, rtsim::Dialogue),
+    AbandonQuest(rtsim::QuestId),
     SetBattleMode(BattleMode),
 }
 
@@ -936,6 +939,7 @@ pub struct Show {
     diary: bool,
     group: bool,
     quest: bool,
+    quest_log: bool,
     group_menu: bool,
     esc_menu: bool,
     open_windows: Windows,
@@ -987,6 +991,7 @@ impl Show {
             self.crafting_fields.salvage = false;
             self.social = false;
             self.quest = false;
+            self.quest_log = false;
             self.diary = false;
             self.want_grab = !self.any_window_requires_cursor();
         }
@@ -1013,6 +1018,15 @@ impl Show {
         }
     }
 
+    fn quest_log(&mut self, open: bool) {
+        if !self.esc_menu {
+            self.quest_log = open;
+            self.diary = false;
+            self.map = false;
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
     fn crafting(&mut self, open: bool) {
         if !self.esc_menu {
             if !self.crafting && open {
@@ -1050,6 +1064,7 @@ impl Show {
         if !self.esc_menu {
             self.social = false;
             self.quest = false;
+            self.quest_log = false;
             self.crafting = false;
             self.crafting_fields.salvage = false;
             self.bag = false;
@@ -1070,6 +1085,7 @@ impl Show {
             self.bag = false;
             self.social = false;
             self.quest = false;
+            self.quest_log = false;
             self.crafting = false;
             self.crafting_fields.salvage = false;
             self.diary = false;
@@ -1087,6 +1103,8 @@ impl Show {
 
     fn toggle_diary(&mut self) { self.diary(!self.diary) }
 
+    fn toggle_quest_log(&mut self) { self.quest_log(!self.quest_log) }
+
     fn toggle_ui(&mut self) { self.ui = !self.ui; }
 
     fn toggle_settings(&mut self, global_state: &GlobalState) {
@@ -1120,6 +1138,7 @@ impl Show {
             || self.diary
             || self.intro
             || self.quest
+            || self.quest_log
             || !matches!(self.open_windows, Windows::None)
     }
 
@@ -1132,6 +1151,7 @@ impl Show {
             self.map = false;
             self.social = false;
             self.quest = false;
+            self.quest_log = false;
             self.diary = false;
             self.crafting = false;
             self.open_windows = Windows::None;
@@ -1323,6 +1343,7 @@ pub struct Hud {
     clear_chat: bool,
     current_dialogue: Option<(EcsEntity, Instant, rtsim::Dialogue<true>)>,
     extra_markers: Vec<map::ExtraMarker>,
+    tracked_quest: Option<rtsim::QuestId>,
 }
 
 impl Hud {
@@ -1426,6 +1447,7 @@ impl Hud {
                 group: false,
                 // Change this before implementation!
                 quest: false,
+                quest_log: false,
                 group_menu: false,
                 chat_tab_settings_index: None,
                 settings_tab: SettingsTab::Interface,
@@ -1465,6 +1487,7 @@ impl Hud {
             clear_chat: false,
             current_dialogue: None,
             extra_markers: Vec::new(),
+            tracked_quest: None,
         }
     }
 
@@ -3122,6 +3145,42 @@ impl Hud {
         )
         .set(self.ids.popup, ui_widgets);
 
+        // Quest markers, which replace the markers NPCs gave out for the same quests.
+        // The minimap only shows the tracked quest.
+        let quests = client.quests();
+        if self.tracked_quest.is_some_and(|id| {
+            !quests
+                .iter()
+                .any(|quest| quest.id == id && quest.resolution.is_none())
+        }) {
+            self.tracked_quest = None;
+        }
+        let with_quest_markers = |tracked_only: bool| {
+            let quest_markers = quests
+                .iter()
+                .filter(|quest| {
+                    quest.resolution.is_none()
+                        && (!tracked_only || self.tracked_quest == Some(quest.id))
+                })
+                .filter_map(|quest| quest.marker.clone())
+                .collect::<Vec<_>>();
+            self.extra_markers
+                .iter()
+                .filter(|em| {
+                    !quest_markers
+                        .iter()
+                        .any(|marker| marker.is_same(&em.marker))
+                })
+                .cloned()
+                .chain(quest_markers.into_iter().map(|marker| map::ExtraMarker {
+                    recv_pos: marker.wpos,
+                    marker,
+                }))
+                .collect::<Vec<_>>()
+        };
+        let minimap_markers = with_quest_markers(true);
+        let map_markers = with_quest_markers(false);
+
         let persisted_state = self.persisted_state.borrow();
         // MiniMap
         for event in MiniMap::new(
@@ -3135,7 +3194,7 @@ impl Hud {
             global_state,
             &persisted_state.location_markers,
             &self.voxel_minimap,
-            &self.extra_markers,
+            &minimap_markers,
         )
         .set(self.ids.minimap, ui_widgets)
         {
@@ -3751,6 +3810,43 @@ impl Hud {
             }));
         }
 
+        // Quest Log
+        if self.show.quest_log {
+            for event in QuestLog::new(
+                client,
+                &self.imgs,
+                &self.fonts,
+                i18n,
+                &self.item_imgs,
+                self.tracked_quest,
+                self.pulse,
+            )
+            .set(self.ids.quest_log_window, ui_widgets)
+            {
+                match event {
+                    quest::LogEvent::Close => {
+                        self.show.quest_log(false);
+                        self.show.want_grab = true;
+                        self.force_ungrab = false;
+                    },
+                    quest::LogEvent::Track(quest) => self.tracked_quest = quest,
+                    quest::LogEvent::Abandon(quest) => events.push(Event::AbandonQuest(quest)),
+                    quest::LogEvent::ShowOnMap => self.show.map(true),
+                }
+            }
+        }
+
+        // Tracked quest
+        if let Some(quest) = self
+            .tracked_quest
+            .and_then(|id| client.quests().iter().find(|quest| quest.id == id))
+        {
+            QuestTracker::new(&self.fonts, i18n, quest)
+                .w_h(240.0, 70.0)
+                .top_right_with_margins_on(ui_widgets.window, 300.0, 5.0)
+                .set(self.ids.quest_tracker, ui_widgets);
+        }
+
         // Social Window
         if self.show.social {
             let ecs = client.state().ecs();
@@ -3879,7 +3975,7 @@ impl Hud {
                 tooltip_manager,
                 &persisted_state.location_markers,
                 self.map_drag,
-                &self.extra_markers,
+                &map_markers,
             )
             .set(self.ids.map, ui_widgets)
             {
@@ -4926,6 +5022,10 @@ impl Hud {
                         self.show.toggle_diary();
                         true
                     },
+                    GameInput::QuestLog if state => {
+                        self.show.toggle_quest_log();
+                        true
+                    },
                     GameInput::Settings if state => {
                         self.show.toggle_settings(global_state);
                         true
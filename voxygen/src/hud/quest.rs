@@ -5,7 +5,8 @@ use common::{
 };
 use conrod_core::{
     Borderable, Color, Colorable, Positionable, Sizeable, UiCell, Widget, WidgetCommon, color,
-    widget::{self, Button, Image, Rectangle, Scrollbar, Text},
+    position::{Place, Relative},
+    widget::{self, Button, Image, Rectangle, Scrollbar, Text, button},
     widget_ids,
 };
 use i18n::Localization;
@@ -389,3 +390,429 @@ impl Widget for Quest<'_> {
         event
     }
 }
+
+widget_ids! {
+    pub struct LogIds {
+        bg,
+        title,
+        close,
+        list_align,
+        list_scrollbar,
+        details_align,
+        no_quests_txt,
+        quest_btns[],
+        objective_txt,
+        contact_txt,
+        destination_txt,
+        reward_txt,
+        reward_icon,
+        reward_amount,
+        time_txt,
+        status_txt,
+        track_btn,
+        map_btn,
+        abandon_btn,
+    }
+}
+
+pub struct LogState {
+    ids: LogIds,
+    selected: Option<rtsim::QuestId>,
+}
+
+/// The quest journal, listing the quests the character takes part in
+#[derive(WidgetCommon)]
+pub struct QuestLog<'a> {
+    client: &'a Client,
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    item_imgs: &'a ItemImgs,
+    tracked: Option<rtsim::QuestId>,
+    pulse: f32,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> QuestLog<'a> {
+    pub fn new(
+        client: &'a Client,
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        item_imgs: &'a ItemImgs,
+        tracked: Option<rtsim::QuestId>,
+        pulse: f32,
+    ) -> Self {
+        Self {
+            client,
+            imgs,
+            fonts,
+            localized_strings,
+            item_imgs,
+            tracked,
+            pulse,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+
+    fn text<'b>(&self, txt: &'b str) -> Text<'b> {
+        Text::new(txt)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .w(370.0 - LOG_MARGIN * 2.0)
+            .wrap_by_word()
+    }
+
+    fn action_btn<'b>(&self, label: &'b str) -> Button<'b, button::Image> {
+        Button::image(self.imgs.button)
+            .w_h(110.0, 24.0)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .label(label)
+            .label_color(TEXT_COLOR)
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .label_font_size(self.fonts.cyri.scale(12))
+    }
+
+    fn status(&self, quest: &rtsim::QuestInfo) -> (Cow<'a, str>, Color) {
+        match quest.resolution {
+            None => (
+                self.localized_strings.get_msg("hud-quest-status-active"),
+                TEXT_COLOR,
+            ),
+            Some(true) => (
+                self.localized_strings.get_msg("hud-quest-status-completed"),
+                QUEST_COMPLETED_COLOR,
+            ),
+            Some(false) => (
+                self.localized_strings.get_msg("hud-quest-status-failed"),
+                QUEST_FAILED_COLOR,
+            ),
+        }
+    }
+}
+
+pub enum LogEvent {
+    Close,
+    Track(Option<rtsim::QuestId>),
+    Abandon(rtsim::QuestId),
+    ShowOnMap,
+}
+
+const LOG_MARGIN: f64 = 10.0;
+const QUEST_COMPLETED_COLOR: Color = Color::Rgba(0.4, 1.0, 0.4, 1.0);
+const QUEST_FAILED_COLOR: Color = Color::Rgba(1.0, 0.4, 0.4, 1.0);
+
+impl Widget for QuestLog<'_> {
+    type Event = Vec<LogEvent>;
+    type State = LogState;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        LogState {
+            ids: LogIds::new(id_gen),
+            selected: None,
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let mut events = Vec::new();
+        let quests = self.client.quests();
+
+        const BACKGROUND: Color = Color::Rgba(0.0, 0.0, 0.0, 0.85);
+
+        Rectangle::fill_with([620.0, 360.0], BACKGROUND)
+            .mid_left_with_margin_on(ui.window, 60.0)
+            .set(state.ids.bg, ui);
+        Text::new(&self.localized_strings.get_msg("hud-quest-log"))
+            .mid_top_with_margin_on(state.ids.bg, 8.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(18))
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+        if Button::image(self.imgs.close_button)
+            .w_h(24.0, 25.0)
+            .hover_image(self.imgs.close_button_hover)
+            .press_image(self.imgs.close_button_press)
+            .top_right_with_margins_on(state.ids.bg, 2.0, 2.0)
+            .set(state.ids.close, ui)
+            .was_clicked()
+        {
+            events.push(LogEvent::Close);
+        }
+
+        // Quest list on the left
+        Rectangle::fill_with([220.0, 310.0], color::TRANSPARENT)
+            .top_left_with_margins_on(state.ids.bg, 40.0, LOG_MARGIN)
+            .scroll_kids_vertically()
+            .set(state.ids.list_align, ui);
+        Scrollbar::y_axis(state.ids.list_align)
+            .thickness(5.0)
+            .auto_hide(true)
+            .rgba(1.0, 1.0, 1.0, 0.2)
+            .set(state.ids.list_scrollbar, ui);
+        // Details on the right
+        Rectangle::fill_with([370.0, 310.0], color::TRANSPARENT)
+            .top_right_with_margins_on(state.ids.bg, 40.0, LOG_MARGIN)
+            .set(state.ids.details_align, ui);
+
+        if quests.is_empty() {
+            Text::new(&self.localized_strings.get_msg("hud-quest-log-empty"))
+                .top_left_with_margins_on(state.ids.list_align, LOG_MARGIN, LOG_MARGIN)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_COLOR)
+                .set(state.ids.no_quests_txt, ui);
+            return events;
+        }
+
+        if state.ids.quest_btns.len() < quests.len() {
+            state.update(|s| {
+                s.ids
+                    .quest_btns
+                    .resize(quests.len(), &mut ui.widget_id_generator())
+            });
+        }
+
+        // Keep the selection on a quest that still exists
+        let selected = state
+            .selected
+            .filter(|id| quests.iter().any(|quest| quest.id == *id))
+            .or(self
+                .tracked
+                .filter(|id| quests.iter().any(|quest| quest.id == *id)))
+            .unwrap_or(quests[0].id);
+        if state.selected != Some(selected) {
+            state.update(|s| s.selected = Some(selected));
+        }
+
+        for (i, quest) in quests.iter().enumerate() {
+            let (_, status_color) = self.status(quest);
+            let label = quest
+                .contact
+                .as_deref()
+                .map_or_else(
+                    || self.localized_strings.get_msg("hud-quest"),
+                    |contact| {
+                        self.localized_strings.get_msg_ctx(
+                            "hud-quest-log-entry",
+                            &i18n::fluent_args! { "name" => contact },
+                        )
+                    },
+                )
+                .into_owned();
+            let label = if self.tracked == Some(quest.id) {
+                format!("> {label}")
+            } else {
+                label
+            };
+            let btn = Button::new()
+                .border_color(color::TRANSPARENT)
+                .color(if quest.id == selected {
+                    Color::Rgba(1.0, 1.0, 1.0, 0.1)
+                } else {
+                    Color::Rgba(1.0, 1.0, 1.0, 0.0)
+                })
+                .hover_color(Color::Rgba(1.0, 1.0, 1.0, 0.05))
+                .press_color(Color::Rgba(1.0, 1.0, 1.0, 0.1))
+                .parent(state.ids.list_align)
+                .w_h(215.0, 26.0)
+                .label(&label)
+                .label_color(status_color)
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .label_font_size(self.fonts.cyri.scale(13))
+                .label_x(Relative::Place(Place::Start(Some(8.0))));
+            let btn = if i == 0 {
+                btn.top_left_with_margins_on(state.ids.list_align, 0.0, 0.0)
+            } else {
+                btn.down_from(state.ids.quest_btns[i - 1], 0.0)
+            };
+            if btn.set(state.ids.quest_btns[i], ui).was_clicked() {
+                state.update(|s| s.selected = Some(quest.id));
+            }
+        }
+
+        let Some(quest) = quests.iter().find(|quest| quest.id == selected) else {
+            return events;
+        };
+
+        self.text(&self.localized_strings.get_content(&quest.objective))
+            .top_left_with_margins_on(state.ids.details_align, 0.0, LOG_MARGIN)
+            .set(state.ids.objective_txt, ui);
+        let mut last = state.ids.objective_txt;
+
+        if let Some(contact) = &quest.contact {
+            self.text(&self.localized_strings.get_msg_ctx(
+                "hud-quest-contact",
+                &i18n::fluent_args! { "name" => contact },
+            ))
+            .down_from(last, 12.0)
+            .set(state.ids.contact_txt, ui);
+            last = state.ids.contact_txt;
+        }
+
+        if let Some(site) = &quest.target_site {
+            self.text(&self.localized_strings.get_msg_ctx(
+                "hud-quest-destination",
+                &i18n::fluent_args! { "place" => site },
+            ))
+            .down_from(last, 6.0)
+            .set(state.ids.destination_txt, ui);
+            last = state.ids.destination_txt;
+        }
+
+        if let Some((item, amount)) = &quest.reward {
+            self.text(&self.localized_strings.get_msg("hud-quest-log-reward"))
+                .down_from(last, 6.0)
+                .set(state.ids.reward_txt, ui);
+            let item_def = item.to_equivalent_item_def();
+            Image::new(animate_by_pulse(
+                &self
+                    .item_imgs
+                    .img_ids_or_not_found_img(ItemKey::from(&*item_def)),
+                self.pulse,
+            ))
+            .right_from(state.ids.reward_txt, 8.0)
+            .w_h(20.0, 20.0)
+            .set(state.ids.reward_icon, ui);
+            Text::new(&format!("x{amount}"))
+                .right_from(state.ids.reward_icon, 4.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(12))
+                .color(TEXT_COLOR)
+                .set(state.ids.reward_amount, ui);
+            last = state.ids.reward_txt;
+        }
+
+        if quest.resolution.is_none()
+            && let Some(timeout) = quest.timeout
+        {
+            let now = self.client.state().get_time();
+            let mins_left = ((timeout.0 - now) / 60.0).ceil().max(0.0) as u64;
+            self.text(&self.localized_strings.get_msg_ctx(
+                "hud-quest-time-left",
+                &i18n::fluent_args! { "minutes" => mins_left },
+            ))
+            .down_from(last, 6.0)
+            .set(state.ids.time_txt, ui);
+            last = state.ids.time_txt;
+        }
+
+        let (status, status_color) = self.status(quest);
+        self.text(&status)
+            .color(status_color)
+            .down_from(last, 12.0)
+            .set(state.ids.status_txt, ui);
+
+        // Actions, only for quests that can still be completed
+        if quest.resolution.is_some() {
+            return events;
+        }
+        let is_tracked = self.tracked == Some(quest.id);
+        if self
+            .action_btn(&self.localized_strings.get_msg(if is_tracked {
+                "hud-quest-untrack"
+            } else {
+                "hud-quest-track"
+            }))
+            .bottom_left_with_margins_on(state.ids.details_align, 0.0, LOG_MARGIN)
+            .set(state.ids.track_btn, ui)
+            .was_clicked()
+        {
+            events.push(LogEvent::Track((!is_tracked).then_some(quest.id)));
+        }
+        if quest.marker.is_some()
+            && self
+                .action_btn(&self.localized_strings.get_msg("hud-quest-show-on-map"))
+                .right_from(state.ids.track_btn, 8.0)
+                .set(state.ids.map_btn, ui)
+                .was_clicked()
+        {
+            events.push(LogEvent::Track(Some(quest.id)));
+            events.push(LogEvent::ShowOnMap);
+        }
+        if self
+            .action_btn(&self.localized_strings.get_msg("hud-quest-abandon"))
+            .bottom_right_with_margins_on(state.ids.details_align, 0.0, 0.0)
+            .set(state.ids.abandon_btn, ui)
+            .was_clicked()
+        {
+            events.push(LogEvent::Abandon(quest.id));
+        }
+
+        events
+    }
+}
+
+widget_ids! {
+    pub struct TrackerIds {
+        bg,
+        title,
+        objective,
+    }
+}
+
+/// Shows the objective of the tracked quest below the minimap
+#[derive(WidgetCommon)]
+pub struct QuestTracker<'a> {
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    quest: &'a rtsim::QuestInfo,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> QuestTracker<'a> {
+    pub fn new(
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        quest: &'a rtsim::QuestInfo,
+    ) -> Self {
+        Self {
+            fonts,
+            localized_strings,
+            quest,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+impl Widget for QuestTracker<'_> {
+    type Event = ();
+    type State = TrackerIds;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State { TrackerIds::new(id_gen) }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, ui, .. } = args;
+
+        Rectangle::fill_with([240.0, 70.0], Color::Rgba(0.0, 0.0, 0.0, 0.4))
+            .middle_of(id)
+            .graphics_for(id)
+            .set(state.bg, ui);
+        Text::new(&self.localized_strings.get_msg("hud-quest-tracked"))
+            .top_left_with_margins_on(state.bg, 6.0, 8.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(12))
+            .color(Color::Rgba(1.0, 0.8, 0.3, 1.0))
+            .set(state.title, ui);
+        Text::new(&self.localized_strings.get_content(&self.quest.objective))
+            .down_from(state.title, 4.0)
+            .w(224.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(12))
+            .color(TEXT_COLOR)
+            .wrap_by_word()
+            .set(state.objective, ui);
+    }
+}
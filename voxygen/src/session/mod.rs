@@ -2211,6 +2211,9 @@ impl PlayState for SessionState {
                     HudEvent::Dialogue(target, dialogue) => {
                         self.client.borrow_mut().perform_dialogue(target, dialogue);
                     },
+                    HudEvent::AbandonQuest(quest) => {
+                        self.client.borrow_mut().abandon_quest(quest);
+                    },
                     HudEvent::SetBattleMode(mode) => {
                         self.client.borrow_mut().set_battle_mode(mode);
                     },
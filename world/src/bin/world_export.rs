@@ -0,0 +1,249 @@
+//! Export the maps and features of a world, for use by external tools such as
+//! community maps and wikis.
+//!
+//! The altitude, water, biome and temperature maps are written as 16-bit
+//! greyscale PNGs with one pixel per chunk, north up. Sites, roads, cave
+//! entrances and points of interest are written as GeoJSON, in world
+//! coordinates (blocks, with y pointing north). A JSON sidecar describes the
+//! world and how to turn pixel values back into the quantities they encode.
+
+use clap::Parser;
+use common::terrain::{BiomeKind, TerrainChunkSize};
+use image::{ImageBuffer, Luma};
+use serde_json::{Value, json};
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+use strum::IntoEnumIterator;
+use vek::*;
+use veloren_world::{
+    CONFIG, IndexRef, Land, World,
+    civ::PoiKind,
+    layer,
+    sim::{DEFAULT_WORLD_SEED, FileOpts, GenOpts, WorldOpts, WorldSim},
+    site::SiteKind,
+};
+
+#[derive(Parser)]
+struct Cli {
+    /// Seed of the world
+    #[arg(short, long, default_value_t = DEFAULT_WORLD_SEED)]
+    seed: u32,
+    /// Saved world file to load, rather than generating the world from the
+    /// seed
+    #[arg(short, long)]
+    world_file: Option<PathBuf>,
+    /// Base 2 logarithm of the width and height, in chunks, of a generated
+    /// world
+    #[arg(long, default_value_t = 10)]
+    size_lg: u32,
+    /// Directory that the exported files are written to
+    #[arg(short, long, default_value = "world_export")]
+    out: PathBuf,
+}
+
+const ALTITUDE_FILE: &str = "altitude.png";
+const WATER_FILE: &str = "water.png";
+const BIOME_FILE: &str = "biome.png";
+const TEMPERATURE_FILE: &str = "temperature.png";
+const FEATURES_FILE: &str = "features.geojson";
+const METADATA_FILE: &str = "world.json";
+
+/// The range of temperatures that the temperature map covers.
+const TEMPERATURE_RANGE: (f32, f32) = (-1.0, 1.0);
+
+/// Write a map of the world as a 16-bit greyscale PNG, mapping values in the
+/// range `lo..=hi` to the full range of a pixel.
+fn write_map(
+    path: &Path,
+    sim: &WorldSim,
+    (lo, hi): (f32, f32),
+    mut f: impl FnMut(Vec2<i32>) -> f32,
+) -> Result<(), Box<dyn Error>> {
+    let size = sim.get_size();
+    let image = ImageBuffer::<Luma<u16>, _>::from_fn(size.x, size.y, |x, y| {
+        // Images go from top to bottom, while the world goes from south to north
+        let pos = Vec2::new(x, size.y - y - 1).as_();
+        let value = ((f(pos) - lo) / (hi - lo)).clamp(0.0, 1.0);
+        Luma([(value * u16::MAX as f32).round() as u16])
+    });
+    image.save(path)?;
+    Ok(())
+}
+
+fn site_kind_name(kind: &SiteKind) -> String {
+    match kind {
+        SiteKind::Bridge(..) => "Bridge".to_string(),
+        kind => format!("{kind:?}"),
+    }
+}
+
+fn point(wpos: Vec2<i32>, properties: Value) -> Value {
+    json!({
+        "type": "Feature",
+        "geometry": { "type": "Point", "coordinates": [wpos.x, wpos.y] },
+        "properties": properties,
+    })
+}
+
+fn features(world: &World, index: IndexRef) -> Value {
+    let cpos_to_wpos = |cpos: Vec2<i32>| cpos * TerrainChunkSize::RECT_SIZE.as_::<i32>();
+    let chunk_center =
+        |cpos: Vec2<i32>| cpos_to_wpos(cpos) + TerrainChunkSize::RECT_SIZE.as_::<i32>() / 2;
+    let civs = world.civs();
+
+    let sites = civs.sites.values().map(|site| {
+        let name = site
+            .site_tmp
+            .and_then(|id| index.sites[id].name())
+            .unwrap_or_default();
+        point(
+            cpos_to_wpos(site.center),
+            json!({
+                "feature": "site",
+                "kind": site_kind_name(&site.kind),
+                "name": name,
+            }),
+        )
+    });
+
+    let roads = civs.tracks.values().map(|track| {
+        let coordinates = track
+            .path()
+            .iter()
+            .map(|cpos| {
+                let wpos = chunk_center(*cpos);
+                [wpos.x, wpos.y]
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "type": "Feature",
+            "geometry": { "type": "LineString", "coordinates": coordinates },
+            "properties": { "feature": "road", "cost": track.cost },
+        })
+    });
+
+    let caves = layer::cave::surface_entrances(&Land::from_sim(world.sim()), index)
+        .map(|wpos| point(wpos, json!({ "feature": "cave_entrance" })));
+
+    let pois = civs.pois.values().map(|poi| {
+        let (kind, value) = match poi.kind {
+            PoiKind::Peak(alt) => ("peak", alt),
+            PoiKind::Biome(size) => ("lake", size),
+        };
+        point(
+            cpos_to_wpos(poi.loc),
+            json!({
+                "feature": "poi",
+                "kind": kind,
+                "name": poi.name,
+                // The altitude of peaks, or a measure of the size of lakes
+                "value": value,
+            }),
+        )
+    });
+
+    json!({
+        "type": "FeatureCollection",
+        "features": sites.chain(roads).chain(caves).chain(pois).collect::<Vec<_>>(),
+    })
+}
+
+/// Describe a map, with the range of values that it covers.
+fn map_info(file: &str, unit: &str, (lo, hi): (f32, f32)) -> Value {
+    json!({ "file": file, "unit": unit, "min": lo, "max": hi })
+}
+
+fn metadata(sim: &WorldSim, altitude_range: (f32, f32)) -> Value {
+    let size = sim.get_size();
+    json!({
+        "seed": sim.seed,
+        "map_size_lg": { "x": sim.map_size_lg().vec().x, "y": sim.map_size_lg().vec().y },
+        "size_chunks": { "x": size.x, "y": size.y },
+        "chunk_size": TerrainChunkSize::RECT_SIZE.x,
+        "sea_level": CONFIG.sea_level,
+        "max_height": sim.max_height,
+        "maps": {
+            "altitude": map_info(ALTITUDE_FILE, "blocks", altitude_range),
+            "water": map_info(WATER_FILE, "blocks of depth", altitude_range),
+            "temperature": map_info(TEMPERATURE_FILE, "relative", TEMPERATURE_RANGE),
+            "biome": {
+                "file": BIOME_FILE,
+                // Pixel values index into this list
+                "kinds": BiomeKind::iter().map(|kind| format!("{kind:?}")).collect::<Vec<_>>(),
+            },
+        },
+        "features": FEATURES_FILE,
+    })
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Cli::parse();
+
+    let world_file = match args.world_file {
+        Some(path) => FileOpts::Load(path),
+        None => FileOpts::Generate(GenOpts {
+            x_lg: args.size_lg,
+            y_lg: args.size_lg,
+            ..GenOpts::default()
+        }),
+    };
+
+    let pool = rayon::ThreadPoolBuilder::new().build()?;
+    println!("Generating world");
+    let (world, index) = World::generate(
+        args.seed,
+        WorldOpts {
+            seed_elements: true,
+            world_file,
+            calendar: None,
+        },
+        &pool,
+        &|_| {},
+    );
+    let index = index.as_index_ref();
+    let sim = world.sim();
+    fs::create_dir_all(&args.out)?;
+
+    println!("Writing maps");
+    let altitude_range = (0.0, CONFIG.sea_level + sim.max_height);
+    let chunk = |pos: Vec2<i32>| sim.get(pos).expect("Position is within the world");
+    write_map(&args.out.join(ALTITUDE_FILE), sim, altitude_range, |pos| {
+        chunk(pos).alt
+    })?;
+    write_map(&args.out.join(WATER_FILE), sim, altitude_range, |pos| {
+        (chunk(pos).water_alt - chunk(pos).alt).max(0.0)
+    })?;
+    write_map(
+        &args.out.join(TEMPERATURE_FILE),
+        sim,
+        TEMPERATURE_RANGE,
+        |pos| chunk(pos).temp,
+    )?;
+    let biomes = BiomeKind::iter().collect::<Vec<_>>();
+    write_map(
+        &args.out.join(BIOME_FILE),
+        sim,
+        (0.0, u16::MAX as f32),
+        |pos| {
+            let biome = chunk(pos).get_biome();
+            biomes.iter().position(|b| *b == biome).unwrap_or(0) as f32
+        },
+    )?;
+
+    println!("Writing features");
+    serde_json::to_writer(
+        BufWriter::new(File::create(args.out.join(FEATURES_FILE))?),
+        &features(&world, index),
+    )?;
+    serde_json::to_writer_pretty(
+        BufWriter::new(File::create(args.out.join(METADATA_FILE))?),
+        &metadata(sim, altitude_range),
+    )?;
+
+    println!("Exported world to {}", args.out.display());
+    Ok(())
+}
@@ -0,0 +1,346 @@
+//! Regression snapshots of world generation.
+//!
+//! A small world is generated from a fixed seed, and every chunk of it is
+//! generated and hashed, along with the sites of the world and its altitude
+//! and biome maps. Chunks are generated deterministically, and only their
+//! blocks and sprites are hashed, since many sites still pick the loadouts of
+//! the entities they spawn at random. `check` compares the result against the
+//! snapshot committed in `world/snapshots`, reporting the chunks and sites that
+//! changed and writing an image of where the world differs. When a change to
+//! world generation is intentional, `update` replaces the committed snapshot.
+//!
+//! Run with `cargo world-snapshot check` or `cargo world-snapshot update`.
+
+use clap::{Parser, Subcommand};
+use common::{
+    terrain::{BiomeKind, TerrainChunk, TerrainChunkSize},
+    vol::{IntoVolIterator, RectVolSize},
+};
+use fxhash::FxHasher64;
+use image::{ImageBuffer, Luma, Rgb};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fs,
+    hash::Hasher,
+    path::{Path, PathBuf},
+};
+use strum::IntoEnumIterator;
+use vek::*;
+use veloren_world::{
+    CONFIG, World,
+    sim::{FileOpts, GenOpts, WorldOpts, WorldSim},
+    site::SiteKind,
+};
+
+/// Seed of the snapshot world. Changing it invalidates the snapshot.
+const SEED: u32 = 1337;
+/// Base 2 logarithm of the width and height, in chunks, of the snapshot world.
+const SIZE_LG: u32 = 6;
+
+const SNAPSHOT_FILE: &str = "snapshot.ron";
+const ALTITUDE_FILE: &str = "altitude.png";
+const BIOME_FILE: &str = "biome.png";
+const DIFF_FILE: &str = "diff.png";
+
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Directory of the committed snapshot
+    #[arg(short, long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/snapshots"))]
+    dir: PathBuf,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Compare the generated world against the snapshot, failing if it differs
+    Check {
+        /// Directory that the image of the differences is written to
+        #[arg(short, long, default_value = "world_snapshot_diff")]
+        out: PathBuf,
+    },
+    /// Replace the snapshot with the generated world
+    Update,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct SiteSnapshot {
+    kind: String,
+    name: String,
+    /// Position of the site, in chunks
+    pos: (i32, i32),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WorldSnapshot {
+    seed: u32,
+    size_lg: u32,
+    sites: Vec<SiteSnapshot>,
+    /// Hash of the blocks and sprites of each chunk, by position
+    chunks: BTreeMap<(i32, i32), u64>,
+}
+
+/// The maps of a world, with one value per chunk in the same layout as the
+/// images they are stored as.
+struct Maps {
+    altitude: ImageBuffer<Luma<u16>, Vec<u16>>,
+    biome: ImageBuffer<Luma<u8>, Vec<u8>>,
+}
+
+impl Maps {
+    fn generate(sim: &WorldSim) -> Self {
+        let size = sim.get_size();
+        let max_alt = CONFIG.sea_level + sim.max_height;
+        let biomes = BiomeKind::iter().collect::<Vec<_>>();
+        let chunk = |x: u32, y: u32| {
+            // Images go from top to bottom, while the world goes from south to north
+            sim.get(Vec2::new(x, size.y - y - 1).as_())
+                .expect("Position is within the world")
+        };
+        Self {
+            altitude: ImageBuffer::from_fn(size.x, size.y, |x, y| {
+                let alt = (chunk(x, y).alt / max_alt).clamp(0.0, 1.0);
+                Luma([(alt * u16::MAX as f32).round() as u16])
+            }),
+            biome: ImageBuffer::from_fn(size.x, size.y, |x, y| {
+                let biome = chunk(x, y).get_biome();
+                Luma([biomes.iter().position(|b| *b == biome).unwrap_or(0) as u8])
+            }),
+        }
+    }
+
+    fn load(dir: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            altitude: image::open(dir.join(ALTITUDE_FILE))?.into_luma16(),
+            biome: image::open(dir.join(BIOME_FILE))?.into_luma8(),
+        })
+    }
+
+    fn save(&self, dir: &Path) -> Result<(), Box<dyn Error>> {
+        self.altitude.save(dir.join(ALTITUDE_FILE))?;
+        self.biome.save(dir.join(BIOME_FILE))?;
+        Ok(())
+    }
+}
+
+/// Hash the blocks and sprites that world generation produces for a chunk. The
+/// hasher is fed explicitly sized values, so that the hash does not depend on
+/// the platform.
+fn hash_chunk(chunk: &TerrainChunk) -> u64 {
+    let mut hasher = FxHasher64::default();
+    hasher.write_i32(chunk.get_min_z());
+    hasher.write_i32(chunk.get_max_z());
+    let lo = Vec3::new(0, 0, chunk.get_min_z());
+    let hi = TerrainChunkSize::RECT_SIZE.as_().with_z(chunk.get_max_z());
+    for (rpos, block) in chunk.vol_iter(lo, hi) {
+        hasher.write_u32(block.to_u32());
+        if block.get_sprite().is_some()
+            && let Some(cfg) = chunk.sprite_cfg_at(rpos)
+        {
+            hasher.write(format!("{cfg:?}").as_bytes());
+        }
+    }
+    hasher.finish()
+}
+
+fn site_kind_name(kind: &SiteKind) -> String {
+    match kind {
+        SiteKind::Bridge(..) => "Bridge".to_string(),
+        kind => format!("{kind:?}"),
+    }
+}
+
+fn generate() -> (WorldSnapshot, Maps) {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .build()
+        .expect("Failed to build thread pool");
+    println!("Generating world");
+    let (mut world, index) = World::generate(
+        SEED,
+        WorldOpts {
+            seed_elements: true,
+            world_file: FileOpts::Generate(GenOpts {
+                x_lg: SIZE_LG,
+                y_lg: SIZE_LG,
+                ..GenOpts::default()
+            }),
+            calendar: None,
+        },
+        &pool,
+        &|_| {},
+    );
+    world.set_deterministic_chunks(true);
+    let index = index.as_index_ref();
+    let sim = world.sim();
+
+    let sites = world
+        .civs()
+        .sites
+        .values()
+        .map(|site| SiteSnapshot {
+            kind: site_kind_name(&site.kind),
+            name: site
+                .site_tmp
+                .and_then(|id| index.sites[id].name())
+                .unwrap_or_default()
+                .to_string(),
+            pos: site.center.into_tuple(),
+        })
+        .collect();
+
+    println!("Generating chunks");
+    let size = sim.get_size().as_::<i32>();
+    let chunks = pool.install(|| {
+        (0..size.x)
+            .flat_map(|x| (0..size.y).map(move |y| (x, y)))
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|pos| {
+                let (chunk, _) = world
+                    .generate_chunk(index, pos.into(), None, None, || false, None)
+                    .expect("Chunk generation is never cancelled");
+                (pos, hash_chunk(&chunk))
+            })
+            .collect()
+    });
+
+    (
+        WorldSnapshot {
+            seed: SEED,
+            size_lg: SIZE_LG,
+            sites,
+            chunks,
+        },
+        Maps::generate(sim),
+    )
+}
+
+fn update(dir: &Path) -> Result<(), Box<dyn Error>> {
+    let (snapshot, maps) = generate();
+    fs::create_dir_all(dir)?;
+    fs::write(
+        dir.join(SNAPSHOT_FILE),
+        ron::ser::to_string_pretty(&snapshot, ron::ser::PrettyConfig::default())?,
+    )?;
+    maps.save(dir)?;
+    println!("Updated snapshot in {}", dir.display());
+    Ok(())
+}
+
+/// Report the sites that were added, removed or changed, keyed by position.
+fn diff_sites(old: &[SiteSnapshot], new: &[SiteSnapshot]) -> Vec<String> {
+    let by_pos = |sites: &[SiteSnapshot]| {
+        sites
+            .iter()
+            .map(|site| (site.pos, site.clone()))
+            .collect::<BTreeMap<_, _>>()
+    };
+    let (old, new) = (by_pos(old), by_pos(new));
+    let removed = old
+        .iter()
+        .filter(|(pos, _)| !new.contains_key(pos))
+        .map(|(_, site)| format!("removed {} '{}' at {:?}", site.kind, site.name, site.pos));
+    let changed = new.iter().filter_map(|(pos, site)| match old.get(pos) {
+        None => Some(format!(
+            "added {} '{}' at {:?}",
+            site.kind, site.name, site.pos
+        )),
+        Some(old) if old != site => Some(format!(
+            "{} '{}' at {:?} became {} '{}'",
+            old.kind, old.name, site.pos, site.kind, site.name
+        )),
+        Some(_) => None,
+    });
+    removed.chain(changed).collect()
+}
+
+fn check(dir: &Path, out: &Path) -> Result<(), Box<dyn Error>> {
+    let old = fs::read_to_string(dir.join(SNAPSHOT_FILE)).map_err(|e| {
+        format!(
+            "Could not read the snapshot in {} ({e}), create it with `cargo world-snapshot update`",
+            dir.display()
+        )
+    })?;
+    let old = ron::from_str::<WorldSnapshot>(&old)?;
+    let old_maps = Maps::load(dir)?;
+    if (old.seed, old.size_lg) != (SEED, SIZE_LG) {
+        return Err("The snapshot was made with a different seed or size, update it".into());
+    }
+
+    let (new, new_maps) = generate();
+
+    // Chunks that changed, or are only in one of the snapshot and the new run
+    let changed_chunks = old
+        .chunks
+        .keys()
+        .chain(new.chunks.keys())
+        .filter(|pos| old.chunks.get(pos) != new.chunks.get(pos))
+        .copied()
+        .collect::<BTreeSet<_>>();
+    let changed_sites = diff_sites(&old.sites, &new.sites);
+    let changed_maps = old_maps.altitude != new_maps.altitude || old_maps.biome != new_maps.biome;
+
+    if changed_chunks.is_empty() && changed_sites.is_empty() && !changed_maps {
+        println!("World generation matches the snapshot");
+        return Ok(());
+    }
+
+    for pos in &changed_chunks {
+        match (old.chunks.contains_key(pos), new.chunks.contains_key(pos)) {
+            (true, false) => println!("Chunk {pos:?} is missing"),
+            (false, true) => println!("Chunk {pos:?} was added"),
+            _ => println!("Chunk {pos:?} changed"),
+        }
+    }
+    for site in &changed_sites {
+        println!("Site {site}");
+    }
+
+    // Altitude in grey, with changed altitudes in red, changed biomes in blue
+    // and chunks whose blocks changed in yellow
+    let (w, h) = new_maps.altitude.dimensions();
+    let diff = ImageBuffer::<Rgb<u8>, _>::from_fn(w, h, |x, y| {
+        let alt = new_maps.altitude.get_pixel(x, y).0[0];
+        let grey = (alt >> 9) as u8;
+        let old_alt = old_maps.altitude.get_pixel_checked(x, y).map(|p| p.0[0]);
+        let old_biome = old_maps.biome.get_pixel_checked(x, y).map(|p| p.0[0]);
+        let chunk_pos = (x as i32, (h - y - 1) as i32);
+        if old_alt != Some(alt) {
+            Rgb([255, grey / 2, grey / 2])
+        } else if old_biome != Some(new_maps.biome.get_pixel(x, y).0[0]) {
+            Rgb([grey / 2, grey / 2, 255])
+        } else if changed_chunks.contains(&chunk_pos) {
+            Rgb([255, 255, grey / 2])
+        } else {
+            Rgb([grey, grey, grey])
+        }
+    });
+    fs::create_dir_all(out)?;
+    diff.save(out.join(DIFF_FILE))?;
+
+    Err(format!(
+        "World generation differs from the snapshot: {} of {} chunks and {} sites changed{}. See \
+         {} for where. If this is intended, update the snapshot with `cargo world-snapshot update`",
+        changed_chunks.len(),
+        old.chunks.len().max(new.chunks.len()),
+        changed_sites.len(),
+        if changed_maps {
+            ", and the maps differ"
+        } else {
+            ""
+        },
+        out.join(DIFF_FILE).display(),
+    )
+    .into())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Cli::parse();
+    match args.command {
+        Command::Check { out } => check(&args.dir, &out),
+        Command::Update => update(&args.dir),
+    }
+}
@@ -489,7 +489,10 @@ pub fn block_from_structure<'a>(
             }
         },
         StructureBlock::Choice(block_table) => block_table
-            .choose_weighted(&mut rand::rng(), |(w, _)| *w)
+            .choose_weighted(
+                &mut ChaChaRng::from_seed(seed_expan::rng_state(field.get(pos))),
+                |(w, _)| *w,
+            )
             .map(|(_, item)| {
                 block_from_structure(
                     index,
@@ -504,5 +507,9 @@ pub fn block_from_structure<'a>(
                 )
             })
             .unwrap_or(None),
+        StructureBlock::Block(block) => Some((*block, None, None)),
+        StructureBlock::BlockWithCfg(block, sprite_cfg) => {
+            Some((*block, Some(sprite_cfg.clone()), None))
+        },
     }
 }
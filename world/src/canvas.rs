@@ -118,6 +118,7 @@ impl<'a> CanvasInfo<'a> {
             path: Default::default(),
             cliff_height: 0.0,
             contains_waypoint: false,
+            water_route: false,
             spot: None,
         };
         f(&CanvasInfo {
@@ -2,13 +2,17 @@
 
 pub mod airship_travel;
 mod econ;
+pub mod water_routes;
 
 #[cfg(feature = "airship_maps")]
 pub mod airship_route_map;
 
 use crate::{
     Index, IndexRef, Land,
-    civ::airship_travel::Airships,
+    civ::{
+        airship_travel::Airships,
+        water_routes::{WaterRouteKind, WaterRoutes},
+    },
     config::CONFIG,
     sim::WorldSim,
     site::{self, Site as WorldSite, SiteKind, SitesGenMeta, namegen::NameGen},
@@ -57,6 +61,7 @@ pub struct Civs {
 
     pub sites: Store<Site>,
     pub airships: Airships,
+    pub water_routes: WaterRoutes,
 }
 
 // Change this to get rid of particularly horrid seeds
@@ -272,7 +277,7 @@ impl Civs {
         let world_dims = ctx.sim.get_aabr();
         for _ in 0..initial_civ_count * 3 {
             attempt(5, || {
-                let (loc, kind) = match ctx.rng.random_range(0..116) {
+                let (loc, kind) = match ctx.rng.random_range(0..121) {
                     0..=4 => (
                         find_site_loc(
                             &mut ctx,
@@ -431,6 +436,16 @@ impl Civs {
                         )?,
                         SiteKind::GliderCourse,
                     ),
+                    108..=112 => (
+                        find_site_loc(
+                            &mut ctx,
+                            &ProximityRequirementsBuilder::new()
+                                .avoid_all_of(this.procedural_dungeon_enemies(), 40)
+                                .finalize(&world_dims),
+                            &SiteKind::ProceduralDungeon,
+                        )?,
+                        SiteKind::ProceduralDungeon,
+                    ),
                     /*103..=108 => (
                         find_site_loc(
                             &mut ctx,
@@ -498,6 +513,7 @@ impl Civs {
                 SiteKind::VampireCastle => (10i32, 16.0),
                 SiteKind::GliderCourse => (0, 0.0),
                 SiteKind::Myrmidon => (64i32, 35.0),
+                SiteKind::ProceduralDungeon => (8i32, 3.0),
             };
 
             // Flatten ground
@@ -696,6 +712,11 @@ impl Civs {
                     SiteKind::VampireCastle => {
                         WorldSite::generate_vampire_castle(&Land::from_sim(ctx.sim), &mut rng, wpos)
                     },
+                    SiteKind::ProceduralDungeon => WorldSite::generate_procedural_dungeon(
+                        &Land::from_sim(ctx.sim),
+                        &mut rng,
+                        wpos,
+                    ),
                 }
             });
             sim_site.site_tmp = Some(site);
@@ -741,6 +762,32 @@ impl Civs {
             }
         }
 
+        prof_span!(guard, "generate water routes");
+        this.water_routes = WaterRoutes::generate(ctx.sim, &this.sites);
+        // Settlements that trade by boat are neighbours in the economy, even without a
+        // road between them
+        for route in &this.water_routes.routes {
+            let [a, b] = route.docks.map(|dock| &this.water_routes.docks[dock]);
+            if route.kind == WaterRouteKind::Trade
+                && this.track_between(a.civ_site, b.civ_site).is_none()
+                && index.sites.get(a.site).do_economic_simulation()
+                && index.sites.get(b.site).do_economic_simulation()
+            {
+                let cost = route.path.len();
+                index
+                    .sites
+                    .get_mut(a.site)
+                    .economy_mut()
+                    .add_neighbor(b.site, cost);
+                index
+                    .sites
+                    .get_mut(b.site)
+                    .economy_mut()
+                    .add_neighbor(a.site, cost);
+            }
+        }
+        drop(guard);
+
         prof_span!(guard, "generate airship routes");
         this.airships.generate_airship_routes(ctx.sim, index);
         drop(guard);
@@ -1447,6 +1494,10 @@ impl Civs {
         self.sites().map(|s| s.center)
     }
 
+    fn procedural_dungeon_enemies(&self) -> impl Iterator<Item = Vec2<i32>> + '_ {
+        self.sites().map(|s| s.center)
+    }
+
     fn tree_enemies(&self) -> impl Iterator<Item = Vec2<i32>> + '_ {
         self.sites().map(|s| s.center)
     }
@@ -1949,6 +2000,9 @@ impl SiteKind {
                 },
                 SiteKind::Cultist => on_land() && chunk.temp < 0.5 && chunk.near_cliffs(),
                 SiteKind::VampireCastle => on_land() && chunk.temp <= -0.8 && chunk.near_cliffs(),
+                SiteKind::ProceduralDungeon => {
+                    on_land() && on_flat_terrain() && !chunk.river.near_water()
+                },
                 SiteKind::Refactor => suitable_for_town(),
                 SiteKind::Bridge(_, _) => true,
             }
@@ -1991,6 +2045,7 @@ impl Site {
                 | SiteKind::Cultist
                 | SiteKind::Sahagin
                 | SiteKind::VampireCastle
+                | SiteKind::ProceduralDungeon
         )
     }
 
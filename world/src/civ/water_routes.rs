@@ -0,0 +1,358 @@
+//! The network of routes that boats sail along, between docks at coastal and
+//! riverside settlements.
+//!
+//! Routes are found over chunks of water that are wide and deep enough for the
+//! boat that sails them, and only follow rivers along their course, so a boat
+//! following a route never has to leave the water.
+
+use crate::{
+    civ::Site,
+    sim::{RiverKind, SimChunk, WorldSim},
+    site::Site as WorldSite,
+    util::{DHashSet, NEIGHBORS},
+};
+use common::{
+    astar::Astar,
+    comp::ship,
+    path::Path,
+    spiral::Spiral2d,
+    store::{Id, Store},
+    terrain::{CoordinateConversions, SiteKindMeta, TerrainChunkSize, river_spline_coeffs},
+};
+use core::hash::BuildHasherDefault;
+use fxhash::FxHasher64;
+use vek::*;
+
+/// How far from the center of a settlement, in chunks, a dock may be placed.
+const DOCK_SEARCH_RADIUS: i32 = 6;
+/// How many of its nearest neighbours each dock tries to find a route to.
+const MAX_ROUTES_PER_DOCK: usize = 3;
+/// Docks further apart than this, in chunks, are never connected directly.
+const MAX_ROUTE_DIST: i32 = 160;
+/// Routes of at most this many chunks are served by ferries, longer ones by
+/// trading ships.
+const FERRY_MAX_LEN: usize = 40;
+/// Travelling up a river is slower than crossing open water.
+const RIVER_COST: f32 = 2.0;
+/// How many stretches the course of a river through a chunk is split into
+/// when following it.
+const RIVER_WAYPOINTS: usize = 4;
+
+/// A place at a settlement where boats moor.
+#[derive(Clone, Debug)]
+pub struct WaterDock {
+    /// The civ site that the dock belongs to.
+    pub civ_site: Id<Site>,
+    /// The world site that the dock belongs to.
+    pub site: Id<WorldSite>,
+    /// The chunk of water that boats moor in.
+    pub chunk: Vec2<i32>,
+}
+
+impl WaterDock {
+    /// Where boats moor, in world coordinates.
+    pub fn wpos(&self) -> Vec2<f32> { TerrainChunkSize::center_wpos(self.chunk).as_() }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WaterRouteKind {
+    /// A short crossing, shuttled along by small boats.
+    Ferry,
+    /// A long voyage between settlements, sailed by trading ships.
+    Trade,
+}
+
+impl WaterRouteKind {
+    /// The kind of boat that sails routes of this kind.
+    pub fn boat(&self) -> ship::Body {
+        match self {
+            WaterRouteKind::Ferry => ship::Body::SailBoat,
+            WaterRouteKind::Trade => ship::Body::Galleon,
+        }
+    }
+}
+
+/// A route over water between two docks.
+#[derive(Clone, Debug)]
+pub struct WaterRoute {
+    pub kind: WaterRouteKind,
+    /// Indices of the docks at either end, in [`WaterRoutes::docks`].
+    pub docks: [usize; 2],
+    /// The chunks of the route, from `docks[0]` to `docks[1]`.
+    pub path: Path<Vec2<i32>>,
+    /// The positions, in world coordinates, that a boat sails through to
+    /// follow the route from `docks[0]` to `docks[1]`.
+    pub waypoints: Vec<Vec2<f32>>,
+}
+
+/// The docks and routes of boats. This is generated world data.
+#[derive(Clone, Default)]
+pub struct WaterRoutes {
+    pub docks: Vec<WaterDock>,
+    pub routes: Vec<WaterRoute>,
+}
+
+/// Whether a boat floats in a chunk. The water has to be deeper than the boat
+/// is tall, and rivers have to be as wide as the boat is long, so that it can
+/// turn around.
+fn can_sail_in(chunk: &SimChunk, boat: ship::Body) -> bool {
+    let size = boat.dimensions();
+    match chunk.river.river_kind {
+        Some(RiverKind::Ocean | RiverKind::Lake { .. }) => chunk.water_alt - chunk.alt >= size.z,
+        Some(RiverKind::River { cross_section }) => {
+            cross_section.x >= size.y && cross_section.y >= size.z
+        },
+        None => false,
+    }
+}
+
+/// If one of two neighbouring chunks is a river that flows into the other, the
+/// one that is upstream.
+fn upstream(sim: &WorldSim, a: Vec2<i32>, b: Vec2<i32>) -> Option<Vec2<i32>> {
+    let flows_into = |from: Vec2<i32>, to: Vec2<i32>| {
+        sim.get(from).is_some_and(|chunk| {
+            matches!(chunk.river.river_kind, Some(RiverKind::River { .. }))
+                && chunk.downhill.map(|wpos| wpos.wpos_to_cpos()) == Some(to)
+        })
+    };
+    if flows_into(a, b) {
+        Some(a)
+    } else if flows_into(b, a) {
+        Some(b)
+    } else {
+        None
+    }
+}
+
+/// The cost of sailing from a chunk to a neighbouring one, if a boat can sail
+/// between them at all. Rivers may only be followed along their course, since
+/// the land between two rivers that run side by side cannot be sailed across.
+fn sailing_cost(sim: &WorldSim, from: Vec2<i32>, to: Vec2<i32>, boat: ship::Body) -> Option<f32> {
+    let is_river =
+        |chunk: &SimChunk| matches!(chunk.river.river_kind, Some(RiverKind::River { .. }));
+    let (from_chunk, to_chunk) = (sim.get(from)?, sim.get(to)?);
+    if !can_sail_in(to_chunk, boat) {
+        None
+    } else if is_river(from_chunk) || is_river(to_chunk) {
+        upstream(sim, from, to).map(|_| RIVER_COST)
+    } else {
+        Some(1.0)
+    }
+}
+
+/// The positions, in world coordinates, that a boat sails through to follow a
+/// path. Where the path follows a river, the waypoints follow the course of the
+/// river between the centres of its chunks, which rivers always pass through.
+fn waypoints(sim: &WorldSim, path: &Path<Vec2<i32>>) -> Vec<Vec2<f32>> {
+    let center = |chunk: Vec2<i32>| TerrainChunkSize::center_wpos(chunk).as_::<f64>();
+    let mut waypoints = Vec::new();
+    for (i, chunk) in path.iter().enumerate() {
+        waypoints.push(center(*chunk).as_());
+        let Some(next) = path.nodes.get(i + 1) else {
+            continue;
+        };
+        if let Some(upstream) = upstream(sim, *chunk, *next)
+            && let Some(upstream_chunk) = sim.get(upstream)
+        {
+            let downstream = if upstream == *chunk { *next } else { *chunk };
+            let coeffs = river_spline_coeffs(
+                center(upstream),
+                upstream_chunk.river.spline_derivative,
+                center(downstream),
+            );
+            let mut course = (1..RIVER_WAYPOINTS)
+                .map(|i| {
+                    let t = i as f64 / RIVER_WAYPOINTS as f64;
+                    (coeffs.x * t * t + coeffs.y * t + coeffs.z).as_::<f32>()
+                })
+                .collect::<Vec<_>>();
+            if upstream != *chunk {
+                course.reverse();
+            }
+            waypoints.extend(course);
+        }
+    }
+    waypoints
+}
+
+/// Find the cheapest path over water between two chunks, given the cost of
+/// sailing from one chunk to a neighbouring one.
+fn find_water_path(
+    cost: impl Fn(Vec2<i32>, Vec2<i32>) -> Option<f32>,
+    a: Vec2<i32>,
+    b: Vec2<i32>,
+) -> Option<(Path<Vec2<i32>>, f32)> {
+    const MAX_PATH_ITERS: usize = 50_000;
+    let heuristic = move |l: &Vec2<i32>| (l.distance_squared(b) as f32).sqrt();
+    let neighbors = |l: &Vec2<i32>| {
+        let l = *l;
+        NEIGHBORS.into_iter().filter_map(move |dir| {
+            let next = l + dir;
+            let step = (dir.magnitude_squared() as f32).sqrt();
+            cost(l, next).map(|cost| (next, cost * step))
+        })
+    };
+    let satisfied = |l: &Vec2<i32>| *l == b;
+    // We use this hasher (FxHasher64) because
+    // (1) we don't care about DDOS attacks (ruling out SipHash);
+    // (2) we care about determinism across computers (ruling out AAHash);
+    // (3) we have 8-byte keys (for which FxHash is fastest).
+    let mut astar = Astar::new(
+        MAX_PATH_ITERS,
+        a,
+        BuildHasherDefault::<FxHasher64>::default(),
+    )
+    .with_max_cost(MAX_ROUTE_DIST as f32 * RIVER_COST);
+    astar
+        .poll(MAX_PATH_ITERS, heuristic, neighbors, satisfied)
+        .into_path()
+}
+
+impl WaterRoutes {
+    /// Place docks at the settlements that are near enough to navigable water,
+    /// and connect each of them to its nearest neighbours that can be reached
+    /// by boat. Nearby docks are connected by ferries where sail boats can
+    /// reach them, and distant ones by trading ships where galleons can. The
+    /// chunks of each route are marked for the world map.
+    ///
+    /// Docks are only places on the water for now, settlements do not build
+    /// anything at them.
+    pub fn generate(sim: &mut WorldSim, sites: &Store<Site>) -> Self {
+        let docks = sites
+            .iter()
+            .filter(|(_, site)| matches!(site.kind.meta(), Some(SiteKindMeta::Settlement(_))))
+            .filter_map(|(civ_site, site)| {
+                let chunk = Spiral2d::new()
+                    .take((DOCK_SEARCH_RADIUS as usize * 2 + 1).pow(2))
+                    .map(|offs| site.center + offs)
+                    .find(|chunk| {
+                        sim.get(*chunk)
+                            .is_some_and(|chunk| can_sail_in(chunk, ship::Body::SailBoat))
+                    })?;
+                Some(WaterDock {
+                    civ_site,
+                    site: site.site_tmp?,
+                    chunk,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut connected = DHashSet::default();
+        let mut routes = Vec::new();
+        for (i, dock) in docks.iter().enumerate() {
+            let mut nearest = docks
+                .iter()
+                .enumerate()
+                .filter(|(j, other)| {
+                    *j != i && other.chunk.distance_squared(dock.chunk) <= MAX_ROUTE_DIST.pow(2)
+                })
+                .collect::<Vec<_>>();
+            nearest.sort_by_key(|(_, other)| other.chunk.distance_squared(dock.chunk));
+
+            for (j, other) in nearest.into_iter().take(MAX_ROUTES_PER_DOCK) {
+                if !connected.insert((i.min(j), i.max(j))) {
+                    continue;
+                }
+                let find_path = |kind: WaterRouteKind| {
+                    let boat = kind.boat();
+                    if [dock, other].iter().any(|dock| {
+                        sim.get(dock.chunk)
+                            .is_none_or(|chunk| !can_sail_in(chunk, boat))
+                    }) {
+                        return None;
+                    }
+                    find_water_path(
+                        |from, to| sailing_cost(sim, from, to, boat),
+                        dock.chunk,
+                        other.chunk,
+                    )
+                    .map(|(path, _)| (kind, path))
+                };
+                // Galleons are too large for most rivers, so long routes have to be
+                // found again for them
+                let route = match find_path(WaterRouteKind::Ferry) {
+                    Some((kind, path)) if path.len() <= FERRY_MAX_LEN => Some((kind, path)),
+                    _ => find_path(WaterRouteKind::Trade),
+                };
+                if let Some((kind, path)) = route {
+                    routes.push(WaterRoute {
+                        kind,
+                        docks: [i, j],
+                        waypoints: waypoints(sim, &path),
+                        path,
+                    });
+                }
+            }
+        }
+
+        for route in &routes {
+            for chunk in route.path.iter() {
+                if let Some(chunk) = sim.get_mut(*chunk) {
+                    chunk.water_route = true;
+                }
+            }
+        }
+
+        Self { docks, routes }
+    }
+
+    /// The dock closest to a position, if there is one within `max_dist`
+    /// blocks of it.
+    pub fn dock_near(&self, wpos: Vec2<f32>, max_dist: f32) -> Option<usize> {
+        self.docks
+            .iter()
+            .enumerate()
+            .map(|(i, dock)| (i, dock.wpos().distance_squared(wpos)))
+            .filter(|(_, dist2)| *dist2 <= max_dist.powi(2))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i)
+    }
+
+    /// The routes that depart from a dock, and whether they have to be sailed
+    /// in reverse to do so.
+    pub fn routes_from(&self, dock: usize) -> impl Iterator<Item = (usize, bool)> + '_ {
+        self.routes
+            .iter()
+            .enumerate()
+            .filter_map(move |(i, route)| match route.docks {
+                [a, _] if a == dock => Some((i, false)),
+                [_, b] if b == dock => Some((i, true)),
+                _ => None,
+            })
+    }
+
+    /// The positions, in world coordinates, that a boat sails through to
+    /// follow a route.
+    pub fn waypoints(&self, route: usize, reversed: bool) -> Vec<Vec2<f32>> {
+        let mut waypoints = self.routes[route].waypoints.clone();
+        if reversed {
+            waypoints.reverse();
+        }
+        waypoints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn water_paths_stay_in_water() {
+        // A lake split by a wall of land, with a gap at the north end
+        let cost = |_: Vec2<i32>, chunk: Vec2<i32>| {
+            let in_lake = (0..10).contains(&chunk.x) && (0..10).contains(&chunk.y);
+            let in_wall = chunk.x == 5 && chunk.y < 8;
+            (in_lake && !in_wall).then_some(1.0)
+        };
+        let (path, _) = find_water_path(cost, Vec2::new(2, 2), Vec2::new(8, 2))
+            .expect("The two halves of the lake are connected");
+        assert_eq!(path.nodes.first(), Some(&Vec2::new(2, 2)));
+        assert_eq!(path.nodes.last(), Some(&Vec2::new(8, 2)));
+        assert!(path.iter().all(|chunk| cost(*chunk, *chunk).is_some()));
+        assert!(path.iter().any(|chunk| chunk.y >= 8));
+
+        // Closing the gap separates the two halves
+        let closed = |from: Vec2<i32>, chunk: Vec2<i32>| cost(from, chunk).filter(|_| chunk.x != 5);
+        assert!(find_water_path(closed, Vec2::new(2, 2), Vec2::new(8, 2)).is_none());
+    }
+}
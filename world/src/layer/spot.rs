@@ -5,13 +5,12 @@ use crate::{
 };
 use common::{
     generation::EntityInfo,
-    spot::{RON_SPOT_PROPERTIES, Spot, SpotCondition},
-    terrain::{BiomeKind, Structure, TerrainChunkSize},
+    spot::{Spot, SpotCondition},
+    terrain::{Structure, TerrainChunkSize},
     vol::RectVolSize,
 };
 use rand::{prelude::*, seq::IndexedRandom};
 use rand_chacha::ChaChaRng;
-use std::ops::Range;
 use vek::*;
 
 pub trait SpotGenerate {
@@ -28,307 +27,16 @@ pub trait SpotGenerate {
 
 impl SpotGenerate for Spot {
     fn generate(world: &mut WorldSim) {
-        use BiomeKind::*;
-        // Trees/spawn: false => *No* trees around the spot
-        // Themed Spots -> Act as an introduction to themes of sites
-        for s in RON_SPOT_PROPERTIES.0.iter() {
+        for spot in Spot::iter() {
+            let properties = spot.properties();
             Self::generate_spots(
-                Spot::RonFile(s),
+                spot,
                 world,
-                s.freq,
-                |g, c| is_valid(&s.condition, g, c),
-                s.spawn,
+                properties.freq,
+                |g, c| is_valid(&properties.condition, g, c),
+                properties.spawn,
             );
         }
-        Self::generate_spots(
-            Spot::WitchHouse,
-            world,
-            1.0,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(
-                        c.get_biome(),
-                        Grassland | Forest | Taiga | Snowland | Jungle
-                    )
-            },
-            false,
-        );
-        Self::generate_spots(
-            Spot::Igloo,
-            world,
-            2.0,
-            |g, c| {
-                g < 0.5
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Snowland)
-            },
-            false,
-        );
-        Self::generate_spots(
-            Spot::SaurokAltar,
-            world,
-            1.0,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Jungle | Forest)
-            },
-            false,
-        );
-        Self::generate_spots(
-            Spot::SaurokTotem,
-            world,
-            1.0,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Jungle | Forest)
-            },
-            false,
-        );
-        Self::generate_spots(
-            Spot::JungleOutpost,
-            world,
-            1.0,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Jungle | Forest)
-            },
-            false,
-        );
-        Self::generate_spots(
-            Spot::JungleTemple,
-            world,
-            0.5,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Jungle | Forest)
-            },
-            false,
-        );
-        Self::generate_spots(
-            Spot::MyrmidonTemple,
-            world,
-            1.0,
-            |g, c| {
-                g < 0.1
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Desert | Jungle)
-            },
-            false,
-        );
-        Self::generate_spots(
-            Spot::GnarlingTotem,
-            world,
-            1.5,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Forest | Grassland)
-            },
-            false,
-        );
-        Self::generate_spots(
-            Spot::FallenTree,
-            world,
-            1.5,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Forest | Grassland)
-            },
-            false,
-        );
-        // Random World Objects -> Themed to their Biome and the NPCs that regularly
-        // spawn there
-        Self::generate_spots(
-            Spot::LionRock,
-            world,
-            1.5,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Savannah)
-            },
-            false,
-        );
-        Self::generate_spots(
-            Spot::WolfBurrow,
-            world,
-            1.5,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Forest | Grassland)
-            },
-            false,
-        );
-        Self::generate_spots(
-            Spot::TreeStumpForest,
-            world,
-            20.0,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Jungle | Forest)
-            },
-            true,
-        );
-        Self::generate_spots(
-            Spot::DesertBones,
-            world,
-            6.0,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Desert)
-            },
-            false,
-        );
-        Self::generate_spots(
-            Spot::Arch,
-            world,
-            2.0,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Desert)
-            },
-            false,
-        );
-        Self::generate_spots(
-            Spot::AirshipCrash,
-            world,
-            0.7,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && !matches!(c.get_biome(), Mountain | Void | Ocean)
-            },
-            false,
-        );
-        Self::generate_spots(
-            Spot::FruitTree,
-            world,
-            20.0,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Forest)
-            },
-            true,
-        );
-        Self::generate_spots(
-            Spot::GnomeSpring,
-            world,
-            1.0,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Forest)
-            },
-            false,
-        );
-        Self::generate_spots(
-            Spot::Shipwreck,
-            world,
-            1.0,
-            |g, c| {
-                g < 0.25 && c.is_underwater() && c.sites.is_empty() && c.water_alt > c.alt + 30.0
-            },
-            true,
-        );
-        Self::generate_spots(
-            Spot::Shipwreck2,
-            world,
-            1.0,
-            |g, c| {
-                g < 0.25 && c.is_underwater() && c.sites.is_empty() && c.water_alt > c.alt + 30.0
-            },
-            true,
-        );
-        // Small Grave
-        Self::generate_spots(
-            Spot::GraveSmall,
-            world,
-            2.0,
-            |g, c| {
-                g < 0.25
-                    && !c.near_cliffs()
-                    && !c.river.near_water()
-                    && !c.path.0.is_way()
-                    && c.sites.is_empty()
-                    && matches!(c.get_biome(), Forest | Taiga | Jungle | Grassland)
-            },
-            false,
-        );
-
-        // Missing:
-        /*
-        Bandit Camp
-        Hunter Camp
-        TowerRuinForest
-        TowerRuinDesert
-        WellOfLight
-        Merchant Outpost -> Near a road!
-        *Quirky:*
-        TreeHouse (Forest)
-        EnchantedRock (Forest, Jungle)
-        */
     }
 
     fn generate_spots(
@@ -366,174 +74,6 @@ impl SpotGenerate for Spot {
     }
 }
 
-#[derive(Default)]
-struct SpotConfig<'a> {
-    // The manifest containing a list of possible base structures for the spot (one will be
-    // chosen)
-    base_structures: Option<&'a str>,
-    // The maximum distance from the centre of the spot that entities will spawn
-    entity_radius: f32,
-    // The entities that should be spawned in the spot, from closest to furthest
-    // (count_range, spec)
-    // count_range = number of entities, chosen randomly within this range (not inclusive!)
-    // spec = Manifest spec for the entity kind
-    entities: &'a [(Range<i32>, &'a str)],
-}
-fn spot_config(spot: &Spot) -> SpotConfig<'_> {
-    match spot {
-        // Themed Spots
-        Spot::DwarvenGrave => SpotConfig {
-            base_structures: Some("spots_grasslands.dwarven_grave"),
-            entity_radius: 60.0,
-            entities: &[(6..12, "common.entity.spot.dwarf_grave_robber")],
-        },
-        Spot::SaurokAltar => SpotConfig {
-            base_structures: Some("spots.jungle.saurok-altar"),
-            entity_radius: 12.0,
-            entities: &[
-                (0..3, "common.entity.wild.aggressive.occult_saurok"),
-                (0..3, "common.entity.wild.aggressive.sly_saurok"),
-                (0..3, "common.entity.wild.aggressive.mighty_saurok"),
-            ],
-        },
-        Spot::SaurokTotem => SpotConfig {
-            base_structures: Some("spots.jungle.saurok_totem"),
-            entity_radius: 20.0,
-            entities: &[
-                (0..3, "common.entity.wild.aggressive.occult_saurok"),
-                (0..3, "common.entity.wild.aggressive.sly_saurok"),
-                (0..3, "common.entity.wild.aggressive.mighty_saurok"),
-            ],
-        },
-        Spot::JungleOutpost => SpotConfig {
-            base_structures: Some("spots.jungle.outpost"),
-            entity_radius: 40.0,
-            entities: &[(6..12, "common.entity.spot.grim_salvager")],
-        },
-        Spot::JungleTemple => SpotConfig {
-            base_structures: Some("spots.jungle.temple_small"),
-            entity_radius: 40.0,
-            entities: &[
-                (2..8, "common.entity.wild.aggressive.occult_saurok"),
-                (2..8, "common.entity.wild.aggressive.sly_saurok"),
-                (2..8, "common.entity.wild.aggressive.mighty_saurok"),
-            ],
-        },
-        Spot::MyrmidonTemple => SpotConfig {
-            base_structures: Some("spots.myrmidon-temple"),
-            entity_radius: 10.0,
-            entities: &[
-                (3..5, "common.entity.dungeon.myrmidon.hoplite"),
-                (3..5, "common.entity.dungeon.myrmidon.strategian"),
-                (2..3, "common.entity.dungeon.myrmidon.marksman"),
-            ],
-        },
-        Spot::WitchHouse => SpotConfig {
-            base_structures: Some("spots_general.witch_hut"),
-            entity_radius: 1.0,
-            entities: &[
-                (1..2, "common.entity.spot.witch_dark"),
-                (0..4, "common.entity.wild.peaceful.cat"),
-                (0..3, "common.entity.wild.peaceful.frog"),
-            ],
-        },
-        Spot::Igloo => SpotConfig {
-            base_structures: Some("spots_general.igloo"),
-            entity_radius: 2.0,
-            entities: &[
-                (3..5, "common.entity.dungeon.adlet.hunter"),
-                (3..5, "common.entity.dungeon.adlet.icepicker"),
-                (2..3, "common.entity.dungeon.adlet.tracker"),
-            ],
-        },
-        Spot::GnarlingTotem => SpotConfig {
-            base_structures: Some("site_structures.gnarling.totem"),
-            entity_radius: 30.0,
-            entities: &[
-                (3..5, "common.entity.dungeon.gnarling.mugger"),
-                (3..5, "common.entity.dungeon.gnarling.stalker"),
-                (3..5, "common.entity.dungeon.gnarling.logger"),
-                (2..4, "common.entity.dungeon.gnarling.mandragora"),
-                (1..3, "common.entity.wild.aggressive.deadwood"),
-                (1..2, "common.entity.dungeon.gnarling.woodgolem"),
-            ],
-        },
-        Spot::FallenTree => SpotConfig {
-            base_structures: Some("spots_grasslands.fallen_tree"),
-            entity_radius: 64.0,
-            entities: &[
-                (1..2, "common.entity.dungeon.gnarling.mandragora"),
-                (2..6, "common.entity.wild.aggressive.deadwood"),
-                (0..2, "common.entity.wild.aggressive.mossdrake"),
-            ],
-        },
-        // Random World Objects
-        Spot::LionRock => SpotConfig {
-            base_structures: Some("spots_savannah.lion_rock"),
-            entity_radius: 30.0,
-            entities: &[
-                (5..10, "common.entity.spot.female_lion"),
-                (1..2, "common.entity.wild.aggressive.male_lion"),
-            ],
-        },
-        Spot::WolfBurrow => SpotConfig {
-            base_structures: Some("spots_savannah.wolf_burrow"),
-            entity_radius: 10.0,
-            entities: &[(5..8, "common.entity.wild.aggressive.wolf")],
-        },
-        Spot::TreeStumpForest => SpotConfig {
-            base_structures: Some("trees.oak_stumps"),
-            entity_radius: 30.0,
-            entities: &[(0..2, "common.entity.wild.aggressive.deadwood")],
-        },
-        Spot::DesertBones => SpotConfig {
-            base_structures: Some("spots.bones"),
-            entity_radius: 40.0,
-            entities: &[(4..9, "common.entity.wild.aggressive.hyena")],
-        },
-        Spot::Arch => SpotConfig {
-            base_structures: Some("spots.arch"),
-            entity_radius: 50.0,
-            entities: &[],
-        },
-        Spot::AirshipCrash => SpotConfig {
-            base_structures: Some("trees.airship_crash"),
-            entity_radius: 20.0,
-            entities: &[(4..9, "common.entity.spot.grim_salvager")],
-        },
-        Spot::FruitTree => SpotConfig {
-            base_structures: Some("trees.fruit_trees"),
-            entity_radius: 2.0,
-            entities: &[(0..2, "common.entity.spot.bear")],
-        },
-        Spot::GnomeSpring => SpotConfig {
-            base_structures: Some("spots.gnome_spring"),
-            entity_radius: 40.0,
-            entities: &[(7..10, "common.entity.spot.gnome.spear")],
-        },
-        Spot::Shipwreck => SpotConfig {
-            base_structures: Some("spots.water.shipwreck"),
-            entity_radius: 2.0,
-            entities: &[(0..2, "common.entity.wild.peaceful.clownfish")],
-        },
-        Spot::Shipwreck2 => SpotConfig {
-            base_structures: Some("spots.water.shipwreck2"),
-            entity_radius: 20.0,
-            entities: &[(0..3, "common.entity.wild.peaceful.clownfish")],
-        },
-        Spot::GraveSmall => SpotConfig {
-            base_structures: Some("spots.grave_small"),
-            entity_radius: 2.0,
-            entities: &[],
-        },
-        Spot::RonFile(properties) => SpotConfig {
-            base_structures: Some(&properties.base_structures),
-            entity_radius: 1.0,
-            entities: &[],
-        },
-    }
-}
-
 pub fn apply_spots_to(canvas: &mut Canvas, _dynamic_rng: &mut impl Rng) {
     let nearby_spots = canvas.nearby_spots().collect::<Vec<_>>();
 
@@ -542,24 +82,23 @@ pub fn apply_spots_to(canvas: &mut Canvas, _dynamic_rng: &mut impl Rng) {
 
         let units = UnitChooser::new(seed).get(seed).into();
 
-        let spot_config = spot_config(&spot);
+        let properties = spot.properties();
         // Blit base structure
-        if let Some(base_structures) = spot_config.base_structures {
-            let structures = Structure::load_group(base_structures).read();
-            let structure = structures.choose(&mut rng).unwrap();
-            let origin = spot_wpos2d.with_z(
-                canvas
-                    .col_or_gen(spot_wpos2d)
-                    .map(|c| c.alt as i32)
-                    .unwrap_or(0),
-            );
-            canvas.blit_structure(origin, structure, seed, units, true);
-        }
+        let structures = Structure::load_group(&properties.base_structures).read();
+        let structure = structures.choose(&mut rng).unwrap();
+        let origin = spot_wpos2d.with_z(
+            canvas
+                .col_or_gen(spot_wpos2d)
+                .map(|c| c.alt as i32)
+                .unwrap_or(0),
+        );
+        canvas.blit_structure(origin, structure, seed, units, true);
 
         // Spawn entities
         const PHI: f32 = 1.618;
-        for (spawn_count, spec) in spot_config.entities {
-            let spawn_count = rng.random_range(spawn_count.clone()).max(0);
+        for entities in &properties.entities {
+            let (min, max) = entities.count;
+            let spawn_count = rng.random_range(min..=max.max(min));
 
             let dir_offset = rng.random::<f32>();
             for i in 0..spawn_count {
@@ -567,7 +106,7 @@ pub fn apply_spots_to(canvas: &mut Canvas, _dynamic_rng: &mut impl Rng) {
                     ((dir_offset + i as f32 * PHI) * std::f32::consts::TAU).sin(),
                     ((dir_offset + i as f32 * PHI) * std::f32::consts::TAU).cos(),
                 );
-                let dist = i as f32 / spawn_count as f32 * spot_config.entity_radius;
+                let dist = i as f32 / spawn_count as f32 * properties.entity_radius;
                 let wpos2d = spot_wpos2d + (dir * dist).map(|e| e.round() as i32);
 
                 let alt = canvas.col_or_gen(wpos2d).map(|c| c.alt as i32).unwrap_or(0);
@@ -578,10 +117,13 @@ pub fn apply_spots_to(canvas: &mut Canvas, _dynamic_rng: &mut impl Rng) {
                     .then(|| canvas.find_spawn_pos(wpos2d.with_z(alt)))
                     .flatten()
                 {
-                    canvas.spawn(
+                    let mut entity =
                         EntityInfo::at(wpos.map(|e| e as f32) + Vec3::new(0.5, 0.5, 0.0))
-                            .with_asset_expect(spec, &mut rng, None),
-                    );
+                            .with_asset_expect(&entities.entity, &mut rng, None);
+                    if let Some(loot) = &entities.loot {
+                        entity = entity.with_loot_drop(loot.clone());
+                    }
+                    canvas.spawn(entity);
                 }
             }
         }
@@ -611,40 +153,21 @@ pub fn is_valid(condition: &SpotCondition, g: f32, c: &SimChunk) -> bool {
 
 #[test]
 fn test_spot_configs() {
-    let all_spots = [
-        Spot::DwarvenGrave,
-        Spot::SaurokAltar,
-        Spot::MyrmidonTemple,
-        Spot::GnarlingTotem,
-        Spot::WitchHouse,
-        Spot::GnomeSpring,
-        Spot::WolfBurrow,
-        Spot::Igloo,
-        Spot::LionRock,
-        Spot::TreeStumpForest,
-        Spot::DesertBones,
-        Spot::Arch,
-        Spot::AirshipCrash,
-        Spot::FruitTree,
-        Spot::Shipwreck,
-        Spot::Shipwreck2,
-        Spot::FallenTree,
-        Spot::GraveSmall,
-        Spot::JungleTemple,
-        Spot::SaurokTotem,
-        Spot::JungleOutpost,
-    ];
-
-    for spot in all_spots
-        .into_iter()
-        .chain(RON_SPOT_PROPERTIES.0.iter().map(Spot::RonFile))
-    {
-        let config = spot_config(&spot);
-
-        if let Some(base_structures) = config.base_structures {
-            let _structures = Structure::load_group(base_structures).read();
+    let mut names = std::collections::HashSet::new();
+    for spot in Spot::iter() {
+        assert!(
+            names.insert(spot.name()),
+            "spot {} is defined more than once",
+            spot.name()
+        );
+
+        let properties = spot.properties();
+        let _structures = Structure::load_group(&properties.base_structures).read();
+        let mut rng = rand::rng();
+        for entities in &properties.entities {
+            assert!(entities.count.0 <= entities.count.1);
+            let _entity =
+                EntityInfo::at(Vec3::zero()).with_asset_expect(&entities.entity, &mut rng, None);
         }
-        // Entities are tested in another test so don't really need to test it
-        // here.
     }
 }
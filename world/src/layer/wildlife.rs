@@ -4,10 +4,12 @@ use common::{
     calendar::{Calendar, CalendarEvent},
     generation::{ChunkSupplement, EntityInfo},
     resources::TimeOfDay,
+    rtsim::WildlifeKind,
     terrain::{BiomeKind, Block},
     time::DayPeriod,
     vol::{ReadVol, RectSizedVol, WriteVol},
 };
+use enum_map::EnumMap;
 use rand::prelude::*;
 use serde::Deserialize;
 use std::f32;
@@ -552,6 +554,7 @@ pub fn apply_wildlife_supplement<'a, R: Rng>(
     chunk: &SimChunk,
     supplement: &mut ChunkSupplement,
     time: Option<&(TimeOfDay, Calendar)>,
+    rtsim_wildlife: Option<&EnumMap<WildlifeKind, f32>>,
 ) {
     let scatter = &index.wildlife_spawns;
     // Configurable density multiplier
@@ -617,6 +620,18 @@ pub fn apply_wildlife_supplement<'a, R: Rng>(
                     (wpos2d.map(|e| e as f32) + 0.5).with_z(desired_alt),
                     dynamic_rng,
                 );
+
+                // Spawn according to the wildlife population that rtsim tracks for the
+                // chunk, such that hunted out areas stay empty and booming ones get
+                // larger packs
+                let population = rtsim_wildlife
+                    .zip(WildlifeKind::from_alignment(&entity.alignment))
+                    .map_or(1.0, |(wildlife, kind)| wildlife[kind]);
+                if dynamic_rng.random::<f32>() >= population {
+                    continue;
+                }
+                let group_size = (group_size as f32 * population.max(1.0)).round() as u8;
+
                 for e in 0..group_size {
                     // Choose a nearby position
                     let offs_wpos2d = (Vec2::new(
@@ -53,7 +53,7 @@ use common::{
     lod,
     map::{Marker, MarkerKind},
     resources::TimeOfDay,
-    rtsim::TerrainResource,
+    rtsim::{TerrainResource, WildlifeKind},
     spiral::Spiral2d,
     spot::Spot,
     terrain::{
@@ -102,6 +102,9 @@ pub enum WorldGenerateStage {
 pub struct World {
     sim: sim::WorldSim,
     civs: civ::Civs,
+    /// Whether chests, entities and other dynamic elements of a chunk are the
+    /// same each time it is generated.
+    deterministic_chunks: bool,
 }
 
 #[derive(Deserialize)]
@@ -125,6 +128,7 @@ impl World {
             Self {
                 sim: sim::WorldSim::empty(),
                 civs: civ::Civs::default(),
+                deterministic_chunks: false,
             },
             IndexOwned::new(index),
         )
@@ -158,7 +162,14 @@ impl World {
             report_stage(WorldGenerateStage::SpotGeneration);
             Spot::generate(&mut sim);
 
-            (Self { sim, civs }, IndexOwned::new(index))
+            (
+                Self {
+                    sim,
+                    civs,
+                    deterministic_chunks: false,
+                },
+                IndexOwned::new(index),
+            )
         })
     }
 
@@ -166,6 +177,13 @@ impl World {
 
     pub fn civs(&self) -> &civ::Civs { &self.civs }
 
+    /// Seed the dynamic elements of chunks from the world seed and the position
+    /// of the chunk, rather than randomly, so that generating a chunk twice
+    /// gives the same result.
+    pub fn set_deterministic_chunks(&mut self, deterministic: bool) {
+        self.deterministic_chunks = deterministic;
+    }
+
     pub fn tick(&self, _dt: Duration) {
         // TODO
     }
@@ -329,7 +347,7 @@ impl World {
         // Unwrapping because generate_chunk only returns err when should_continue evals
         // to true
         let (tc, _cs) = self
-            .generate_chunk(index, chunk_pos, None, || false, None)
+            .generate_chunk(index, chunk_pos, None, None, || false, None)
             .unwrap();
 
         tc.find_accessible_pos(spawn_wpos, ascending)
@@ -341,6 +359,7 @@ impl World {
         index: IndexRef,
         chunk_pos: Vec2<i32>,
         rtsim_resources: Option<EnumMap<TerrainResource, f32>>,
+        rtsim_wildlife: Option<EnumMap<WildlifeKind, f32>>,
         // TODO: misleading name
         mut should_continue: impl FnMut() -> bool,
         time: Option<(TimeOfDay, Calendar)>,
@@ -456,7 +475,15 @@ impl World {
         };
 
         // Only use for rng affecting dynamic elements like chests and entities!
-        let mut dynamic_rng = ChaCha8Rng::from_seed(rand::rng().random());
+        let mut dynamic_rng = if self.deterministic_chunks {
+            let mut seed = [0; 32];
+            seed[..4].copy_from_slice(&self.sim.seed.to_le_bytes());
+            seed[4..8].copy_from_slice(&chunk_pos.x.to_le_bytes());
+            seed[8..12].copy_from_slice(&chunk_pos.y.to_le_bytes());
+            ChaCha8Rng::from_seed(seed)
+        } else {
+            ChaCha8Rng::from_seed(rand::rng().random())
+        };
 
         // Apply layers (paths, caves, etc.)
         let mut canvas = Canvas {
@@ -558,6 +585,7 @@ impl World {
             sim_chunk,
             &mut supplement,
             time.as_ref(),
+            rtsim_wildlife.as_ref(),
         );
 
         // Apply site supplementary information
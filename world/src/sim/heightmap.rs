@@ -0,0 +1,311 @@
+use super::{GenOpts, erosion::Alt, map_edge_factor};
+use crate::CONFIG;
+use common::terrain::{BiomeKind, MapSizeLg, vec2_as_uniform_idx};
+use image::{DynamicImage, ImageError, imageops::FilterType};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use strum::IntoEnumIterator;
+use vek::*;
+
+/// Options for generating a world on top of hand-designed terrain.
+///
+/// All images are greyscale and north up, and get resampled to the size of the
+/// world, so they don't need to match it exactly.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HeightmapOpts {
+    /// Path to the heightmap, where black is the lowest point of the world and
+    /// white its highest.
+    pub path: PathBuf,
+    /// The altitude, in blocks, that black maps to.
+    #[serde(default = "HeightmapOpts::default_min_alt")]
+    pub min_alt: f32,
+    /// The altitude, in blocks, that white maps to.
+    #[serde(default = "HeightmapOpts::default_max_alt")]
+    pub max_alt: f32,
+    /// Mask of the temperature, from the coldest (black) to the hottest
+    /// (white) climate.
+    #[serde(default)]
+    pub temperature: Option<PathBuf>,
+    /// Mask of the humidity, from the driest (black) to the wettest (white)
+    /// climate.
+    #[serde(default)]
+    pub humidity: Option<PathBuf>,
+    /// Mask of biomes, where each value is the index of a [`BiomeKind`] (as in
+    /// the biome maps of `world_export`). Biomes that depend on the terrain
+    /// alone, such as oceans and mountains, are left to the terrain. Takes
+    /// precedence over the temperature and humidity masks.
+    #[serde(default)]
+    pub biome: Option<PathBuf>,
+    /// Size of the world and quality of the erosion. Hand-designed terrain is
+    /// eroded like generated terrain, and then rescaled to span the same
+    /// altitudes as the heightmap again.
+    #[serde(default)]
+    pub opts: GenOpts,
+}
+
+impl HeightmapOpts {
+    fn default_min_alt() -> f32 { 0.0 }
+
+    fn default_max_alt() -> f32 { CONFIG.sea_level + CONFIG.mountain_scale * 0.5 }
+
+    pub(crate) fn load(&self, map_size_lg: MapSizeLg) -> Result<Heightmap, ImageError> {
+        let alt = load_mask(&self.path, map_size_lg, FilterType::Triangle)?
+            .iter()
+            .map(|h| self.min_alt + h * (self.max_alt - self.min_alt))
+            .collect();
+
+        let mut climate = vec![(None, None); map_size_lg.chunks_len()].into_boxed_slice();
+        if let Some(path) = &self.temperature {
+            let temp = load_mask(path, map_size_lg, FilterType::Triangle)?;
+            for (climate, temp) in climate.iter_mut().zip(temp.iter()) {
+                // Temperatures go from -1 to 1
+                climate.0 = Some(temp * 2.0 - 1.0);
+            }
+        }
+        if let Some(path) = &self.humidity {
+            let humidity = load_mask(path, map_size_lg, FilterType::Triangle)?;
+            for (climate, humidity) in climate.iter_mut().zip(humidity.iter()) {
+                climate.1 = Some(*humidity);
+            }
+        }
+        if let Some(path) = &self.biome {
+            let biomes = BiomeKind::iter().collect::<Vec<_>>();
+            let biome = load_indices(path, map_size_lg)?;
+            for (climate, idx) in climate.iter_mut().zip(biome.iter()) {
+                if let Some((temp, humidity)) = biomes.get(*idx).and_then(biome_climate) {
+                    *climate = (Some(temp), Some(humidity));
+                }
+            }
+        }
+
+        Ok(Heightmap { alt, climate })
+    }
+}
+
+/// Hand-designed terrain and climate, per chunk.
+pub(crate) struct Heightmap {
+    /// Altitude in blocks.
+    pub alt: Box<[f32]>,
+    /// Temperature and humidity, where the masks set them.
+    pub climate: Box<[(Option<f32>, Option<f32>)]>,
+}
+
+impl Heightmap {
+    /// The altitude of a chunk relative to sea level, sinking into the sea at
+    /// the edges of the map in the same way as generated terrain.
+    pub fn alt_above_sea(&self, map_size_lg: MapSizeLg, posi: usize) -> f32 {
+        self.alt[posi] * map_edge_factor(map_size_lg, posi) - CONFIG.sea_level
+    }
+
+    /// Linearly map eroded altitudes and basement, relative to sea level, back
+    /// onto the range of altitudes of the heightmap, which erosion and uplift
+    /// move the terrain away from.
+    pub fn rescale(
+        &self,
+        map_size_lg: MapSizeLg,
+        mut alt: Box<[Alt]>,
+        mut basement: Box<[Alt]>,
+    ) -> (Box<[Alt]>, Box<[Alt]>) {
+        fn range(alts: impl Iterator<Item = Alt>) -> (Alt, Alt) {
+            alts.fold((Alt::INFINITY, Alt::NEG_INFINITY), |(min, max), alt| {
+                (min.min(alt), max.max(alt))
+            })
+        }
+        let (min, max) = range(
+            (0..map_size_lg.chunks_len()).map(|posi| self.alt_above_sea(map_size_lg, posi) as Alt),
+        );
+        let (eroded_min, eroded_max) = range(alt.iter().copied());
+        let scale = if eroded_max > eroded_min {
+            (max - min) / (eroded_max - eroded_min)
+        } else {
+            1.0
+        };
+        for alt in alt.iter_mut().chain(basement.iter_mut()) {
+            *alt = min + (*alt - eroded_min) * scale;
+        }
+        (alt, basement)
+    }
+}
+
+/// A temperature and humidity at which chunks end up with the given biome,
+/// for biomes that are decided by the climate.
+fn biome_climate(biome: &BiomeKind) -> Option<(f32, f32)> {
+    match biome {
+        BiomeKind::Snowland => Some((-0.9, 0.5)),
+        BiomeKind::Taiga => Some((-0.5, 0.6)),
+        BiomeKind::Forest => Some((0.0, 0.7)),
+        BiomeKind::Grassland => Some((0.1, 0.3)),
+        BiomeKind::Savannah => Some((0.5, 0.3)),
+        BiomeKind::Jungle => Some((0.6, 0.9)),
+        BiomeKind::Desert => Some((0.9, 0.05)),
+        BiomeKind::Void
+        | BiomeKind::Lake
+        | BiomeKind::Ocean
+        | BiomeKind::Mountain
+        | BiomeKind::Swamp => None,
+    }
+}
+
+/// Load a greyscale image, resampled to one pixel per chunk, as values from 0
+/// to 1 in the order of chunk indices.
+fn load_mask(
+    path: &Path,
+    map_size_lg: MapSizeLg,
+    filter: FilterType,
+) -> Result<Box<[f32]>, ImageError> {
+    Ok(resample(image::open(path)?, map_size_lg, filter))
+}
+
+/// Load a greyscale image of indices, such as a biome map, resampled to one
+/// pixel per chunk, in the order of chunk indices. Indices can't be blended, so
+/// the nearest pixel is taken. The values of 8-bit images are taken as they
+/// are, rather than scaled up to 16 bits.
+fn load_indices(path: &Path, map_size_lg: MapSizeLg) -> Result<Box<[usize]>, ImageError> {
+    let image = image::open(path)?;
+    let color = image.color();
+    let max = if color.bits_per_pixel() / u16::from(color.channel_count()) <= 8 {
+        u8::MAX as f32
+    } else {
+        u16::MAX as f32
+    };
+    Ok(resample(image, map_size_lg, FilterType::Nearest)
+        .iter()
+        .map(|value| (value * max).round() as usize)
+        .collect())
+}
+
+fn resample(image: DynamicImage, map_size_lg: MapSizeLg, filter: FilterType) -> Box<[f32]> {
+    let size = map_size_lg.chunks().map(u32::from);
+    let image = image::imageops::resize(&image.into_luma16(), size.x, size.y, filter);
+
+    let mut mask = vec![0.0; map_size_lg.chunks_len()].into_boxed_slice();
+    for (x, y, pixel) in image.enumerate_pixels() {
+        // Images go from top to bottom, while the world goes from south to north
+        let pos = Vec2::new(x, size.y - y - 1).as_();
+        mask[vec2_as_uniform_idx(map_size_lg, pos)] = pixel.0[0] as f32 / u16::MAX as f32;
+    }
+    mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::{FileOpts, WorldOpts, WorldSim};
+    use image::{ImageBuffer, Luma};
+
+    #[test]
+    fn masks_are_resampled_north_up() {
+        let map_size_lg = MapSizeLg::new(Vec2::new(2, 2)).unwrap();
+        let path = std::env::temp_dir().join("veloren_heightmap_mask_test.png");
+        // The top half of the image is white, the bottom half black
+        ImageBuffer::from_fn(8, 8, |_, y| Luma([if y < 4 { u16::MAX } else { 0 }]))
+            .save(&path)
+            .unwrap();
+
+        let mask = load_mask(&path, map_size_lg, FilterType::Nearest).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(mask.len(), 16);
+        for x in 0..4 {
+            let at = |y| mask[vec2_as_uniform_idx(map_size_lg, Vec2::new(x, y))];
+            assert_eq!(at(0), 0.0);
+            assert_eq!(at(3), 1.0);
+        }
+    }
+
+    #[test]
+    fn eight_bit_biome_masks_are_not_scaled() {
+        let map_size_lg = MapSizeLg::new(Vec2::new(1, 1)).unwrap();
+        let path = std::env::temp_dir().join("veloren_heightmap_biome_test.png");
+        ImageBuffer::from_fn(2, 2, |x, y| Luma([(x + y * 2) as u8]))
+            .save(&path)
+            .unwrap();
+
+        let biome = load_indices(&path, map_size_lg).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let at = |x, y| biome[vec2_as_uniform_idx(map_size_lg, Vec2::new(x, y))];
+        assert_eq!([at(0, 1), at(1, 1), at(0, 0), at(1, 0)], [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn generated_altitude_follows_the_heightmap() {
+        let path = std::env::temp_dir().join("veloren_heightmap_alt_test.png");
+        // Rising from west to east
+        ImageBuffer::from_fn(32, 32, |x, _| Luma([(x * u16::MAX as u32 / 31) as u16]))
+            .save(&path)
+            .unwrap();
+        let heightmap = HeightmapOpts {
+            path: path.clone(),
+            min_alt: 200.0,
+            max_alt: 800.0,
+            temperature: None,
+            humidity: None,
+            biome: None,
+            opts: GenOpts {
+                x_lg: 5,
+                y_lg: 5,
+                ..GenOpts::default()
+            },
+        };
+        let threadpool = rayon::ThreadPoolBuilder::new().build().unwrap();
+        let sim = WorldSim::generate(
+            0,
+            WorldOpts {
+                seed_elements: false,
+                world_file: FileOpts::Heightmap(heightmap),
+                calendar: None,
+            },
+            &threadpool,
+            &|_| {},
+        );
+        let _ = std::fs::remove_file(&path);
+
+        // Erosion reshapes the terrain, but it stays within the altitudes of the
+        // heightmap, where the edges of the map sink down to 0
+        for x in 0..32 {
+            for y in 0..32 {
+                let alt = sim.get(Vec2::new(x, y)).unwrap().alt;
+                assert!(
+                    (-1.0..=801.0).contains(&alt),
+                    "Chunk ({x}, {y}) is at {alt}, outside of the heightmap"
+                );
+            }
+        }
+        // And still rises from west to east, away from the edges of the map
+        let mean_alt = |xs: std::ops::Range<i32>| {
+            let len = xs.len() * 30;
+            xs.flat_map(|x| (1..31).map(move |y| Vec2::new(x, y)))
+                .map(|pos| sim.get(pos).unwrap().alt)
+                .sum::<f32>()
+                / len as f32
+        };
+        assert!(mean_alt(1..8) < mean_alt(24..31));
+    }
+
+    #[test]
+    #[should_panic(expected = "Couldn't load heightmap")]
+    fn missing_heightmap_panics() {
+        let threadpool = rayon::ThreadPoolBuilder::new().build().unwrap();
+        WorldSim::generate(
+            0,
+            WorldOpts {
+                seed_elements: false,
+                world_file: FileOpts::Heightmap(HeightmapOpts {
+                    path: std::env::temp_dir().join("veloren_heightmap_missing.png"),
+                    min_alt: 0.0,
+                    max_alt: 100.0,
+                    temperature: None,
+                    humidity: None,
+                    biome: None,
+                    opts: GenOpts {
+                        x_lg: 5,
+                        y_lg: 5,
+                        ..GenOpts::default()
+                    },
+                }),
+                calendar: None,
+            },
+            &threadpool,
+            &|_| {},
+        );
+    }
+}
@@ -105,6 +105,7 @@ pub fn sample_pos(
         river_kind,
         spline_derivative,
         is_path,
+        is_water_route,
         is_bridge,
     ) = sampler
         .get(pos)
@@ -120,6 +121,7 @@ pub fn sample_pos(
                 sample.river.river_kind,
                 sample.river.spline_derivative,
                 sample.path.0.is_way(),
+                sample.water_route,
                 sample.sites.iter().any(|site| {
                     let site = &index.sites.get(*site);
                     match site.kind {
@@ -149,6 +151,7 @@ pub fn sample_pos(
             Vec2::zero(),
             false,
             false,
+            false,
         ));
 
     let humidity = humidity.clamp(0.0, 1.0);
@@ -270,6 +273,8 @@ pub fn sample_pos(
         Rgb::new(0x80, 0x80, 0x80)
     } else if is_path {
         Rgb::new(0x37, 0x29, 0x23)
+    } else if is_water_route && is_water {
+        Rgb::new(0x4a, 0x8c, 0xbe)
     } else {
         rgb
     };
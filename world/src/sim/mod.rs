@@ -1,5 +1,6 @@
 mod diffusion;
 mod erosion;
+mod heightmap;
 mod location;
 mod map;
 mod util;
@@ -9,6 +10,7 @@ mod way;
 use self::erosion::Compute;
 pub use self::{
     diffusion::diffusion,
+    heightmap::HeightmapOpts,
     location::Location,
     map::{sample_pos, sample_wpos},
     util::get_horizon_map,
@@ -109,6 +111,9 @@ struct GenCdf {
     pure_flux: InverseCdf<Compute>,
     alt_no_water: InverseCdf,
     rivers: Box<[RiverData]>,
+    /// Temperature and humidity that take the place of the generated ones,
+    /// see [`HeightmapOpts`].
+    climate: Option<Box<[(Option<f32>, Option<f32>)]>>,
 }
 
 pub(crate) struct GenCtx {
@@ -195,6 +200,10 @@ pub enum FileOpts {
     /// NOTE: Could stand to merge this with `Load` and construct an enum that
     /// can handle either a PathBuf or an asset specifier, at some point.
     LoadAsset(String),
+    /// If set, generate the world map on top of the terrain of a heightmap
+    /// image, rather than from noise (panics if an image can't be loaded). The
+    /// world file isn't saved.
+    Heightmap(HeightmapOpts),
 }
 
 impl Default for FileOpts {
@@ -231,9 +240,10 @@ impl FileOpts {
 
     fn gen_opts(&self) -> Option<GenOpts> {
         match self {
-            Self::Generate(opts) | Self::Save(_, opts) | Self::LoadOrGenerate { opts, .. } => {
-                Some(opts.clone())
-            },
+            Self::Generate(opts)
+            | Self::Save(_, opts)
+            | Self::LoadOrGenerate { opts, .. }
+            | Self::Heightmap(HeightmapOpts { opts, .. }) => Some(opts.clone()),
             _ => None,
         }
     }
@@ -241,16 +251,17 @@ impl FileOpts {
     // TODO: this should return Option so that caller can choose fallback
     fn map_size(&self) -> MapSizeLg {
         match self {
-            Self::Generate(opts) | Self::Save(_, opts) | Self::LoadOrGenerate { opts, .. } => {
-                MapSizeLg::new(Vec2 {
-                    x: opts.x_lg,
-                    y: opts.y_lg,
-                })
-                .unwrap_or_else(|e| {
-                    warn!("World size does not satisfy invariants: {:?}", e);
-                    DEFAULT_WORLD_CHUNKS_LG
-                })
-            },
+            Self::Generate(opts)
+            | Self::Save(_, opts)
+            | Self::LoadOrGenerate { opts, .. }
+            | Self::Heightmap(HeightmapOpts { opts, .. }) => MapSizeLg::new(Vec2 {
+                x: opts.x_lg,
+                y: opts.y_lg,
+            })
+            .unwrap_or_else(|e| {
+                warn!("World size does not satisfy invariants: {:?}", e);
+                DEFAULT_WORLD_CHUNKS_LG
+            }),
             _ => DEFAULT_WORLD_CHUNKS_LG,
         }
     }
@@ -392,7 +403,7 @@ impl FileOpts {
 
                 map.into_modern()
             },
-            Self::Generate { .. } | Self::Save { .. } => return None,
+            Self::Generate { .. } | Self::Save { .. } | Self::Heightmap(_) => return None,
         };
 
         match map {
@@ -408,6 +419,18 @@ impl FileOpts {
         }
     }
 
+    fn load_heightmap(&self, map_size_lg: MapSizeLg) -> Option<heightmap::Heightmap> {
+        let Self::Heightmap(opts) = self else {
+            return None;
+        };
+        // The heightmap was asked for explicitly, so quietly generating an entirely
+        // different world instead would only be noticed once it's too late
+        match opts.load(map_size_lg) {
+            Ok(heightmap) => Some(heightmap),
+            Err(e) => panic!("Couldn't load heightmap {:?} or its masks: {e}", opts.path),
+        }
+    }
+
     fn map_path(&self) -> Option<PathBuf> {
         // TODO: Work out a nice bincode file extension.
         match self {
@@ -748,6 +771,7 @@ impl WorldSim {
                 cliff_height: 0.0,
                 spot: None,
                 contains_waypoint: false,
+                water_route: false,
             }],
             _locations: Vec::new(),
             gen_ctx,
@@ -771,6 +795,7 @@ impl WorldSim {
         // Currently only used with LoadOrGenerate to know if we need to
         // overwrite world file
         let fresh = parsed_world_file.is_none();
+        let heightmap = world_file.load_heightmap(map_size_lg);
 
         let mut rng = ChaChaRng::from_seed(seed_expan::rng_state(seed));
         let continent_scale = gen_opts.scale
@@ -1016,6 +1041,12 @@ impl WorldSim {
         // No NaNs in these uniform vectors, since the original noise value always
         // returns Some.
         let (alt_old, _) = uniform_noise(map_size_lg, |posi, wposf| {
+            // Hand-designed terrain takes the place of the noise, in the same units and
+            // sinking into the sea at the edges of the map in the same way
+            if let Some(heightmap) = &heightmap {
+                return Some(heightmap.alt_above_sea(map_size_lg, posi) / CONFIG.mountain_scale);
+            }
+
             // This is the extension upwards from the base added to some extra noise from -1
             // to 1.
             //
@@ -1367,6 +1398,12 @@ impl WorldSim {
         let (alt, basement) = if let Some(map) = parsed_world_file {
             (map.alt, map.basement)
         } else {
+            // Hand-designed terrain is eroded starting from the altitudes it was designed
+            // with, rather than from the noise
+            let initial_alt = |posi| match &heightmap {
+                Some(heightmap) => heightmap.alt_above_sea(map_size_lg, posi),
+                None => alt_func(posi),
+            };
             let (alt, basement) = do_erosion(
                 map_size_lg,
                 max_erosion_per_delta_t as f32,
@@ -1375,8 +1412,8 @@ impl WorldSim {
                 // varying conditions
                 &rock_strength_nz,
                 // initial conditions
-                alt_func,
-                alt_func,
+                initial_alt,
+                initial_alt,
                 is_ocean_fn,
                 // empirical constants
                 uplift_fn,
@@ -1396,7 +1433,7 @@ impl WorldSim {
             );
 
             // Quick "small scale" erosion cycle in order to lower extreme angles.
-            do_erosion(
+            let (alt, basement) = do_erosion(
                 map_size_lg,
                 1.0f32,
                 n_small_steps,
@@ -1418,7 +1455,12 @@ impl WorldSim {
                 k_da_scale,
                 threadpool,
                 report_erosion,
-            )
+            );
+
+            match &heightmap {
+                Some(heightmap) => heightmap.rescale(map_size_lg, alt, basement),
+                None => (alt, basement),
+            }
         };
 
         // Save map, if necessary.
@@ -1689,6 +1731,7 @@ impl WorldSim {
             pure_flux,
             alt_no_water,
             rivers,
+            climate: heightmap.map(|heightmap| heightmap.climate),
         };
 
         let chunks = (0..map_size_lg.chunks_len())
@@ -2508,6 +2551,8 @@ pub struct SimChunk {
     pub spot: Option<Spot>,
 
     pub contains_waypoint: bool,
+    /// Whether a route of boats passes through this chunk.
+    pub water_route: bool,
 }
 
 #[derive(Copy, Clone)]
@@ -2584,6 +2629,14 @@ impl SimChunk {
                     .div(1.0 - CONFIG.tropical_temp))
             .max(0.0);
 
+        let (temp, humidity) = match gen_cdf.climate.as_ref().map(|climate| climate[posi]) {
+            Some((temp_override, humid_override)) => (
+                temp_override.unwrap_or(temp),
+                humid_override.unwrap_or(humidity),
+            ),
+            None => (temp, humidity),
+        };
+
         let mut alt = CONFIG.sea_level.add(alt_pre);
         let basement = CONFIG.sea_level.add(basement_pre);
         let water_alt = CONFIG.sea_level.add(water_alt_pre);
@@ -2764,6 +2817,7 @@ impl SimChunk {
             spot: None,
 
             contains_waypoint: false,
+            water_route: false,
         }
     }
 
@@ -111,6 +111,7 @@ pub enum SiteKind {
     VampireCastle,
     GliderCourse,
     Myrmidon,
+    ProceduralDungeon,
 }
 
 impl SiteKind {
@@ -135,6 +136,7 @@ impl SiteKind {
             SiteKind::Cultist => Some(SiteKindMeta::Dungeon(DungeonKindMeta::Cultist)),
             SiteKind::Sahagin => Some(SiteKindMeta::Dungeon(DungeonKindMeta::Sahagin)),
             SiteKind::VampireCastle => Some(SiteKindMeta::Dungeon(DungeonKindMeta::VampireCastle)),
+            SiteKind::ProceduralDungeon => Some(SiteKindMeta::Dungeon(DungeonKindMeta::Procedural)),
 
             _ => None,
         }
@@ -172,6 +174,7 @@ impl SiteKind {
             SiteKind::Adlet => Some(MarkerKind::Adlet),
             SiteKind::Haniwa => Some(MarkerKind::Haniwa),
             SiteKind::VampireCastle => Some(MarkerKind::VampireCastle),
+            SiteKind::ProceduralDungeon => Some(MarkerKind::ProceduralDungeon),
 
             SiteKind::PirateHideout
             | SiteKind::JungleRuin
@@ -286,6 +289,7 @@ impl Site {
                 PlotKind::VampireCastle(vc) => Some(vc.spawn_rules(wpos)),
                 PlotKind::MyrmidonArena(ma) => Some(ma.spawn_rules(wpos)),
                 PlotKind::MyrmidonHouse(mh) => Some(mh.spawn_rules(wpos)),
+                PlotKind::ProceduralDungeon(pd) => Some(pd.spawn_rules(wpos)),
                 PlotKind::AirshipDock(ad) => Some(ad.spawn_rules(wpos)),
                 PlotKind::CoastalAirshipDock(cad) => Some(cad.spawn_rules(wpos)),
                 PlotKind::CliffTownAirshipDock(clad) => Some(clad.spawn_rules(wpos)),
@@ -2778,6 +2782,45 @@ impl Site {
         site
     }
 
+    pub fn generate_procedural_dungeon(land: &Land, rng: &mut impl Rng, origin: Vec2<i32>) -> Self {
+        let mut rng = reseed(rng);
+        let theme = plot::DungeonTheme::choose(&mut rng);
+        let mut site = Site {
+            origin,
+            name: Some({
+                let name = NameGen::location(&mut rng).generate();
+                match theme.names.choose(&mut rng) {
+                    Some(suffix) => format!("{} {}", name, suffix),
+                    None => name,
+                }
+            }),
+            kind: Some(SiteKind::ProceduralDungeon),
+            ..Site::default()
+        };
+        let size = plot::ProceduralDungeon::RADIUS;
+        let aabr = Aabr {
+            min: Vec2::broadcast(-size),
+            max: Vec2::broadcast(size),
+        };
+        {
+            let dungeon =
+                plot::ProceduralDungeon::generate(land, &mut reseed(&mut rng), &site, aabr, theme);
+            let dungeon_alt = dungeon.alt;
+            let plot = site.create_plot(Plot {
+                kind: PlotKind::ProceduralDungeon(dungeon),
+                root_tile: aabr.center(),
+                tiles: aabr_tiles(aabr).collect(),
+            });
+
+            site.blit_aabr(aabr, Tile {
+                kind: TileKind::Building,
+                plot: Some(plot),
+                hard_alt: Some(dungeon_alt),
+            });
+        }
+        site
+    }
+
     pub fn generate_sahagin(
         land: &Land,
         index: IndexRef,
@@ -1,7 +1,11 @@
 use super::*;
-use crate::{Land, util::within_distance};
+use crate::{
+    Land,
+    util::{RandomField, within_distance},
+};
 use generation::render_prefab;
 use rand::prelude::*;
+use rand_chacha::ChaChaRng;
 use vek::*;
 
 const TILE_SIZE: i32 = 13;
@@ -432,7 +436,9 @@ fn spawn_entities(
 }
 
 fn spawn_random_entity(pos: Vec3<i32>, painter: &Painter, rot: u8) {
-    let mut rng = rand::rng();
+    // Seeded from the position, so that the sprites are the same each time the
+    // chunk is generated
+    let mut rng = ChaChaRng::seed_from_u64(RandomField::new(0).get(pos) as u64);
 
     let entities = [
         "common.entity.dungeon.dwarven_quarry.miner",
@@ -29,6 +29,7 @@ mod myrmidon_arena;
 mod myrmidon_house;
 mod pirate_hideout;
 mod plaza;
+mod procedural_dungeon;
 mod road;
 mod rock_circle;
 mod sahagin;
@@ -76,6 +77,7 @@ pub use self::{
     myrmidon_house::MyrmidonHouse,
     pirate_hideout::PirateHideout,
     plaza::Plaza,
+    procedural_dungeon::{DungeonTheme, ProceduralDungeon},
     road::{Road, RoadKind, RoadLights, RoadMaterial},
     rock_circle::RockCircle,
     sahagin::Sahagin,
@@ -205,6 +207,7 @@ pub enum PlotKind {
     VampireCastle(VampireCastle),
     MyrmidonArena(MyrmidonArena),
     MyrmidonHouse(MyrmidonHouse),
+    ProceduralDungeon(ProceduralDungeon),
 }
 
 impl PlotKind {
@@ -290,6 +293,7 @@ impl PlotKind {
             PlotKind::TerracottaPalace(_) => Some(PlotKindMeta::Dungeon),
             PlotKind::VampireCastle(_) => Some(PlotKindMeta::Dungeon),
             PlotKind::MyrmidonArena(_) => Some(PlotKindMeta::Dungeon),
+            PlotKind::ProceduralDungeon(_) => Some(PlotKindMeta::Dungeon),
             PlotKind::GliderRing(_)
             | PlotKind::GliderPlatform(_)
             | PlotKind::GliderFinish(_)
@@ -368,6 +372,7 @@ macro_rules! foreach_plot {
             PlotKind::GliderFinish($x) => $y,
             PlotKind::MyrmidonArena($x) => $y,
             PlotKind::MyrmidonHouse($x) => $y,
+            PlotKind::ProceduralDungeon($x) => $y,
         }
     };
 }
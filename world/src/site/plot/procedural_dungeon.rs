@@ -0,0 +1,819 @@
+//! Dungeons that are laid out from a [`DungeonTheme`], rather than by hand.
+//!
+//! Each floor is a tree of rooms on a grid, joined by corridors, that grows
+//! out from the stairs coming down from the floor above. The room furthest
+//! from the stairs is locked, and holds either the stairs down to the next
+//! floor or, on the deepest floor, the boss. Its key is carried by an enemy in
+//! the furthest room that can be reached without it, so every floor has to be
+//! explored before moving on. Encounters get harder the deeper they are.
+//!
+//! Themes are defined in `assets/world/manifests/dungeon_themes.ron`: adding a
+//! theme only requires adding an entry there.
+
+use super::*;
+use crate::{
+    Land,
+    site::generation::spiral_staircase,
+    util::{CARDINALS, RandomField, attempt, sampler::Sampler, within_distance},
+};
+use common::{
+    assets::{AssetCombined, AssetHandle, Ron},
+    comp::{item::ItemDefinitionIdOwned, misc::PortalData},
+    generation::{EntityInfo, SpecialEntity},
+    lottery::LootSpec,
+    resources::Secs,
+    terrain::{SpriteCfg, SpriteKind, UnlockKind},
+};
+use lazy_static::lazy_static;
+use rand::prelude::*;
+use rand_chacha::ChaChaRng;
+use serde::Deserialize;
+use std::sync::Arc;
+use vek::*;
+
+/// The width and length of the cells of the grid that rooms are laid out on,
+/// in blocks.
+const CELL_SIZE: i32 = 28;
+/// The width and length of the grid that rooms are laid out on, in cells.
+const GRID_SIZE: i32 = 5;
+/// The smallest width or length of a room.
+const MIN_ROOM_SIZE: i32 = 10;
+/// The width of corridors, not counting their walls.
+const CORRIDOR_WIDTH: i32 = 3;
+const CORRIDOR_HEIGHT: i32 = 4;
+/// How far stairwells extend from their center.
+const STAIRS_RADIUS: i32 = 3;
+/// The smallest width or length of a room with stairs in it, which leaves
+/// space to walk around them.
+const MIN_STAIRS_ROOM_SIZE: i32 = 2 * STAIRS_RADIUS + 8;
+
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Eq)]
+pub enum RoomKind {
+    /// The room at the bottom of the stairs that lead down to a floor.
+    Entrance,
+    /// A room guarded by enemies.
+    Combat,
+    /// A dead end with a reward.
+    Treasure,
+    /// The locked room with the stairs down to the next floor.
+    Stairs,
+    /// The locked room at the end of the deepest floor.
+    Boss,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Material {
+    pub kind: BlockKind,
+    /// The colours that blocks are picked from at random.
+    pub colors: Vec<(u8, u8, u8)>,
+}
+
+impl Material {
+    fn fill(&self, seed: u32) -> Fill {
+        let kind = self.kind;
+        let colors = self
+            .colors
+            .iter()
+            .copied()
+            .map(Rgb::from)
+            .collect::<Vec<_>>();
+        Fill::Sampling(Arc::new(move |pos| {
+            RandomField::new(seed)
+                .choose(pos, &colors)
+                .map(|color| Block::new(kind, *color))
+        }))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct RoomTemplate {
+    /// The kinds of room that the template may be used for.
+    pub kinds: Vec<RoomKind>,
+    /// How likely the template is to be picked, relative to other templates
+    /// of the same kind.
+    pub weight: f32,
+    /// The smallest and largest width and length of the room, inclusive.
+    pub size: (i32, i32),
+    pub height: i32,
+    /// Whether the room has pillars holding up its ceiling.
+    #[serde(default)]
+    pub pillars: bool,
+    /// Sprites scattered along the walls, with the chance of each being placed
+    /// at any block along them.
+    #[serde(default)]
+    pub sprites: Vec<(SpriteKind, f32)>,
+    /// A sprite placed in the middle of the room, such as a chest.
+    #[serde(default)]
+    pub centerpiece: Option<SpriteKind>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct EncounterEntity {
+    /// The entity config to spawn the entities from.
+    pub entity: String,
+    /// How many entities spawn at the lowest and highest difficulty of the
+    /// encounter.
+    pub count: (u32, u32),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Encounter {
+    /// The lowest and highest difficulty that the encounter appears at,
+    /// inclusive. Difficulty goes from 0 at the entrance of the dungeon to 1
+    /// at the boss.
+    pub difficulty: (f32, f32),
+    /// How likely the encounter is to be picked, relative to other encounters
+    /// of the same difficulty.
+    pub weight: f32,
+    pub entities: Vec<EncounterEntity>,
+}
+
+impl Encounter {
+    /// The entities to spawn at the given difficulty, and how many of each.
+    fn spawns(&self, difficulty: f32) -> Vec<(String, u32)> {
+        let (lo, hi) = self.difficulty;
+        let t = if hi > lo {
+            ((difficulty - lo) / (hi - lo)).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        self.entities
+            .iter()
+            .map(|entity| {
+                let (min, max) = entity.count;
+                let count = Lerp::lerp(min as f32, max as f32, t).round() as u32;
+                (entity.entity.clone(), count)
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DungeonTheme {
+    /// The name of the theme, to tell themes apart.
+    pub name: String,
+    /// How likely the theme is to be picked, relative to other themes.
+    pub weight: f32,
+    /// What dungeons of the theme are called, such as "Crypt". Names are made
+    /// of a random place name followed by one of these.
+    pub names: Vec<String>,
+    /// The smallest and largest number of floors, inclusive.
+    pub floors: (u32, u32),
+    /// The smallest and largest number of rooms on each floor, inclusive.
+    pub rooms_per_floor: (u32, u32),
+    /// The distance between the floors of two levels, in blocks.
+    pub floor_height: i32,
+    pub wall: Material,
+    pub floor: Material,
+    /// The item that opens the locked rooms.
+    pub key: String,
+    /// The entity config of the enemy that carries the key of each floor. It
+    /// drops the key instead of the loot of its entity config.
+    pub key_holder: String,
+    pub rooms: Vec<RoomTemplate>,
+    pub encounters: Vec<Encounter>,
+    /// The entities that spawn in the boss room. How many of each spawn goes
+    /// from the first count in dungeons with the fewest floors to the second
+    /// in dungeons with the most.
+    pub boss: Vec<EncounterEntity>,
+}
+
+impl DungeonTheme {
+    /// Pick a random theme, weighted by how likely each is.
+    pub fn choose(rng: &mut impl Rng) -> &'static Self {
+        RON_DUNGEON_THEMES
+            .0
+            .choose_weighted(rng, |theme| theme.weight)
+            .expect("At least one dungeon theme must be defined")
+    }
+
+    fn room_template(&self, rng: &mut impl Rng, kind: RoomKind) -> usize {
+        let candidates = self
+            .rooms
+            .iter()
+            .enumerate()
+            .filter(|(_, template)| template.kinds.contains(&kind))
+            .collect::<Vec<_>>();
+        candidates
+            .choose_weighted(rng, |(_, template)| template.weight)
+            .map_or(0, |(idx, _)| *idx)
+    }
+
+    /// The entities of the boss room of a dungeon with the given number of
+    /// floors, and how many of each.
+    fn boss(&self, floor_count: u32) -> Vec<(String, u32)> {
+        Encounter {
+            difficulty: (self.floors.0 as f32, self.floors.1 as f32),
+            weight: 1.0,
+            entities: self.boss.clone(),
+        }
+        .spawns(floor_count as f32)
+    }
+
+    fn encounter(&self, rng: &mut impl Rng, difficulty: f32) -> Vec<(String, u32)> {
+        let candidates = self
+            .encounters
+            .iter()
+            .filter(|encounter| {
+                (encounter.difficulty.0..=encounter.difficulty.1).contains(&difficulty)
+            })
+            .collect::<Vec<_>>();
+        candidates
+            .choose_weighted(rng, |encounter| encounter.weight)
+            .map(|encounter| encounter.spawns(difficulty))
+            .unwrap_or_default()
+    }
+}
+
+pub type RonDungeonThemes = Ron<Vec<DungeonTheme>>;
+
+lazy_static! {
+    pub static ref RON_DUNGEON_THEMES: RonDungeonThemes = {
+        let themes: AssetHandle<RonDungeonThemes> =
+            RonDungeonThemes::load_expect_combined_static("world.manifests.dungeon_themes");
+        Ron(themes.read().0.to_vec())
+    };
+}
+
+struct Room {
+    kind: RoomKind,
+    /// The cell of the grid that the room is in.
+    cell: Vec2<i32>,
+    /// The room that this one is reached from.
+    parent: Option<usize>,
+    /// How many rooms away from the entrance of the floor this one is.
+    depth: u32,
+    template: usize,
+    size: Vec2<i32>,
+    height: i32,
+    /// Whether the way in from the parent room is locked.
+    locked: bool,
+    /// Whether one of the enemies in the room carries the key of the floor.
+    has_key: bool,
+    encounter: Vec<(String, u32)>,
+}
+
+/// The rooms of each floor, from the top down. The first room of each floor is
+/// its entrance.
+struct Layout {
+    floors: Vec<Vec<Room>>,
+}
+
+impl Layout {
+    fn generate(theme: &DungeonTheme, rng: &mut impl Rng) -> Self {
+        let floor_count = rng.random_range(theme.floors.0..=theme.floors.1).max(1);
+        let mut start = Vec2::broadcast(GRID_SIZE / 2);
+        let floors = (0..floor_count)
+            .map(|floor| {
+                let rooms = Self::generate_floor(theme, rng, start, floor, floor_count);
+                // The next floor starts at the bottom of the stairs
+                start = rooms
+                    .iter()
+                    .find(|room| room.kind == RoomKind::Stairs)
+                    .map_or(start, |room| room.cell);
+                rooms
+            })
+            .collect();
+        Self { floors }
+    }
+
+    fn generate_floor(
+        theme: &DungeonTheme,
+        rng: &mut impl Rng,
+        start: Vec2<i32>,
+        floor: u32,
+        floor_count: u32,
+    ) -> Vec<Room> {
+        let room_count = rng
+            .random_range(theme.rooms_per_floor.0..=theme.rooms_per_floor.1)
+            .clamp(2, (GRID_SIZE * GRID_SIZE) as u32) as usize;
+
+        // Grow a tree of rooms out from the entrance
+        let mut cells = vec![(start, None, 0)];
+        while cells.len() < room_count {
+            let Some(cell) = attempt(32, || {
+                let parent = rng.random_range(0..cells.len());
+                let (parent_cell, _, depth) = cells[parent];
+                let cell = parent_cell + *CARDINALS.choose(rng)?;
+                let in_grid =
+                    cell.x >= 0 && cell.y >= 0 && cell.x < GRID_SIZE && cell.y < GRID_SIZE;
+                (in_grid && cells.iter().all(|(other, _, _)| *other != cell)).then_some((
+                    cell,
+                    Some(parent),
+                    depth + 1,
+                ))
+            }) else {
+                break;
+            };
+            cells.push(cell);
+        }
+
+        // The deepest room is always a dead end, and the key goes in the deepest of
+        // the rooms that remain
+        let goal = (1..cells.len()).max_by_key(|idx| cells[*idx].2);
+        let key_room = (1..cells.len())
+            .filter(|idx| Some(*idx) != goal)
+            .max_by_key(|idx| cells[*idx].2)
+            .or(goal.map(|_| 0));
+        let max_depth = cells.iter().map(|(_, _, depth)| *depth).max().unwrap_or(0);
+        let is_last_floor = floor + 1 == floor_count;
+
+        (0..cells.len())
+            .map(|idx| {
+                let (cell, parent, depth) = cells[idx];
+                let is_leaf = cells.iter().all(|(_, parent, _)| *parent != Some(idx));
+                let kind = if Some(idx) == goal || (goal.is_none() && idx == 0) {
+                    if is_last_floor {
+                        RoomKind::Boss
+                    } else {
+                        RoomKind::Stairs
+                    }
+                } else if idx == 0 {
+                    RoomKind::Entrance
+                } else if is_leaf && Some(idx) != key_room {
+                    RoomKind::Treasure
+                } else {
+                    RoomKind::Combat
+                };
+
+                let difficulty =
+                    (floor as f32 + depth as f32 / max_depth.max(1) as f32) / floor_count as f32;
+                let encounter = match kind {
+                    RoomKind::Combat | RoomKind::Stairs => theme.encounter(rng, difficulty),
+                    RoomKind::Boss => theme.boss(floor_count),
+                    RoomKind::Entrance | RoomKind::Treasure => Vec::new(),
+                };
+
+                let template = theme.room_template(rng, kind);
+                let (min_size, max_size) = theme
+                    .rooms
+                    .get(template)
+                    .map_or((MIN_ROOM_SIZE, MIN_ROOM_SIZE), |template| template.size);
+                // Entrances have the bottom of the stairs from the floor above in them
+                let smallest = match kind {
+                    RoomKind::Entrance | RoomKind::Stairs => MIN_STAIRS_ROOM_SIZE,
+                    _ => MIN_ROOM_SIZE,
+                };
+                let mut size = || {
+                    rng.random_range(min_size..=max_size)
+                        .clamp(smallest, CELL_SIZE - 4)
+                };
+                let size = Vec2::new(size(), size());
+                let height = theme
+                    .rooms
+                    .get(template)
+                    .map_or(CORRIDOR_HEIGHT, |template| template.height)
+                    .clamp(CORRIDOR_HEIGHT + 1, theme.floor_height - 2);
+
+                Room {
+                    kind,
+                    cell,
+                    parent,
+                    depth,
+                    template,
+                    size,
+                    height,
+                    locked: Some(idx) == goal,
+                    has_key: goal.is_some() && Some(idx) == key_room,
+                    encounter,
+                }
+            })
+            .collect()
+    }
+}
+
+pub struct ProceduralDungeon {
+    theme: &'static DungeonTheme,
+    pub(crate) alt: i32,
+    center: Vec2<i32>,
+    layout: Layout,
+}
+
+impl ProceduralDungeon {
+    /// How far the dungeon extends from its center, in tiles.
+    pub const RADIUS: i32 = 13;
+
+    pub fn generate(
+        land: &Land,
+        rng: &mut impl Rng,
+        site: &Site,
+        tile_aabr: Aabr<i32>,
+        theme: &'static DungeonTheme,
+    ) -> Self {
+        let center = site.tile_center_wpos(tile_aabr.center());
+        Self {
+            theme,
+            alt: land.get_alt_approx(center) as i32,
+            center,
+            layout: Layout::generate(theme, rng),
+        }
+    }
+
+    pub fn spawn_rules(&self, wpos: Vec2<i32>) -> SpawnRules {
+        SpawnRules {
+            waypoints: false,
+            trees: !within_distance(wpos, self.center, 16),
+            ..SpawnRules::default()
+        }
+    }
+
+    fn cell_center(&self, cell: Vec2<i32>) -> Vec2<i32> {
+        self.center + (cell - GRID_SIZE / 2) * CELL_SIZE
+    }
+
+    /// The altitude of the floor blocks of the given floor.
+    fn floor_alt(&self, floor: usize) -> i32 {
+        self.alt - (floor as i32 + 2) * self.theme.floor_height
+    }
+
+    /// The inside of a room, not counting its walls, floor and ceiling.
+    fn room_aabb(&self, floor: usize, room: &Room) -> Aabb<i32> {
+        let min = self.cell_center(room.cell) - room.size / 2;
+        let floor_alt = self.floor_alt(floor);
+        Aabb {
+            min: min.with_z(floor_alt + 1),
+            max: (min + room.size).with_z(floor_alt + 1 + room.height),
+        }
+    }
+
+    /// The inside of the corridor between a room and its parent.
+    fn corridor_aabb(&self, floor: usize, a: Vec2<i32>, b: Vec2<i32>) -> Aabb<i32> {
+        let (a, b) = (self.cell_center(a), self.cell_center(b));
+        let floor_alt = self.floor_alt(floor);
+        Aabb {
+            min: (Vec2::min(a, b) - CORRIDOR_WIDTH / 2).with_z(floor_alt + 1),
+            max: (Vec2::max(a, b) + CORRIDOR_WIDTH / 2 + 1).with_z(floor_alt + 1 + CORRIDOR_HEIGHT),
+        }
+    }
+
+    /// The stairwell down from the room at `cell` on `floor`, or from the
+    /// surface if there is no floor above.
+    fn stairs_aabb(&self, floor: Option<usize>, cell: Vec2<i32>) -> Aabb<i32> {
+        let center = self.cell_center(cell);
+        let (top, bottom) = match floor {
+            Some(floor) => (self.floor_alt(floor) + 1, floor + 1),
+            None => (self.alt + 1, 0),
+        };
+        Aabb {
+            min: (center - STAIRS_RADIUS).with_z(self.floor_alt(bottom) + 1),
+            max: (center + STAIRS_RADIUS + 1).with_z(top),
+        }
+    }
+}
+
+impl Structure for ProceduralDungeon {
+    #[cfg(feature = "use-dyn-lib")]
+    const UPDATE_FN: &'static [u8] = b"render_procedural_dungeon\0";
+
+    #[cfg_attr(
+        feature = "be-dyn-lib",
+        unsafe(export_name = "render_procedural_dungeon")
+    )]
+    fn render_inner(&self, _site: &Site, _land: &Land, painter: &Painter) {
+        let theme = self.theme;
+        let wall = theme.wall.fill(0);
+        let floor_fill = theme.floor.fill(1);
+        let shell = |aabb: Aabb<i32>| {
+            painter.aabb(Aabb {
+                min: aabb.min - 1,
+                max: aabb.max + 1,
+            })
+        };
+        let floor_of = |aabb: Aabb<i32>| {
+            painter.aabb(Aabb {
+                min: aabb.min.with_z(aabb.min.z - 1),
+                max: aabb.max.with_z(aabb.min.z),
+            })
+        };
+
+        let floors = self.layout.floors.iter().enumerate();
+        let stairwells = floors
+            .clone()
+            .flat_map(|(floor, rooms)| {
+                rooms
+                    .iter()
+                    .filter(|room| room.kind == RoomKind::Stairs)
+                    .map(move |room| self.stairs_aabb(Some(floor), room.cell))
+            })
+            .chain([self.stairs_aabb(None, Vec2::broadcast(GRID_SIZE / 2))])
+            .collect::<Vec<_>>();
+        let corridors = floors
+            .clone()
+            .flat_map(|(floor, rooms)| {
+                rooms.iter().filter_map(move |room| {
+                    let parent = &rooms[room.parent?];
+                    Some(self.corridor_aabb(floor, parent.cell, room.cell))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // Walls go around everything before anything gets hollowed out, so that
+        // rooms and corridors can run into each other
+        for (floor, rooms) in floors.clone() {
+            for room in rooms {
+                shell(self.room_aabb(floor, room)).fill(wall.clone());
+            }
+        }
+        for aabb in corridors.iter().chain(&stairwells) {
+            shell(*aabb).fill(wall.clone());
+        }
+        for (floor, rooms) in floors.clone() {
+            for room in rooms {
+                let aabb = self.room_aabb(floor, room);
+                painter.aabb(aabb).clear();
+                floor_of(aabb).fill(floor_fill.clone());
+            }
+        }
+        for aabb in &corridors {
+            painter.aabb(*aabb).clear();
+            floor_of(*aabb).fill(floor_fill.clone());
+        }
+        for aabb in &stairwells {
+            let stairs = painter.aabb(*aabb);
+            stairs.clear();
+            stairs
+                .sample(spiral_staircase(
+                    aabb.center().with_z(aabb.max.z),
+                    (STAIRS_RADIUS + 1) as f32,
+                    0.5,
+                    9.0,
+                ))
+                .fill(wall.clone());
+        }
+
+        for (floor, rooms) in floors {
+            for (idx, room) in rooms.iter().enumerate() {
+                let aabb = self.room_aabb(floor, room);
+                let center = self.cell_center(room.cell);
+                let floor_alt = aabb.min.z;
+                let template = theme.rooms.get(room.template);
+                let seed = RandomField::new(floor as u32).get(center.with_z(idx as i32));
+                let mut rng = ChaChaRng::seed_from_u64(seed as u64);
+
+                // Pillars would get in the way of the stairs
+                let has_stairs = matches!(room.kind, RoomKind::Entrance | RoomKind::Stairs);
+                if template.is_some_and(|template| template.pillars) && !has_stairs {
+                    for dir in [
+                        Vec2::new(-1, -1),
+                        Vec2::new(-1, 1),
+                        Vec2::new(1, -1),
+                        Vec2::new(1, 1),
+                    ] {
+                        let pos = center + dir * room.size / 4;
+                        painter
+                            .aabb(Aabb {
+                                min: pos.with_z(floor_alt),
+                                max: (pos + 2).with_z(aabb.max.z),
+                            })
+                            .fill(wall.clone());
+                    }
+                }
+
+                // Sprites along the walls, leaving the ways in clear
+                if let Some(template) = template.filter(|template| !template.sprites.is_empty()) {
+                    let sprites = template.sprites.clone();
+                    painter
+                        .aabb(Aabb {
+                            min: aabb.min,
+                            max: aabb.max.with_z(floor_alt + 1),
+                        })
+                        .without(painter.aabb(Aabb {
+                            min: (aabb.min.xy() + 1).with_z(floor_alt),
+                            max: (aabb.max.xy() - 1).with_z(floor_alt + 1),
+                        }))
+                        .fill(Fill::Sampling(Arc::new(move |pos| {
+                            let rpos = pos.xy() - center;
+                            if rpos.x.abs() <= CORRIDOR_WIDTH / 2 + 1
+                                || rpos.y.abs() <= CORRIDOR_WIDTH / 2 + 1
+                            {
+                                return None;
+                            }
+                            sprites
+                                .iter()
+                                .enumerate()
+                                .find_map(|(i, (sprite, chance))| {
+                                    RandomField::new(seed.wrapping_add(i as u32))
+                                        .chance(pos, *chance)
+                                        .then(|| Block::air(*sprite))
+                                })
+                        })));
+                }
+                if let Some(sprite) = template.and_then(|template| template.centerpiece) {
+                    painter.sprite(center.with_z(floor_alt), sprite);
+                }
+
+                // The way into locked rooms is barred by a door, with the keyhole in the
+                // corridor that leads to it
+                if room.locked
+                    && let Some(parent) = room.parent
+                {
+                    let dir = room.cell - rooms[parent].cell;
+                    let across = dir.yx();
+                    // The wall of the room that faces its parent
+                    let min = center - room.size / 2;
+                    let max = min + room.size;
+                    let wall_pos = Vec2::new(
+                        if dir.x > 0 { min.x - 1 } else { max.x },
+                        if dir.y > 0 { min.y - 1 } else { max.y },
+                    );
+                    let door_pos = Vec2::new(
+                        if dir.x == 0 { center.x } else { wall_pos.x },
+                        if dir.y == 0 { center.y } else { wall_pos.y },
+                    );
+                    let door = |offset: i32| door_pos + across * offset;
+                    painter
+                        .aabb(Aabb {
+                            min: Vec2::min(door(-1), door(1)).with_z(floor_alt),
+                            max: (Vec2::max(door(-1), door(1)) + 1)
+                                .with_z(floor_alt + CORRIDOR_HEIGHT),
+                        })
+                        .fill(Fill::Block(Block::air(SpriteKind::KeyDoor)));
+                    painter.rotated_sprite_with_cfg(
+                        (door(-1) - dir).with_z(floor_alt + 1),
+                        SpriteKind::Keyhole,
+                        0,
+                        SpriteCfg {
+                            unlock: Some(UnlockKind::Consumes(ItemDefinitionIdOwned::Simple(
+                                theme.key.clone(),
+                            ))),
+                            ..SpriteCfg::default()
+                        },
+                    );
+                }
+
+                // Enemies keep away from the walls and the stairs
+                let spawn_pos = |i: u32| {
+                    let field = RandomField::new(seed.wrapping_add(i));
+                    let inner = (room.size - 4).map(|e| e.max(1) as u32);
+                    let mut rpos = Vec2::new(
+                        (field.get(center.with_z(0)) % inner.x) as i32,
+                        (field.get(center.with_z(1)) % inner.y) as i32,
+                    ) - (inner / 2).as_();
+                    if has_stairs
+                        && rpos.x.abs() <= STAIRS_RADIUS + 1
+                        && rpos.y.abs() <= STAIRS_RADIUS + 1
+                    {
+                        rpos.x = if rpos.x < 0 { -1 } else { 1 } * (STAIRS_RADIUS + 2);
+                    }
+                    (center + rpos).with_z(floor_alt).as_::<f32>()
+                };
+                let spawns = room
+                    .encounter
+                    .iter()
+                    .flat_map(|(entity, count)| std::iter::repeat_n(entity, *count as usize));
+                let mut spawn_count = 0;
+                for (i, entity) in spawns.enumerate() {
+                    painter.spawn(
+                        EntityInfo::at(spawn_pos(i as u32))
+                            .with_asset_expect(entity, &mut rng, None),
+                    );
+                    spawn_count += 1;
+                }
+                if room.has_key {
+                    painter.spawn(
+                        EntityInfo::at(spawn_pos(spawn_count))
+                            .with_asset_expect(&theme.key_holder, &mut rng, None)
+                            .with_loot_drop(LootSpec::Item(theme.key.clone())),
+                    );
+                }
+
+                // Once the boss is defeated, a portal leads back to the surface
+                if room.kind == RoomKind::Boss {
+                    let portal_pos = center + Vec2::new(0, room.size.y / 2 - 2);
+                    painter.spawn(
+                        EntityInfo::at(portal_pos.with_z(floor_alt).as_()).into_special(
+                            SpecialEntity::Teleporter(PortalData {
+                                target: self.center.with_z(self.alt + 1).as_(),
+                                requires_no_aggro: true,
+                                buildup_time: Secs(5.),
+                            }),
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{assets::AssetExt, comp::Item, generation::EntityConfig};
+
+    fn themes() -> &'static [DungeonTheme] { &RON_DUNGEON_THEMES.0 }
+
+    #[test]
+    fn dungeon_themes_are_valid() {
+        assert!(!themes().is_empty());
+        for theme in themes() {
+            assert!(theme.floors.0 >= 1 && theme.floors.0 <= theme.floors.1);
+            assert!(theme.rooms_per_floor.0 >= 2);
+            assert!(theme.rooms_per_floor.0 <= theme.rooms_per_floor.1);
+            assert!(!theme.names.is_empty() && !theme.wall.colors.is_empty());
+            for kind in [
+                RoomKind::Entrance,
+                RoomKind::Combat,
+                RoomKind::Treasure,
+                RoomKind::Stairs,
+                RoomKind::Boss,
+            ] {
+                assert!(
+                    theme.rooms.iter().any(|room| room.kinds.contains(&kind)),
+                    "Theme {} has no {kind:?} rooms",
+                    theme.name
+                );
+            }
+            for room in &theme.rooms {
+                assert!(room.size.0 <= room.size.1);
+            }
+
+            Item::new_from_asset_expect(&theme.key);
+            let entities = theme
+                .encounters
+                .iter()
+                .flat_map(|encounter| &encounter.entities)
+                .chain(&theme.boss)
+                .map(|entity| entity.entity.as_str())
+                .chain([theme.key_holder.as_str()]);
+            for entity in entities {
+                Ron::<EntityConfig>::load_expect(entity);
+            }
+        }
+    }
+
+    #[test]
+    fn floors_can_be_cleared_in_order() {
+        for theme in themes() {
+            for seed in 0..64 {
+                let layout = Layout::generate(theme, &mut ChaChaRng::seed_from_u64(seed));
+                let mut start = Vec2::broadcast(GRID_SIZE / 2);
+                for (floor, rooms) in layout.floors.iter().enumerate() {
+                    assert_eq!(rooms[0].cell, start);
+                    assert!(rooms[0].parent.is_none());
+
+                    let is_last_floor = floor + 1 == layout.floors.len();
+                    let goals = rooms
+                        .iter()
+                        .filter(|room| {
+                            room.kind
+                                == if is_last_floor {
+                                    RoomKind::Boss
+                                } else {
+                                    RoomKind::Stairs
+                                }
+                        })
+                        .collect::<Vec<_>>();
+                    assert_eq!(goals.len(), 1);
+                    assert!(goals[0].locked);
+                    start = goals[0].cell;
+
+                    // The key can be reached without going through the locked door
+                    let key_room = rooms.iter().position(|room| room.has_key).unwrap();
+                    let mut idx = Some(key_room);
+                    while let Some(room) = idx {
+                        assert!(!rooms[room].locked);
+                        idx = rooms[room].parent;
+                    }
+
+                    for room in rooms {
+                        if let Some(parent) = room.parent {
+                            assert_eq!(room.depth, rooms[parent].depth + 1);
+                            let dir = room.cell - rooms[parent].cell;
+                            assert_eq!(dir.map(i32::abs).sum(), 1);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn deeper_encounters_are_harder() {
+        let encounter = Encounter {
+            difficulty: (0.2, 0.6),
+            weight: 1.0,
+            entities: vec![EncounterEntity {
+                entity: "enemy".to_string(),
+                count: (1, 5),
+            }],
+        };
+        assert_eq!(encounter.spawns(0.2), vec![("enemy".to_string(), 1)]);
+        assert_eq!(encounter.spawns(0.4), vec![("enemy".to_string(), 3)]);
+        assert_eq!(encounter.spawns(0.6), vec![("enemy".to_string(), 5)]);
+    }
+
+    #[test]
+    fn deeper_dungeons_have_more_bosses() {
+        let theme = DungeonTheme {
+            floors: (2, 4),
+            boss: vec![EncounterEntity {
+                entity: "boss".to_string(),
+                count: (1, 3),
+            }],
+            ..themes()[0].clone()
+        };
+        assert_eq!(theme.boss(2), vec![("boss".to_string(), 1)]);
+        assert_eq!(theme.boss(3), vec![("boss".to_string(), 2)]);
+        assert_eq!(theme.boss(4), vec![("boss".to_string(), 3)]);
+    }
+}